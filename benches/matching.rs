@@ -0,0 +1,99 @@
+//! Benchmarks of corpus filtering and similarity scoring.
+//!
+//! Run with `cargo bench --bench matching`. Each case is timed over several
+//! iterations on a synthetic corpus of 100,000 palettes.
+
+use blockpalettes_client::Palette;
+use blockpalettes_client::analysis;
+use blockpalettes_client::blocks;
+use blockpalettes_client::index::PaletteIndex;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const CORPUS_SIZE: usize = 100_000;
+const ITERATIONS: u32 = 10;
+
+/// Builds a deterministic corpus drawing blocks from the block color table.
+fn corpus() -> Vec<Palette> {
+    let names: Vec<&str> = blocks::colored_blocks().map(|(name, _)| name).collect();
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        names[(state % names.len() as u64) as usize].to_string()
+    };
+    (0..CORPUS_SIZE as u64)
+        .map(|id| Palette {
+            id,
+            user_id: id % 500,
+            date: "2024-01-01 00:00:00".to_string(),
+            likes: (id % 97) as u32,
+            block_one: next(),
+            block_two: next(),
+            block_three: next(),
+            block_four: next(),
+            block_five: next(),
+            block_six: next(),
+            hidden: Some(0),
+            featured: Some(0),
+            hash: None,
+            time_ago: String::new(),
+        })
+        .collect()
+}
+
+fn bench(name: &str, mut f: impl FnMut() -> usize) {
+    let mut total = Duration::ZERO;
+    let mut result = 0;
+    for _ in 0..ITERATIONS {
+        let started = Instant::now();
+        result = black_box(f());
+        total += started.elapsed();
+    }
+    println!(
+        "{name:<40} {:>12.3?} per iteration ({result} results)",
+        total / ITERATIONS
+    );
+}
+
+fn main() {
+    let corpus = corpus();
+    let query = [
+        corpus[0].block_one.as_str(),
+        corpus[1].block_two.as_str(),
+        corpus[2].block_three.as_str(),
+    ];
+    let pair = &query[..2];
+
+    bench("contains_all_blocks, 3 blocks", || {
+        corpus
+            .iter()
+            .filter(|p| p.contains_all_blocks(black_box(&query)))
+            .count()
+    });
+    bench("contains_all_blocks, 2 blocks", || {
+        corpus
+            .iter()
+            .filter(|p| p.contains_all_blocks(black_box(pair)))
+            .count()
+    });
+
+    let started = Instant::now();
+    let index = PaletteIndex::new(&corpus);
+    println!("{:<40} {:>12.3?}", "PaletteIndex::new", started.elapsed());
+    bench("PaletteIndex::containing_all, 3 blocks", || {
+        index.containing_all(black_box(&query)).count()
+    });
+    bench("PaletteIndex::containing_all, 2 blocks", || {
+        index.containing_all(black_box(pair)).count()
+    });
+    bench("PaletteIndex::containing_any, 3 blocks", || {
+        index.containing_any(black_box(&query)).count()
+    });
+
+    let sample = &corpus[..CORPUS_SIZE / 10];
+    bench("possible_duplicates, 10k palettes", || {
+        analysis::possible_duplicates(black_box(&corpus[0]), sample, 0.8).len()
+    });
+}
@@ -0,0 +1,470 @@
+//! Local analysis of palettes based on block metadata.
+//!
+//! Everything in this module works offline, on palettes already fetched.
+
+use crate::Palette;
+use crate::blocks;
+use crate::color::{ColorVision, Lab, Rgb};
+use serde::{Deserialize, Serialize};
+
+/// The color distance (CIE76 ΔE) at which two different blocks stop counting
+/// as similar in [`possible_duplicates`].
+const SIMILAR_COLOR_DISTANCE: f32 = 25.0;
+
+/// The color distance (CIE76 ΔE) below which two blocks are hard to tell
+/// apart in [`colorblind_report`].
+const CONFUSABLE_DISTANCE: f32 = 10.0;
+
+/// The minimum HSL saturation for a block to count as colored.
+const MIN_SATURATION: f32 = 0.2;
+
+/// Blocks darker or lighter than this (HSL lightness) count as neutral.
+const LIGHTNESS_RANGE: (f32, f32) = (0.12, 0.9);
+
+/// The largest hue spread (in degrees) of a monochrome palette.
+const MONOCHROME_SPREAD: f32 = 25.0;
+
+/// The largest hue spread (in degrees) of an analogous palette.
+const ANALOGOUS_SPREAD: f32 = 60.0;
+
+/// The largest hue spread (in degrees) of a single cluster in a
+/// complementary or triadic palette.
+const CLUSTER_SPREAD: f32 = 45.0;
+
+/// How far (in degrees) cluster offsets may deviate from the ideal 180° or 120°.
+const OFFSET_TOLERANCE: f32 = 30.0;
+
+/// The color-theory classification of a palette.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Harmony {
+    /// No colored blocks: only grays, blacks and whites.
+    Achromatic,
+    /// All colored blocks share (nearly) the same hue.
+    Monochrome,
+    /// Colored blocks use neighbouring hues.
+    Analogous,
+    /// Colored blocks form two groups of opposite hues.
+    Complementary,
+    /// Colored blocks form three groups of evenly spaced hues.
+    Triadic,
+    /// None of the above.
+    Mixed,
+}
+
+/// The result of [`harmony_score`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HarmonyReport {
+    /// The detected harmony.
+    pub harmony: Harmony,
+    /// How closely the palette follows its harmony, from `0.0` to `1.0`.
+    pub score: f32,
+    /// The smallest arc of the hue wheel (in degrees) containing every colored block.
+    pub hue_spread: f32,
+    /// The number of blocks considered colored (as opposed to neutral).
+    pub colored_blocks: usize,
+    /// Blocks whose color is unknown and were ignored.
+    pub unknown_blocks: Vec<String>,
+}
+
+/// Classifies a palette using simple color-theory heuristics.
+///
+/// Each block is mapped to the approximate average color of its texture
+/// (see [`blocks::color`]). Neutral blocks (low saturation, or very dark or
+/// light) do not influence the hue analysis, so a palette of deepslate and a
+/// single copper tone is still monochrome. Blocks with an unknown color are
+/// ignored and listed in the report.
+///
+/// # Examples
+///
+/// ```rust
+/// use blockpalettes_client::analysis::{self, Harmony};
+/// # let palette = |blocks: [&str; 6]| blockpalettes_client::Palette {
+/// #    id: 1, user_id: 1, date: "2023-01-01 12:00:00".to_string(), likes: 10,
+/// #    block_one: blocks[0].to_string(), block_two: blocks[1].to_string(),
+/// #    block_three: blocks[2].to_string(), block_four: blocks[3].to_string(),
+/// #    block_five: blocks[4].to_string(), block_six: blocks[5].to_string(),
+/// #    hidden: Some(0), featured: Some(0), hash: None, time_ago: String::new()
+/// # };
+/// let grays = palette(["stone", "andesite", "deepslate", "cobblestone", "smooth_stone", "tuff"]);
+/// assert_eq!(analysis::harmony_score(&grays).harmony, Harmony::Achromatic);
+///
+/// let blues = palette(["blue_wool", "blue_concrete", "lapis_block", "stone", "blue_concrete_powder", "deepslate"]);
+/// assert_eq!(analysis::harmony_score(&blues).harmony, Harmony::Monochrome);
+///
+/// let opposites = palette(["blue_concrete", "lapis_block", "orange_concrete", "orange_wool", "stone", "deepslate"]);
+/// assert_eq!(analysis::harmony_score(&opposites).harmony, Harmony::Complementary);
+/// ```
+pub fn harmony_score(palette: &Palette) -> HarmonyReport {
+    harmony_of(palette.blocks())
+}
+
+/// Classifies any set of blocks like [`harmony_score`].
+pub(crate) fn harmony_of<'a>(palette_blocks: impl IntoIterator<Item = &'a str>) -> HarmonyReport {
+    let mut hues = Vec::new();
+    let mut unknown_blocks = Vec::new();
+
+    for block in palette_blocks {
+        match blocks::color(block) {
+            Some(color) => {
+                let hsl = color.to_hsl();
+                if hsl.s >= MIN_SATURATION
+                    && (LIGHTNESS_RANGE.0..=LIGHTNESS_RANGE.1).contains(&hsl.l)
+                {
+                    hues.push(hsl.h);
+                }
+            }
+            None => unknown_blocks.push(block.to_string()),
+        }
+    }
+    hues.sort_by(f32::total_cmp);
+
+    let spread = arc_spread(&hues);
+    let (harmony, score) = classify(&hues, spread);
+
+    HarmonyReport {
+        harmony,
+        score: score.clamp(0.0, 1.0),
+        hue_spread: spread,
+        colored_blocks: hues.len(),
+        unknown_blocks,
+    }
+}
+
+fn classify(hues: &[f32], spread: f32) -> (Harmony, f32) {
+    if hues.is_empty() {
+        return (Harmony::Achromatic, 1.0);
+    }
+    if spread <= MONOCHROME_SPREAD {
+        return (Harmony::Monochrome, 1.0 - spread / 180.0);
+    }
+    if spread <= ANALOGOUS_SPREAD {
+        return (Harmony::Analogous, 1.0 - spread / 180.0);
+    }
+
+    for (harmony, count, ideal) in [
+        (Harmony::Complementary, 2, 180.0),
+        (Harmony::Triadic, 3, 120.0),
+    ] {
+        if hues.len() < count {
+            continue;
+        }
+        let groups = clusters(hues, count);
+        let widest = groups.iter().map(|g| arc_spread(g)).fold(0.0_f32, f32::max);
+        if widest > CLUSTER_SPREAD {
+            continue;
+        }
+
+        let centers: Vec<f32> = groups.iter().map(|g| circular_mean(g)).collect();
+        let worst_offset = centers
+            .iter()
+            .enumerate()
+            .flat_map(|(i, a)| centers[i + 1..].iter().map(move |b| hue_distance(*a, *b)))
+            .map(|d| (d - ideal).abs().min((360.0 - 2.0 * ideal - d).abs()))
+            .fold(0.0_f32, f32::max);
+        if worst_offset <= OFFSET_TOLERANCE {
+            let score = 1.0 - 0.5 * worst_offset / OFFSET_TOLERANCE - 0.5 * widest / 180.0;
+            return (harmony, score);
+        }
+    }
+
+    (Harmony::Mixed, 1.0 - spread / 360.0)
+}
+
+/// The smallest arc (in degrees) containing all of the sorted `hues`.
+fn arc_spread(hues: &[f32]) -> f32 {
+    if hues.len() < 2 {
+        return 0.0;
+    }
+    let largest_gap = (0..hues.len())
+        .map(|i| (hues[(i + 1) % hues.len()] - hues[i]).rem_euclid(360.0))
+        .fold(0.0_f32, f32::max);
+    360.0 - largest_gap
+}
+
+/// Splits sorted `hues` into `count` groups by cutting at the largest gaps.
+fn clusters(hues: &[f32], count: usize) -> Vec<Vec<f32>> {
+    let n = hues.len();
+    let mut gaps: Vec<(f32, usize)> = (0..n)
+        .map(|i| ((hues[(i + 1) % n] - hues[i]).rem_euclid(360.0), i))
+        .collect();
+    gaps.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    let mut cuts: Vec<usize> = gaps.iter().take(count).map(|&(_, i)| i).collect();
+    cuts.sort_unstable();
+
+    (0..cuts.len())
+        .map(|c| {
+            let start = (cuts[c] + 1) % n;
+            let end = cuts[(c + 1) % cuts.len()];
+            let len = (end + n - start) % n + 1;
+            let mut group: Vec<f32> = (0..len).map(|k| hues[(start + k) % n]).collect();
+            group.sort_by(f32::total_cmp);
+            group
+        })
+        .collect()
+}
+
+fn circular_mean(hues: &[f32]) -> f32 {
+    let (sin, cos) = hues.iter().fold((0.0_f32, 0.0_f32), |(s, c), h| {
+        let r = h.to_radians();
+        (s + r.sin(), c + r.cos())
+    });
+    sin.atan2(cos).to_degrees().rem_euclid(360.0)
+}
+
+/// The angular distance between two hues, in `0.0..=180.0`.
+fn hue_distance(a: f32, b: f32) -> f32 {
+    let d = (a - b).rem_euclid(360.0);
+    d.min(360.0 - d)
+}
+
+/// How a palette from the corpus relates to the checked palette.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MatchKind {
+    /// The same six blocks, in the same order.
+    Exact,
+    /// The same six blocks, in a different order.
+    Reordered,
+    /// Different blocks, but similar enough to score above the threshold.
+    Near,
+}
+
+/// A palette of the corpus that may be a duplicate.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DuplicateMatch {
+    /// The ID of the palette in the corpus.
+    pub palette_id: u64,
+    /// How the palettes relate.
+    pub kind: MatchKind,
+    /// The similarity of the palettes, from `0.0` to `1.0` (identical blocks).
+    pub score: f32,
+    /// The number of blocks the palettes have in common, in any position.
+    pub shared_blocks: usize,
+}
+
+/// Finds palettes of `corpus` that may be reposts of `palette`.
+///
+/// Every block is paired with the most similar block of the other palette:
+/// identical blocks score `1.0`, different blocks score by how close their
+/// colors are (see [`blocks::color`]), and blocks with an unknown color score
+/// `0.0`. The score of a pair of palettes is the average over the blocks of
+/// both palettes, so it is symmetric.
+///
+/// Palettes scoring at least `threshold` are returned, best first. Exact and
+/// reordered matches always score `1.0`. A palette of the corpus with the same
+/// ID as `palette` is ignored.
+///
+/// # Examples
+///
+/// ```rust
+/// use blockpalettes_client::analysis::{self, MatchKind};
+/// # let palette = |id, blocks: [&str; 6]| blockpalettes_client::Palette {
+/// #    id, user_id: 1, date: "2023-01-01 12:00:00".to_string(), likes: 10,
+/// #    block_one: blocks[0].to_string(), block_two: blocks[1].to_string(),
+/// #    block_three: blocks[2].to_string(), block_four: blocks[3].to_string(),
+/// #    block_five: blocks[4].to_string(), block_six: blocks[5].to_string(),
+/// #    hidden: Some(0), featured: Some(0), hash: None, time_ago: String::new()
+/// # };
+/// let submitted = palette(100, ["stone", "andesite", "oak_planks", "spruce_log", "moss_block", "dirt"]);
+/// let corpus = [
+///     palette(1, ["andesite", "stone", "oak_planks", "spruce_log", "moss_block", "dirt"]),
+///     palette(2, ["stone", "andesite", "birch_planks", "spruce_log", "moss_block", "coarse_dirt"]),
+///     palette(3, ["pink_wool", "purple_concrete", "amethyst_block", "white_wool", "quartz_block", "cherry_planks"]),
+/// ];
+///
+/// let matches = analysis::possible_duplicates(&submitted, &corpus, 0.8);
+/// assert_eq!(matches.len(), 2);
+/// assert_eq!((matches[0].palette_id, matches[0].kind), (1, MatchKind::Reordered));
+/// assert_eq!((matches[1].palette_id, matches[1].kind), (2, MatchKind::Near));
+/// assert_eq!(matches[1].shared_blocks, 4);
+/// ```
+pub fn possible_duplicates(
+    palette: &Palette,
+    corpus: &[Palette],
+    threshold: f32,
+) -> Vec<DuplicateMatch> {
+    let blocks = palette.blocks();
+    let colored = with_colors(&blocks);
+    let mut sorted = blocks;
+    sorted.sort_unstable();
+
+    let mut matches: Vec<DuplicateMatch> = corpus
+        .iter()
+        .filter(|candidate| candidate.id != palette.id)
+        .filter_map(|candidate| {
+            let other = candidate.blocks();
+            let shared_blocks = blocks.iter().filter(|b| other.contains(b)).count();
+            let mut other_sorted = other;
+            other_sorted.sort_unstable();
+
+            let (kind, score) = if other == blocks {
+                (MatchKind::Exact, 1.0)
+            } else if other_sorted == sorted {
+                (MatchKind::Reordered, 1.0)
+            } else {
+                let other = with_colors(&other);
+                let score =
+                    (best_pairing(&colored, &other) + best_pairing(&other, &colored)) / 12.0;
+                (MatchKind::Near, score)
+            };
+
+            (score >= threshold).then_some(DuplicateMatch {
+                palette_id: candidate.id,
+                kind,
+                score,
+                shared_blocks,
+            })
+        })
+        .collect();
+
+    matches.sort_by(|a, b| b.score.total_cmp(&a.score));
+    matches
+}
+
+/// The blocks of a palette with their colors, converted once per palette.
+fn with_colors<'a>(blocks: &[&'a str; 6]) -> [(&'a str, Option<Lab>); 6] {
+    blocks.map(|block| (block, blocks::color(block).map(Rgb::to_lab)))
+}
+
+/// Sums, for each block of `from`, its similarity to the closest block of `to`.
+fn best_pairing(from: &[(&str, Option<Lab>); 6], to: &[(&str, Option<Lab>); 6]) -> f32 {
+    from.iter()
+        .map(|a| {
+            to.iter()
+                .map(|b| block_similarity(*a, *b))
+                .fold(0.0_f32, f32::max)
+        })
+        .sum()
+}
+
+fn block_similarity((a, a_lab): (&str, Option<Lab>), (b, b_lab): (&str, Option<Lab>)) -> f32 {
+    if a == b {
+        return 1.0;
+    }
+    match (a_lab, b_lab) {
+        (Some(a), Some(b)) => {
+            let distance = ((a.l - b.l).powi(2) + (a.a - b.a).powi(2) + (a.b - b.b).powi(2)).sqrt();
+            (1.0 - distance / SIMILAR_COLOR_DISTANCE).max(0.0)
+        }
+        _ => 0.0,
+    }
+}
+
+/// Two blocks of a palette that look alike with a color vision deficiency.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfusablePair {
+    /// The block that comes first in the palette.
+    pub first: String,
+    /// The block that comes second in the palette.
+    pub second: String,
+    /// The color distance (CIE76 ΔE) with normal color vision.
+    pub normal_distance: f32,
+    /// The color distance (CIE76 ΔE) with the simulated deficiency.
+    pub simulated_distance: f32,
+}
+
+/// The confusable blocks of a palette for one color vision deficiency.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConditionReport {
+    /// The simulated deficiency.
+    pub vision: ColorVision,
+    /// Pairs of blocks that become hard to tell apart, most similar first.
+    pub confusable_pairs: Vec<ConfusablePair>,
+}
+
+/// The result of [`colorblind_report`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColorblindReport {
+    /// One report per deficiency, in the order of [`ColorVision::ALL`].
+    pub conditions: Vec<ConditionReport>,
+    /// Blocks whose color is unknown and were ignored.
+    pub unknown_blocks: Vec<String>,
+}
+
+impl ColorblindReport {
+    /// Returns `true` if no pair of blocks becomes confusable with any deficiency.
+    pub fn is_accessible(&self) -> bool {
+        self.conditions
+            .iter()
+            .all(|c| c.confusable_pairs.is_empty())
+    }
+}
+
+/// Checks which blocks of a palette become hard to tell apart with
+/// protanopia, deuteranopia and tritanopia.
+///
+/// Block colors (see [`blocks::color`]) are passed through a simulation of
+/// each deficiency (see [`Rgb::simulate`](crate::color::Rgb::simulate)). A
+/// pair is flagged if the blocks are distinct with normal color vision but
+/// their simulated colors are closer than a ΔE of 10. Pairs that already look
+/// alike with normal vision are a design choice and are not flagged. Blocks
+/// with an unknown color are ignored and listed in the report.
+///
+/// # Examples
+///
+/// ```rust
+/// use blockpalettes_client::analysis;
+/// use blockpalettes_client::color::ColorVision;
+/// # let palette = |blocks: [&str; 6]| blockpalettes_client::Palette {
+/// #    id: 1, user_id: 1, date: "2023-01-01 12:00:00".to_string(), likes: 10,
+/// #    block_one: blocks[0].to_string(), block_two: blocks[1].to_string(),
+/// #    block_three: blocks[2].to_string(), block_four: blocks[3].to_string(),
+/// #    block_five: blocks[4].to_string(), block_six: blocks[5].to_string(),
+/// #    hidden: Some(0), featured: Some(0), hash: None, time_ago: String::new()
+/// # };
+/// let christmas = palette(["red_wool", "green_wool", "white_wool", "black_wool", "blue_wool", "yellow_wool"]);
+/// let report = analysis::colorblind_report(&christmas);
+/// assert!(!report.is_accessible());
+///
+/// let deuteranopia = report
+///     .conditions
+///     .iter()
+///     .find(|c| c.vision == ColorVision::Deuteranopia)
+///     .unwrap();
+/// let pair = &deuteranopia.confusable_pairs[0];
+/// assert_eq!((pair.first.as_str(), pair.second.as_str()), ("red_wool", "green_wool"));
+/// ```
+pub fn colorblind_report(palette: &Palette) -> ColorblindReport {
+    let mut colors = Vec::new();
+    let mut unknown_blocks = Vec::new();
+    for block in palette.blocks() {
+        match blocks::color(block) {
+            Some(color) => colors.push((block, color)),
+            None => unknown_blocks.push(block.to_string()),
+        }
+    }
+
+    let conditions = ColorVision::ALL
+        .into_iter()
+        .map(|vision| {
+            let simulated: Vec<_> = colors.iter().map(|(_, c)| c.simulate(vision)).collect();
+            let mut confusable_pairs = Vec::new();
+            for i in 0..colors.len() {
+                for j in i + 1..colors.len() {
+                    let normal_distance = colors[i].1.distance(colors[j].1);
+                    let simulated_distance = simulated[i].distance(simulated[j]);
+                    if normal_distance >= CONFUSABLE_DISTANCE
+                        && simulated_distance < CONFUSABLE_DISTANCE
+                    {
+                        confusable_pairs.push(ConfusablePair {
+                            first: colors[i].0.to_string(),
+                            second: colors[j].0.to_string(),
+                            normal_distance,
+                            simulated_distance,
+                        });
+                    }
+                }
+            }
+            confusable_pairs.sort_by(|a, b| a.simulated_distance.total_cmp(&b.simulated_distance));
+            ConditionReport {
+                vision,
+                confusable_pairs,
+            }
+        })
+        .collect();
+
+    ColorblindReport {
+        conditions,
+        unknown_blocks,
+    }
+}
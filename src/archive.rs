@@ -0,0 +1,127 @@
+//! Recovering deleted palettes from the Internet Archive.
+//!
+//! Palettes removed from the site may still have a snapshot of their page in
+//! the Wayback Machine. [`BlockPalettesClient::fetch_from_wayback`] finds the
+//! most recent snapshot of a palette page with the archive's availability
+//! API, downloads the page as it was archived, and parses it with the same
+//! scraper as [`BlockPalettesClient::scrape_palette_page`]. Results are
+//! [`ArchivedPalettePage`]s, which record where and when the page was
+//! archived, so they are never mistaken for live data.
+//!
+//! Requests to the archive go through the client like any other, with its
+//! pool, retry policy and request queue. With TLS pinning, the archive's key
+//! must be pinned too.
+//!
+//! Requires the `archive` feature.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use blockpalettes_client::BlockPalettesClient;
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let client = BlockPalettesClient::new(reqwest::Client::new());
+//!     match client.fetch_from_wayback("https://www.blockpalettes.com/palette/1234").await? {
+//!         Some(archived) => println!(
+//!             "archived {}: {:?}",
+//!             archived.archived_at, archived.details.blocks
+//!         ),
+//!         None => println!("never archived"),
+//!     }
+//!     Ok(())
+//! }
+//! ```
+//!
+//! [`BlockPalettesClient::fetch_from_wayback`]: crate::BlockPalettesClient::fetch_from_wayback
+//! [`BlockPalettesClient::scrape_palette_page`]: crate::BlockPalettesClient::scrape_palette_page
+
+use crate::permalink::{self, PaletteLink};
+use crate::{BlockPalettesError, DEFAULT_BASE_URL, PalettePageDetails, Result, export};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+/// The base URL of the Wayback Machine.
+pub const WAYBACK_URL: &str = "https://archive.org";
+
+/// The endpoint of snapshots, for the client's circuit breaker.
+pub(crate) const SNAPSHOT_ENDPOINT: &str = "https://web.archive.org/web";
+
+/// A palette page as archived by the Wayback Machine.
+#[derive(Debug, Serialize)]
+pub struct ArchivedPalettePage {
+    /// The palette the page belongs to.
+    pub link: PaletteLink,
+    /// The URL the page was archived from.
+    pub original_url: String,
+    /// The URL of the snapshot in the Wayback Machine.
+    pub snapshot_url: String,
+    /// When the snapshot was taken, in UTC.
+    pub archived_at: NaiveDateTime,
+    /// The content of the page, scraped from the snapshot.
+    pub details: PalettePageDetails,
+}
+
+/// The response of the `/wayback/available` endpoint.
+#[derive(Debug, Deserialize)]
+pub(crate) struct Availability {
+    /// Empty when the URL was never archived.
+    archived_snapshots: Snapshots,
+}
+
+#[derive(Debug, Deserialize)]
+struct Snapshots {
+    #[serde(default)]
+    closest: Option<Snapshot>,
+}
+
+/// The snapshot closest to the requested time.
+#[derive(Debug, Deserialize)]
+pub(crate) struct Snapshot {
+    available: bool,
+    /// The HTTP status the page was archived with.
+    status: String,
+    /// The time the snapshot was taken, as `YYYYMMDDhhmmss`.
+    pub(crate) timestamp: String,
+}
+
+impl Availability {
+    /// Returns the closest snapshot of a successfully served page, if any.
+    pub(crate) fn snapshot(self) -> Option<Snapshot> {
+        self.archived_snapshots
+            .closest
+            .filter(|snapshot| snapshot.available && snapshot.status == "200")
+    }
+}
+
+impl Snapshot {
+    /// Returns the time the snapshot was taken.
+    pub(crate) fn archived_at(&self) -> Result<NaiveDateTime> {
+        NaiveDateTime::parse_from_str(&self.timestamp, "%Y%m%d%H%M%S").map_err(|source| {
+            BlockPalettesError::InvalidDateFormat {
+                input: self.timestamp.clone(),
+                source,
+            }
+        })
+    }
+
+    /// Returns the URL of the snapshot of `original_url`, as archived,
+    /// without the archive's toolbar and rewritten links.
+    pub(crate) fn raw_url(&self, original_url: &str) -> String {
+        format!(
+            "https://web.archive.org/web/{}id_/{original_url}",
+            self.timestamp
+        )
+    }
+}
+
+/// Returns the palette named by `id_or_url` and the URL of its page on the
+/// production site, where the archive would have found it.
+pub(crate) fn original_url(id_or_url: &str) -> Option<(PaletteLink, String)> {
+    let link = permalink::parse(id_or_url)?;
+    let url = match &link {
+        PaletteLink::Id(id) => export::deep_link_for_id(*id),
+        PaletteLink::Hash(hash) => format!("{DEFAULT_BASE_URL}/palette/{hash}"),
+    };
+    Some((link, url))
+}
@@ -0,0 +1,157 @@
+//! Crediting palette creators.
+//!
+//! Apps that embed palettes should credit whoever made them. [`for_palette`]
+//! gathers what a credit needs (the creator's username, links to the palette
+//! and the creator's profile, and when the palette was retrieved) into an
+//! [`Attribution`], which formats it as plain text, Markdown or HTML, so every
+//! app credits creators the same way.
+//!
+//! Links always point to the production site, like
+//! [`export::deep_link`](crate::export::deep_link).
+//!
+//! # Examples
+//!
+//! ```rust
+//! use blockpalettes_client::attribution;
+//! use chrono::NaiveDate;
+//! # let details = blockpalettes_client::PaletteDetails {
+//! #    id: 56655, user_id: 7, date: "2023-01-01 12:00:00".to_string(), likes: 10,
+//! #    block_one: "stone".to_string(), block_two: "dirt".to_string(),
+//! #    block_three: "grass_block".to_string(), block_four: "oak_log".to_string(),
+//! #    block_five: "cobblestone".to_string(), block_six: "sand".to_string(),
+//! #    hidden: 0, featured: 0, hash: String::new(), username: "Steve_<3".to_string(),
+//! #    time_ago: "1 day ago".to_string()
+//! # };
+//! let credit = attribution::for_palette(&details)
+//!     .retrieved_on(NaiveDate::from_ymd_opt(2024, 5, 1).unwrap());
+//!
+//! assert_eq!(
+//!     credit.plain_text(),
+//!     "Palette #56655 by Steve_<3 (https://www.blockpalettes.com/palette/56655), retrieved 2024-05-01"
+//! );
+//! assert_eq!(
+//!     credit.markdown(),
+//!     "[Palette #56655](https://www.blockpalettes.com/palette/56655) by \
+//!      [Steve\\_\\<3](https://www.blockpalettes.com/profile/7), retrieved 2024-05-01"
+//! );
+//! assert_eq!(
+//!     credit.html(),
+//!     "<a href=\"https://www.blockpalettes.com/palette/56655\">Palette #56655</a> by \
+//!      <a href=\"https://www.blockpalettes.com/profile/7\">Steve_&lt;3</a>, retrieved 2024-05-01"
+//! );
+//! ```
+
+use crate::export::deep_link_for_id;
+use crate::{DEFAULT_BASE_URL, PaletteDetails};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The credit owed to the creator of a palette.
+///
+/// [`Display`](fmt::Display) writes the [plain text](Self::plain_text) form.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attribution {
+    /// The ID of the palette.
+    pub palette_id: u64,
+    /// The username of the palette's creator.
+    pub username: String,
+    /// The URL of the palette's page.
+    pub palette_url: String,
+    /// The URL of the creator's profile page.
+    pub profile_url: String,
+    /// When the palette was retrieved from the site.
+    pub retrieved: NaiveDate,
+}
+
+/// Returns the attribution of the palette described by `details`, retrieved
+/// today (UTC).
+///
+/// Use [`Attribution::retrieved_on`] for palettes loaded from an older mirror.
+pub fn for_palette(details: &PaletteDetails) -> Attribution {
+    Attribution {
+        palette_id: details.id,
+        username: details.username.clone(),
+        palette_url: deep_link_for_id(details.id),
+        profile_url: format!("{DEFAULT_BASE_URL}/profile/{}", details.user_id),
+        retrieved: chrono::Utc::now().date_naive(),
+    }
+}
+
+impl Attribution {
+    /// Sets when the palette was retrieved.
+    pub fn retrieved_on(mut self, date: NaiveDate) -> Self {
+        self.retrieved = date;
+        self
+    }
+
+    /// Formats the credit as plain text, with the palette URL in parentheses.
+    pub fn plain_text(&self) -> String {
+        format!(
+            "Palette #{} by {} ({}), retrieved {}",
+            self.palette_id, self.username, self.palette_url, self.retrieved
+        )
+    }
+
+    /// Formats the credit as Markdown, linking the palette and the creator's
+    /// profile.
+    ///
+    /// Markdown syntax in the username is escaped.
+    pub fn markdown(&self) -> String {
+        format!(
+            "[Palette #{}]({}) by [{}]({}), retrieved {}",
+            self.palette_id,
+            self.palette_url,
+            escape_markdown(&self.username),
+            self.profile_url,
+            self.retrieved
+        )
+    }
+
+    /// Formats the credit as an HTML fragment, linking the palette and the
+    /// creator's profile.
+    ///
+    /// The username is escaped.
+    pub fn html(&self) -> String {
+        format!(
+            "<a href=\"{}\">Palette #{}</a> by <a href=\"{}\">{}</a>, retrieved {}",
+            escape_html(&self.palette_url),
+            self.palette_id,
+            escape_html(&self.profile_url),
+            escape_html(&self.username),
+            self.retrieved
+        )
+    }
+}
+
+impl fmt::Display for Attribution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.plain_text())
+    }
+}
+
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if r"\`*_{}[]()<>#+-.!|~".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
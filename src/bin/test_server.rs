@@ -0,0 +1,42 @@
+//! Serves a local mock of the Block Palettes site, for integration tests.
+//!
+//! Usage: `blockpalettes-test-server [ADDRESS] [FIXTURES]`
+//!
+//! `ADDRESS` defaults to `127.0.0.1:8080`. `FIXTURES` is an optional JSON file
+//! holding an array of palette details, as returned in the `palette` field of
+//! `/api/palettes/single_palette.php`; the built-in fixtures are served
+//! otherwise.
+
+use blockpalettes_client::PaletteDetails;
+use blockpalettes_client::test_server::{self, TestServer};
+use std::net::SocketAddr;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = std::env::args().skip(1);
+    let addr: SocketAddr = args.next().as_deref().unwrap_or("127.0.0.1:8080").parse()?;
+    let palettes: Vec<PaletteDetails> = match args.next() {
+        Some(path) => serde_json::from_slice(&std::fs::read(path)?)?,
+        None => test_server::fixtures(),
+    };
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+    runtime.block_on(async {
+        let server = TestServer::bind(addr, palettes).await?;
+        eprintln!("serving on {}", server.url());
+        server.wait().await;
+        Ok(())
+    })
+}
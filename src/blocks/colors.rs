@@ -0,0 +1,300 @@
+//! Approximate average colors of block textures.
+
+use crate::color::Rgb;
+
+/// Average texture colors, sorted by block name for binary search.
+///
+/// Colors are approximations of the side texture as displayed on the site;
+/// biome-tinted blocks use their default (plains) tint.
+pub(crate) static COLORS: &[(&str, Rgb)] = &[
+    ("acacia_log", Rgb::new(103, 96, 86)),
+    ("acacia_planks", Rgb::new(168, 90, 50)),
+    ("acacia_wood", Rgb::new(103, 96, 86)),
+    ("amethyst_block", Rgb::new(133, 97, 191)),
+    ("andesite", Rgb::new(136, 136, 136)),
+    ("bamboo_block", Rgb::new(127, 144, 58)),
+    ("bamboo_mosaic", Rgb::new(190, 170, 78)),
+    ("bamboo_planks", Rgb::new(194, 173, 80)),
+    ("basalt", Rgb::new(73, 72, 77)),
+    ("birch_log", Rgb::new(216, 215, 210)),
+    ("birch_planks", Rgb::new(192, 175, 121)),
+    ("birch_wood", Rgb::new(216, 215, 210)),
+    ("black_concrete", Rgb::new(8, 10, 15)),
+    ("black_concrete_powder", Rgb::new(25, 27, 32)),
+    ("black_glazed_terracotta", Rgb::new(68, 30, 32)),
+    ("black_stained_glass", Rgb::new(25, 25, 25)),
+    ("black_terracotta", Rgb::new(37, 23, 17)),
+    ("black_wool", Rgb::new(21, 21, 26)),
+    ("blackstone", Rgb::new(42, 35, 40)),
+    ("blue_concrete", Rgb::new(45, 47, 143)),
+    ("blue_concrete_powder", Rgb::new(70, 73, 167)),
+    ("blue_glazed_terracotta", Rgb::new(47, 65, 139)),
+    ("blue_ice", Rgb::new(116, 167, 253)),
+    ("blue_stained_glass", Rgb::new(51, 76, 178)),
+    ("blue_terracotta", Rgb::new(74, 60, 91)),
+    ("blue_wool", Rgb::new(53, 57, 157)),
+    ("bone_block", Rgb::new(229, 225, 207)),
+    ("bookshelf", Rgb::new(117, 94, 59)),
+    ("bricks", Rgb::new(150, 97, 83)),
+    ("brown_concrete", Rgb::new(96, 60, 32)),
+    ("brown_concrete_powder", Rgb::new(126, 85, 54)),
+    ("brown_glazed_terracotta", Rgb::new(120, 106, 86)),
+    ("brown_mushroom_block", Rgb::new(149, 111, 81)),
+    ("brown_stained_glass", Rgb::new(102, 76, 51)),
+    ("brown_terracotta", Rgb::new(77, 51, 36)),
+    ("brown_wool", Rgb::new(114, 72, 41)),
+    ("calcite", Rgb::new(223, 224, 220)),
+    ("cherry_log", Rgb::new(54, 33, 44)),
+    ("cherry_planks", Rgb::new(226, 178, 172)),
+    ("cherry_wood", Rgb::new(54, 33, 44)),
+    ("chiseled_deepslate", Rgb::new(54, 54, 54)),
+    ("chiseled_nether_bricks", Rgb::new(47, 23, 28)),
+    ("chiseled_polished_blackstone", Rgb::new(53, 48, 56)),
+    ("chiseled_quartz_block", Rgb::new(231, 226, 218)),
+    ("chiseled_red_sandstone", Rgb::new(183, 96, 27)),
+    ("chiseled_sandstone", Rgb::new(216, 202, 155)),
+    ("chiseled_stone_bricks", Rgb::new(119, 118, 119)),
+    ("chiseled_tuff", Rgb::new(89, 94, 87)),
+    ("clay", Rgb::new(160, 166, 179)),
+    ("coal_block", Rgb::new(16, 15, 15)),
+    ("coarse_dirt", Rgb::new(119, 85, 59)),
+    ("cobbled_deepslate", Rgb::new(77, 77, 80)),
+    ("cobblestone", Rgb::new(127, 127, 127)),
+    ("copper_block", Rgb::new(192, 107, 79)),
+    ("cracked_deepslate_bricks", Rgb::new(64, 64, 65)),
+    ("cracked_deepslate_tiles", Rgb::new(52, 52, 52)),
+    ("cracked_nether_bricks", Rgb::new(40, 20, 23)),
+    ("cracked_polished_blackstone_bricks", Rgb::new(44, 37, 43)),
+    ("cracked_stone_bricks", Rgb::new(118, 117, 118)),
+    ("crafting_table", Rgb::new(119, 73, 42)),
+    ("crimson_hyphae", Rgb::new(92, 25, 29)),
+    ("crimson_planks", Rgb::new(101, 48, 70)),
+    ("crimson_stem", Rgb::new(92, 25, 29)),
+    ("crying_obsidian", Rgb::new(32, 10, 60)),
+    ("cut_copper", Rgb::new(191, 106, 80)),
+    ("cut_red_sandstone", Rgb::new(189, 101, 31)),
+    ("cut_sandstone", Rgb::new(217, 206, 159)),
+    ("cyan_concrete", Rgb::new(21, 119, 136)),
+    ("cyan_concrete_powder", Rgb::new(37, 148, 157)),
+    ("cyan_glazed_terracotta", Rgb::new(52, 119, 125)),
+    ("cyan_stained_glass", Rgb::new(76, 127, 153)),
+    ("cyan_terracotta", Rgb::new(87, 91, 91)),
+    ("cyan_wool", Rgb::new(21, 138, 145)),
+    ("dark_oak_log", Rgb::new(60, 46, 26)),
+    ("dark_oak_planks", Rgb::new(67, 43, 20)),
+    ("dark_oak_wood", Rgb::new(60, 46, 26)),
+    ("dark_prismarine", Rgb::new(51, 91, 75)),
+    ("deepslate", Rgb::new(80, 80, 82)),
+    ("deepslate_bricks", Rgb::new(70, 70, 71)),
+    ("deepslate_tiles", Rgb::new(54, 54, 55)),
+    ("diamond_block", Rgb::new(98, 237, 228)),
+    ("diorite", Rgb::new(188, 188, 188)),
+    ("dirt", Rgb::new(134, 96, 67)),
+    ("dried_kelp_block", Rgb::new(50, 58, 38)),
+    ("dripstone_block", Rgb::new(134, 107, 92)),
+    ("emerald_block", Rgb::new(42, 203, 87)),
+    ("end_stone", Rgb::new(219, 222, 158)),
+    ("end_stone_bricks", Rgb::new(218, 224, 162)),
+    ("exposed_copper", Rgb::new(161, 125, 103)),
+    ("exposed_cut_copper", Rgb::new(155, 122, 101)),
+    ("gilded_blackstone", Rgb::new(55, 42, 38)),
+    ("glass", Rgb::new(175, 213, 219)),
+    ("glowstone", Rgb::new(171, 131, 84)),
+    ("gold_block", Rgb::new(246, 208, 61)),
+    ("granite", Rgb::new(149, 103, 85)),
+    ("grass_block", Rgb::new(97, 130, 58)),
+    ("gravel", Rgb::new(131, 127, 126)),
+    ("gray_concrete", Rgb::new(55, 58, 62)),
+    ("gray_concrete_powder", Rgb::new(77, 81, 85)),
+    ("gray_glazed_terracotta", Rgb::new(83, 90, 94)),
+    ("gray_stained_glass", Rgb::new(76, 76, 76)),
+    ("gray_terracotta", Rgb::new(58, 42, 36)),
+    ("gray_wool", Rgb::new(63, 68, 72)),
+    ("green_concrete", Rgb::new(73, 91, 36)),
+    ("green_concrete_powder", Rgb::new(97, 119, 45)),
+    ("green_glazed_terracotta", Rgb::new(117, 142, 67)),
+    ("green_stained_glass", Rgb::new(102, 127, 51)),
+    ("green_terracotta", Rgb::new(76, 83, 42)),
+    ("green_wool", Rgb::new(85, 110, 28)),
+    ("hay_block", Rgb::new(166, 139, 12)),
+    ("honey_block", Rgb::new(251, 185, 52)),
+    ("honeycomb_block", Rgb::new(229, 148, 29)),
+    ("ice", Rgb::new(145, 183, 253)),
+    ("iron_block", Rgb::new(220, 220, 220)),
+    ("jack_o_lantern", Rgb::new(214, 152, 52)),
+    ("jungle_log", Rgb::new(85, 67, 25)),
+    ("jungle_planks", Rgb::new(160, 115, 81)),
+    ("jungle_wood", Rgb::new(85, 67, 25)),
+    ("lapis_block", Rgb::new(30, 67, 140)),
+    ("light_blue_concrete", Rgb::new(36, 137, 199)),
+    ("light_blue_concrete_powder", Rgb::new(74, 181, 213)),
+    ("light_blue_glazed_terracotta", Rgb::new(95, 165, 209)),
+    ("light_blue_stained_glass", Rgb::new(102, 153, 216)),
+    ("light_blue_terracotta", Rgb::new(113, 109, 138)),
+    ("light_blue_wool", Rgb::new(58, 175, 217)),
+    ("light_gray_concrete", Rgb::new(125, 125, 115)),
+    ("light_gray_concrete_powder", Rgb::new(155, 155, 148)),
+    ("light_gray_glazed_terracotta", Rgb::new(144, 166, 168)),
+    ("light_gray_stained_glass", Rgb::new(153, 153, 153)),
+    ("light_gray_terracotta", Rgb::new(135, 107, 98)),
+    ("light_gray_wool", Rgb::new(142, 142, 135)),
+    ("lime_concrete", Rgb::new(94, 169, 25)),
+    ("lime_concrete_powder", Rgb::new(125, 189, 42)),
+    ("lime_glazed_terracotta", Rgb::new(163, 198, 55)),
+    ("lime_stained_glass", Rgb::new(127, 204, 25)),
+    ("lime_terracotta", Rgb::new(104, 118, 53)),
+    ("lime_wool", Rgb::new(112, 185, 26)),
+    ("lodestone", Rgb::new(147, 149, 152)),
+    ("magenta_concrete", Rgb::new(169, 48, 159)),
+    ("magenta_concrete_powder", Rgb::new(193, 84, 185)),
+    ("magenta_glazed_terracotta", Rgb::new(208, 100, 192)),
+    ("magenta_stained_glass", Rgb::new(178, 76, 216)),
+    ("magenta_terracotta", Rgb::new(150, 88, 109)),
+    ("magenta_wool", Rgb::new(190, 69, 180)),
+    ("magma_block", Rgb::new(142, 63, 31)),
+    ("mangrove_log", Rgb::new(84, 66, 41)),
+    ("mangrove_planks", Rgb::new(117, 54, 48)),
+    ("mangrove_wood", Rgb::new(84, 66, 41)),
+    ("melon", Rgb::new(111, 145, 30)),
+    ("moss_block", Rgb::new(89, 109, 45)),
+    ("mossy_cobblestone", Rgb::new(110, 118, 94)),
+    ("mossy_stone_bricks", Rgb::new(115, 121, 105)),
+    ("mud", Rgb::new(60, 57, 60)),
+    ("mud_bricks", Rgb::new(137, 103, 79)),
+    ("mushroom_stem", Rgb::new(203, 196, 185)),
+    ("mycelium", Rgb::new(111, 99, 105)),
+    ("nether_bricks", Rgb::new(44, 21, 26)),
+    ("nether_wart_block", Rgb::new(114, 3, 3)),
+    ("netherite_block", Rgb::new(66, 61, 63)),
+    ("netherrack", Rgb::new(97, 38, 38)),
+    ("note_block", Rgb::new(88, 58, 40)),
+    ("oak_log", Rgb::new(109, 85, 50)),
+    ("oak_planks", Rgb::new(162, 130, 78)),
+    ("oak_wood", Rgb::new(109, 85, 50)),
+    ("obsidian", Rgb::new(15, 10, 24)),
+    ("orange_concrete", Rgb::new(224, 97, 1)),
+    ("orange_concrete_powder", Rgb::new(227, 132, 32)),
+    ("orange_glazed_terracotta", Rgb::new(155, 147, 92)),
+    ("orange_stained_glass", Rgb::new(216, 127, 51)),
+    ("orange_terracotta", Rgb::new(162, 84, 38)),
+    ("orange_wool", Rgb::new(241, 118, 20)),
+    ("oxidized_copper", Rgb::new(82, 162, 132)),
+    ("oxidized_cut_copper", Rgb::new(80, 154, 126)),
+    ("packed_ice", Rgb::new(141, 180, 250)),
+    ("packed_mud", Rgb::new(142, 106, 79)),
+    ("pale_oak_log", Rgb::new(88, 78, 74)),
+    ("pale_oak_planks", Rgb::new(227, 217, 215)),
+    ("pale_oak_wood", Rgb::new(88, 78, 74)),
+    ("pink_concrete", Rgb::new(214, 101, 143)),
+    ("pink_concrete_powder", Rgb::new(229, 153, 181)),
+    ("pink_glazed_terracotta", Rgb::new(235, 155, 182)),
+    ("pink_stained_glass", Rgb::new(242, 127, 165)),
+    ("pink_terracotta", Rgb::new(162, 78, 79)),
+    ("pink_wool", Rgb::new(238, 141, 172)),
+    ("podzol", Rgb::new(92, 63, 24)),
+    ("polished_andesite", Rgb::new(132, 134, 133)),
+    ("polished_basalt", Rgb::new(99, 98, 100)),
+    ("polished_blackstone", Rgb::new(53, 48, 56)),
+    ("polished_blackstone_bricks", Rgb::new(48, 42, 49)),
+    ("polished_deepslate", Rgb::new(72, 72, 73)),
+    ("polished_diorite", Rgb::new(192, 193, 194)),
+    ("polished_granite", Rgb::new(154, 106, 89)),
+    ("polished_tuff", Rgb::new(97, 104, 99)),
+    ("prismarine", Rgb::new(99, 156, 151)),
+    ("prismarine_bricks", Rgb::new(99, 171, 158)),
+    ("pumpkin", Rgb::new(198, 118, 24)),
+    ("purple_concrete", Rgb::new(100, 32, 156)),
+    ("purple_concrete_powder", Rgb::new(132, 56, 178)),
+    ("purple_glazed_terracotta", Rgb::new(110, 48, 152)),
+    ("purple_stained_glass", Rgb::new(127, 63, 178)),
+    ("purple_terracotta", Rgb::new(118, 70, 86)),
+    ("purple_wool", Rgb::new(122, 42, 173)),
+    ("purpur_block", Rgb::new(169, 125, 169)),
+    ("purpur_pillar", Rgb::new(171, 129, 171)),
+    ("quartz_block", Rgb::new(235, 229, 222)),
+    ("quartz_bricks", Rgb::new(234, 229, 221)),
+    ("quartz_pillar", Rgb::new(235, 230, 224)),
+    ("raw_copper_block", Rgb::new(154, 105, 79)),
+    ("raw_gold_block", Rgb::new(221, 169, 46)),
+    ("raw_iron_block", Rgb::new(166, 135, 107)),
+    ("red_concrete", Rgb::new(142, 33, 33)),
+    ("red_concrete_powder", Rgb::new(168, 54, 51)),
+    ("red_glazed_terracotta", Rgb::new(181, 59, 53)),
+    ("red_mushroom_block", Rgb::new(200, 46, 45)),
+    ("red_nether_bricks", Rgb::new(69, 7, 9)),
+    ("red_sand", Rgb::new(190, 102, 33)),
+    ("red_sandstone", Rgb::new(186, 99, 29)),
+    ("red_stained_glass", Rgb::new(153, 51, 51)),
+    ("red_terracotta", Rgb::new(143, 61, 47)),
+    ("red_wool", Rgb::new(161, 39, 35)),
+    ("redstone_block", Rgb::new(175, 24, 5)),
+    ("rooted_dirt", Rgb::new(144, 103, 76)),
+    ("sand", Rgb::new(219, 207, 163)),
+    ("sandstone", Rgb::new(216, 203, 155)),
+    ("sculk", Rgb::new(12, 29, 36)),
+    ("sea_lantern", Rgb::new(172, 199, 190)),
+    ("shroomlight", Rgb::new(240, 146, 70)),
+    ("slime_block", Rgb::new(111, 192, 91)),
+    ("smooth_basalt", Rgb::new(72, 72, 78)),
+    ("smooth_quartz", Rgb::new(235, 229, 222)),
+    ("smooth_red_sandstone", Rgb::new(181, 98, 31)),
+    ("smooth_sandstone", Rgb::new(223, 214, 170)),
+    ("smooth_stone", Rgb::new(158, 158, 158)),
+    ("snow_block", Rgb::new(249, 254, 254)),
+    ("soul_sand", Rgb::new(81, 62, 50)),
+    ("soul_soil", Rgb::new(75, 57, 46)),
+    ("sponge", Rgb::new(195, 192, 74)),
+    ("spruce_log", Rgb::new(58, 37, 16)),
+    ("spruce_planks", Rgb::new(115, 85, 49)),
+    ("spruce_wood", Rgb::new(58, 37, 16)),
+    ("stone", Rgb::new(125, 125, 125)),
+    ("stone_bricks", Rgb::new(122, 121, 122)),
+    ("stripped_acacia_log", Rgb::new(174, 92, 59)),
+    ("stripped_acacia_wood", Rgb::new(174, 92, 59)),
+    ("stripped_bamboo_block", Rgb::new(192, 173, 80)),
+    ("stripped_birch_log", Rgb::new(196, 176, 118)),
+    ("stripped_birch_wood", Rgb::new(196, 176, 118)),
+    ("stripped_cherry_log", Rgb::new(215, 145, 148)),
+    ("stripped_cherry_wood", Rgb::new(215, 145, 148)),
+    ("stripped_crimson_hyphae", Rgb::new(137, 57, 90)),
+    ("stripped_crimson_stem", Rgb::new(137, 57, 90)),
+    ("stripped_dark_oak_log", Rgb::new(72, 56, 36)),
+    ("stripped_dark_oak_wood", Rgb::new(72, 56, 36)),
+    ("stripped_jungle_log", Rgb::new(171, 132, 84)),
+    ("stripped_jungle_wood", Rgb::new(171, 132, 84)),
+    ("stripped_mangrove_log", Rgb::new(119, 54, 47)),
+    ("stripped_mangrove_wood", Rgb::new(119, 54, 47)),
+    ("stripped_oak_log", Rgb::new(177, 144, 86)),
+    ("stripped_oak_wood", Rgb::new(177, 144, 86)),
+    ("stripped_pale_oak_log", Rgb::new(245, 238, 236)),
+    ("stripped_pale_oak_wood", Rgb::new(245, 238, 236)),
+    ("stripped_spruce_log", Rgb::new(115, 89, 52)),
+    ("stripped_spruce_wood", Rgb::new(115, 89, 52)),
+    ("stripped_warped_hyphae", Rgb::new(57, 150, 147)),
+    ("stripped_warped_stem", Rgb::new(57, 150, 147)),
+    ("target", Rgb::new(226, 170, 157)),
+    ("terracotta", Rgb::new(152, 94, 68)),
+    ("tinted_glass", Rgb::new(44, 38, 46)),
+    ("tnt", Rgb::new(142, 62, 53)),
+    ("tuff", Rgb::new(108, 109, 102)),
+    ("tuff_bricks", Rgb::new(98, 102, 95)),
+    ("warped_hyphae", Rgb::new(58, 59, 78)),
+    ("warped_planks", Rgb::new(43, 104, 99)),
+    ("warped_stem", Rgb::new(58, 59, 78)),
+    ("warped_wart_block", Rgb::new(22, 119, 121)),
+    ("weathered_copper", Rgb::new(108, 153, 110)),
+    ("weathered_cut_copper", Rgb::new(109, 145, 107)),
+    ("wet_sponge", Rgb::new(171, 181, 70)),
+    ("white_concrete", Rgb::new(207, 213, 214)),
+    ("white_concrete_powder", Rgb::new(226, 227, 228)),
+    ("white_glazed_terracotta", Rgb::new(188, 212, 203)),
+    ("white_stained_glass", Rgb::new(255, 255, 255)),
+    ("white_terracotta", Rgb::new(210, 178, 161)),
+    ("white_wool", Rgb::new(234, 236, 237)),
+    ("yellow_concrete", Rgb::new(241, 175, 21)),
+    ("yellow_concrete_powder", Rgb::new(233, 199, 55)),
+    ("yellow_glazed_terracotta", Rgb::new(234, 192, 89)),
+    ("yellow_stained_glass", Rgb::new(229, 229, 51)),
+    ("yellow_terracotta", Rgb::new(186, 133, 35)),
+    ("yellow_wool", Rgb::new(249, 198, 40)),
+];
@@ -0,0 +1,429 @@
+//! Block metadata (colors, names, shapes, versions, obtainability) and groups of related blocks.
+//!
+//! Block names follow the site's format: Minecraft block IDs without the
+//! `minecraft:` namespace (e.g., `"oak_log"`).
+
+mod colors;
+mod names;
+mod obtainability;
+mod shapes;
+#[cfg(feature = "textures")]
+mod textures;
+mod versions;
+
+pub use names::{Locale, NameFormat, display_name};
+pub use obtainability::{GameMode, Obtainability, obtainability};
+pub use shapes::{BlockShape, shape};
+#[cfg(feature = "textures")]
+pub use textures::{TEXTURE_SIZE, texture_png};
+pub use versions::{GameVersion, ParseGameVersionError, introduced_blocks, introduced_in};
+
+use crate::Palette;
+use crate::color::Rgb;
+use serde::Serialize;
+
+/// Normalizes a block name for registry lookups.
+///
+/// The `minecraft:` namespace is stripped, and waxed copper blocks are mapped
+/// to their unwaxed variant, which looks identical.
+pub(crate) fn normalize(block: &str) -> &str {
+    let block = block.trim();
+    let block = block.strip_prefix("minecraft:").unwrap_or(block);
+    block.strip_prefix("waxed_").unwrap_or(block)
+}
+
+/// Returns the approximate average color of a block's texture.
+///
+/// Colors are known for full blocks commonly used in palettes; `None` is
+/// returned for anything else.
+///
+/// # Examples
+///
+/// ```rust
+/// use blockpalettes_client::blocks;
+/// use blockpalettes_client::color::Rgb;
+///
+/// assert_eq!(blocks::color("stone"), Some(Rgb::new(125, 125, 125)));
+/// assert_eq!(blocks::color("minecraft:waxed_copper_block"), blocks::color("copper_block"));
+/// assert_eq!(blocks::color("not_a_block"), None);
+/// ```
+pub fn color(block: &str) -> Option<Rgb> {
+    let block = normalize(block);
+    colors::COLORS
+        .binary_search_by(|(name, _)| (*name).cmp(block))
+        .ok()
+        .map(|index| colors::COLORS[index].1)
+}
+
+/// Iterates over every block with a known color, in alphabetical order.
+pub fn colored_blocks() -> impl Iterator<Item = (&'static str, Rgb)> {
+    colors::COLORS.iter().copied()
+}
+
+/// Returns the block whose color is closest to `block`'s, ignoring `exclude`.
+///
+/// Distances are perceptual (see [`Rgb::distance`]). `block` itself is never
+/// returned. Returns `None` if the color of `block` is unknown.
+///
+/// # Examples
+///
+/// ```rust
+/// use blockpalettes_client::blocks;
+///
+/// assert_eq!(blocks::nearest_by_color("waxed_copper_block", &[]), Some("cut_copper"));
+/// assert_ne!(blocks::nearest_by_color("stone", &["andesite"]), Some("andesite"));
+/// assert_eq!(blocks::nearest_by_color("not_a_block", &[]), None);
+/// ```
+pub fn nearest_by_color(block: &str, exclude: &[&str]) -> Option<&'static str> {
+    let block = normalize(block);
+    let target = color(block)?;
+    colored_blocks()
+        .filter(|(name, _)| *name != block && !exclude.iter().any(|e| normalize(e) == *name))
+        .min_by(|(_, a), (_, b)| target.distance(*a).total_cmp(&target.distance(*b)))
+        .map(|(name, _)| name)
+}
+
+/// A named group of related blocks, such as all wool colors.
+///
+/// Groups are used wherever "any block of this kind" semantics are needed,
+/// through [`BlockQuery::Group`].
+///
+/// # Examples
+///
+/// ```rust
+/// use blockpalettes_client::blocks::BlockGroup;
+///
+/// assert!(BlockGroup::WOOL.contains("light_blue_wool"));
+/// assert!(BlockGroup::DARK_WOOD.contains("stripped_dark_oak_log"));
+/// assert_eq!(BlockGroup::CONCRETE.blocks.len(), 16);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub struct BlockGroup {
+    /// A short identifier for the group (e.g., `"wool"`).
+    pub name: &'static str,
+    /// The blocks in the group.
+    pub blocks: &'static [&'static str],
+}
+
+impl BlockGroup {
+    /// All sixteen colors of wool.
+    pub const WOOL: BlockGroup = BlockGroup {
+        name: "wool",
+        blocks: &[
+            "white_wool",
+            "orange_wool",
+            "magenta_wool",
+            "light_blue_wool",
+            "yellow_wool",
+            "lime_wool",
+            "pink_wool",
+            "gray_wool",
+            "light_gray_wool",
+            "cyan_wool",
+            "purple_wool",
+            "blue_wool",
+            "brown_wool",
+            "green_wool",
+            "red_wool",
+            "black_wool",
+        ],
+    };
+
+    /// All sixteen colors of concrete.
+    pub const CONCRETE: BlockGroup = BlockGroup {
+        name: "concrete",
+        blocks: &[
+            "white_concrete",
+            "orange_concrete",
+            "magenta_concrete",
+            "light_blue_concrete",
+            "yellow_concrete",
+            "lime_concrete",
+            "pink_concrete",
+            "gray_concrete",
+            "light_gray_concrete",
+            "cyan_concrete",
+            "purple_concrete",
+            "blue_concrete",
+            "brown_concrete",
+            "green_concrete",
+            "red_concrete",
+            "black_concrete",
+        ],
+    };
+
+    /// All sixteen colors of concrete powder.
+    pub const CONCRETE_POWDER: BlockGroup = BlockGroup {
+        name: "concrete_powder",
+        blocks: &[
+            "white_concrete_powder",
+            "orange_concrete_powder",
+            "magenta_concrete_powder",
+            "light_blue_concrete_powder",
+            "yellow_concrete_powder",
+            "lime_concrete_powder",
+            "pink_concrete_powder",
+            "gray_concrete_powder",
+            "light_gray_concrete_powder",
+            "cyan_concrete_powder",
+            "purple_concrete_powder",
+            "blue_concrete_powder",
+            "brown_concrete_powder",
+            "green_concrete_powder",
+            "red_concrete_powder",
+            "black_concrete_powder",
+        ],
+    };
+
+    /// Plain terracotta and its sixteen dyed variants.
+    pub const TERRACOTTA: BlockGroup = BlockGroup {
+        name: "terracotta",
+        blocks: &[
+            "terracotta",
+            "white_terracotta",
+            "orange_terracotta",
+            "magenta_terracotta",
+            "light_blue_terracotta",
+            "yellow_terracotta",
+            "lime_terracotta",
+            "pink_terracotta",
+            "gray_terracotta",
+            "light_gray_terracotta",
+            "cyan_terracotta",
+            "purple_terracotta",
+            "blue_terracotta",
+            "brown_terracotta",
+            "green_terracotta",
+            "red_terracotta",
+            "black_terracotta",
+        ],
+    };
+
+    /// All sixteen colors of glazed terracotta.
+    pub const GLAZED_TERRACOTTA: BlockGroup = BlockGroup {
+        name: "glazed_terracotta",
+        blocks: &[
+            "white_glazed_terracotta",
+            "orange_glazed_terracotta",
+            "magenta_glazed_terracotta",
+            "light_blue_glazed_terracotta",
+            "yellow_glazed_terracotta",
+            "lime_glazed_terracotta",
+            "pink_glazed_terracotta",
+            "gray_glazed_terracotta",
+            "light_gray_glazed_terracotta",
+            "cyan_glazed_terracotta",
+            "purple_glazed_terracotta",
+            "blue_glazed_terracotta",
+            "brown_glazed_terracotta",
+            "green_glazed_terracotta",
+            "red_glazed_terracotta",
+            "black_glazed_terracotta",
+        ],
+    };
+
+    /// All sixteen colors of stained glass.
+    pub const STAINED_GLASS: BlockGroup = BlockGroup {
+        name: "stained_glass",
+        blocks: &[
+            "white_stained_glass",
+            "orange_stained_glass",
+            "magenta_stained_glass",
+            "light_blue_stained_glass",
+            "yellow_stained_glass",
+            "lime_stained_glass",
+            "pink_stained_glass",
+            "gray_stained_glass",
+            "light_gray_stained_glass",
+            "cyan_stained_glass",
+            "purple_stained_glass",
+            "blue_stained_glass",
+            "brown_stained_glass",
+            "green_stained_glass",
+            "red_stained_glass",
+            "black_stained_glass",
+        ],
+    };
+
+    /// Every overworld log and nether stem, unstripped.
+    pub const LOGS: BlockGroup = BlockGroup {
+        name: "logs",
+        blocks: &[
+            "oak_log",
+            "spruce_log",
+            "birch_log",
+            "jungle_log",
+            "acacia_log",
+            "dark_oak_log",
+            "mangrove_log",
+            "cherry_log",
+            "pale_oak_log",
+            "crimson_stem",
+            "warped_stem",
+        ],
+    };
+
+    /// Every stripped overworld log and nether stem.
+    pub const STRIPPED_LOGS: BlockGroup = BlockGroup {
+        name: "stripped_logs",
+        blocks: &[
+            "stripped_oak_log",
+            "stripped_spruce_log",
+            "stripped_birch_log",
+            "stripped_jungle_log",
+            "stripped_acacia_log",
+            "stripped_dark_oak_log",
+            "stripped_mangrove_log",
+            "stripped_cherry_log",
+            "stripped_pale_oak_log",
+            "stripped_crimson_stem",
+            "stripped_warped_stem",
+        ],
+    };
+
+    /// Every six-sided bark block (wood and hyphae), stripped or not.
+    pub const WOOD: BlockGroup = BlockGroup {
+        name: "wood",
+        blocks: &[
+            "oak_wood",
+            "spruce_wood",
+            "birch_wood",
+            "jungle_wood",
+            "acacia_wood",
+            "dark_oak_wood",
+            "mangrove_wood",
+            "cherry_wood",
+            "pale_oak_wood",
+            "crimson_hyphae",
+            "warped_hyphae",
+            "stripped_oak_wood",
+            "stripped_spruce_wood",
+            "stripped_birch_wood",
+            "stripped_jungle_wood",
+            "stripped_acacia_wood",
+            "stripped_dark_oak_wood",
+            "stripped_mangrove_wood",
+            "stripped_cherry_wood",
+            "stripped_pale_oak_wood",
+            "stripped_crimson_hyphae",
+            "stripped_warped_hyphae",
+        ],
+    };
+
+    /// Every kind of planks.
+    pub const PLANKS: BlockGroup = BlockGroup {
+        name: "planks",
+        blocks: &[
+            "oak_planks",
+            "spruce_planks",
+            "birch_planks",
+            "jungle_planks",
+            "acacia_planks",
+            "dark_oak_planks",
+            "mangrove_planks",
+            "cherry_planks",
+            "pale_oak_planks",
+            "bamboo_planks",
+            "crimson_planks",
+            "warped_planks",
+        ],
+    };
+
+    /// Logs, wood and planks of the darker wood types (spruce and dark oak).
+    pub const DARK_WOOD: BlockGroup = BlockGroup {
+        name: "dark_wood",
+        blocks: &[
+            "spruce_log",
+            "spruce_wood",
+            "stripped_spruce_log",
+            "stripped_spruce_wood",
+            "dark_oak_log",
+            "dark_oak_wood",
+            "stripped_dark_oak_log",
+            "stripped_dark_oak_wood",
+            "spruce_planks",
+            "dark_oak_planks",
+        ],
+    };
+
+    /// Every group defined by this crate.
+    pub const ALL: &'static [BlockGroup] = &[
+        BlockGroup::WOOL,
+        BlockGroup::CONCRETE,
+        BlockGroup::CONCRETE_POWDER,
+        BlockGroup::TERRACOTTA,
+        BlockGroup::GLAZED_TERRACOTTA,
+        BlockGroup::STAINED_GLASS,
+        BlockGroup::LOGS,
+        BlockGroup::STRIPPED_LOGS,
+        BlockGroup::WOOD,
+        BlockGroup::PLANKS,
+        BlockGroup::DARK_WOOD,
+    ];
+
+    /// Returns `true` if `block` belongs to the group.
+    pub fn contains(&self, block: &str) -> bool {
+        self.blocks.contains(&block)
+    }
+
+    /// Returns the group named `name`, if it exists.
+    pub fn by_name(name: &str) -> Option<BlockGroup> {
+        Self::ALL.iter().copied().find(|group| group.name == name)
+    }
+}
+
+/// A requirement on the blocks of a palette.
+///
+/// # Examples
+///
+/// ```rust
+/// use blockpalettes_client::blocks::{BlockGroup, BlockQuery};
+/// # let palette = blockpalettes_client::Palette {
+/// #    id: 1, user_id: 1, date: "2023-01-01 12:00:00".to_string(), likes: 10,
+/// #    block_one: "stone".to_string(), block_two: "dirt".to_string(),
+/// #    block_three: "spruce_planks".to_string(), block_four: "oak_log".to_string(),
+/// #    block_five: "cobblestone".to_string(), block_six: "sand".to_string(),
+/// #    hidden: Some(0), featured: Some(0), hash: None, time_ago: "1 day ago".to_string()
+/// # };
+/// let queries = [BlockQuery::Block("stone"), BlockQuery::Group(BlockGroup::DARK_WOOD)];
+/// assert!(palette.matches_all(&queries));
+/// assert!(!palette.matches_all(&[BlockQuery::Group(BlockGroup::WOOL)]));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum BlockQuery<'a> {
+    /// The palette must contain this exact block.
+    Block(&'a str),
+    /// The palette must contain at least one block of this group.
+    Group(BlockGroup),
+}
+
+impl BlockQuery<'_> {
+    /// Returns `true` if `palette` satisfies the requirement.
+    pub fn matches(&self, palette: &Palette) -> bool {
+        let blocks = palette.blocks();
+        match self {
+            BlockQuery::Block(block) => blocks.contains(block),
+            BlockQuery::Group(group) => blocks.iter().any(|b| group.contains(b)),
+        }
+    }
+
+    /// Returns the individual blocks to query the API with.
+    pub(crate) fn api_blocks(&self) -> Vec<&str> {
+        match self {
+            BlockQuery::Block(block) => vec![block],
+            BlockQuery::Group(group) => group.blocks.to_vec(),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for BlockQuery<'a> {
+    fn from(block: &'a str) -> Self {
+        BlockQuery::Block(block)
+    }
+}
+
+impl From<BlockGroup> for BlockQuery<'_> {
+    fn from(group: BlockGroup) -> Self {
+        BlockQuery::Group(group)
+    }
+}
@@ -0,0 +1,212 @@
+//! Localized block display names.
+
+use crate::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// The prefix of block keys in Minecraft language files.
+const LANG_KEY_PREFIX: &str = "block.minecraft.";
+
+/// English names that do not follow from the block ID, sorted by ID.
+///
+/// Every other block's English name is its ID in title case.
+static EN_US_OVERRIDES: &[(&str, &str)] = &[
+    ("amethyst_block", "Block of Amethyst"),
+    ("bamboo_block", "Block of Bamboo"),
+    ("coal_block", "Block of Coal"),
+    ("copper_block", "Block of Copper"),
+    ("diamond_block", "Block of Diamond"),
+    ("emerald_block", "Block of Emerald"),
+    ("gold_block", "Block of Gold"),
+    ("hay_block", "Hay Bale"),
+    ("iron_block", "Block of Iron"),
+    ("jack_o_lantern", "Jack o'Lantern"),
+    ("lapis_block", "Block of Lapis Lazuli"),
+    ("netherite_block", "Block of Netherite"),
+    ("quartz_block", "Block of Quartz"),
+    ("raw_copper_block", "Block of Raw Copper"),
+    ("raw_gold_block", "Block of Raw Gold"),
+    ("raw_iron_block", "Block of Raw Iron"),
+    ("redstone_block", "Block of Redstone"),
+    ("smooth_quartz", "Smooth Quartz Block"),
+    ("stripped_bamboo_block", "Block of Stripped Bamboo"),
+    ("tnt", "TNT"),
+];
+
+/// Block names in one language, as loaded from a Minecraft language file.
+///
+/// English (`en_us`) is bundled; other languages are loaded from the JSON
+/// language files shipped with the game (e.g., `assets/minecraft/lang/de_de.json`
+/// inside the client jar or its asset index). Names missing from a language
+/// fall back to English.
+///
+/// # Examples
+///
+/// ```rust
+/// use blockpalettes_client::blocks::{self, Locale};
+///
+/// let german = Locale::from_lang_json(
+///     "de_de",
+///     r#"{"block.minecraft.polished_blackstone": "Polierter Schwarzstein", "item.minecraft.stick": "Stock"}"#,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(blocks::display_name("polished_blackstone", &german), "Polierter Schwarzstein");
+/// assert_eq!(blocks::display_name("tnt", &german), "TNT");
+/// assert_eq!(blocks::display_name("minecraft:lapis_block", &Locale::en_us()), "Block of Lapis Lazuli");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Locale {
+    code: String,
+    names: HashMap<String, String>,
+}
+
+impl Locale {
+    /// Returns the bundled English locale.
+    pub fn en_us() -> Self {
+        Self {
+            code: "en_us".to_string(),
+            names: HashMap::new(),
+        }
+    }
+
+    /// Parses the contents of a Minecraft JSON language file.
+    ///
+    /// Only block names are kept.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BlockPalettesError::Json`](crate::BlockPalettesError::Json)
+    /// if `json` is not an object of strings.
+    pub fn from_lang_json(code: impl Into<String>, json: &str) -> Result<Self> {
+        let entries: HashMap<String, String> = serde_json::from_str(json)?;
+        let names = entries
+            .into_iter()
+            .filter_map(|(key, name)| Some((key.strip_prefix(LANG_KEY_PREFIX)?.to_string(), name)))
+            .filter(|(block, _)| !block.contains('.'))
+            .collect();
+        Ok(Self {
+            code: code.into(),
+            names,
+        })
+    }
+
+    /// Loads a Minecraft JSON language file, such as `de_de.json`.
+    ///
+    /// The locale code is the file name without its extension.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BlockPalettesError::Io`](crate::BlockPalettesError::Io) if
+    /// the file cannot be read, or
+    /// [`BlockPalettesError::Json`](crate::BlockPalettesError::Json) if it is
+    /// not a language file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let code = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        Self::from_lang_json(code, &std::fs::read_to_string(path)?)
+    }
+
+    /// Returns the locale code (e.g., `"de_de"`).
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    /// Returns the localized name of a block, if this locale has one.
+    pub fn get(&self, block: &str) -> Option<&str> {
+        self.names.get(strip_namespace(block)).map(String::as_str)
+    }
+}
+
+/// Returns the display name of a block in `locale`.
+///
+/// Blocks missing from `locale` are named in English. English names come from
+/// a bundled table of names that differ from the block ID (such as
+/// `"Block of Iron"` for `iron_block`), and are otherwise the ID in title case.
+pub fn display_name(block: &str, locale: &Locale) -> String {
+    match locale.get(block) {
+        Some(name) => name.to_string(),
+        None => english_name(strip_namespace(block)),
+    }
+}
+
+fn english_name(block: &str) -> String {
+    if let Ok(index) = EN_US_OVERRIDES.binary_search_by(|(id, _)| (*id).cmp(block)) {
+        return EN_US_OVERRIDES[index].1.to_string();
+    }
+    if let Some(unwaxed) = block.strip_prefix("waxed_") {
+        return format!("Waxed {}", english_name(unwaxed));
+    }
+
+    title_case(block)
+}
+
+fn title_case(block: &str) -> String {
+    block
+        .split('_')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// How block names are written in output meant for other tools or for
+/// people.
+///
+/// Block names are stored as the site returns them; a format is applied when
+/// they leave the crate, by [`Palette::blocks_as`](crate::Palette::blocks_as),
+/// [`CollectionStore::export_csv_as`](crate::collections::CollectionStore::export_csv_as)
+/// and the [`model`](crate::model) exporters.
+///
+/// # Examples
+///
+/// ```rust
+/// use blockpalettes_client::blocks::NameFormat;
+///
+/// assert_eq!(NameFormat::Id.apply("minecraft:oak_log"), "oak_log");
+/// assert_eq!(NameFormat::Namespaced.apply("oak_log"), "minecraft:oak_log");
+/// assert_eq!(NameFormat::TitleCase.apply("polished_blackstone_bricks"), "Polished Blackstone Bricks");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NameFormat {
+    /// The block ID without namespace, as used by the site (`oak_log`).
+    #[default]
+    Id,
+    /// The namespaced block ID, as used by commands, WorldEdit and datapacks
+    /// (`minecraft:oak_log`).
+    Namespaced,
+    /// The block ID in title case, for display (`Oak Log`).
+    ///
+    /// Unlike [`display_name`], this never uses the game's name for a block,
+    /// so `iron_block` is written `Iron Block`, not `Block of Iron`.
+    TitleCase,
+}
+
+impl NameFormat {
+    /// Writes `block`, with or without the `minecraft:` namespace, in this
+    /// format.
+    pub fn apply(self, block: &str) -> String {
+        let block = strip_namespace(block);
+        match self {
+            NameFormat::Id => block.to_string(),
+            NameFormat::Namespaced => format!("minecraft:{block}"),
+            NameFormat::TitleCase => title_case(block),
+        }
+    }
+}
+
+fn strip_namespace(block: &str) -> &str {
+    let block = block.trim();
+    block.strip_prefix("minecraft:").unwrap_or(block)
+}
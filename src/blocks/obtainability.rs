@@ -0,0 +1,137 @@
+//! How hard blocks are to obtain in survival.
+
+use super::normalize;
+use serde::{Deserialize, Serialize};
+
+/// The game mode a palette is built in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GameMode {
+    /// Blocks must be mined, crafted, traded or looted.
+    #[default]
+    Survival,
+    /// Every block is available from the inventory.
+    Creative,
+}
+
+/// How hard a block is to obtain in survival, from easiest to hardest.
+///
+/// Levels compare in that order, so `level <= Obtainability::BiomeLocked`
+/// selects blocks at most as hard to get as biome-locked ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Obtainability {
+    /// Mined, crafted or smelted from materials found almost anywhere.
+    Common,
+    /// Only found in specific biomes, such as cherry groves or badlands.
+    BiomeLocked,
+    /// Obtained by bartering with piglins or trading with villagers, or
+    /// looted from their structures.
+    Bartering,
+    /// Requires late-game progression or extensive grinding: the End, ocean
+    /// monuments, netherite or large amounts of rare materials.
+    Grind,
+    /// Cannot be obtained as an item in survival at all.
+    CreativeOnly,
+}
+
+impl Obtainability {
+    /// Returns the score of the level, from `1.0` for [`Common`](Self::Common)
+    /// blocks down to `0.0` for [`CreativeOnly`](Self::CreativeOnly) ones.
+    pub fn score(self) -> f64 {
+        match self {
+            Obtainability::Common => 1.0,
+            Obtainability::BiomeLocked => 0.75,
+            Obtainability::Bartering => 0.5,
+            Obtainability::Grind => 0.25,
+            Obtainability::CreativeOnly => 0.0,
+        }
+    }
+}
+
+/// Blocks whose obtainability does not follow from their ID, sorted by ID.
+static OVERRIDES: &[(&str, Obtainability)] = &[
+    ("ancient_debris", Obtainability::Grind),
+    ("barrier", Obtainability::CreativeOnly),
+    ("beacon", Obtainability::Grind),
+    ("bedrock", Obtainability::CreativeOnly),
+    ("blue_ice", Obtainability::BiomeLocked),
+    ("budding_amethyst", Obtainability::CreativeOnly),
+    ("chain_command_block", Obtainability::CreativeOnly),
+    ("chorus_flower", Obtainability::Grind),
+    ("chorus_plant", Obtainability::CreativeOnly),
+    ("command_block", Obtainability::CreativeOnly),
+    ("crying_obsidian", Obtainability::Bartering),
+    ("diamond_block", Obtainability::Grind),
+    ("dirt_path", Obtainability::CreativeOnly),
+    ("dragon_egg", Obtainability::Grind),
+    ("emerald_block", Obtainability::Bartering),
+    ("end_portal_frame", Obtainability::CreativeOnly),
+    ("farmland", Obtainability::CreativeOnly),
+    ("frogspawn", Obtainability::CreativeOnly),
+    ("gilded_blackstone", Obtainability::Bartering),
+    ("ice", Obtainability::BiomeLocked),
+    ("jigsaw", Obtainability::CreativeOnly),
+    ("light", Obtainability::CreativeOnly),
+    ("mud", Obtainability::BiomeLocked),
+    ("mycelium", Obtainability::BiomeLocked),
+    ("netherite_block", Obtainability::Grind),
+    ("packed_ice", Obtainability::BiomeLocked),
+    ("petrified_oak_slab", Obtainability::CreativeOnly),
+    ("podzol", Obtainability::BiomeLocked),
+    ("red_sand", Obtainability::BiomeLocked),
+    ("reinforced_deepslate", Obtainability::CreativeOnly),
+    ("repeating_command_block", Obtainability::CreativeOnly),
+    ("sculk", Obtainability::BiomeLocked),
+    ("sea_lantern", Obtainability::Grind),
+    ("spawner", Obtainability::CreativeOnly),
+    ("sponge", Obtainability::Grind),
+    ("structure_block", Obtainability::CreativeOnly),
+    ("structure_void", Obtainability::CreativeOnly),
+    ("trial_spawner", Obtainability::CreativeOnly),
+    ("vault", Obtainability::CreativeOnly),
+    ("wet_sponge", Obtainability::Grind),
+];
+
+/// Obtainability given by parts of block IDs, checked in order.
+const PATTERNS: &[(&str, Obtainability)] = &[
+    ("infested_", Obtainability::CreativeOnly),
+    ("purpur", Obtainability::Grind),
+    ("end_stone", Obtainability::Grind),
+    ("prismarine", Obtainability::Grind),
+    ("cherry_", Obtainability::BiomeLocked),
+    ("pale_oak_", Obtainability::BiomeLocked),
+    ("mangrove_", Obtainability::BiomeLocked),
+    ("packed_mud", Obtainability::BiomeLocked),
+    ("mud_brick", Obtainability::BiomeLocked),
+    ("bamboo", Obtainability::BiomeLocked),
+    ("red_sandstone", Obtainability::BiomeLocked),
+];
+
+/// Returns how hard a block is to obtain in survival.
+///
+/// Known hard-to-get blocks are listed in a table, and block families such
+/// as purpur, prismarine or cherry wood follow from their ID. Anything else,
+/// including unknown blocks, is assumed to be [`Obtainability::Common`].
+///
+/// # Examples
+///
+/// ```rust
+/// use blockpalettes_client::blocks::{self, Obtainability};
+///
+/// assert_eq!(blocks::obtainability("stone"), Obtainability::Common);
+/// assert_eq!(blocks::obtainability("cherry_planks"), Obtainability::BiomeLocked);
+/// assert_eq!(blocks::obtainability("minecraft:crying_obsidian"), Obtainability::Bartering);
+/// assert_eq!(blocks::obtainability("purpur_pillar"), Obtainability::Grind);
+/// assert_eq!(blocks::obtainability("infested_stone_bricks"), Obtainability::CreativeOnly);
+/// ```
+pub fn obtainability(block: &str) -> Obtainability {
+    let block = normalize(block);
+    if let Ok(index) = OVERRIDES.binary_search_by(|(name, _)| (*name).cmp(block)) {
+        return OVERRIDES[index].1;
+    }
+    PATTERNS
+        .iter()
+        .find(|(pattern, _)| block.contains(pattern))
+        .map_or(Obtainability::Common, |(_, level)| *level)
+}
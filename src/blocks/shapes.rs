@@ -0,0 +1,128 @@
+//! The shapes of blocks: full cubes, slabs, stairs and the like.
+
+use super::normalize;
+use serde::{Deserialize, Serialize};
+
+/// The shape of a block.
+///
+/// Palettes on the site may contain blocks that are not full cubes, which
+/// tools placing palettes in a world (schematic exporters, wall generators)
+/// usually cannot use as-is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BlockShape {
+    /// A full cube, such as stone or planks.
+    Full,
+    /// A half-height slab.
+    Slab,
+    /// Stairs.
+    Stairs,
+    /// A wall post, such as a cobblestone wall.
+    Wall,
+    /// A fence post.
+    Fence,
+    /// A fence gate.
+    FenceGate,
+    /// A two-block-high door.
+    Door,
+    /// A trapdoor.
+    Trapdoor,
+    /// A glass pane or iron bars.
+    Pane,
+    /// A button.
+    Button,
+    /// A pressure plate.
+    PressurePlate,
+    /// A carpet.
+    Carpet,
+    /// Any other partial block, such as lanterns, chains or snow layers.
+    Other,
+}
+
+impl BlockShape {
+    /// Returns `true` for full cubes.
+    pub fn is_full(self) -> bool {
+        self == BlockShape::Full
+    }
+}
+
+/// Shapes given by the end of block IDs, checked in order.
+const SUFFIXES: &[(&str, BlockShape)] = &[
+    ("_slab", BlockShape::Slab),
+    ("_stairs", BlockShape::Stairs),
+    ("_wall", BlockShape::Wall),
+    ("_fence_gate", BlockShape::FenceGate),
+    ("_fence", BlockShape::Fence),
+    ("_trapdoor", BlockShape::Trapdoor),
+    ("_door", BlockShape::Door),
+    ("_pane", BlockShape::Pane),
+    ("_button", BlockShape::Button),
+    ("_pressure_plate", BlockShape::PressurePlate),
+    ("_carpet", BlockShape::Carpet),
+    ("_banner", BlockShape::Other),
+    ("_bed", BlockShape::Other),
+    ("_candle", BlockShape::Other),
+    ("_head", BlockShape::Other),
+    ("_lantern", BlockShape::Other),
+    ("_sapling", BlockShape::Other),
+    ("_sign", BlockShape::Other),
+    ("_skull", BlockShape::Other),
+    ("_torch", BlockShape::Other),
+];
+
+/// Blocks whose shape does not follow from their ID, sorted by ID.
+static OVERRIDES: &[(&str, BlockShape)] = &[
+    ("cake", BlockShape::Other),
+    ("candle", BlockShape::Other),
+    ("chain", BlockShape::Other),
+    ("cobweb", BlockShape::Other),
+    ("daylight_detector", BlockShape::Other),
+    ("dirt_path", BlockShape::Other),
+    ("enchanting_table", BlockShape::Other),
+    ("end_rod", BlockShape::Other),
+    ("farmland", BlockShape::Other),
+    ("iron_bars", BlockShape::Pane),
+    ("jack_o_lantern", BlockShape::Full),
+    ("ladder", BlockShape::Other),
+    ("lantern", BlockShape::Other),
+    ("lectern", BlockShape::Other),
+    ("lever", BlockShape::Other),
+    ("lightning_rod", BlockShape::Other),
+    ("scaffolding", BlockShape::Other),
+    ("sea_lantern", BlockShape::Full),
+    ("snow", BlockShape::Other),
+    ("stonecutter", BlockShape::Other),
+    ("torch", BlockShape::Other),
+];
+
+/// Returns the shape of a block.
+///
+/// Shapes follow from the block ID (`_slab`, `_stairs`, `_wall`, ...), plus a
+/// table of partial blocks whose ID does not tell. Anything else, including
+/// unknown blocks, is assumed to be a full cube.
+///
+/// # Examples
+///
+/// ```rust
+/// use blockpalettes_client::blocks::{self, BlockShape};
+///
+/// assert_eq!(blocks::shape("stone"), BlockShape::Full);
+/// assert_eq!(blocks::shape("minecraft:oak_slab"), BlockShape::Slab);
+/// assert_eq!(blocks::shape("spruce_door"), BlockShape::Door);
+/// assert_eq!(blocks::shape("spruce_trapdoor"), BlockShape::Trapdoor);
+/// assert_eq!(blocks::shape("mossy_cobblestone_wall"), BlockShape::Wall);
+/// assert_eq!(blocks::shape("waxed_cut_copper_stairs"), BlockShape::Stairs);
+/// assert_eq!(blocks::shape("iron_bars"), BlockShape::Pane);
+/// assert_eq!(blocks::shape("sea_lantern"), BlockShape::Full);
+/// assert!(!blocks::shape("chain").is_full());
+/// ```
+pub fn shape(block: &str) -> BlockShape {
+    let block = normalize(block);
+    if let Ok(index) = OVERRIDES.binary_search_by(|(name, _)| (*name).cmp(block)) {
+        return OVERRIDES[index].1;
+    }
+    SUFFIXES
+        .iter()
+        .find(|(suffix, _)| block.ends_with(suffix))
+        .map_or(BlockShape::Full, |(_, shape)| *shape)
+}
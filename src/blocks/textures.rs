@@ -0,0 +1,54 @@
+//! Bundled block textures, rendered from the block color table.
+
+use super::color;
+use crate::color::Rgb;
+use crate::png;
+
+/// The width and height of bundled textures, in pixels.
+pub const TEXTURE_SIZE: u32 = 16;
+
+/// How much the edges of a texture are lightened or darkened.
+const EDGE_SHADE: f32 = 0.2;
+
+/// Returns the texture of a block face as a PNG image, or `None` if the
+/// block's color is unknown.
+///
+/// Minecraft's own textures cannot be redistributed, so textures are swatches
+/// rendered from the average texture color of the block (see [`color`]): a
+/// face of [`TEXTURE_SIZE`] pixels in the block's color, with a light
+/// top-left edge and a dark bottom-right edge so neighbouring blocks stay
+/// apart in a grid.
+///
+/// # Examples
+///
+/// ```rust
+/// use blockpalettes_client::blocks;
+///
+/// let png = blocks::texture_png("oak_planks").unwrap();
+/// assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+/// assert!(blocks::texture_png("not_a_block").is_none());
+/// ```
+pub fn texture_png(block: &str) -> Option<Vec<u8>> {
+    let base = color(block)?;
+    let last = TEXTURE_SIZE - 1;
+
+    let mut pixels = Vec::with_capacity((TEXTURE_SIZE * TEXTURE_SIZE * 3) as usize);
+    for y in 0..TEXTURE_SIZE {
+        for x in 0..TEXTURE_SIZE {
+            let pixel = if x == last || y == last {
+                shade(base, 1.0 - EDGE_SHADE)
+            } else if x == 0 || y == 0 {
+                shade(base, 1.0 + EDGE_SHADE)
+            } else {
+                base
+            };
+            pixels.extend_from_slice(&[pixel.r, pixel.g, pixel.b]);
+        }
+    }
+    Some(png::encode_rgb(TEXTURE_SIZE, TEXTURE_SIZE, &pixels))
+}
+
+fn shade(color: Rgb, factor: f32) -> Rgb {
+    let channel = |c: u8| (f32::from(c) * factor).round().clamp(0.0, 255.0) as u8;
+    Rgb::new(channel(color.r), channel(color.g), channel(color.b))
+}
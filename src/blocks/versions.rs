@@ -0,0 +1,485 @@
+//! The Minecraft version each block was introduced in.
+
+use super::normalize;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// A Minecraft: Java Edition release, such as `1.17` or `1.21.4`.
+///
+/// Versions compare in release order. They serialize as strings.
+///
+/// # Examples
+///
+/// ```rust
+/// use blockpalettes_client::blocks::GameVersion;
+///
+/// let version: GameVersion = "1.21.4".parse().unwrap();
+/// assert_eq!(version, GameVersion::new(21, 4));
+/// assert_eq!(version.to_string(), "1.21.4");
+/// assert_eq!(GameVersion::new(18, 0).to_string(), "1.18");
+/// assert!(GameVersion::new(18, 2) < GameVersion::new(19, 0));
+/// assert!("1.x".parse::<GameVersion>().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub struct GameVersion {
+    /// The minor version, e.g. `21` in `1.21.4`.
+    pub minor: u16,
+    /// The patch version, e.g. `4` in `1.21.4`; `0` for the first release.
+    pub patch: u16,
+}
+
+impl GameVersion {
+    /// Returns the version `1.minor.patch`.
+    pub const fn new(minor: u16, patch: u16) -> Self {
+        Self { minor, patch }
+    }
+
+    /// Returns the date the version was released, if known.
+    ///
+    /// Dates are known for the versions blocks were introduced in, and for
+    /// other major releases. A version without a patch number dates from its
+    /// first release, e.g. `1.2.1` for `1.2`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use blockpalettes_client::blocks::GameVersion;
+    /// use chrono::NaiveDate;
+    ///
+    /// assert_eq!(GameVersion::new(20, 0).release_date(), NaiveDate::from_ymd_opt(2023, 6, 7));
+    /// assert_eq!(GameVersion::new(20, 3).release_date(), None);
+    /// ```
+    pub fn release_date(&self) -> Option<NaiveDate> {
+        RELEASES
+            .binary_search_by(|(version, _)| version.cmp(self))
+            .ok()
+            .and_then(|index| {
+                let (year, month, day) = RELEASES[index].1;
+                NaiveDate::from_ymd_opt(year, month, day)
+            })
+    }
+}
+
+/// The release dates of Java Edition versions, sorted by version.
+static RELEASES: &[(GameVersion, (i32, u32, u32))] = &[
+    (GameVersion::new(0, 0), (2011, 11, 18)),
+    (GameVersion::new(1, 0), (2012, 1, 12)),
+    (GameVersion::new(2, 0), (2012, 3, 1)),
+    (GameVersion::new(3, 0), (2012, 8, 1)),
+    (GameVersion::new(4, 0), (2012, 10, 25)),
+    (GameVersion::new(5, 0), (2013, 3, 13)),
+    (GameVersion::new(6, 0), (2013, 7, 1)),
+    (GameVersion::new(7, 0), (2013, 10, 25)),
+    (GameVersion::new(8, 0), (2014, 9, 2)),
+    (GameVersion::new(9, 0), (2016, 2, 29)),
+    (GameVersion::new(10, 0), (2016, 6, 8)),
+    (GameVersion::new(11, 0), (2016, 11, 14)),
+    (GameVersion::new(12, 0), (2017, 6, 7)),
+    (GameVersion::new(13, 0), (2018, 7, 18)),
+    (GameVersion::new(14, 0), (2019, 4, 23)),
+    (GameVersion::new(15, 0), (2019, 12, 10)),
+    (GameVersion::new(16, 0), (2020, 6, 23)),
+    (GameVersion::new(17, 0), (2021, 6, 8)),
+    (GameVersion::new(18, 0), (2021, 11, 30)),
+    (GameVersion::new(18, 2), (2022, 2, 28)),
+    (GameVersion::new(19, 0), (2022, 6, 7)),
+    (GameVersion::new(20, 0), (2023, 6, 7)),
+    (GameVersion::new(21, 0), (2024, 6, 13)),
+    (GameVersion::new(21, 2), (2024, 10, 22)),
+    (GameVersion::new(21, 4), (2024, 12, 3)),
+];
+
+impl fmt::Display for GameVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.patch {
+            0 => write!(f, "1.{}", self.minor),
+            patch => write!(f, "1.{}.{patch}", self.minor),
+        }
+    }
+}
+
+/// The error returned when parsing a [`GameVersion`] fails.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("invalid Minecraft version `{0}`, expected e.g. `1.20` or `1.20.4`")]
+pub struct ParseGameVersionError(String);
+
+impl FromStr for GameVersion {
+    type Err = ParseGameVersionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ParseGameVersionError(s.to_string());
+        let rest = s.trim().strip_prefix("1.").ok_or_else(invalid)?;
+        let (minor, patch) = rest.split_once('.').unwrap_or((rest, "0"));
+        Ok(Self {
+            minor: minor.parse().map_err(|_| invalid())?,
+            patch: patch.parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+impl From<GameVersion> for String {
+    fn from(version: GameVersion) -> Self {
+        version.to_string()
+    }
+}
+
+impl TryFrom<String> for GameVersion {
+    type Error = ParseGameVersionError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+/// The version each block with a known color was introduced in, sorted by
+/// block name for binary search.
+///
+/// Blocks from before 1.0 (Alpha and Beta) are listed as `1.0`. Blocks that
+/// only got their current ID later, such as `mushroom_stem`, use the version
+/// the block itself appeared in.
+static VERSIONS: &[(&str, GameVersion)] = &[
+    ("acacia_log", GameVersion::new(7, 0)),
+    ("acacia_planks", GameVersion::new(7, 0)),
+    ("acacia_wood", GameVersion::new(13, 0)),
+    ("amethyst_block", GameVersion::new(17, 0)),
+    ("andesite", GameVersion::new(8, 0)),
+    ("bamboo_block", GameVersion::new(20, 0)),
+    ("bamboo_mosaic", GameVersion::new(20, 0)),
+    ("bamboo_planks", GameVersion::new(20, 0)),
+    ("basalt", GameVersion::new(16, 0)),
+    ("birch_log", GameVersion::new(0, 0)),
+    ("birch_planks", GameVersion::new(2, 0)),
+    ("birch_wood", GameVersion::new(13, 0)),
+    ("black_concrete", GameVersion::new(12, 0)),
+    ("black_concrete_powder", GameVersion::new(12, 0)),
+    ("black_glazed_terracotta", GameVersion::new(12, 0)),
+    ("black_stained_glass", GameVersion::new(7, 0)),
+    ("black_terracotta", GameVersion::new(6, 0)),
+    ("black_wool", GameVersion::new(0, 0)),
+    ("blackstone", GameVersion::new(16, 0)),
+    ("blue_concrete", GameVersion::new(12, 0)),
+    ("blue_concrete_powder", GameVersion::new(12, 0)),
+    ("blue_glazed_terracotta", GameVersion::new(12, 0)),
+    ("blue_ice", GameVersion::new(13, 0)),
+    ("blue_stained_glass", GameVersion::new(7, 0)),
+    ("blue_terracotta", GameVersion::new(6, 0)),
+    ("blue_wool", GameVersion::new(0, 0)),
+    ("bone_block", GameVersion::new(10, 0)),
+    ("bookshelf", GameVersion::new(0, 0)),
+    ("bricks", GameVersion::new(0, 0)),
+    ("brown_concrete", GameVersion::new(12, 0)),
+    ("brown_concrete_powder", GameVersion::new(12, 0)),
+    ("brown_glazed_terracotta", GameVersion::new(12, 0)),
+    ("brown_mushroom_block", GameVersion::new(0, 0)),
+    ("brown_stained_glass", GameVersion::new(7, 0)),
+    ("brown_terracotta", GameVersion::new(6, 0)),
+    ("brown_wool", GameVersion::new(0, 0)),
+    ("calcite", GameVersion::new(17, 0)),
+    ("cherry_log", GameVersion::new(20, 0)),
+    ("cherry_planks", GameVersion::new(20, 0)),
+    ("cherry_wood", GameVersion::new(20, 0)),
+    ("chiseled_deepslate", GameVersion::new(17, 0)),
+    ("chiseled_nether_bricks", GameVersion::new(14, 0)),
+    ("chiseled_polished_blackstone", GameVersion::new(16, 0)),
+    ("chiseled_quartz_block", GameVersion::new(5, 0)),
+    ("chiseled_red_sandstone", GameVersion::new(8, 0)),
+    ("chiseled_sandstone", GameVersion::new(2, 0)),
+    ("chiseled_stone_bricks", GameVersion::new(2, 0)),
+    ("chiseled_tuff", GameVersion::new(21, 0)),
+    ("clay", GameVersion::new(0, 0)),
+    ("coal_block", GameVersion::new(5, 0)),
+    ("coarse_dirt", GameVersion::new(8, 0)),
+    ("cobbled_deepslate", GameVersion::new(17, 0)),
+    ("cobblestone", GameVersion::new(0, 0)),
+    ("copper_block", GameVersion::new(17, 0)),
+    ("cracked_deepslate_bricks", GameVersion::new(17, 0)),
+    ("cracked_deepslate_tiles", GameVersion::new(17, 0)),
+    ("cracked_nether_bricks", GameVersion::new(16, 0)),
+    (
+        "cracked_polished_blackstone_bricks",
+        GameVersion::new(16, 0),
+    ),
+    ("cracked_stone_bricks", GameVersion::new(0, 0)),
+    ("crafting_table", GameVersion::new(0, 0)),
+    ("crimson_hyphae", GameVersion::new(16, 0)),
+    ("crimson_planks", GameVersion::new(16, 0)),
+    ("crimson_stem", GameVersion::new(16, 0)),
+    ("crying_obsidian", GameVersion::new(16, 0)),
+    ("cut_copper", GameVersion::new(17, 0)),
+    ("cut_red_sandstone", GameVersion::new(8, 0)),
+    ("cut_sandstone", GameVersion::new(2, 0)),
+    ("cyan_concrete", GameVersion::new(12, 0)),
+    ("cyan_concrete_powder", GameVersion::new(12, 0)),
+    ("cyan_glazed_terracotta", GameVersion::new(12, 0)),
+    ("cyan_stained_glass", GameVersion::new(7, 0)),
+    ("cyan_terracotta", GameVersion::new(6, 0)),
+    ("cyan_wool", GameVersion::new(0, 0)),
+    ("dark_oak_log", GameVersion::new(7, 0)),
+    ("dark_oak_planks", GameVersion::new(7, 0)),
+    ("dark_oak_wood", GameVersion::new(13, 0)),
+    ("dark_prismarine", GameVersion::new(8, 0)),
+    ("deepslate", GameVersion::new(17, 0)),
+    ("deepslate_bricks", GameVersion::new(17, 0)),
+    ("deepslate_tiles", GameVersion::new(17, 0)),
+    ("diamond_block", GameVersion::new(0, 0)),
+    ("diorite", GameVersion::new(8, 0)),
+    ("dirt", GameVersion::new(0, 0)),
+    ("dried_kelp_block", GameVersion::new(13, 0)),
+    ("dripstone_block", GameVersion::new(17, 0)),
+    ("emerald_block", GameVersion::new(3, 0)),
+    ("end_stone", GameVersion::new(0, 0)),
+    ("end_stone_bricks", GameVersion::new(9, 0)),
+    ("exposed_copper", GameVersion::new(17, 0)),
+    ("exposed_cut_copper", GameVersion::new(17, 0)),
+    ("gilded_blackstone", GameVersion::new(16, 0)),
+    ("glass", GameVersion::new(0, 0)),
+    ("glowstone", GameVersion::new(0, 0)),
+    ("gold_block", GameVersion::new(0, 0)),
+    ("granite", GameVersion::new(8, 0)),
+    ("grass_block", GameVersion::new(0, 0)),
+    ("gravel", GameVersion::new(0, 0)),
+    ("gray_concrete", GameVersion::new(12, 0)),
+    ("gray_concrete_powder", GameVersion::new(12, 0)),
+    ("gray_glazed_terracotta", GameVersion::new(12, 0)),
+    ("gray_stained_glass", GameVersion::new(7, 0)),
+    ("gray_terracotta", GameVersion::new(6, 0)),
+    ("gray_wool", GameVersion::new(0, 0)),
+    ("green_concrete", GameVersion::new(12, 0)),
+    ("green_concrete_powder", GameVersion::new(12, 0)),
+    ("green_glazed_terracotta", GameVersion::new(12, 0)),
+    ("green_stained_glass", GameVersion::new(7, 0)),
+    ("green_terracotta", GameVersion::new(6, 0)),
+    ("green_wool", GameVersion::new(0, 0)),
+    ("hay_block", GameVersion::new(6, 0)),
+    ("honey_block", GameVersion::new(15, 0)),
+    ("honeycomb_block", GameVersion::new(15, 0)),
+    ("ice", GameVersion::new(0, 0)),
+    ("iron_block", GameVersion::new(0, 0)),
+    ("jack_o_lantern", GameVersion::new(0, 0)),
+    ("jungle_log", GameVersion::new(2, 0)),
+    ("jungle_planks", GameVersion::new(2, 0)),
+    ("jungle_wood", GameVersion::new(13, 0)),
+    ("lapis_block", GameVersion::new(0, 0)),
+    ("light_blue_concrete", GameVersion::new(12, 0)),
+    ("light_blue_concrete_powder", GameVersion::new(12, 0)),
+    ("light_blue_glazed_terracotta", GameVersion::new(12, 0)),
+    ("light_blue_stained_glass", GameVersion::new(7, 0)),
+    ("light_blue_terracotta", GameVersion::new(6, 0)),
+    ("light_blue_wool", GameVersion::new(0, 0)),
+    ("light_gray_concrete", GameVersion::new(12, 0)),
+    ("light_gray_concrete_powder", GameVersion::new(12, 0)),
+    ("light_gray_glazed_terracotta", GameVersion::new(12, 0)),
+    ("light_gray_stained_glass", GameVersion::new(7, 0)),
+    ("light_gray_terracotta", GameVersion::new(6, 0)),
+    ("light_gray_wool", GameVersion::new(0, 0)),
+    ("lime_concrete", GameVersion::new(12, 0)),
+    ("lime_concrete_powder", GameVersion::new(12, 0)),
+    ("lime_glazed_terracotta", GameVersion::new(12, 0)),
+    ("lime_stained_glass", GameVersion::new(7, 0)),
+    ("lime_terracotta", GameVersion::new(6, 0)),
+    ("lime_wool", GameVersion::new(0, 0)),
+    ("lodestone", GameVersion::new(16, 0)),
+    ("magenta_concrete", GameVersion::new(12, 0)),
+    ("magenta_concrete_powder", GameVersion::new(12, 0)),
+    ("magenta_glazed_terracotta", GameVersion::new(12, 0)),
+    ("magenta_stained_glass", GameVersion::new(7, 0)),
+    ("magenta_terracotta", GameVersion::new(6, 0)),
+    ("magenta_wool", GameVersion::new(0, 0)),
+    ("magma_block", GameVersion::new(10, 0)),
+    ("mangrove_log", GameVersion::new(19, 0)),
+    ("mangrove_planks", GameVersion::new(19, 0)),
+    ("mangrove_wood", GameVersion::new(19, 0)),
+    ("melon", GameVersion::new(0, 0)),
+    ("moss_block", GameVersion::new(17, 0)),
+    ("mossy_cobblestone", GameVersion::new(0, 0)),
+    ("mossy_stone_bricks", GameVersion::new(0, 0)),
+    ("mud", GameVersion::new(19, 0)),
+    ("mud_bricks", GameVersion::new(19, 0)),
+    ("mushroom_stem", GameVersion::new(0, 0)),
+    ("mycelium", GameVersion::new(0, 0)),
+    ("nether_bricks", GameVersion::new(0, 0)),
+    ("nether_wart_block", GameVersion::new(10, 0)),
+    ("netherite_block", GameVersion::new(16, 0)),
+    ("netherrack", GameVersion::new(0, 0)),
+    ("note_block", GameVersion::new(0, 0)),
+    ("oak_log", GameVersion::new(0, 0)),
+    ("oak_planks", GameVersion::new(0, 0)),
+    ("oak_wood", GameVersion::new(13, 0)),
+    ("obsidian", GameVersion::new(0, 0)),
+    ("orange_concrete", GameVersion::new(12, 0)),
+    ("orange_concrete_powder", GameVersion::new(12, 0)),
+    ("orange_glazed_terracotta", GameVersion::new(12, 0)),
+    ("orange_stained_glass", GameVersion::new(7, 0)),
+    ("orange_terracotta", GameVersion::new(6, 0)),
+    ("orange_wool", GameVersion::new(0, 0)),
+    ("oxidized_copper", GameVersion::new(17, 0)),
+    ("oxidized_cut_copper", GameVersion::new(17, 0)),
+    ("packed_ice", GameVersion::new(7, 0)),
+    ("packed_mud", GameVersion::new(19, 0)),
+    ("pale_oak_log", GameVersion::new(21, 4)),
+    ("pale_oak_planks", GameVersion::new(21, 4)),
+    ("pale_oak_wood", GameVersion::new(21, 4)),
+    ("pink_concrete", GameVersion::new(12, 0)),
+    ("pink_concrete_powder", GameVersion::new(12, 0)),
+    ("pink_glazed_terracotta", GameVersion::new(12, 0)),
+    ("pink_stained_glass", GameVersion::new(7, 0)),
+    ("pink_terracotta", GameVersion::new(6, 0)),
+    ("pink_wool", GameVersion::new(0, 0)),
+    ("podzol", GameVersion::new(7, 0)),
+    ("polished_andesite", GameVersion::new(8, 0)),
+    ("polished_basalt", GameVersion::new(16, 0)),
+    ("polished_blackstone", GameVersion::new(16, 0)),
+    ("polished_blackstone_bricks", GameVersion::new(16, 0)),
+    ("polished_deepslate", GameVersion::new(17, 0)),
+    ("polished_diorite", GameVersion::new(8, 0)),
+    ("polished_granite", GameVersion::new(8, 0)),
+    ("polished_tuff", GameVersion::new(21, 0)),
+    ("prismarine", GameVersion::new(8, 0)),
+    ("prismarine_bricks", GameVersion::new(8, 0)),
+    ("pumpkin", GameVersion::new(0, 0)),
+    ("purple_concrete", GameVersion::new(12, 0)),
+    ("purple_concrete_powder", GameVersion::new(12, 0)),
+    ("purple_glazed_terracotta", GameVersion::new(12, 0)),
+    ("purple_stained_glass", GameVersion::new(7, 0)),
+    ("purple_terracotta", GameVersion::new(6, 0)),
+    ("purple_wool", GameVersion::new(0, 0)),
+    ("purpur_block", GameVersion::new(9, 0)),
+    ("purpur_pillar", GameVersion::new(9, 0)),
+    ("quartz_block", GameVersion::new(5, 0)),
+    ("quartz_bricks", GameVersion::new(16, 0)),
+    ("quartz_pillar", GameVersion::new(5, 0)),
+    ("raw_copper_block", GameVersion::new(17, 0)),
+    ("raw_gold_block", GameVersion::new(17, 0)),
+    ("raw_iron_block", GameVersion::new(17, 0)),
+    ("red_concrete", GameVersion::new(12, 0)),
+    ("red_concrete_powder", GameVersion::new(12, 0)),
+    ("red_glazed_terracotta", GameVersion::new(12, 0)),
+    ("red_mushroom_block", GameVersion::new(0, 0)),
+    ("red_nether_bricks", GameVersion::new(10, 0)),
+    ("red_sand", GameVersion::new(7, 0)),
+    ("red_sandstone", GameVersion::new(8, 0)),
+    ("red_stained_glass", GameVersion::new(7, 0)),
+    ("red_terracotta", GameVersion::new(6, 0)),
+    ("red_wool", GameVersion::new(0, 0)),
+    ("redstone_block", GameVersion::new(5, 0)),
+    ("rooted_dirt", GameVersion::new(17, 0)),
+    ("sand", GameVersion::new(0, 0)),
+    ("sandstone", GameVersion::new(0, 0)),
+    ("sculk", GameVersion::new(19, 0)),
+    ("sea_lantern", GameVersion::new(8, 0)),
+    ("shroomlight", GameVersion::new(16, 0)),
+    ("slime_block", GameVersion::new(8, 0)),
+    ("smooth_basalt", GameVersion::new(17, 0)),
+    ("smooth_quartz", GameVersion::new(13, 0)),
+    ("smooth_red_sandstone", GameVersion::new(13, 0)),
+    ("smooth_sandstone", GameVersion::new(13, 0)),
+    ("smooth_stone", GameVersion::new(13, 0)),
+    ("snow_block", GameVersion::new(0, 0)),
+    ("soul_sand", GameVersion::new(0, 0)),
+    ("soul_soil", GameVersion::new(16, 0)),
+    ("sponge", GameVersion::new(0, 0)),
+    ("spruce_log", GameVersion::new(0, 0)),
+    ("spruce_planks", GameVersion::new(2, 0)),
+    ("spruce_wood", GameVersion::new(13, 0)),
+    ("stone", GameVersion::new(0, 0)),
+    ("stone_bricks", GameVersion::new(0, 0)),
+    ("stripped_acacia_log", GameVersion::new(13, 0)),
+    ("stripped_acacia_wood", GameVersion::new(13, 0)),
+    ("stripped_bamboo_block", GameVersion::new(20, 0)),
+    ("stripped_birch_log", GameVersion::new(13, 0)),
+    ("stripped_birch_wood", GameVersion::new(13, 0)),
+    ("stripped_cherry_log", GameVersion::new(20, 0)),
+    ("stripped_cherry_wood", GameVersion::new(20, 0)),
+    ("stripped_crimson_hyphae", GameVersion::new(16, 0)),
+    ("stripped_crimson_stem", GameVersion::new(16, 0)),
+    ("stripped_dark_oak_log", GameVersion::new(13, 0)),
+    ("stripped_dark_oak_wood", GameVersion::new(13, 0)),
+    ("stripped_jungle_log", GameVersion::new(13, 0)),
+    ("stripped_jungle_wood", GameVersion::new(13, 0)),
+    ("stripped_mangrove_log", GameVersion::new(19, 0)),
+    ("stripped_mangrove_wood", GameVersion::new(19, 0)),
+    ("stripped_oak_log", GameVersion::new(13, 0)),
+    ("stripped_oak_wood", GameVersion::new(13, 0)),
+    ("stripped_pale_oak_log", GameVersion::new(21, 4)),
+    ("stripped_pale_oak_wood", GameVersion::new(21, 4)),
+    ("stripped_spruce_log", GameVersion::new(13, 0)),
+    ("stripped_spruce_wood", GameVersion::new(13, 0)),
+    ("stripped_warped_hyphae", GameVersion::new(16, 0)),
+    ("stripped_warped_stem", GameVersion::new(16, 0)),
+    ("target", GameVersion::new(16, 0)),
+    ("terracotta", GameVersion::new(6, 0)),
+    ("tinted_glass", GameVersion::new(17, 0)),
+    ("tnt", GameVersion::new(0, 0)),
+    ("tuff", GameVersion::new(17, 0)),
+    ("tuff_bricks", GameVersion::new(21, 0)),
+    ("warped_hyphae", GameVersion::new(16, 0)),
+    ("warped_planks", GameVersion::new(16, 0)),
+    ("warped_stem", GameVersion::new(16, 0)),
+    ("warped_wart_block", GameVersion::new(16, 0)),
+    ("weathered_copper", GameVersion::new(17, 0)),
+    ("weathered_cut_copper", GameVersion::new(17, 0)),
+    ("wet_sponge", GameVersion::new(8, 0)),
+    ("white_concrete", GameVersion::new(12, 0)),
+    ("white_concrete_powder", GameVersion::new(12, 0)),
+    ("white_glazed_terracotta", GameVersion::new(12, 0)),
+    ("white_stained_glass", GameVersion::new(7, 0)),
+    ("white_terracotta", GameVersion::new(6, 0)),
+    ("white_wool", GameVersion::new(0, 0)),
+    ("yellow_concrete", GameVersion::new(12, 0)),
+    ("yellow_concrete_powder", GameVersion::new(12, 0)),
+    ("yellow_glazed_terracotta", GameVersion::new(12, 0)),
+    ("yellow_stained_glass", GameVersion::new(7, 0)),
+    ("yellow_terracotta", GameVersion::new(6, 0)),
+    ("yellow_wool", GameVersion::new(0, 0)),
+];
+
+/// Returns the Minecraft version `block` was introduced in.
+///
+/// Versions are known for the same full blocks as [`color`](super::color);
+/// `None` is returned for anything else.
+///
+/// # Examples
+///
+/// ```rust
+/// use blockpalettes_client::blocks::{self, GameVersion};
+///
+/// assert_eq!(blocks::introduced_in("stone"), Some(GameVersion::new(0, 0)));
+/// assert_eq!(blocks::introduced_in("minecraft:cherry_planks"), Some(GameVersion::new(20, 0)));
+/// assert_eq!(blocks::introduced_in("waxed_oxidized_copper"), Some(GameVersion::new(17, 0)));
+/// assert_eq!(blocks::introduced_in("pale_oak_planks").unwrap().to_string(), "1.21.4");
+/// assert_eq!(blocks::introduced_in("not_a_block"), None);
+/// ```
+pub fn introduced_in(block: &str) -> Option<GameVersion> {
+    let block = normalize(block);
+    VERSIONS
+        .binary_search_by(|(name, _)| (*name).cmp(block))
+        .ok()
+        .map(|index| VERSIONS[index].1)
+}
+
+/// Returns the blocks introduced in `version`, in alphabetical order.
+///
+/// Only blocks known to [`introduced_in`] are listed, and only those
+/// introduced in exactly that version: `1.21` does not include the blocks of
+/// `1.21.4`.
+///
+/// # Examples
+///
+/// ```rust
+/// use blockpalettes_client::blocks::{self, GameVersion};
+///
+/// let blocks = blocks::introduced_blocks(GameVersion::new(20, 0));
+/// assert!(blocks.contains(&"cherry_planks"));
+/// assert!(blocks.iter().all(|block| blocks::introduced_in(block) == Some(GameVersion::new(20, 0))));
+/// ```
+pub fn introduced_blocks(version: GameVersion) -> Vec<&'static str> {
+    VERSIONS
+        .iter()
+        .filter(|(_, introduced)| *introduced == version)
+        .map(|(block, _)| *block)
+        .collect()
+}
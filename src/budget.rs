@@ -0,0 +1,137 @@
+//! A cap on the requests in flight across every part of an application.
+//!
+//! Batch fetchers such as
+//! [`get_palette_details_many`](crate::BlockPalettesClient::get_palette_details_many)
+//! and [`mirror::diff`](crate::mirror::diff) bound their own concurrency,
+//! but a bot running a crawl, a [`watcher`](crate::watcher) and a few
+//! hydration helpers at once still multiplies those bounds. A
+//! [`ConcurrencyBudget`] is a semaphore shared by all of them: attached to a
+//! client with
+//! [`BlockPalettesClient::with_concurrency_budget`](crate::BlockPalettesClient::with_concurrency_budget),
+//! every request the client sends holds a permit until its response has been
+//! read, so the total number of requests in flight never exceeds the budget,
+//! however many subsystems run simultaneously.
+//!
+//! Budgets are cheap to clone and clones share their permits, so a budget
+//! can be attached to several clients (e.g. one per priority or per pool)
+//! and still cap them together. Work outside the client, such as fetching
+//! images from a CDN, can take permits from the same budget with
+//! [`ConcurrencyBudget::acquire`].
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use blockpalettes_client::budget::ConcurrencyBudget;
+//! use blockpalettes_client::watcher::PaletteWatcher;
+//! use blockpalettes_client::{BlockPalettesClient, SortOrder};
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let client = BlockPalettesClient::new(reqwest::Client::new())
+//!         .with_concurrency_budget(ConcurrencyBudget::new(6));
+//!
+//!     // the crawler and the watcher share the six permits
+//!     let crawler = client.clone();
+//!     tokio::spawn(async move {
+//!         let mut walker = crawler.walk_palettes(SortOrder::Recent, 100);
+//!         while let Ok(Some(palettes)) = walker.next_page().await {
+//!             println!("crawled {} palettes", palettes.len());
+//!         }
+//!     });
+//!
+//!     let mut watcher = PaletteWatcher::new(client);
+//!     let palettes = watcher.poll().await?;
+//!     println!("{} new palettes", palettes.len());
+//!     Ok(())
+//! }
+//! ```
+
+use std::fmt;
+use std::sync::Arc;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// A shared limit on the number of requests in flight.
+///
+/// See the [module documentation](self) for details.
+#[derive(Clone)]
+pub struct ConcurrencyBudget {
+    semaphore: Arc<Semaphore>,
+    max: usize,
+}
+
+impl ConcurrencyBudget {
+    /// Creates a budget allowing at most `max` requests in flight.
+    ///
+    /// A budget of zero is raised to one, so requests always make progress.
+    pub fn new(max: usize) -> Self {
+        let max = max.clamp(1, Semaphore::MAX_PERMITS);
+        Self {
+            semaphore: Arc::new(Semaphore::new(max)),
+            max,
+        }
+    }
+
+    /// Returns the maximum number of requests in flight.
+    pub fn max(&self) -> usize {
+        self.max
+    }
+
+    /// Returns the number of permits currently held.
+    pub fn in_flight(&self) -> usize {
+        self.max - self.semaphore.available_permits()
+    }
+
+    /// Waits for a permit, which is returned to the budget when dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use blockpalettes_client::budget::ConcurrencyBudget;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let budget = ConcurrencyBudget::new(2);
+    ///     let shared = budget.clone();
+    ///
+    ///     let first = budget.acquire().await;
+    ///     let _second = shared.acquire().await;
+    ///     assert_eq!(budget.in_flight(), 2);
+    ///     assert!(budget.try_acquire().is_none());
+    ///
+    ///     drop(first);
+    ///     assert_eq!(shared.in_flight(), 1);
+    ///     assert!(budget.try_acquire().is_some());
+    /// }
+    /// ```
+    pub async fn acquire(&self) -> BudgetPermit {
+        let permit = Arc::clone(&self.semaphore)
+            .acquire_owned()
+            .await
+            .expect("budget semaphore is never closed");
+        BudgetPermit { _permit: permit }
+    }
+
+    /// Takes a permit if one is available right away.
+    pub fn try_acquire(&self) -> Option<BudgetPermit> {
+        Arc::clone(&self.semaphore)
+            .try_acquire_owned()
+            .ok()
+            .map(|permit| BudgetPermit { _permit: permit })
+    }
+}
+
+impl fmt::Debug for ConcurrencyBudget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConcurrencyBudget")
+            .field("max", &self.max)
+            .field("in_flight", &self.in_flight())
+            .finish()
+    }
+}
+
+/// A permit of a [`ConcurrencyBudget`], held while a request is in flight.
+#[derive(Debug)]
+#[must_use = "the permit is returned to the budget as soon as it is dropped"]
+pub struct BudgetPermit {
+    _permit: OwnedSemaphorePermit,
+}
@@ -0,0 +1,231 @@
+//! Per-endpoint circuit breaking.
+//!
+//! A [`CircuitBreaker`] tracks consecutive failures of every endpoint the
+//! client calls. Once an endpoint fails [`failure_threshold`] times in a row,
+//! its circuit *opens*: calls to it fail immediately with
+//! [`BlockPalettesError::CircuitOpen`](crate::BlockPalettesError::CircuitOpen)
+//! instead of hitting the network. After the [`cooldown`], the circuit is
+//! *half-open* and lets a single trial request through; if it succeeds the
+//! circuit closes again, otherwise it re-opens for another cooldown.
+//!
+//! Transport errors, `429 Too Many Requests` and server errors count as
+//! failures. Scraped pages share one endpoint regardless of the palette ID.
+//!
+//! [`failure_threshold`]: CircuitBreaker::failure_threshold
+//! [`cooldown`]: CircuitBreaker::cooldown
+//!
+//! # Examples
+//!
+//! ```rust
+//! use blockpalettes_client::BlockPalettesClient;
+//! use blockpalettes_client::circuit::{CircuitBreaker, CircuitState};
+//! use std::time::Duration;
+//!
+//! let breaker = CircuitBreaker::new()
+//!     .failure_threshold(3)
+//!     .cooldown(Duration::from_secs(60));
+//! let client = BlockPalettesClient::new(reqwest::Client::new()).with_circuit_breaker(breaker);
+//!
+//! // e.g. in a health check handler
+//! let breaker = client.circuit_breaker().unwrap();
+//! assert_eq!(breaker.state("/api/palettes/all_palettes.php"), CircuitState::Closed);
+//! assert!(breaker.health().iter().all(|h| h.state != CircuitState::Open));
+//! ```
+
+use crate::pool;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// The default number of consecutive failures that opens a circuit.
+pub const DEFAULT_FAILURE_THRESHOLD: u32 = 5;
+
+/// The default time an open circuit rejects calls before letting a trial through.
+pub const DEFAULT_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// The state of an endpoint's circuit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CircuitState {
+    /// Calls go through normally.
+    Closed,
+    /// Calls are rejected until the cooldown ends.
+    Open,
+    /// The cooldown has ended; the next call decides whether the circuit closes.
+    HalfOpen,
+}
+
+/// A snapshot of the circuit of one endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EndpointHealth {
+    /// The endpoint path (e.g., `/api/palettes/all_palettes.php`).
+    pub endpoint: String,
+    /// The current state of the circuit.
+    pub state: CircuitState,
+    /// Failures since the last success.
+    pub consecutive_failures: u32,
+    /// How long until an open circuit becomes half-open.
+    pub retry_after: Option<Duration>,
+}
+
+/// Tracks the health of every endpoint and short-circuits failing ones.
+///
+/// Attach it to a client with
+/// [`BlockPalettesClient::with_circuit_breaker`](crate::BlockPalettesClient::with_circuit_breaker).
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    cooldown: Duration,
+    endpoints: Mutex<BTreeMap<String, Endpoint>>,
+}
+
+#[derive(Debug, Default)]
+struct Endpoint {
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+    /// When the trial request of a half-open circuit was let through.
+    trial_started: Option<Instant>,
+}
+
+impl Endpoint {
+    fn state(&self, now: Instant) -> CircuitState {
+        match self.open_until {
+            None => CircuitState::Closed,
+            Some(until) if until > now => CircuitState::Open,
+            Some(_) => CircuitState::HalfOpen,
+        }
+    }
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CircuitBreaker {
+    /// Creates a circuit breaker with the default settings.
+    pub fn new() -> Self {
+        Self {
+            failure_threshold: DEFAULT_FAILURE_THRESHOLD,
+            cooldown: DEFAULT_COOLDOWN,
+            endpoints: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Sets how many consecutive failures open a circuit.
+    ///
+    /// A threshold of `0` is treated as `1`.
+    pub fn failure_threshold(mut self, failures: u32) -> Self {
+        self.failure_threshold = failures.max(1);
+        self
+    }
+
+    /// Sets how long an open circuit rejects calls.
+    pub fn cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    /// Returns the state of an endpoint's circuit.
+    ///
+    /// Endpoints that were never called are closed.
+    pub fn state(&self, endpoint: &str) -> CircuitState {
+        self.endpoints
+            .lock()
+            .unwrap()
+            .get(&endpoint_key(endpoint))
+            .map_or(CircuitState::Closed, |e| e.state(Instant::now()))
+    }
+
+    /// Returns a snapshot of every endpoint called so far, sorted by path.
+    pub fn health(&self) -> Vec<EndpointHealth> {
+        let now = Instant::now();
+        self.endpoints
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(endpoint, e)| EndpointHealth {
+                endpoint: endpoint.clone(),
+                state: e.state(now),
+                consecutive_failures: e.consecutive_failures,
+                retry_after: e
+                    .open_until
+                    .map(|until| until.saturating_duration_since(now))
+                    .filter(|d| !d.is_zero()),
+            })
+            .collect()
+    }
+
+    /// Closes every circuit and forgets all failures.
+    pub fn reset(&self) {
+        self.endpoints.lock().unwrap().clear();
+    }
+
+    /// Checks whether a call to `endpoint` may go through.
+    ///
+    /// Returns the key of the endpoint, or how long until it may be retried.
+    pub(crate) fn acquire(&self, endpoint: &str) -> Result<String, (String, Duration)> {
+        let key = endpoint_key(endpoint);
+        let now = Instant::now();
+        let mut endpoints = self.endpoints.lock().unwrap();
+        let Some(state) = endpoints.get_mut(&key) else {
+            return Ok(key);
+        };
+
+        match state.state(now) {
+            CircuitState::Closed => Ok(key),
+            CircuitState::Open => {
+                let until = state.open_until.unwrap_or(now);
+                Err((key, until.saturating_duration_since(now)))
+            }
+            CircuitState::HalfOpen => {
+                // a trial that never reported back (e.g. a dropped future)
+                // does not block the circuit forever
+                let trial_pending = state
+                    .trial_started
+                    .is_some_and(|started| now.duration_since(started) < self.cooldown);
+                if trial_pending {
+                    Err((key, Duration::ZERO))
+                } else {
+                    state.trial_started = Some(now);
+                    Ok(key)
+                }
+            }
+        }
+    }
+
+    /// Records the outcome of a call to the endpoint returned by [`acquire`](Self::acquire).
+    pub(crate) fn record(&self, key: String, outcome: Result<StatusCode, ()>) {
+        let now = Instant::now();
+        let mut endpoints = self.endpoints.lock().unwrap();
+        let state = endpoints.entry(key).or_default();
+
+        if !pool::is_failure(outcome) {
+            *state = Endpoint::default();
+            return;
+        }
+
+        state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+        let trial_failed = state.trial_started.take().is_some();
+        if trial_failed || state.consecutive_failures >= self.failure_threshold {
+            state.open_until = Some(now + self.cooldown);
+        }
+    }
+}
+
+/// Maps a request path to its endpoint, replacing numeric segments such as
+/// palette IDs with `{id}`.
+fn endpoint_key(path: &str) -> String {
+    path.split('/')
+        .map(|segment| {
+            if !segment.is_empty() && segment.bytes().all(|b| b.is_ascii_digit()) {
+                "{id}"
+            } else {
+                segment
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
@@ -0,0 +1,139 @@
+//! Request coalescing for hot endpoints.
+//!
+//! Bots often receive bursts of identical commands: a dozen users asking for
+//! the popular blocks within the same second each trigger the same upstream
+//! request. A [`Coalescer`] lets identical requests (same endpoint and query)
+//! to selected endpoints share work: a request made while an identical one is
+//! in flight waits for it instead of being sent, and a successful response is
+//! reused for every identical request made within the endpoint's window after
+//! it completes.
+//!
+//! Failed requests are not shared: callers waiting on a request that failed
+//! send their own. Unlike the [`HttpCache`](crate::HttpCache), which honours
+//! the server's caching headers, windows are meant to be short (tens of
+//! milliseconds), so coalesced results are never noticeably stale.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use blockpalettes_client::BlockPalettesClient;
+//! use blockpalettes_client::coalesce::Coalescer;
+//! use std::time::Duration;
+//!
+//! let coalescer = Coalescer::new()
+//!     .endpoint("/api/palettes/popular-blocks.php", Duration::from_millis(50))
+//!     .endpoint("/api/palettes/search-block.php", Duration::from_millis(20));
+//! assert_eq!(
+//!     coalescer.window("/api/palettes/popular-blocks.php"),
+//!     Some(Duration::from_millis(50))
+//! );
+//! assert_eq!(coalescer.window("/api/palettes/all_palettes.php"), None);
+//!
+//! let client = BlockPalettesClient::new(reqwest::Client::new()).with_coalescer(coalescer);
+//! ```
+
+use crate::HttpResponse;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// The window used by [`Coalescer::hot_endpoints`].
+pub const DEFAULT_COALESCE_WINDOW: Duration = Duration::from_millis(50);
+
+/// The endpoints coalesced by [`Coalescer::hot_endpoints`].
+pub const HOT_ENDPOINTS: &[&str] = &[
+    "/api/palettes/popular-blocks.php",
+    "/api/palettes/search-block.php",
+];
+
+/// The latest response to one request and when it completed, locked while
+/// the request is in flight.
+type Slot = Arc<tokio::sync::Mutex<Option<(Instant, HttpResponse)>>>;
+
+/// Shares identical concurrent requests to selected endpoints.
+///
+/// Attach it to a client with
+/// [`BlockPalettesClient::with_coalescer`](crate::BlockPalettesClient::with_coalescer).
+/// Clones of the client share the coalescer.
+#[derive(Debug, Default)]
+pub struct Coalescer {
+    windows: HashMap<String, Duration>,
+    slots: Mutex<HashMap<String, (Duration, Slot)>>,
+}
+
+impl Coalescer {
+    /// Creates a coalescer that does not coalesce any endpoint yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a coalescer for the [`HOT_ENDPOINTS`], with the
+    /// [`DEFAULT_COALESCE_WINDOW`].
+    pub fn hot_endpoints() -> Self {
+        HOT_ENDPOINTS.iter().fold(Self::new(), |coalescer, path| {
+            coalescer.endpoint(*path, DEFAULT_COALESCE_WINDOW)
+        })
+    }
+
+    /// Coalesces requests to the endpoint at `path` (e.g.,
+    /// `/api/palettes/popular-blocks.php`), reusing responses for `window`.
+    ///
+    /// A zero window only shares requests that are in flight at the same time.
+    pub fn endpoint(mut self, path: impl Into<String>, window: Duration) -> Self {
+        self.windows.insert(path.into(), window);
+        self
+    }
+
+    /// Returns the window of the endpoint at `path`, if it is coalesced.
+    pub fn window(&self, path: &str) -> Option<Duration> {
+        self.windows.get(path).copied()
+    }
+
+    /// Returns the response to the request identified by `key`, sending it
+    /// with `send` unless an identical request is in flight or completed
+    /// within `window`.
+    ///
+    /// The flag is `true` if the response was shared.
+    pub(crate) async fn run<F, Fut>(
+        &self,
+        key: String,
+        window: Duration,
+        send: F,
+    ) -> crate::Result<(HttpResponse, bool)>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = crate::Result<HttpResponse>>,
+    {
+        let arrived = Instant::now();
+        let slot = {
+            let mut slots = self.slots.lock().unwrap();
+            // forget idle slots whose response went stale
+            slots.retain(|_, (window, slot)| {
+                Arc::strong_count(slot) > 1
+                    || slot.try_lock().map_or(true, |latest| {
+                        latest
+                            .as_ref()
+                            .is_some_and(|(at, _)| at.elapsed() < *window)
+                    })
+            });
+            Arc::clone(
+                &slots
+                    .entry(key)
+                    .or_insert_with(|| (window, Slot::default()))
+                    .1,
+            )
+        };
+
+        let mut latest = slot.lock().await;
+        // a response completed after we arrived was in flight when we did
+        if let Some((at, response)) = &*latest
+            && (*at >= arrived || at.elapsed() < window)
+        {
+            return Ok((response.clone(), true));
+        }
+        let response = send().await?;
+        *latest = Some((Instant::now(), response.clone()));
+        Ok((response, false))
+    }
+}
@@ -0,0 +1,389 @@
+//! Locally curated collections of palettes.
+//!
+//! A [`Collection`] is a named list of palette IDs, each with an optional note
+//! and a set of tags. Collections are grouped in a [`CollectionStore`], which
+//! can be persisted to a JSON file, refreshed from the API with
+//! [`Collection::sync`], and exported as JSON or CSV.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use blockpalettes_client::collections::CollectionStore;
+//!
+//! let mut store = CollectionStore::new();
+//! let favorites = store.create("favorites");
+//! favorites.add(56655).note = Some("great for castles".into());
+//! favorites.add(12345).tags.insert("nether".into());
+//!
+//! assert_eq!(store.get("favorites").unwrap().ids(), vec![56655, 12345]);
+//! assert_eq!(store.get("favorites").unwrap().tagged("nether").count(), 1);
+//! ```
+
+use crate::blocks::NameFormat;
+use crate::dataset::{self, SchemaVersion};
+use crate::{BlockPalettesClient, BlockPalettesError, PaletteDetails, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+/// A single palette saved in a [`Collection`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectionEntry {
+    /// The ID of the saved palette.
+    pub palette_id: u64,
+    /// A free-form note attached to the palette.
+    #[serde(default)]
+    pub note: Option<String>,
+    /// Tags attached to the palette.
+    #[serde(default)]
+    pub tags: BTreeSet<String>,
+    /// The latest palette data retrieved by [`Collection::sync`], if any.
+    #[serde(default)]
+    pub palette: Option<PaletteDetails>,
+    /// When the palette data was last synced (`YYYY-MM-DD HH:MM:SS`, UTC).
+    #[serde(default)]
+    pub synced_at: Option<String>,
+}
+
+impl CollectionEntry {
+    /// Creates an entry for `palette_id` with no note, tags or synced data.
+    pub fn new(palette_id: u64) -> Self {
+        Self {
+            palette_id,
+            note: None,
+            tags: BTreeSet::new(),
+            palette: None,
+            synced_at: None,
+        }
+    }
+}
+
+/// A named collection of palettes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Collection {
+    /// The name of the collection.
+    pub name: String,
+    /// An optional description of the collection.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// The palettes in the collection, in insertion order.
+    #[serde(default)]
+    pub entries: Vec<CollectionEntry>,
+}
+
+/// The outcome of [`Collection::sync`].
+#[derive(Debug, Default)]
+pub struct SyncReport {
+    /// IDs of the palettes whose data was refreshed.
+    pub updated: Vec<u64>,
+    /// IDs of the palettes that could not be refreshed, with the cause.
+    pub failed: Vec<(u64, BlockPalettesError)>,
+}
+
+impl Collection {
+    /// Creates an empty collection named `name`.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Adds a palette to the collection, returning its entry.
+    ///
+    /// If the palette is already in the collection, the existing entry is
+    /// returned unchanged.
+    pub fn add(&mut self, palette_id: u64) -> &mut CollectionEntry {
+        let index = match self.position(palette_id) {
+            Some(index) => index,
+            None => {
+                self.entries.push(CollectionEntry::new(palette_id));
+                self.entries.len() - 1
+            }
+        };
+        &mut self.entries[index]
+    }
+
+    /// Removes a palette from the collection.
+    ///
+    /// Returns `true` if the palette was present.
+    pub fn remove(&mut self, palette_id: u64) -> bool {
+        match self.position(palette_id) {
+            Some(index) => {
+                self.entries.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the entry for `palette_id`, if present.
+    pub fn get(&self, palette_id: u64) -> Option<&CollectionEntry> {
+        self.entries.iter().find(|e| e.palette_id == palette_id)
+    }
+
+    /// Returns a mutable reference to the entry for `palette_id`, if present.
+    pub fn get_mut(&mut self, palette_id: u64) -> Option<&mut CollectionEntry> {
+        self.entries.iter_mut().find(|e| e.palette_id == palette_id)
+    }
+
+    /// Returns the IDs of all palettes in the collection, in insertion order.
+    pub fn ids(&self) -> Vec<u64> {
+        self.entries.iter().map(|e| e.palette_id).collect()
+    }
+
+    /// Returns the entries carrying `tag`.
+    pub fn tagged<'a>(&'a self, tag: &'a str) -> impl Iterator<Item = &'a CollectionEntry> + 'a {
+        self.entries.iter().filter(move |e| e.tags.contains(tag))
+    }
+
+    /// Refreshes the stored palette data of every entry from the API.
+    ///
+    /// Palettes are fetched one at a time with
+    /// [`BlockPalettesClient::get_palette_details`]. A failure for one palette
+    /// does not stop the sync; it is recorded in the returned [`SyncReport`]
+    /// and the entry keeps its previous data.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use blockpalettes_client::BlockPalettesClient;
+    /// use blockpalettes_client::collections::CollectionStore;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = BlockPalettesClient::new(reqwest::Client::new());
+    ///     let mut store = CollectionStore::load("collections.json")?;
+    ///     for collection in store.iter_mut() {
+    ///         let report = collection.sync(&client).await;
+    ///         println!("{}: {} updated", collection.name, report.updated.len());
+    ///     }
+    ///     store.save("collections.json")?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn sync(&mut self, client: &BlockPalettesClient) -> SyncReport {
+        let mut report = SyncReport::default();
+        for entry in &mut self.entries {
+            match client.get_palette_details(entry.palette_id).await {
+                Ok(details) => {
+                    entry.palette = Some(details);
+                    entry.synced_at = Some(
+                        chrono::Utc::now()
+                            .naive_utc()
+                            .format("%Y-%m-%d %H:%M:%S")
+                            .to_string(),
+                    );
+                    report.updated.push(entry.palette_id);
+                }
+                Err(e) => report.failed.push((entry.palette_id, e)),
+            }
+        }
+        report
+    }
+
+    fn position(&self, palette_id: u64) -> Option<usize> {
+        self.entries.iter().position(|e| e.palette_id == palette_id)
+    }
+}
+
+/// The version of the JSON format of a [`CollectionStore`] written by this
+/// crate.
+///
+/// Version 0 is the format of stores saved before versioning, without a
+/// `version` field.
+pub const STORE_VERSION: SchemaVersion = SchemaVersion(1);
+
+/// A set of named [`Collection`]s that can be persisted as JSON.
+///
+/// Saved stores record the [`STORE_VERSION`] they were written with, and
+/// stores written by older versions of this crate are upgraded when loaded.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CollectionStore {
+    collections: BTreeMap<String, Collection>,
+}
+
+impl CollectionStore {
+    /// Creates an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads a store from a JSON file.
+    ///
+    /// A missing file yields an empty store, so the same path can be used
+    /// for the first run and subsequent ones. Stores saved by older versions
+    /// of this crate are upgraded in memory; the file is only rewritten by
+    /// [`save`](Self::save).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BlockPalettesError::Io`] if the file cannot be read,
+    /// [`BlockPalettesError::Json`] if it is not a valid store, or
+    /// [`BlockPalettesError::Validation`] if it was written by a newer
+    /// version of this crate.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use blockpalettes_client::collections::CollectionStore;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let path = std::env::temp_dir().join("blockpalettes-collections-doctest.json");
+    /// // saved before stores were versioned
+    /// std::fs::write(&path, r#"{"collections":{"castles":{"name":"castles","entries":[]}}}"#)?;
+    /// let store = CollectionStore::load(&path)?;
+    /// assert!(store.get("castles").is_some());
+    ///
+    /// store.save(&path)?;
+    /// assert!(std::fs::read_to_string(&path)?.contains(r#""version": 1"#));
+    ///
+    /// std::fs::write(&path, r#"{"version":99,"collections":{}}"#)?;
+    /// assert!(CollectionStore::load(&path).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        match fs::read(path) {
+            Ok(data) => {
+                let mut store: serde_json::Value = serde_json::from_slice(&data)?;
+                // version 0 only lacks the version
+                dataset::upgrade(&mut store, STORE_VERSION, "collection store", |_, store| {
+                    Ok(store)
+                })?;
+                Ok(serde_json::from_value(store)?)
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Saves the store to a JSON file, replacing any previous content.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BlockPalettesError::Io`] if the file cannot be written.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        fs::write(path, self.to_json()?)?;
+        Ok(())
+    }
+
+    /// Returns the collection named `name`, creating it if necessary.
+    pub fn create(&mut self, name: impl Into<String>) -> &mut Collection {
+        let name = name.into();
+        self.collections
+            .entry(name.clone())
+            .or_insert_with(|| Collection::new(name))
+    }
+
+    /// Returns the collection named `name`, if present.
+    pub fn get(&self, name: &str) -> Option<&Collection> {
+        self.collections.get(name)
+    }
+
+    /// Returns a mutable reference to the collection named `name`, if present.
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Collection> {
+        self.collections.get_mut(name)
+    }
+
+    /// Removes and returns the collection named `name`.
+    pub fn remove(&mut self, name: &str) -> Option<Collection> {
+        self.collections.remove(name)
+    }
+
+    /// Iterates over all collections, ordered by name.
+    pub fn iter(&self) -> impl Iterator<Item = &Collection> {
+        self.collections.values()
+    }
+
+    /// Iterates mutably over all collections, ordered by name.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Collection> {
+        self.collections.values_mut()
+    }
+
+    /// Serializes the whole store as pretty-printed JSON, with its
+    /// [`STORE_VERSION`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BlockPalettesError::Json`] if serialization fails.
+    pub fn to_json(&self) -> Result<String> {
+        let mut store = serde_json::to_value(self)?;
+        store["version"] = STORE_VERSION.0.into();
+        Ok(serde_json::to_string_pretty(&store)?)
+    }
+
+    /// Exports every entry as CSV, one row per palette per collection.
+    ///
+    /// The columns are `collection`, `palette_id`, `tags` (separated by `;`),
+    /// `note`, `username`, `likes` and `blocks` (separated by `;`). Columns
+    /// that depend on synced data are empty for entries never synced.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use blockpalettes_client::collections::CollectionStore;
+    ///
+    /// let mut store = CollectionStore::new();
+    /// store.create("builds").add(42).note = Some("roof, walls".into());
+    ///
+    /// let csv = store.export_csv();
+    /// assert_eq!(csv.lines().nth(1), Some("builds,42,,\"roof, walls\",,,"));
+    /// ```
+    pub fn export_csv(&self) -> String {
+        self.export_csv_as(NameFormat::Id)
+    }
+
+    /// Exports every entry as CSV like [`export_csv`](Self::export_csv), with
+    /// block names written in `format`.
+    pub fn export_csv_as(&self, format: NameFormat) -> String {
+        let mut out = String::from("collection,palette_id,tags,note,username,likes,blocks\n");
+        for collection in self.iter() {
+            for entry in &collection.entries {
+                let tags = entry.tags.iter().cloned().collect::<Vec<_>>().join(";");
+                let (username, likes, blocks) = match &entry.palette {
+                    Some(p) => (
+                        p.username.clone(),
+                        p.likes.to_string(),
+                        [
+                            &p.block_one,
+                            &p.block_two,
+                            &p.block_three,
+                            &p.block_four,
+                            &p.block_five,
+                            &p.block_six,
+                        ]
+                        .map(|block| format.apply(block))
+                        .join(";"),
+                    ),
+                    None => Default::default(),
+                };
+                let row = [
+                    collection.name.as_str(),
+                    &entry.palette_id.to_string(),
+                    &tags,
+                    entry.note.as_deref().unwrap_or_default(),
+                    &username,
+                    &likes,
+                    &blocks,
+                ]
+                .map(csv_field)
+                .join(",");
+                out.push_str(&row);
+                out.push('\n');
+            }
+        }
+        out
+    }
+}
+
+/// Quotes a CSV field if it contains a separator, quote or newline.
+pub(crate) fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
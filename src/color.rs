@@ -0,0 +1,210 @@
+//! Color types and conversions used by block metadata and analysis.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// An sRGB color with 8 bits per channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Rgb {
+    /// The red channel.
+    pub r: u8,
+    /// The green channel.
+    pub g: u8,
+    /// The blue channel.
+    pub b: u8,
+}
+
+/// A color in the HSL space.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Hsl {
+    /// The hue in degrees, in `0.0..360.0`.
+    pub h: f32,
+    /// The saturation, in `0.0..=1.0`.
+    pub s: f32,
+    /// The lightness, in `0.0..=1.0`.
+    pub l: f32,
+}
+
+/// A color in the CIE L\*a\*b\* space (D65 white point).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Lab {
+    /// Lightness, in `0.0..=100.0`.
+    pub l: f32,
+    /// The green–red axis.
+    pub a: f32,
+    /// The blue–yellow axis.
+    pub b: f32,
+}
+
+impl Rgb {
+    /// Creates a color from its channels.
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// Converts the color to HSL.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use blockpalettes_client::color::Rgb;
+    ///
+    /// let hsl = Rgb::new(255, 0, 0).to_hsl();
+    /// assert_eq!((hsl.h, hsl.s, hsl.l), (0.0, 1.0, 0.5));
+    /// ```
+    pub fn to_hsl(self) -> Hsl {
+        let [r, g, b] = [self.r, self.g, self.b].map(|c| f32::from(c) / 255.0);
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let l = (max + min) / 2.0;
+        let delta = max - min;
+
+        if delta == 0.0 {
+            return Hsl { h: 0.0, s: 0.0, l };
+        }
+
+        let s = delta / (1.0 - (2.0 * l - 1.0).abs());
+        let h = if max == r {
+            60.0 * ((g - b) / delta).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / delta + 2.0)
+        } else {
+            60.0 * ((r - g) / delta + 4.0)
+        };
+
+        Hsl {
+            h: h.rem_euclid(360.0),
+            s: s.min(1.0),
+            l,
+        }
+    }
+
+    /// Converts the color to CIE L\*a\*b\*.
+    pub fn to_lab(self) -> Lab {
+        let [r, g, b] = self.to_linear();
+
+        let x = (0.4124 * r + 0.3576 * g + 0.1805 * b) / 0.95047;
+        let y = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+        let z = (0.0193 * r + 0.1192 * g + 0.9505 * b) / 1.08883;
+
+        let f = |t: f32| {
+            if t > 0.008856 {
+                t.cbrt()
+            } else {
+                7.787 * t + 16.0 / 116.0
+            }
+        };
+        let (fx, fy, fz) = (f(x), f(y), f(z));
+
+        Lab {
+            l: 116.0 * fy - 16.0,
+            a: 500.0 * (fx - fy),
+            b: 200.0 * (fy - fz),
+        }
+    }
+
+    /// Simulates how the color looks with a color vision deficiency.
+    ///
+    /// This uses the full-severity matrices of Machado et al. (2009), applied
+    /// in linear RGB.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use blockpalettes_client::color::{ColorVision, Rgb};
+    ///
+    /// let red = Rgb::new(161, 39, 35);
+    /// let green = Rgb::new(85, 110, 28);
+    /// assert!(red.distance(green) > 50.0);
+    ///
+    /// let (red, green) = (red.simulate(ColorVision::Deuteranopia), green.simulate(ColorVision::Deuteranopia));
+    /// assert!(red.distance(green) < 5.0);
+    /// ```
+    pub fn simulate(self, vision: ColorVision) -> Rgb {
+        let m = vision.matrix();
+        let c = self.to_linear();
+        let [r, g, b] = std::array::from_fn(|i| {
+            let v = m[i][0] * c[0] + m[i][1] * c[1] + m[i][2] * c[2];
+            linear_to_srgb(v)
+        });
+        Rgb { r, g, b }
+    }
+
+    /// Converts the channels to linear RGB, in `0.0..=1.0`.
+    fn to_linear(self) -> [f32; 3] {
+        [self.r, self.g, self.b].map(|c| {
+            let c = f32::from(c) / 255.0;
+            if c <= 0.04045 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        })
+    }
+
+    /// Returns the perceptual distance (CIE76 ΔE) between two colors.
+    ///
+    /// A distance below about `2.3` is generally not noticeable.
+    pub fn distance(self, other: Rgb) -> f32 {
+        let (a, b) = (self.to_lab(), other.to_lab());
+        ((a.l - b.l).powi(2) + (a.a - b.a).powi(2) + (a.b - b.b).powi(2)).sqrt()
+    }
+}
+
+/// A type of color vision deficiency (dichromacy).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ColorVision {
+    /// No functioning red cones.
+    Protanopia,
+    /// No functioning green cones.
+    Deuteranopia,
+    /// No functioning blue cones.
+    Tritanopia,
+}
+
+impl ColorVision {
+    /// Every simulated deficiency.
+    pub const ALL: [ColorVision; 3] = [
+        ColorVision::Protanopia,
+        ColorVision::Deuteranopia,
+        ColorVision::Tritanopia,
+    ];
+
+    fn matrix(self) -> [[f32; 3]; 3] {
+        match self {
+            ColorVision::Protanopia => [
+                [0.152_286, 1.052_583, -0.204_868],
+                [0.114_503, 0.786_281, 0.099_216],
+                [-0.003_882, -0.048_116, 1.051_998],
+            ],
+            ColorVision::Deuteranopia => [
+                [0.367_322, 0.860_646, -0.227_968],
+                [0.280_085, 0.672_501, 0.047_413],
+                [-0.011_820, 0.042_940, 0.968_881],
+            ],
+            ColorVision::Tritanopia => [
+                [1.255_528, -0.076_749, -0.178_779],
+                [-0.078_411, 0.930_809, 0.147_602],
+                [0.004_733, 0.691_367, 0.303_900],
+            ],
+        }
+    }
+}
+
+/// Converts a linear channel back to an 8-bit sRGB channel, clamping it first.
+fn linear_to_srgb(c: f32) -> u8 {
+    let c = c.clamp(0.0, 1.0);
+    let v = if c <= 0.003_130_8 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    };
+    (v * 255.0).round() as u8
+}
+
+impl fmt::Display for Rgb {
+    /// Formats the color as a lowercase hex string (e.g., `#7d7d7d`).
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
@@ -0,0 +1,95 @@
+//! Tuning of the client's HTTP connections.
+//!
+//! Long-lived clients, such as [`watcher`](crate::watcher)s, keep connections
+//! open between requests. Proxies, load balancers and NAT gateways often drop
+//! idle connections without telling either end, and the next request on such
+//! a connection hangs until the operating system gives up on it.
+//! [`ConnectionOptions`] enables keepalive probes that detect dead
+//! connections early, along with other transport settings.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use blockpalettes_client::BlockPalettesClient;
+//! use blockpalettes_client::connection::ConnectionOptions;
+//! use std::time::Duration;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let options = ConnectionOptions {
+//!     http2_keep_alive_interval: Some(Duration::from_secs(30)),
+//!     http2_keep_alive_timeout: Some(Duration::from_secs(10)),
+//!     http2_keep_alive_while_idle: true,
+//!     tcp_keepalive: Some(Duration::from_secs(60)),
+//!     ..ConnectionOptions::default()
+//! };
+//! let client = BlockPalettesClient::new(reqwest::Client::new()).with_connection_options(options)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use reqwest::ClientBuilder;
+use std::time::Duration;
+
+/// Transport settings of the client's HTTP connections.
+///
+/// The defaults are those of `reqwest`. Apply them with
+/// [`BlockPalettesClient::with_connection_options`](crate::BlockPalettesClient::with_connection_options),
+/// or to any `reqwest::ClientBuilder` with [`apply`](Self::apply), e.g. for
+/// the clients of a [`ClientPool`](crate::pool::ClientPool).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConnectionOptions {
+    /// Speaks HTTP/2 right away instead of negotiating it. Only use it with
+    /// servers known to support HTTP/2, such as a local mirror speaking
+    /// cleartext HTTP/2.
+    pub http2_prior_knowledge: bool,
+    /// Adapts the HTTP/2 flow control windows to the connection's bandwidth,
+    /// which speeds up large responses on high-latency links.
+    pub http2_adaptive_window: bool,
+    /// The interval at which HTTP/2 PING frames are sent to check that the
+    /// connection is alive, or `None` to send none.
+    pub http2_keep_alive_interval: Option<Duration>,
+    /// How long to wait for a PING to be acknowledged before closing the
+    /// connection, or `None` for `hyper`'s default (20 seconds).
+    pub http2_keep_alive_timeout: Option<Duration>,
+    /// Whether PING frames are also sent on idle connections, not only while
+    /// requests are in flight.
+    pub http2_keep_alive_while_idle: bool,
+    /// Whether to disable Nagle's algorithm (`TCP_NODELAY`), sending small
+    /// requests without delay.
+    pub tcp_nodelay: bool,
+    /// How long a connection is idle before TCP keepalive probes are sent,
+    /// or `None` to send none.
+    pub tcp_keepalive: Option<Duration>,
+}
+
+impl Default for ConnectionOptions {
+    fn default() -> Self {
+        Self {
+            http2_prior_knowledge: false,
+            http2_adaptive_window: false,
+            http2_keep_alive_interval: None,
+            http2_keep_alive_timeout: None,
+            http2_keep_alive_while_idle: false,
+            tcp_nodelay: true,
+            tcp_keepalive: Some(Duration::from_secs(15)),
+        }
+    }
+}
+
+impl ConnectionOptions {
+    /// Applies the options to `builder`.
+    pub fn apply(&self, mut builder: ClientBuilder) -> ClientBuilder {
+        if self.http2_prior_knowledge {
+            builder = builder.http2_prior_knowledge();
+        }
+        if let Some(timeout) = self.http2_keep_alive_timeout {
+            builder = builder.http2_keep_alive_timeout(timeout);
+        }
+        builder
+            .http2_adaptive_window(self.http2_adaptive_window)
+            .http2_keep_alive_interval(self.http2_keep_alive_interval)
+            .http2_keep_alive_while_idle(self.http2_keep_alive_while_idle)
+            .tcp_nodelay(self.tcp_nodelay)
+            .tcp_keepalive(self.tcp_keepalive)
+    }
+}
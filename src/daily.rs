@@ -0,0 +1,84 @@
+//! Deterministic daily picks, such as a "palette of the day".
+//!
+//! Selection depends only on the date and on the set of candidate palettes,
+//! not on their order or on any state, so independent processes (e.g. several
+//! instances of a bot) pick the same palette without coordinating.
+
+use crate::Palette;
+use chrono::NaiveDate;
+
+/// The minimum number of likes for a palette that is not featured to be
+/// considered by [`palette_of_the_day`].
+pub const DAILY_MIN_LIKES: u32 = 50;
+
+/// Picks the palette of the day for `date` from `corpus`.
+///
+/// Candidates are the featured palettes and those with at least
+/// [`DAILY_MIN_LIKES`] likes; if there are none, every palette is a candidate.
+/// Hidden palettes are never picked. The pick is a stable hash of the date
+/// over the candidates sorted by ID, so it is the same on every machine and
+/// across Rust versions, and consecutive days pick unrelated palettes.
+///
+/// Returns `None` if `corpus` has no visible palette.
+///
+/// # Examples
+///
+/// ```rust
+/// use blockpalettes_client::daily;
+/// use chrono::NaiveDate;
+/// # let palette = |id, likes| blockpalettes_client::Palette {
+/// #    id, user_id: 1, date: "2023-01-01 12:00:00".to_string(), likes,
+/// #    block_one: "stone".to_string(), block_two: "dirt".to_string(),
+/// #    block_three: "grass_block".to_string(), block_four: "oak_log".to_string(),
+/// #    block_five: "cobblestone".to_string(), block_six: "sand".to_string(),
+/// #    hidden: Some(0), featured: Some(0), hash: None, time_ago: String::new()
+/// # };
+/// let corpus = vec![palette(1, 120), palette(2, 3), palette(3, 80), palette(4, 95)];
+/// let day = NaiveDate::from_ymd_opt(2024, 5, 17).unwrap();
+///
+/// let pick = daily::palette_of_the_day(day, &corpus).unwrap();
+/// assert_ne!(pick.id, 2); // not enough likes
+///
+/// // the order of the corpus does not matter
+/// let mut shuffled = corpus.clone();
+/// shuffled.reverse();
+/// assert_eq!(daily::palette_of_the_day(day, &shuffled).unwrap().id, pick.id);
+/// ```
+pub fn palette_of_the_day(date: NaiveDate, corpus: &[Palette]) -> Option<&Palette> {
+    let visible: Vec<&Palette> = corpus
+        .iter()
+        .filter(|p| p.hidden.unwrap_or_default() == 0)
+        .collect();
+
+    let mut candidates: Vec<&Palette> = visible
+        .iter()
+        .copied()
+        .filter(|p| p.featured.unwrap_or_default() != 0 || p.likes >= DAILY_MIN_LIKES)
+        .collect();
+    if candidates.is_empty() {
+        candidates = visible;
+    }
+    candidates.sort_by_key(|p| p.id);
+    candidates.dedup_by_key(|p| p.id);
+
+    if candidates.is_empty() {
+        return None;
+    }
+    let seed = mix(fnv1a(date.format("%Y-%m-%d").to_string().as_bytes()));
+    let index = (seed % candidates.len() as u64) as usize;
+    Some(candidates[index])
+}
+
+/// 64-bit FNV-1a, which is stable across platforms and Rust versions.
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// The SplitMix64 finalizer, so that similar dates give unrelated picks.
+pub(crate) fn mix(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
@@ -0,0 +1,472 @@
+//! On-disk datasets of palettes, for local mirrors of the site.
+//!
+//! [`write_archive`] stores palettes in chunks of [`ARCHIVE_CHUNK_SIZE`]
+//! palettes, next to an index mapping every palette ID to its chunk. An
+//! [`Archive`] then reads single palettes by ID without loading the whole
+//! mirror, by reading only the chunk that contains them.
+//!
+//! The archive is made of two files:
+//!
+//! - the data file at `path`, where each chunk is a run of JSON lines, one
+//!   palette per line;
+//! - the index file at `path` with `.idx` appended, a JSON document listing
+//!   the offset and length of every chunk and the chunk of every palette ID.
+//!
+//! The index records the codec of the chunks. Only uncompressed chunks
+//! (`"none"`) are written for now; readers reject codecs they do not know.
+//!
+//! The index also records the [`SchemaVersion`] of the archive. Archives
+//! written by older versions of this crate are upgraded in place with
+//! [`migrate`]; [`Archive::open`] never writes, and rejects archives that are
+//! outdated or written by newer versions.
+//!
+//! For analytics, [`to_ndjson`] writes palettes in a flat schema
+//! ([`FlatPalette`]) that DuckDB and polars load without any conversion.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use blockpalettes_client::dataset::{self, Archive};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let palette = |id| blockpalettes_client::Palette {
+//! #    id, user_id: 1, date: "2023-01-01 12:00:00".to_string(), likes: 10,
+//! #    block_one: "stone".to_string(), block_two: "dirt".to_string(),
+//! #    block_three: "grass_block".to_string(), block_four: "oak_log".to_string(),
+//! #    block_five: "cobblestone".to_string(), block_six: "sand".to_string(),
+//! #    hidden: Some(0), featured: Some(0), hash: None, time_ago: String::new()
+//! # };
+//! let path = std::env::temp_dir().join("blockpalettes-archive-doctest.jsonl");
+//! let palettes: Vec<_> = (1..=1000).map(palette).collect();
+//! dataset::write_archive(&path, &palettes)?;
+//!
+//! let archive = Archive::open(&path)?;
+//! assert_eq!(archive.len(), 1000);
+//! assert_eq!(archive.get(742)?.map(|p| p.id), Some(742));
+//! assert!(archive.get(5000)?.is_none());
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{BlockPalettesError, Palette, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// The number of palettes per chunk of an archive.
+pub const ARCHIVE_CHUNK_SIZE: usize = 256;
+
+/// The version of the archive format written by this crate.
+const ARCHIVE_VERSION: u32 = 1;
+
+/// A step upgrading the raw index of the archive at a path, and its data
+/// file if needed, from one version to the next.
+type Migration = fn(&Path, serde_json::Value) -> Result<serde_json::Value>;
+
+/// The migrations to [`ARCHIVE_VERSION`]: `MIGRATIONS[i]` upgrades an
+/// archive from version `i` to version `i + 1`.
+///
+/// Version 0 is an index without a version, e.g. written by hand or by
+/// another tool; it only lacks the version.
+const MIGRATIONS: &[Migration] = &[|_, index| Ok(index)];
+
+const _: () = assert!(MIGRATIONS.len() == ARCHIVE_VERSION as usize);
+
+/// The version of the on-disk format of an archive.
+///
+/// # Examples
+///
+/// ```rust
+/// use blockpalettes_client::dataset::{self, SchemaVersion};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let path = std::env::temp_dir().join("blockpalettes-schema-doctest.jsonl");
+/// dataset::write_archive(&path, &[])?;
+/// assert_eq!(dataset::schema_version(&path)?, SchemaVersion::CURRENT);
+///
+/// // already current, nothing to do
+/// assert_eq!(dataset::migrate(&path)?, SchemaVersion::CURRENT);
+///
+/// // an index without a version is version 0, which must be migrated
+/// // before the archive can be opened
+/// let index_path = dataset::archive_index_path(&path);
+/// let mut index: serde_json::Value = serde_json::from_slice(&std::fs::read(&index_path)?)?;
+/// index.as_object_mut().unwrap().remove("version");
+/// std::fs::write(&index_path, serde_json::to_vec(&index)?)?;
+/// assert_eq!(dataset::schema_version(&path)?, SchemaVersion(0));
+/// assert!(dataset::Archive::open(&path).is_err());
+///
+/// assert_eq!(dataset::migrate(&path)?, SchemaVersion(0));
+/// assert_eq!(dataset::schema_version(&path)?, SchemaVersion::CURRENT);
+/// assert!(dataset::Archive::open(&path).is_ok());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SchemaVersion(pub u32);
+
+impl SchemaVersion {
+    /// The version written by this crate.
+    pub const CURRENT: SchemaVersion = SchemaVersion(ARCHIVE_VERSION);
+
+    /// Returns `true` if this is the version written by this crate.
+    pub fn is_current(self) -> bool {
+        self == Self::CURRENT
+    }
+}
+
+impl std::fmt::Display for SchemaVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "v{}", self.0)
+    }
+}
+
+/// The codec of uncompressed chunks.
+const CODEC_NONE: &str = "none";
+
+/// The index of an archive, stored next to its data file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ArchiveIndex {
+    version: u32,
+    codec: String,
+    chunks: Vec<ChunkEntry>,
+    /// The chunk of every palette, by ID.
+    palettes: BTreeMap<u64, usize>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct ChunkEntry {
+    offset: u64,
+    length: u64,
+}
+
+/// Returns the path of the index file of the archive at `path`.
+pub fn archive_index_path(path: &Path) -> PathBuf {
+    let mut index = path.as_os_str().to_owned();
+    index.push(".idx");
+    PathBuf::from(index)
+}
+
+/// Returns the schema version of the archive at `path`, reading only its index.
+///
+/// # Errors
+///
+/// Returns [`BlockPalettesError::Io`] or [`BlockPalettesError::Json`] if
+/// the index cannot be read, or [`BlockPalettesError::Validation`] if its
+/// version is not a number.
+pub fn schema_version(path: impl AsRef<Path>) -> Result<SchemaVersion> {
+    let index = read_raw_index(path.as_ref())?;
+    version_of(&index)
+}
+
+/// Upgrades the archive at `path` to [`SchemaVersion::CURRENT`], returning
+/// the version it had before.
+///
+/// Archives that are already current are left untouched. The upgraded index
+/// is written to a temporary file first and then renamed over the previous
+/// one, so an interrupted migration never leaves a truncated index behind.
+///
+/// # Errors
+///
+/// Returns [`BlockPalettesError::Io`] or [`BlockPalettesError::Json`] if
+/// the archive cannot be read or written, or
+/// [`BlockPalettesError::Validation`] if it was written by a newer version
+/// of this crate.
+pub fn migrate(path: impl AsRef<Path>) -> Result<SchemaVersion> {
+    let path = path.as_ref();
+    let mut index = read_raw_index(path)?;
+    let found = upgrade(
+        &mut index,
+        SchemaVersion::CURRENT,
+        "archive",
+        |from, index| MIGRATIONS[from as usize](path, index),
+    )?;
+    if found.is_current() {
+        return Ok(found);
+    }
+
+    write_index(path, &index)?;
+    Ok(found)
+}
+
+/// Writes `index` as the index of the archive at `path`, through a
+/// temporary file renamed over the previous index.
+fn write_index(path: &Path, index: &impl Serialize) -> Result<()> {
+    let index_path = archive_index_path(path);
+    let mut temp = index_path.as_os_str().to_owned();
+    temp.push(".tmp");
+    let mut file = BufWriter::new(File::create(&temp)?);
+    serde_json::to_writer(&mut file, index)?;
+    file.flush()?;
+    drop(file);
+    std::fs::rename(&temp, &index_path)?;
+    Ok(())
+}
+
+fn read_raw_index(path: &Path) -> Result<serde_json::Value> {
+    let index_file = File::open(archive_index_path(path))?;
+    Ok(serde_json::from_reader(std::io::BufReader::new(
+        index_file,
+    ))?)
+}
+
+/// Returns the version in the `version` field of `document`, or version 0
+/// if it has none.
+pub(crate) fn version_of(document: &serde_json::Value) -> Result<SchemaVersion> {
+    match &document["version"] {
+        serde_json::Value::Null => Ok(SchemaVersion(0)),
+        version => version
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .map(SchemaVersion)
+            .ok_or_else(|| BlockPalettesError::Validation(format!("invalid version {version}"))),
+    }
+}
+
+/// Upgrades the stored `document` of a `what` to the `current` version,
+/// returning the version it had before.
+///
+/// `step(v, document)` upgrades a document from version `v` to `v + 1`; the
+/// `version` field is updated after each step.
+///
+/// # Errors
+///
+/// Returns [`BlockPalettesError::Validation`] if the document was written by
+/// a newer version of this crate, or the error of a failed step.
+pub(crate) fn upgrade(
+    document: &mut serde_json::Value,
+    current: SchemaVersion,
+    what: &str,
+    mut step: impl FnMut(u32, serde_json::Value) -> Result<serde_json::Value>,
+) -> Result<SchemaVersion> {
+    let found = version_of(document)?;
+    if found > current {
+        return Err(BlockPalettesError::Validation(format!(
+            "{what} schema {found} is newer than the supported {current}"
+        )));
+    }
+    for from in found.0..current.0 {
+        *document = step(from, std::mem::take(document))?;
+        document["version"] = serde_json::Value::from(from + 1);
+    }
+    Ok(found)
+}
+
+/// Writes `palettes` to a chunked archive at `path`, with its index.
+///
+/// Existing files are overwritten. If a palette ID appears several times, the
+/// last occurrence wins when reading by ID. The index is written last,
+/// through a temporary file, so an interrupted write never leaves a
+/// truncated index behind.
+///
+/// # Errors
+///
+/// Returns [`BlockPalettesError::Io`] or [`BlockPalettesError::Json`] if a
+/// file cannot be written.
+pub fn write_archive(path: impl AsRef<Path>, palettes: &[Palette]) -> Result<()> {
+    let path = path.as_ref();
+    let mut data = BufWriter::new(File::create(path)?);
+    let mut index = ArchiveIndex {
+        version: ARCHIVE_VERSION,
+        codec: CODEC_NONE.to_string(),
+        chunks: Vec::new(),
+        palettes: BTreeMap::new(),
+    };
+
+    let mut offset = 0;
+    for (chunk_index, chunk) in palettes.chunks(ARCHIVE_CHUNK_SIZE).enumerate() {
+        let mut bytes = Vec::new();
+        for palette in chunk {
+            serde_json::to_writer(&mut bytes, palette)?;
+            bytes.push(b'\n');
+            index.palettes.insert(palette.id, chunk_index);
+        }
+        data.write_all(&bytes)?;
+
+        let length = bytes.len() as u64;
+        index.chunks.push(ChunkEntry { offset, length });
+        offset += length;
+    }
+    data.flush()?;
+
+    write_index(path, &index)
+}
+
+/// A palette archive opened for random access.
+#[derive(Debug)]
+pub struct Archive {
+    path: PathBuf,
+    index: ArchiveIndex,
+}
+
+impl Archive {
+    /// Opens the archive at `path`, reading only its index.
+    ///
+    /// Opening never writes: archives written by older versions of this
+    /// crate must be upgraded with [`migrate`] first.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BlockPalettesError::Io`] or [`BlockPalettesError::Json`] if
+    /// the index cannot be read, or [`BlockPalettesError::Validation`] if it
+    /// is outdated, or uses an unsupported version or codec.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let raw = read_raw_index(&path)?;
+        let found = version_of(&raw)?;
+        if found < SchemaVersion::CURRENT {
+            return Err(BlockPalettesError::Validation(format!(
+                "archive schema {found} is outdated, upgrade it to {} with `dataset::migrate`",
+                SchemaVersion::CURRENT
+            )));
+        }
+        let index: ArchiveIndex = serde_json::from_value(raw)?;
+
+        if index.version != ARCHIVE_VERSION || index.codec != CODEC_NONE {
+            return Err(BlockPalettesError::Validation(format!(
+                "unsupported archive (version {}, codec `{}`)",
+                index.version, index.codec
+            )));
+        }
+        Ok(Self { path, index })
+    }
+
+    /// Returns the number of distinct palettes in the archive.
+    pub fn len(&self) -> usize {
+        self.index.palettes.len()
+    }
+
+    /// Returns `true` if the archive has no palettes.
+    pub fn is_empty(&self) -> bool {
+        self.index.palettes.is_empty()
+    }
+
+    /// Iterates over the IDs of the palettes in the archive, in ascending order.
+    pub fn ids(&self) -> impl Iterator<Item = u64> + '_ {
+        self.index.palettes.keys().copied()
+    }
+
+    /// Reads a single palette by ID, loading only the chunk that contains it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BlockPalettesError::Io`] or [`BlockPalettesError::Json`] if
+    /// the chunk cannot be read.
+    pub fn get(&self, id: u64) -> Result<Option<Palette>> {
+        let Some(&chunk) = self.index.palettes.get(&id) else {
+            return Ok(None);
+        };
+        Ok(self
+            .read_chunk(chunk)?
+            .into_iter()
+            .rev()
+            .find(|p| p.id == id))
+    }
+
+    /// Reads every palette of the archive, in the order they were written.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BlockPalettesError::Io`] or [`BlockPalettesError::Json`] if
+    /// a chunk cannot be read.
+    pub fn read_all(&self) -> Result<Vec<Palette>> {
+        let mut palettes = Vec::new();
+        for chunk in 0..self.index.chunks.len() {
+            palettes.append(&mut self.read_chunk(chunk)?);
+        }
+        Ok(palettes)
+    }
+
+    fn read_chunk(&self, chunk: usize) -> Result<Vec<Palette>> {
+        let entry = *self.index.chunks.get(chunk).ok_or_else(|| {
+            BlockPalettesError::Validation(format!("archive index refers to missing chunk {chunk}"))
+        })?;
+        let mut file = File::open(&self.path)?;
+        file.seek(SeekFrom::Start(entry.offset))?;
+
+        let mut bytes = Vec::new();
+        file.take(entry.length).read_to_end(&mut bytes)?;
+
+        bytes
+            .split(|&b| b == b'\n')
+            .filter(|line| !line.is_empty())
+            .map(|line| Ok(serde_json::from_slice(line)?))
+            .collect()
+    }
+}
+
+/// A palette in a flat schema, suited to columnar analytics tools.
+///
+/// Blocks are available both as six columns and as a single list column, and
+/// the relative `time_ago` text, which goes stale, is left out.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FlatPalette {
+    /// The unique identifier of the palette.
+    pub id: u64,
+    /// The ID of the user who created the palette.
+    pub user_id: u64,
+    /// The creation date, as `YYYY-MM-DD HH:MM:SS`.
+    pub date: String,
+    /// The number of likes.
+    pub likes: u32,
+    /// The first block.
+    pub block_one: String,
+    /// The second block.
+    pub block_two: String,
+    /// The third block.
+    pub block_three: String,
+    /// The fourth block.
+    pub block_four: String,
+    /// The fifth block.
+    pub block_five: String,
+    /// The sixth block.
+    pub block_six: String,
+    /// The six blocks, in display order.
+    pub blocks: Vec<String>,
+    /// Whether the palette is hidden.
+    pub hidden: bool,
+    /// Whether the palette is featured.
+    pub featured: bool,
+}
+
+impl From<&Palette> for FlatPalette {
+    fn from(palette: &Palette) -> Self {
+        FlatPalette {
+            id: palette.id,
+            user_id: palette.user_id,
+            date: palette.date.clone(),
+            likes: palette.likes,
+            block_one: palette.block_one.clone(),
+            block_two: palette.block_two.clone(),
+            block_three: palette.block_three.clone(),
+            block_four: palette.block_four.clone(),
+            block_five: palette.block_five.clone(),
+            block_six: palette.block_six.clone(),
+            blocks: palette.blocks().map(str::to_string).to_vec(),
+            hidden: palette.hidden.unwrap_or_default() != 0,
+            featured: palette.featured.unwrap_or_default() != 0,
+        }
+    }
+}
+
+/// Writes `palettes` as newline-delimited JSON in the [`FlatPalette`] schema.
+///
+/// The file loads directly with DuckDB's `read_json_auto` or polars'
+/// `read_ndjson`. Existing files are overwritten.
+///
+/// # Errors
+///
+/// Returns [`BlockPalettesError::Io`] or [`BlockPalettesError::Json`] if the
+/// file cannot be written.
+pub fn to_ndjson(palettes: &[Palette], path: impl AsRef<Path>) -> Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+    for palette in palettes {
+        serde_json::to_writer(&mut file, &FlatPalette::from(palette))?;
+        file.write_all(b"\n")?;
+    }
+    file.flush()?;
+    Ok(())
+}
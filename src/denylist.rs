@@ -0,0 +1,135 @@
+//! Excluding content from client results.
+//!
+//! A [`Denylist`] registered with
+//! [`BlockPalettesClient::with_denylist`](crate::BlockPalettesClient::with_denylist)
+//! is applied to every result the client returns:
+//!
+//! - palettes from listings, searches and similarity responses are dropped if
+//!   they, their creator or one of their blocks is denied. This also covers
+//!   everything built on top of them, such as the [`watcher`](crate::watcher);
+//! - fetching the details or scraping the page of a denied palette fails with
+//!   [`BlockPalettesError::Validation`](crate::BlockPalettesError::Validation);
+//! - denied blocks are removed from block searches and popular blocks, and
+//!   denied palettes from scraped similar palette IDs.
+//!
+//! Denylists are serializable, so they can be loaded from a configuration file.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use blockpalettes_client::BlockPalettesClient;
+//! use blockpalettes_client::denylist::Denylist;
+//!
+//! let denylist = Denylist::new()
+//!     .block("tnt")
+//!     .user(666)
+//!     .palette(1234);
+//! assert!(denylist.denies_block("minecraft:tnt"));
+//!
+//! let client = BlockPalettesClient::new(reqwest::Client::new()).with_denylist(denylist);
+//! ```
+
+use crate::validation::{ResponseValidator, Validation};
+use crate::{Palette, PaletteDetails};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// Block names, user IDs and palette IDs to exclude from results.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Denylist {
+    /// Denied block names, without the `minecraft:` namespace.
+    pub blocks: BTreeSet<String>,
+    /// Denied user IDs.
+    pub users: BTreeSet<u64>,
+    /// Denied palette IDs.
+    pub palettes: BTreeSet<u64>,
+}
+
+impl Denylist {
+    /// Creates an empty denylist.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Denies palettes containing `block`.
+    pub fn block(mut self, block: impl AsRef<str>) -> Self {
+        self.blocks.insert(normalize(block.as_ref()).to_string());
+        self
+    }
+
+    /// Denies palettes created by `user_id`.
+    pub fn user(mut self, user_id: u64) -> Self {
+        self.users.insert(user_id);
+        self
+    }
+
+    /// Denies the palette with `palette_id`.
+    pub fn palette(mut self, palette_id: u64) -> Self {
+        self.palettes.insert(palette_id);
+        self
+    }
+
+    /// Returns `true` if nothing is denied.
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty() && self.users.is_empty() && self.palettes.is_empty()
+    }
+
+    /// Returns `true` if `block` is denied.
+    ///
+    /// The `minecraft:` namespace and surrounding whitespace are ignored.
+    pub fn denies_block(&self, block: &str) -> bool {
+        self.blocks.contains(normalize(block))
+    }
+
+    /// Returns `true` if the palette with `palette_id` is denied.
+    pub fn denies_palette_id(&self, palette_id: u64) -> bool {
+        self.palettes.contains(&palette_id)
+    }
+
+    /// Returns `true` if the palette, its creator or one of its blocks is denied.
+    pub fn denies_palette(&self, palette: &Palette) -> bool {
+        self.denies_palette_id(palette.id)
+            || self.users.contains(&palette.user_id)
+            || palette.blocks().iter().any(|b| self.denies_block(b))
+    }
+
+    /// Returns `true` if the palette, its creator or one of its blocks is denied.
+    pub fn denies_details(&self, details: &PaletteDetails) -> bool {
+        self.denies_palette_id(details.id)
+            || self.users.contains(&details.user_id)
+            || [
+                &details.block_one,
+                &details.block_two,
+                &details.block_three,
+                &details.block_four,
+                &details.block_five,
+                &details.block_six,
+            ]
+            .iter()
+            .any(|b| self.denies_block(b))
+    }
+}
+
+impl ResponseValidator for Denylist {
+    fn validate_palette(&self, palette: Palette) -> Validation<Palette> {
+        if self.denies_palette(&palette) {
+            Validation::Skip
+        } else {
+            Validation::Keep(palette)
+        }
+    }
+
+    fn validate_details(&self, details: PaletteDetails) -> Validation<PaletteDetails> {
+        if self.denies_details(&details) {
+            Validation::Reject(format!("palette {} is denylisted", details.id))
+        } else {
+            Validation::Keep(details)
+        }
+    }
+}
+
+fn normalize(block: &str) -> &str {
+    let block = block.trim();
+    block.strip_prefix("minecraft:").unwrap_or(block)
+}
@@ -0,0 +1,111 @@
+//! Block-level differences between two palettes.
+//!
+//! [`palettes`] compares the blocks of two palettes, both as a multiset (which
+//! blocks they share, regardless of position) and slot by slot (which
+//! positions hold a different block). It is the basis of side-by-side
+//! comparisons such as [`render::comparison`](crate::render::comparison).
+//!
+//! # Examples
+//!
+//! ```rust
+//! use blockpalettes_client::diff;
+//! # let palette = |id, blocks: [&str; 6]| blockpalettes_client::Palette {
+//! #    id, user_id: 1, date: "2023-01-01 12:00:00".to_string(), likes: 10,
+//! #    block_one: blocks[0].to_string(), block_two: blocks[1].to_string(),
+//! #    block_three: blocks[2].to_string(), block_four: blocks[3].to_string(),
+//! #    block_five: blocks[4].to_string(), block_six: blocks[5].to_string(),
+//! #    hidden: Some(0), featured: Some(0), hash: None, time_ago: String::new()
+//! # };
+//! let original = palette(1, ["stone", "andesite", "oak_planks", "spruce_log", "moss_block", "dirt"]);
+//! let remix = palette(2, ["andesite", "stone", "birch_planks", "spruce_log", "moss_block", "dirt"]);
+//!
+//! let diff = diff::palettes(&original, &remix);
+//! assert_eq!(diff.shared, ["stone", "andesite", "spruce_log", "moss_block", "dirt"]);
+//! assert_eq!(diff.removed, ["oak_planks"]);
+//! assert_eq!(diff.added, ["birch_planks"]);
+//! assert_eq!(diff.changed_slots, [0, 1, 2]);
+//! assert_eq!(diff.block_changes(), 1);
+//! ```
+
+use crate::Palette;
+use serde::{Deserialize, Serialize};
+
+/// The differences between two palettes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaletteDiff {
+    /// The ID of the first palette.
+    pub first: u64,
+    /// The ID of the second palette.
+    pub second: u64,
+    /// The blocks of the first palette that the second one has too, in any
+    /// slot, in the order of the first palette.
+    pub shared: Vec<String>,
+    /// The blocks of the first palette that the second one lacks.
+    pub removed: Vec<String>,
+    /// The blocks of the second palette that the first one lacks.
+    pub added: Vec<String>,
+    /// The slots (`0..6`) holding a different block in each palette.
+    pub changed_slots: Vec<usize>,
+}
+
+impl PaletteDiff {
+    /// Returns `true` if both palettes have the same blocks in the same slots.
+    pub fn is_identical(&self) -> bool {
+        self.changed_slots.is_empty()
+    }
+
+    /// Returns `true` if both palettes have the same blocks, in different slots.
+    pub fn is_reordered(&self) -> bool {
+        !self.is_identical() && self.removed.is_empty() && self.added.is_empty()
+    }
+
+    /// Returns how many blocks must be swapped for others to turn one palette
+    /// into the other, ignoring their order.
+    pub fn block_changes(&self) -> usize {
+        self.removed.len().max(self.added.len())
+    }
+
+    /// Returns `true` if `block` is in both palettes.
+    pub fn is_shared(&self, block: &str) -> bool {
+        self.shared.iter().any(|b| b == block)
+    }
+}
+
+/// Compares the blocks of `first` and `second`.
+///
+/// Blocks appearing several times in a palette are matched one for one, so a
+/// block used twice in `first` and once in `second` is both shared and removed.
+pub fn palettes(first: &Palette, second: &Palette) -> PaletteDiff {
+    let a = first.blocks();
+    let b = second.blocks();
+
+    let mut unmatched: Vec<&str> = b.to_vec();
+    let mut shared = Vec::new();
+    let mut removed = Vec::new();
+    for block in a {
+        match unmatched.iter().position(|other| *other == block) {
+            Some(index) => {
+                unmatched.swap_remove(index);
+                shared.push(block.to_string());
+            }
+            None => removed.push(block.to_string()),
+        }
+    }
+    // keep added blocks in the order of the second palette
+    let mut added = Vec::new();
+    for block in b {
+        if let Some(index) = unmatched.iter().position(|other| *other == block) {
+            unmatched.swap_remove(index);
+            added.push(block.to_string());
+        }
+    }
+
+    PaletteDiff {
+        first: first.id,
+        second: second.id,
+        shared,
+        removed,
+        added,
+        changed_slots: (0..6).filter(|&slot| a[slot] != b[slot]).collect(),
+    }
+}
@@ -0,0 +1,311 @@
+//! Clean domain types, decoupled from the API's wire format.
+//!
+//! The types at the crate root ([`crate::Palette`], [`crate::PaletteDetails`])
+//! mirror the exact shapes returned by the API: dates are strings, flags are
+//! `0`/`1` integers and the six blocks are separate fields. They are lenient
+//! on purpose, so that upstream quirks do not break decoding.
+//!
+//! The types in this module are what applications usually want to work with:
+//! typed dates, real booleans and a block array. Converting from wire types is
+//! explicit (`TryFrom`) and reports *every* problem found in a record at once,
+//! rather than stopping at the first one.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use blockpalettes_client::domain;
+//! # let wire = blockpalettes_client::Palette {
+//! #    id: 1, user_id: 1, date: "2023-01-01 12:00:00".to_string(), likes: 10,
+//! #    block_one: "stone".to_string(), block_two: "dirt".to_string(),
+//! #    block_three: "grass_block".to_string(), block_four: "oak_log".to_string(),
+//! #    block_five: "cobblestone".to_string(), block_six: "sand".to_string(),
+//! #    hidden: Some(0), featured: Some(1), hash: None, time_ago: "1 day ago".to_string()
+//! # };
+//!
+//! let palette = domain::Palette::try_from(&wire).unwrap();
+//! assert!(palette.featured);
+//! assert_eq!(palette.blocks[5], "sand");
+//!
+//! let mut broken = wire.clone();
+//! broken.date = "yesterday".into();
+//! broken.featured = Some(7);
+//! let error = domain::Palette::try_from(&broken).unwrap_err();
+//! assert_eq!(error.problems.len(), 2);
+//! ```
+
+use crate::{Palette as WirePalette, PaletteDetails as WirePaletteDetails};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The date format used by the API.
+const DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// A palette with typed fields.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Palette {
+    /// The unique identifier for the palette.
+    pub id: u64,
+    /// The ID of the user who created the palette.
+    pub user_id: u64,
+    /// When the palette was created, in the site's time zone.
+    pub created_at: NaiveDateTime,
+    /// The number of likes the palette has received.
+    pub likes: u32,
+    /// The six blocks of the palette, in display order.
+    pub blocks: [String; 6],
+    /// Whether the palette is hidden.
+    pub hidden: bool,
+    /// Whether the palette is featured.
+    pub featured: bool,
+    /// The hash associated with the palette, if any.
+    pub hash: Option<String>,
+}
+
+/// Palette details with typed fields.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PaletteDetails {
+    /// The palette itself.
+    pub palette: Palette,
+    /// The username of the palette creator.
+    pub username: String,
+}
+
+/// A single problem found while converting a wire record.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldProblem {
+    /// The wire name of the offending field (e.g., `"blockThree"`).
+    pub field: &'static str,
+    /// The offending value, as received.
+    pub value: String,
+    /// What is wrong with the value.
+    pub message: &'static str,
+}
+
+/// The error returned when a wire record cannot be converted to a domain type.
+///
+/// It lists every problem found in the record.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConversionError {
+    /// The ID of the record that failed to convert.
+    pub palette_id: u64,
+    /// All problems found in the record.
+    pub problems: Vec<FieldProblem>,
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "palette {} is invalid: ", self.palette_id)?;
+        for (i, problem) in self.problems.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(
+                f,
+                "{} `{}` {}",
+                problem.field, problem.value, problem.message
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConversionError {}
+
+/// Collects problems while converting a single record.
+struct Problems(Vec<FieldProblem>);
+
+impl Problems {
+    fn push(&mut self, field: &'static str, value: impl ToString, message: &'static str) {
+        self.0.push(FieldProblem {
+            field,
+            value: value.to_string(),
+            message,
+        });
+    }
+
+    fn date(&mut self, value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value.trim(), DATE_FORMAT).unwrap_or_else(|_| {
+            self.push("date", value, "is not a `YYYY-MM-DD HH:MM:SS` date");
+            NaiveDateTime::default()
+        })
+    }
+
+    fn flag(&mut self, field: &'static str, value: Option<u8>) -> bool {
+        match value {
+            None | Some(0) => false,
+            Some(1) => true,
+            Some(other) => {
+                self.push(field, other, "is not a 0/1 flag");
+                false
+            }
+        }
+    }
+
+    fn blocks(&mut self, wire: [&str; 6]) -> [String; 6] {
+        const FIELDS: [&str; 6] = [
+            "blockOne",
+            "blockTwo",
+            "blockThree",
+            "blockFour",
+            "blockFive",
+            "blockSix",
+        ];
+        std::array::from_fn(|i| {
+            let block = wire[i].trim();
+            if block.is_empty() {
+                self.push(FIELDS[i], wire[i], "is empty");
+            }
+            block.to_string()
+        })
+    }
+
+    fn finish<T>(self, palette_id: u64, value: T) -> Result<T, ConversionError> {
+        if self.0.is_empty() {
+            Ok(value)
+        } else {
+            Err(ConversionError {
+                palette_id,
+                problems: self.0,
+            })
+        }
+    }
+}
+
+impl TryFrom<&WirePalette> for Palette {
+    type Error = ConversionError;
+
+    fn try_from(wire: &WirePalette) -> Result<Self, Self::Error> {
+        let mut problems = Problems(Vec::new());
+        let palette = Palette {
+            id: wire.id,
+            user_id: wire.user_id,
+            created_at: problems.date(&wire.date),
+            likes: wire.likes,
+            blocks: problems.blocks([
+                &wire.block_one,
+                &wire.block_two,
+                &wire.block_three,
+                &wire.block_four,
+                &wire.block_five,
+                &wire.block_six,
+            ]),
+            hidden: problems.flag("hidden", wire.hidden),
+            featured: problems.flag("featured", wire.featured),
+            hash: wire.hash.clone().filter(|h| !h.is_empty()),
+        };
+        problems.finish(wire.id, palette)
+    }
+}
+
+impl TryFrom<WirePalette> for Palette {
+    type Error = ConversionError;
+
+    fn try_from(wire: WirePalette) -> Result<Self, Self::Error> {
+        Palette::try_from(&wire)
+    }
+}
+
+impl TryFrom<&WirePaletteDetails> for PaletteDetails {
+    type Error = ConversionError;
+
+    fn try_from(wire: &WirePaletteDetails) -> Result<Self, Self::Error> {
+        let mut problems = Problems(Vec::new());
+        let palette = Palette {
+            id: wire.id,
+            user_id: wire.user_id,
+            created_at: problems.date(&wire.date),
+            likes: wire.likes,
+            blocks: problems.blocks([
+                &wire.block_one,
+                &wire.block_two,
+                &wire.block_three,
+                &wire.block_four,
+                &wire.block_five,
+                &wire.block_six,
+            ]),
+            hidden: problems.flag("hidden", Some(wire.hidden)),
+            featured: problems.flag("featured", Some(wire.featured)),
+            hash: Some(wire.hash.clone()).filter(|h| !h.is_empty()),
+        };
+        let username = wire.username.trim();
+        if username.is_empty() {
+            problems.push("username", &wire.username, "is empty");
+        }
+        problems.finish(
+            wire.id,
+            PaletteDetails {
+                palette,
+                username: username.to_string(),
+            },
+        )
+    }
+}
+
+impl TryFrom<WirePaletteDetails> for PaletteDetails {
+    type Error = ConversionError;
+
+    fn try_from(wire: WirePaletteDetails) -> Result<Self, Self::Error> {
+        PaletteDetails::try_from(&wire)
+    }
+}
+
+impl From<&Palette> for WirePalette {
+    /// Converts back to the wire format.
+    ///
+    /// `time_ago` is not part of the domain model and is left empty.
+    fn from(palette: &Palette) -> Self {
+        let [one, two, three, four, five, six] = palette.blocks.clone();
+        WirePalette {
+            id: palette.id,
+            user_id: palette.user_id,
+            date: palette.created_at.format(DATE_FORMAT).to_string(),
+            likes: palette.likes,
+            block_one: one,
+            block_two: two,
+            block_three: three,
+            block_four: four,
+            block_five: five,
+            block_six: six,
+            hidden: Some(u8::from(palette.hidden)),
+            featured: Some(u8::from(palette.featured)),
+            hash: palette.hash.clone(),
+            time_ago: String::new(),
+        }
+    }
+}
+
+/// Converts a batch of wire palettes, separating valid records from invalid ones.
+///
+/// The order of the input is preserved in both outputs.
+///
+/// # Examples
+///
+/// ```rust
+/// use blockpalettes_client::domain;
+/// # let wire = blockpalettes_client::Palette {
+/// #    id: 1, user_id: 1, date: "2023-01-01 12:00:00".to_string(), likes: 10,
+/// #    block_one: "stone".to_string(), block_two: "dirt".to_string(),
+/// #    block_three: "grass_block".to_string(), block_four: "oak_log".to_string(),
+/// #    block_five: "cobblestone".to_string(), block_six: "sand".to_string(),
+/// #    hidden: Some(0), featured: Some(1), hash: None, time_ago: "1 day ago".to_string()
+/// # };
+/// let mut broken = wire.clone();
+/// broken.id = 2;
+/// broken.block_two = " ".into();
+///
+/// let (valid, errors) = domain::convert_all(&[wire, broken]);
+/// assert_eq!(valid.len(), 1);
+/// assert_eq!(errors[0].palette_id, 2);
+/// ```
+pub fn convert_all(wire: &[WirePalette]) -> (Vec<Palette>, Vec<ConversionError>) {
+    let mut valid = Vec::with_capacity(wire.len());
+    let mut errors = Vec::new();
+    for palette in wire {
+        match Palette::try_from(palette) {
+            Ok(p) => valid.push(p),
+            Err(e) => errors.push(e),
+        }
+    }
+    (valid, errors)
+}
@@ -0,0 +1,129 @@
+//! Exporting palettes to formats consumed outside this crate, such as links
+//! to the site and WorldEdit patterns.
+
+use crate::{DEFAULT_BASE_URL, Palette};
+use serde::{Deserialize, Serialize};
+
+/// Returns the canonical URL of a palette's page on the site.
+///
+/// The URL always uses the production site over HTTPS, without query string
+/// or trailing slash, so it is stable enough to embed in screenshots or
+/// stream overlays.
+///
+/// # Examples
+///
+/// ```rust
+/// use blockpalettes_client::export;
+/// # let palette = blockpalettes_client::Palette {
+/// #    id: 56655, user_id: 1, date: "2023-01-01 12:00:00".to_string(), likes: 10,
+/// #    block_one: "stone".to_string(), block_two: "dirt".to_string(),
+/// #    block_three: "grass_block".to_string(), block_four: "oak_log".to_string(),
+/// #    block_five: "cobblestone".to_string(), block_six: "sand".to_string(),
+/// #    hidden: Some(0), featured: Some(0), hash: None, time_ago: "1 day ago".to_string()
+/// # };
+/// assert_eq!(export::deep_link(&palette), "https://www.blockpalettes.com/palette/56655");
+/// ```
+pub fn deep_link(palette: &Palette) -> String {
+    deep_link_for_id(palette.id)
+}
+
+/// Returns the canonical URL of the palette page for `palette_id`.
+///
+/// See [`deep_link`].
+pub fn deep_link_for_id(palette_id: u64) -> String {
+    format!("{DEFAULT_BASE_URL}/palette/{palette_id}")
+}
+
+/// How [`worldedit_pattern`] weights the blocks of a palette.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PatternWeights {
+    /// Every block is used equally.
+    #[default]
+    Equal,
+    /// Blocks are weighted by slot order, from 6 for the first slot down to
+    /// 1 for the last, so the first block is the most common.
+    BySlot,
+    /// Explicit weights, one per slot in display order.
+    Custom([u32; 6]),
+}
+
+impl PatternWeights {
+    /// Returns the weight of each slot, in display order.
+    pub fn weights(self) -> [u32; 6] {
+        match self {
+            PatternWeights::Equal => [1; 6],
+            PatternWeights::BySlot => [6, 5, 4, 3, 2, 1],
+            PatternWeights::Custom(weights) => weights,
+        }
+    }
+}
+
+/// Returns a WorldEdit pattern mixing the blocks of a palette, such as
+/// `20%stone,30%andesite,...`, for use with `//set` and `//replace`.
+///
+/// Weights are converted to whole percentages adding up to 100, rounding
+/// so that the total is exact. A block appearing in several slots gets the
+/// sum of their weights; blocks whose weight is zero are left out. Block IDs
+/// are written without the `minecraft:` namespace. If every weight is zero,
+/// the pattern is empty.
+///
+/// # Examples
+///
+/// ```rust
+/// use blockpalettes_client::export::{self, PatternWeights};
+/// # let palette = blockpalettes_client::Palette {
+/// #    id: 56655, user_id: 1, date: "2023-01-01 12:00:00".to_string(), likes: 10,
+/// #    block_one: "stone".to_string(), block_two: "andesite".to_string(),
+/// #    block_three: "tuff".to_string(), block_four: "cobblestone".to_string(),
+/// #    block_five: "gravel".to_string(), block_six: "minecraft:stone".to_string(),
+/// #    hidden: Some(0), featured: Some(0), hash: None, time_ago: "1 day ago".to_string()
+/// # };
+/// assert_eq!(
+///     export::worldedit_pattern(&palette, PatternWeights::Equal),
+///     "33%stone,17%andesite,17%tuff,17%cobblestone,16%gravel"
+/// );
+/// assert_eq!(
+///     export::worldedit_pattern(&palette, PatternWeights::Custom([1, 1, 0, 0, 0, 2])),
+///     "75%stone,25%andesite"
+/// );
+/// ```
+pub fn worldedit_pattern(palette: &Palette, weights: PatternWeights) -> String {
+    let mut entries: Vec<(&str, u32)> = Vec::with_capacity(6);
+    for (block, weight) in palette.blocks().into_iter().zip(weights.weights()) {
+        let block = block.trim();
+        let block = block.strip_prefix("minecraft:").unwrap_or(block);
+        if weight == 0 {
+            continue;
+        }
+        match entries.iter_mut().find(|(name, _)| *name == block) {
+            Some((_, total)) => *total += weight,
+            None => entries.push((block, weight)),
+        }
+    }
+
+    let total: u64 = entries.iter().map(|(_, w)| u64::from(*w)).sum();
+    if total == 0 {
+        return String::new();
+    }
+
+    // largest remainder: floor every share, then hand out the missing
+    // percents to the largest remainders, earliest slots first on ties
+    let mut percents: Vec<(u64, u64)> = entries
+        .iter()
+        .map(|(_, w)| (u64::from(*w) * 100 / total, u64::from(*w) * 100 % total))
+        .collect();
+    let missing = 100 - percents.iter().map(|(p, _)| p).sum::<u64>();
+    let mut order: Vec<usize> = (0..percents.len()).collect();
+    order.sort_by(|a, b| percents[*b].1.cmp(&percents[*a].1));
+    for &index in order.iter().take(missing as usize) {
+        percents[index].0 += 1;
+    }
+
+    entries
+        .iter()
+        .zip(&percents)
+        .filter(|(_, (percent, _))| *percent > 0)
+        .map(|((block, _), (percent, _))| format!("{percent}%{block}"))
+        .collect::<Vec<_>>()
+        .join(",")
+}
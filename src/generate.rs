@@ -0,0 +1,237 @@
+//! Generating random palettes offline.
+//!
+//! Like the site's palette generator tool, [`random`] starts from a random
+//! base block and fills the palette with blocks of close colors, ordered from
+//! lightest to darkest. Generation only uses the block registry (see
+//! [`blocks`]), so it never touches the network and gives bots an endless
+//! supply of fresh palettes beyond user submissions.
+//!
+//! [`Constraints`] narrow down the generated palettes: blocks of required
+//! [groups](BlockGroup), a target color [`Harmony`], or the Minecraft version
+//! the palette must be buildable in.
+//!
+//! Generation is driven by an explicit [`Rng`], so a seed always produces the
+//! same palette; [`random_palette`] seeds it from the operating system.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use blockpalettes_client::generate::{self, Constraints, Rng};
+//!
+//! let constraints = Constraints {
+//!     exclude: vec!["tnt".to_string()],
+//!     full_blocks_only: true,
+//!     ..Default::default()
+//! };
+//! let mut rng = Rng::new(42);
+//! let draft = generate::random(&mut rng, &constraints).unwrap();
+//! assert!(!draft.blocks().contains(&"tnt"));
+//!
+//! // the same seed gives the same palette
+//! assert_eq!(generate::random(&mut Rng::new(42), &constraints), Some(draft));
+//! ```
+//!
+//! A monochrome palette with a block of dark wood, buildable in 1.16:
+//!
+//! ```rust
+//! use blockpalettes_client::analysis::Harmony;
+//! use blockpalettes_client::blocks::{BlockGroup, GameVersion};
+//! use blockpalettes_client::generate::{self, Constraints};
+//!
+//! let constraints = Constraints {
+//!     groups: vec![BlockGroup::DARK_WOOD],
+//!     harmony: Some(Harmony::Monochrome),
+//!     max_version: Some(GameVersion::new(16, 0)),
+//!     ..Default::default()
+//! };
+//! let draft = generate::random_palette(&constraints).unwrap();
+//! assert!(draft.blocks().iter().any(|b| BlockGroup::DARK_WOOD.contains(b)));
+//! ```
+
+use crate::analysis::{self, Harmony};
+use crate::blocks::{self, BlockGroup, GameVersion};
+use crate::color::Rgb;
+use crate::daily;
+use crate::remix::PaletteBuilder;
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+
+/// How many of the blocks closest in color to the base block the other
+/// blocks are picked from.
+const NEIGHBORHOOD: usize = 12;
+
+/// How many palettes are generated before giving up on a harmony target.
+const MAX_ATTEMPTS: usize = 1000;
+
+/// A small, seedable pseudo-random number generator (SplitMix64).
+///
+/// Its output only depends on the seed, on every platform and Rust version.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Creates a generator from `seed`.
+    pub const fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Creates a generator seeded from the operating system's randomness.
+    pub fn from_entropy() -> Self {
+        Self::new(RandomState::new().hash_one(0u8))
+    }
+
+    /// Returns the next pseudo-random number.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        daily::mix(self.state)
+    }
+
+    /// Returns a pseudo-random index below `n`, which must not be zero.
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
+
+/// The requirements on generated palettes.
+///
+/// Every field has a default, so only the ones that matter need to be set.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Constraints {
+    /// Blocks that generated palettes must not contain.
+    pub exclude: Vec<String>,
+    /// Whether to only use full blocks (see [`blocks::shape`]).
+    pub full_blocks_only: bool,
+    /// Groups generated palettes must contain at least one block of each.
+    pub groups: Vec<BlockGroup>,
+    /// The color harmony of generated palettes (see
+    /// [`analysis::harmony_score`]). Defaults to `None`, which accepts any.
+    pub harmony: Option<Harmony>,
+    /// The Minecraft version generated palettes must be buildable in (see
+    /// [`Palette::newest_block_version`](crate::Palette::newest_block_version)).
+    /// Blocks of unknown version are not used when set.
+    pub max_version: Option<GameVersion>,
+}
+
+impl Constraints {
+    /// Returns `true` if `block` may appear in generated palettes.
+    fn allows(&self, block: &str) -> bool {
+        (!self.full_blocks_only || blocks::shape(block).is_full())
+            && self
+                .max_version
+                .is_none_or(|max| blocks::introduced_in(block).is_some_and(|v| v <= max))
+            && !self.exclude.iter().any(|e| {
+                let e = e.trim();
+                e.strip_prefix("minecraft:").unwrap_or(e) == block
+            })
+    }
+}
+
+/// Generates a random palette satisfying `constraints`.
+///
+/// One block of each required group is picked first. The base block is the
+/// first of them with a known color, or a random allowed block with a known
+/// color if there is none; the remaining blocks are picked among the twelve
+/// allowed blocks closest to it in color. The six blocks are distinct and
+/// ordered from lightest to darkest, blocks of unknown color last.
+///
+/// With a [harmony](Constraints::harmony) target, palettes are generated
+/// until one matches, up to a thousand times.
+///
+/// Returns `None` if no palette satisfying `constraints` was found, e.g.
+/// because fewer than six blocks are allowed or a required group has no
+/// allowed block.
+pub fn random(rng: &mut Rng, constraints: &Constraints) -> Option<PaletteBuilder> {
+    let candidates: Vec<_> = blocks::colored_blocks()
+        .filter(|(name, _)| constraints.allows(name))
+        .collect();
+    let groups: Vec<Vec<&'static str>> = constraints
+        .groups
+        .iter()
+        .map(|group| {
+            group
+                .blocks
+                .iter()
+                .copied()
+                .filter(|b| constraints.allows(b))
+                .collect()
+        })
+        .collect();
+    if candidates.len() < 6 || groups.iter().any(Vec::is_empty) {
+        return None;
+    }
+
+    let attempts = if constraints.harmony.is_some() {
+        MAX_ATTEMPTS
+    } else {
+        1
+    };
+    (0..attempts)
+        .filter_map(|_| attempt(rng, &candidates, &groups))
+        .find(|draft| {
+            constraints
+                .harmony
+                .is_none_or(|harmony| analysis::harmony_of(draft.blocks()).harmony == harmony)
+        })
+}
+
+/// Generates a random palette satisfying `constraints`, with a generator
+/// seeded from the operating system.
+///
+/// See [`random`] for details.
+pub fn random_palette(constraints: &Constraints) -> Option<PaletteBuilder> {
+    random(&mut Rng::from_entropy(), constraints)
+}
+
+/// Generates a single palette containing a block of each of `groups`.
+fn attempt(
+    rng: &mut Rng,
+    candidates: &[(&'static str, Rgb)],
+    groups: &[Vec<&'static str>],
+) -> Option<PaletteBuilder> {
+    let mut picked: Vec<(&str, Option<Rgb>)> = Vec::with_capacity(6);
+    for group in groups {
+        if picked.iter().any(|(name, _)| group.contains(name)) {
+            continue;
+        }
+        let block = group[rng.below(group.len())];
+        picked.push((block, blocks::color(block)));
+    }
+    if picked.len() > 6 {
+        return None;
+    }
+
+    let base_color = match picked.iter().find_map(|(_, color)| *color) {
+        Some(color) => color,
+        None => {
+            let (base, color) = candidates[rng.below(candidates.len())];
+            picked.push((base, Some(color)));
+            color
+        }
+    };
+
+    let mut neighbors: Vec<_> = candidates
+        .iter()
+        .copied()
+        .filter(|(name, _)| !picked.iter().any(|(p, _)| p == name))
+        .collect();
+    neighbors.sort_by(|(_, a), (_, b)| base_color.distance(*a).total_cmp(&base_color.distance(*b)));
+    neighbors.truncate(NEIGHBORHOOD);
+    if picked.len() + neighbors.len() < 6 {
+        return None;
+    }
+    while picked.len() < 6 {
+        let (name, color) = neighbors.swap_remove(rng.below(neighbors.len()));
+        picked.push((name, Some(color)));
+    }
+
+    // lightest first, unknown colors last
+    picked.sort_by(|(_, a), (_, b)| {
+        let lightness = |c: &Option<Rgb>| c.map_or(f32::NEG_INFINITY, |c| c.to_lab().l);
+        lightness(b).total_cmp(&lightness(a))
+    });
+    Some(PaletteBuilder::new(std::array::from_fn(|i| {
+        picked[i].0.to_string()
+    })))
+}
@@ -0,0 +1,155 @@
+//! Exploring the similarity graph between palettes.
+//!
+//! Every palette has a handful of similar palettes (see
+//! [`BlockPalettesClient::get_similar_palettes`]), which makes the site a
+//! directed graph. [`path_between`] searches it breadth-first for the
+//! shortest chain of similar palettes leading from one palette to another,
+//! like "six degrees of separation" for palettes.
+//!
+//! A [`SimilarityGraph`] remembers the neighbours it fetched, so several
+//! searches from the same palettes only query each palette once.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use blockpalettes_client::{BlockPalettesClient, graph};
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let client = BlockPalettesClient::new(reqwest::Client::new());
+//!     match graph::path_between(&client, 56655, 1234, 6).await? {
+//!         Some(path) => println!("{} steps: {path:?}", path.len() - 1),
+//!         None => println!("no chain of at most 6 steps"),
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+use crate::{BlockPalettesClient, Result};
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
+
+/// Returns the shortest chain of palette IDs leading from `from` to `to`
+/// through similar palettes, both ends included, or `None` if there is no
+/// chain of at most `max_depth` steps.
+///
+/// This is [`SimilarityGraph::path_between`] on a new graph.
+///
+/// # Errors
+///
+/// Returns an error if the similar palettes of a visited palette cannot be
+/// fetched.
+pub async fn path_between(
+    client: &BlockPalettesClient,
+    from: u64,
+    to: u64,
+    max_depth: usize,
+) -> Result<Option<Vec<u64>>> {
+    SimilarityGraph::new()
+        .path_between(client, from, to, max_depth)
+        .await
+}
+
+/// The part of the similarity graph fetched so far.
+#[derive(Debug, Clone, Default)]
+pub struct SimilarityGraph {
+    neighbours: HashMap<u64, Vec<u64>>,
+}
+
+impl SimilarityGraph {
+    /// Creates an empty graph.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of palettes whose neighbours were fetched.
+    pub fn len(&self) -> usize {
+        self.neighbours.len()
+    }
+
+    /// Returns `true` if no neighbours were fetched yet.
+    pub fn is_empty(&self) -> bool {
+        self.neighbours.is_empty()
+    }
+
+    /// Returns the IDs of the palettes similar to `palette_id`, fetching
+    /// them unless they were fetched before.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the similar palettes cannot be fetched.
+    pub async fn neighbours(
+        &mut self,
+        client: &BlockPalettesClient,
+        palette_id: u64,
+    ) -> Result<&[u64]> {
+        match self.neighbours.entry(palette_id) {
+            Entry::Occupied(entry) => Ok(entry.into_mut()),
+            Entry::Vacant(entry) => {
+                let ids = client
+                    .get_similar_palettes(palette_id)
+                    .await?
+                    .into_iter()
+                    .map(|palette| palette.id)
+                    .filter(|&id| id != palette_id)
+                    .collect();
+                Ok(entry.insert(ids))
+            }
+        }
+    }
+
+    /// Returns the shortest chain of palette IDs leading from `from` to `to`
+    /// through similar palettes, both ends included, or `None` if there is
+    /// no chain of at most `max_depth` steps.
+    ///
+    /// The search is breadth-first and stops at `max_depth` steps from
+    /// `from`. The number of requests grows quickly with the depth, since
+    /// every palette has several neighbours: palettes visited by earlier
+    /// searches are not fetched again.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the similar palettes of a visited palette cannot be
+    /// fetched.
+    pub async fn path_between(
+        &mut self,
+        client: &BlockPalettesClient,
+        from: u64,
+        to: u64,
+        max_depth: usize,
+    ) -> Result<Option<Vec<u64>>> {
+        if from == to {
+            return Ok(Some(vec![from]));
+        }
+
+        // the palette each visited palette was first reached from
+        let mut parents = HashMap::from([(from, from)]);
+        let mut queue = VecDeque::from([(from, 0)]);
+
+        while let Some((id, depth)) = queue.pop_front() {
+            if depth == max_depth {
+                continue;
+            }
+
+            for &next in self.neighbours(client, id).await? {
+                if parents.contains_key(&next) {
+                    continue;
+                }
+                parents.insert(next, id);
+                if next == to {
+                    let mut path = vec![to];
+                    let mut current = to;
+                    while current != from {
+                        current = parents[&current];
+                        path.push(current);
+                    }
+                    path.reverse();
+                    return Ok(Some(path));
+                }
+                queue.push_back((next, depth + 1));
+            }
+        }
+
+        Ok(None)
+    }
+}
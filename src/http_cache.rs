@@ -0,0 +1,365 @@
+//! A disk-backed HTTP cache for the client's request pipeline.
+//!
+//! This module is only available with the `http-cache` feature. Unlike an
+//! application-level cache, it sits directly in front of every HTTP request
+//! made by [`BlockPalettesClient`](crate::BlockPalettesClient), so API calls and
+//! HTML scrapes are cached alike.
+//!
+//! The cache follows the usual rules for a private HTTP cache:
+//!
+//! - `Cache-Control: no-store` responses are never written to disk.
+//! - Fresh entries (per `max-age`, or `Expires` relative to `Date`) are served
+//!   without touching the network.
+//! - Stale entries, or entries marked `no-cache`, are revalidated using
+//!   `If-None-Match` (`ETag`) and `If-Modified-Since` (`Last-Modified`).
+//! - Responses with a `Vary` header are only reused when the listed request
+//!   headers match; `Vary: *` disables caching.
+//!
+//! Cache I/O failures are never surfaced to callers: a corrupt or unreadable
+//! entry is treated as a miss, and a failed write simply leaves the entry
+//! uncached.
+
+use crate::{HttpResponse, Result};
+use reqwest::header::{
+    AGE, CACHE_CONTROL, DATE, ETAG, EXPIRES, HeaderMap, IF_MODIFIED_SINCE, IF_NONE_MATCH,
+    LAST_MODIFIED, VARY,
+};
+use reqwest::{Client, Request, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A standards-compliant HTTP cache that stores responses on disk.
+///
+/// Attach it to a client with [`BlockPalettesClient::with_http_cache`].
+///
+/// [`BlockPalettesClient::with_http_cache`]: crate::BlockPalettesClient::with_http_cache
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use blockpalettes_client::{BlockPalettesClient, HttpCache};
+///
+/// let cache = HttpCache::new("/tmp/blockpalettes-cache").unwrap();
+/// let client = BlockPalettesClient::new(reqwest::Client::new()).with_http_cache(cache);
+/// ```
+#[derive(Debug, Clone)]
+pub struct HttpCache {
+    dir: PathBuf,
+}
+
+impl HttpCache {
+    /// Creates a new cache storing its entries in `dir`.
+    ///
+    /// The directory is created if it does not exist yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the directory cannot be created.
+    pub fn new(dir: impl Into<PathBuf>) -> std::io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    /// Returns the directory where cache entries are stored.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Removes every entry from the cache.
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the cache directory cannot be read or an entry
+    /// cannot be removed.
+    pub fn clear(&self) -> std::io::Result<()> {
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                fs::remove_file(path)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Executes `request` through the cache.
+    pub(crate) async fn execute(&self, client: &Client, request: Request) -> Result<HttpResponse> {
+        let key = request.url().as_str().to_string();
+        let request_headers = request.headers().clone();
+        let path = self.entry_path(&key);
+        let cached = self
+            .load(&path)
+            .filter(|entry| entry.url == key && entry.matches_vary(request.headers()));
+
+        let Some(mut entry) = cached else {
+            let response = HttpResponse::from_reqwest(client.execute(request).await?).await?;
+            self.store(&path, &key, &request_headers, None, &response);
+            return Ok(response);
+        };
+
+        let now = unix_now();
+        if entry.is_fresh(now) {
+            return Ok(entry.to_response());
+        }
+
+        // stale or `no-cache`: revalidate with the stored validators
+        let revalidation = request.try_clone().map(|mut conditional| {
+            let headers = conditional.headers_mut();
+            if let Some(etag) = entry.header(ETAG.as_str()).and_then(|v| v.parse().ok()) {
+                headers.insert(IF_NONE_MATCH, etag);
+            }
+            if let Some(modified) = entry
+                .header(LAST_MODIFIED.as_str())
+                .and_then(|v| v.parse().ok())
+            {
+                headers.insert(IF_MODIFIED_SINCE, modified);
+            }
+            conditional
+        });
+
+        let raw = client.execute(revalidation.unwrap_or(request)).await?;
+        if raw.status() == StatusCode::NOT_MODIFIED {
+            entry.refresh(raw.headers(), now);
+            self.write(&path, &entry);
+            return Ok(entry.to_response());
+        }
+
+        let response = HttpResponse::from_reqwest(raw).await?;
+        self.store(&path, &key, &request_headers, Some(&entry), &response);
+        Ok(response)
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir
+            .join(format!("{:016x}.json", fnv1a(key.as_bytes())))
+    }
+
+    fn load(&self, path: &Path) -> Option<CacheEntry> {
+        let data = fs::read(path).ok()?;
+        serde_json::from_slice(&data).ok()
+    }
+
+    fn store(
+        &self,
+        path: &Path,
+        key: &str,
+        request_headers: &HeaderMap,
+        previous: Option<&CacheEntry>,
+        response: &HttpResponse,
+    ) {
+        let directives = CacheControl::parse(&response.headers);
+        let vary = header_list(&response.headers, VARY.as_str());
+
+        if response.status != StatusCode::OK
+            || directives.no_store
+            || vary.iter().any(|name| name == "*")
+        {
+            if previous.is_some() {
+                let _ = fs::remove_file(path);
+            }
+            return;
+        }
+
+        let entry = CacheEntry {
+            url: key.to_string(),
+            status: response.status.as_u16(),
+            headers: header_pairs(&response.headers),
+            vary: vary
+                .into_iter()
+                .map(|name| {
+                    let value = request_headers
+                        .get(&name)
+                        .and_then(|v| v.to_str().ok())
+                        .map(str::to_string);
+                    (name, value)
+                })
+                .collect(),
+            stored_at: unix_now(),
+            body: response.body.clone(),
+        };
+
+        if entry.lifetime().is_some_and(|secs| secs > 0) || entry.has_validators() {
+            self.write(path, &entry);
+        }
+    }
+
+    fn write(&self, path: &Path, entry: &CacheEntry) {
+        let Ok(data) = serde_json::to_vec(entry) else {
+            return;
+        };
+        let tmp = path.with_extension("tmp");
+        if fs::write(&tmp, data).is_ok() {
+            let _ = fs::rename(&tmp, path);
+        }
+    }
+}
+
+/// A response as persisted on disk.
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    url: String,
+    status: u16,
+    headers: Vec<(String, String)>,
+    vary: Vec<(String, Option<String>)>,
+    stored_at: i64,
+    body: String,
+}
+
+impl CacheEntry {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn header_map(&self) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in &self.headers {
+            if let (Ok(name), Ok(value)) = (
+                name.parse::<reqwest::header::HeaderName>(),
+                value.parse::<reqwest::header::HeaderValue>(),
+            ) {
+                map.append(name, value);
+            }
+        }
+        map
+    }
+
+    fn matches_vary(&self, request_headers: &HeaderMap) -> bool {
+        self.vary.iter().all(|(name, stored)| {
+            let current = request_headers
+                .get(name.as_str())
+                .and_then(|v| v.to_str().ok());
+            current == stored.as_deref()
+        })
+    }
+
+    fn has_validators(&self) -> bool {
+        self.header(ETAG.as_str()).is_some() || self.header(LAST_MODIFIED.as_str()).is_some()
+    }
+
+    /// Returns the freshness lifetime in seconds, if the response declares one.
+    fn lifetime(&self) -> Option<i64> {
+        let directives = CacheControl::parse(&self.header_map());
+        if let Some(max_age) = directives.max_age {
+            return Some(max_age);
+        }
+
+        let expires = parse_http_date(self.header(EXPIRES.as_str())?)?;
+        let date = self
+            .header(DATE.as_str())
+            .and_then(parse_http_date)
+            .unwrap_or(self.stored_at);
+        Some((expires - date).max(0))
+    }
+
+    fn is_fresh(&self, now: i64) -> bool {
+        let directives = CacheControl::parse(&self.header_map());
+        if directives.no_cache {
+            return false;
+        }
+
+        let age = self
+            .header(AGE.as_str())
+            .and_then(|v| v.trim().parse::<i64>().ok())
+            .unwrap_or(0);
+        let current_age = age + (now - self.stored_at).max(0);
+
+        self.lifetime()
+            .is_some_and(|lifetime| current_age < lifetime)
+    }
+
+    /// Merges the headers of a `304 Not Modified` response into the entry.
+    fn refresh(&mut self, headers: &HeaderMap, now: i64) {
+        for (name, value) in header_pairs(headers) {
+            self.headers.retain(|(n, _)| !n.eq_ignore_ascii_case(&name));
+            self.headers.push((name, value));
+        }
+        self.stored_at = now;
+    }
+
+    fn to_response(&self) -> HttpResponse {
+        HttpResponse {
+            status: StatusCode::from_u16(self.status).unwrap_or(StatusCode::OK),
+            headers: self.header_map(),
+            body: self.body.clone(),
+            from_cache: true,
+            url: None,
+        }
+    }
+}
+
+/// The subset of `Cache-Control` directives relevant to a private cache.
+#[derive(Debug, Default)]
+struct CacheControl {
+    no_store: bool,
+    no_cache: bool,
+    max_age: Option<i64>,
+}
+
+impl CacheControl {
+    fn parse(headers: &HeaderMap) -> Self {
+        let mut directives = Self::default();
+        for value in header_list(headers, CACHE_CONTROL.as_str()) {
+            let (name, arg) = match value.split_once('=') {
+                Some((name, arg)) => (name.trim(), Some(arg.trim().trim_matches('"'))),
+                None => (value.as_str(), None),
+            };
+            match name {
+                "no-store" => directives.no_store = true,
+                "no-cache" => directives.no_cache = true,
+                "max-age" => directives.max_age = arg.and_then(|a| a.parse().ok()),
+                _ => {}
+            }
+        }
+        directives
+    }
+}
+
+/// Splits a comma-separated header into lowercase, trimmed items.
+fn header_list(headers: &HeaderMap, name: &str) -> Vec<String> {
+    headers
+        .get_all(name)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .map(|item| item.trim().to_ascii_lowercase())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+fn header_pairs(headers: &HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .filter_map(|(name, value)| {
+            value
+                .to_str()
+                .ok()
+                .map(|v| (name.as_str().to_string(), v.to_string()))
+        })
+        .collect()
+}
+
+/// Parses an HTTP-date (IMF-fixdate) into a unix timestamp.
+fn parse_http_date(value: &str) -> Option<i64> {
+    chrono::DateTime::parse_from_rfc2822(value.trim())
+        .ok()
+        .map(|date| date.timestamp())
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+/// 64-bit FNV-1a, used for stable cache file names across Rust versions.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
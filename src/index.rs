@@ -0,0 +1,184 @@
+//! Fast block lookups over a corpus of palettes.
+//!
+//! Filtering a large corpus with [`Palette::contains_all_blocks`] compares
+//! every palette with every requested block. A [`PaletteIndex`] is built once
+//! for the corpus and keeps, for each block, a bitset of the palettes that
+//! contain it, so a query only intersects one bitset per requested block.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use blockpalettes_client::index::PaletteIndex;
+//! # let palette = |id, blocks: [&str; 6]| blockpalettes_client::Palette {
+//! #    id, user_id: 1, date: "2023-01-01 12:00:00".to_string(), likes: 10,
+//! #    block_one: blocks[0].to_string(), block_two: blocks[1].to_string(),
+//! #    block_three: blocks[2].to_string(), block_four: blocks[3].to_string(),
+//! #    block_five: blocks[4].to_string(), block_six: blocks[5].to_string(),
+//! #    hidden: Some(0), featured: Some(0), hash: None, time_ago: String::new()
+//! # };
+//! let corpus = vec![
+//!     palette(1, ["stone", "andesite", "oak_planks", "spruce_log", "moss_block", "dirt"]),
+//!     palette(2, ["stone", "diorite", "birch_planks", "birch_log", "moss_block", "sand"]),
+//!     palette(3, ["deepslate", "tuff", "calcite", "copper_block", "cut_copper", "dirt"]),
+//! ];
+//! let index = PaletteIndex::new(&corpus);
+//!
+//! let ids: Vec<u64> = index.containing_all(&["stone", "moss_block"]).map(|p| p.id).collect();
+//! assert_eq!(ids, [1, 2]);
+//! assert_eq!(index.count_containing_all(&["dirt"]), 2);
+//! assert_eq!(index.containing_any(&["sand", "tuff"]).count(), 2);
+//! ```
+
+use crate::Palette;
+use crate::intern::Interner;
+
+/// A set of palette positions in a corpus.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Bitset(Vec<u64>);
+
+impl Bitset {
+    fn new(len: usize) -> Self {
+        Self(vec![0; len.div_ceil(64)])
+    }
+
+    fn full(len: usize) -> Self {
+        let mut set = Self(vec![u64::MAX; len.div_ceil(64)]);
+        if let Some(last) = set.0.last_mut()
+            && !len.is_multiple_of(64)
+        {
+            *last = (1 << (len % 64)) - 1;
+        }
+        set
+    }
+
+    fn insert(&mut self, index: usize) {
+        self.0[index / 64] |= 1 << (index % 64);
+    }
+
+    fn intersect(&mut self, other: &Bitset) {
+        for (word, other) in self.0.iter_mut().zip(&other.0) {
+            *word &= other;
+        }
+    }
+
+    fn union(&mut self, other: &Bitset) {
+        for (word, other) in self.0.iter_mut().zip(&other.0) {
+            *word |= other;
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.0.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    fn into_positions(self) -> impl Iterator<Item = usize> {
+        self.0.into_iter().enumerate().flat_map(|(i, mut word)| {
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                Some(i * 64 + bit)
+            })
+        })
+    }
+}
+
+/// An inverted index from blocks to the palettes of a corpus containing them.
+///
+/// Block names are compared exactly, like [`Palette::contains_all_blocks`].
+/// Results are returned in the order of the corpus.
+#[derive(Debug, Clone)]
+pub struct PaletteIndex<'a> {
+    palettes: &'a [Palette],
+    interner: Interner,
+    /// The palettes containing each block, by [`BlockRef`](crate::intern::BlockRef).
+    postings: Vec<Bitset>,
+}
+
+impl<'a> PaletteIndex<'a> {
+    /// Indexes the blocks of every palette of `palettes`.
+    pub fn new(palettes: &'a [Palette]) -> Self {
+        let mut interner = Interner::new();
+        let mut postings = Vec::new();
+        for (position, palette) in palettes.iter().enumerate() {
+            for block in palette.blocks() {
+                let block = interner.intern(block).index();
+                if block == postings.len() {
+                    postings.push(Bitset::new(palettes.len()));
+                }
+                postings[block].insert(position);
+            }
+        }
+        Self {
+            palettes,
+            interner,
+            postings,
+        }
+    }
+
+    /// Returns the number of indexed palettes.
+    pub fn len(&self) -> usize {
+        self.palettes.len()
+    }
+
+    /// Returns `true` if no palette is indexed.
+    pub fn is_empty(&self) -> bool {
+        self.palettes.is_empty()
+    }
+
+    /// Returns the number of distinct blocks in the corpus.
+    pub fn block_count(&self) -> usize {
+        self.postings.len()
+    }
+
+    /// Returns the palettes containing every block of `blocks`.
+    ///
+    /// Every palette matches an empty list of blocks.
+    pub fn containing_all(&self, blocks: &[&str]) -> impl Iterator<Item = &'a Palette> + use<'a> {
+        self.resolve(self.all(blocks))
+    }
+
+    /// Returns the palettes containing at least one block of `blocks`.
+    pub fn containing_any(&self, blocks: &[&str]) -> impl Iterator<Item = &'a Palette> + use<'a> {
+        self.resolve(self.any(blocks))
+    }
+
+    /// Returns the number of palettes containing every block of `blocks`,
+    /// without collecting them.
+    pub fn count_containing_all(&self, blocks: &[&str]) -> usize {
+        self.all(blocks).len()
+    }
+
+    fn all(&self, blocks: &[&str]) -> Bitset {
+        let mut matches = Bitset::full(self.palettes.len());
+        for block in blocks {
+            match self.posting(block) {
+                Some(palettes) => matches.intersect(palettes),
+                None => return Bitset::new(self.palettes.len()),
+            }
+        }
+        matches
+    }
+
+    fn any(&self, blocks: &[&str]) -> Bitset {
+        let mut matches = Bitset::new(self.palettes.len());
+        for palettes in blocks.iter().filter_map(|b| self.posting(b)) {
+            matches.union(palettes);
+        }
+        matches
+    }
+
+    fn posting(&self, block: &str) -> Option<&Bitset> {
+        let block = self.interner.get(block)?;
+        Some(&self.postings[block.index()])
+    }
+
+    fn resolve(&self, matches: Bitset) -> impl Iterator<Item = &'a Palette> + use<'a> {
+        let palettes = self.palettes;
+        matches
+            .into_positions()
+            .map(move |position| &palettes[position])
+    }
+}
@@ -0,0 +1,253 @@
+//! Memory-efficient storage of large corpora of palettes.
+//!
+//! A [`Palette`] stores its six blocks as separate heap-allocated strings,
+//! although a corpus only uses a few hundred distinct block names. An
+//! [`Interner`] stores every block name once and hands out [`BlockRef`]s, two
+//! bytes each; an [`InternedPalette`] keeps those instead of names. Palettes
+//! convert back to regular [`Palette`]s on demand.
+//!
+//! [`InternedCorpus`] bundles interned palettes with their interner, for
+//! mirrors loaded in bulk.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use blockpalettes_client::intern::InternedCorpus;
+//! # let palette = |id, blocks: [&str; 6]| blockpalettes_client::Palette {
+//! #    id, user_id: 1, date: "2023-01-01 12:00:00".to_string(), likes: 10,
+//! #    block_one: blocks[0].to_string(), block_two: blocks[1].to_string(),
+//! #    block_three: blocks[2].to_string(), block_four: blocks[3].to_string(),
+//! #    block_five: blocks[4].to_string(), block_six: blocks[5].to_string(),
+//! #    hidden: Some(0), featured: Some(0), hash: None, time_ago: String::new()
+//! # };
+//! let palettes = vec![
+//!     palette(1, ["stone", "andesite", "oak_planks", "spruce_log", "moss_block", "dirt"]),
+//!     palette(2, ["stone", "diorite", "birch_planks", "birch_log", "moss_block", "sand"]),
+//! ];
+//! let corpus = InternedCorpus::from(palettes.as_slice());
+//!
+//! // "stone" and "moss_block" are stored once for both palettes
+//! assert_eq!(corpus.interner().len(), 10);
+//! assert_eq!(corpus.containing_all(&["stone", "sand"]).map(|p| p.id).collect::<Vec<_>>(), [2]);
+//! assert_eq!(corpus.to_palettes(), palettes);
+//! ```
+
+use crate::Palette;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// A block name stored in an [`Interner`].
+///
+/// References are only meaningful for the interner that created them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BlockRef(u16);
+
+impl BlockRef {
+    /// Returns the position of the block in its interner, from 0.
+    pub fn index(self) -> usize {
+        usize::from(self.0)
+    }
+}
+
+/// Stores each distinct block name once.
+#[derive(Debug, Clone, Default)]
+pub struct Interner {
+    names: Vec<Arc<str>>,
+    refs: HashMap<Arc<str>, BlockRef>,
+}
+
+impl Interner {
+    /// Creates an empty interner.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the reference of `name`, storing the name if it is new.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than 65,536 distinct names are interned; Minecraft has
+    /// about a thousand blocks.
+    pub fn intern(&mut self, name: &str) -> BlockRef {
+        if let Some(&block) = self.refs.get(name) {
+            return block;
+        }
+        let block =
+            BlockRef(u16::try_from(self.names.len()).expect("too many distinct block names"));
+        let name: Arc<str> = name.into();
+        self.names.push(Arc::clone(&name));
+        self.refs.insert(name, block);
+        block
+    }
+
+    /// Returns the reference of `name`, if it was interned.
+    pub fn get(&self, name: &str) -> Option<BlockRef> {
+        self.refs.get(name).copied()
+    }
+
+    /// Returns the name of `block`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `block` was created by another interner with more names.
+    pub fn resolve(&self, block: BlockRef) -> &str {
+        &self.names[block.index()]
+    }
+
+    /// Returns the number of distinct names.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Returns `true` if no name was interned.
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+/// A [`Palette`] whose blocks are stored as [`BlockRef`]s.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InternedPalette {
+    /// The unique identifier for the palette.
+    pub id: u64,
+    /// The ID of the user who created the palette.
+    pub user_id: u64,
+    /// The creation date of the palette (`YYYY-MM-DD HH:MM:SS`).
+    pub date: Box<str>,
+    /// The number of likes the palette has received.
+    pub likes: u32,
+    /// The six blocks of the palette, in order.
+    pub blocks: [BlockRef; 6],
+    /// Whether the palette is hidden, as in [`Palette::hidden`].
+    pub hidden: Option<u8>,
+    /// Whether the palette is featured, as in [`Palette::featured`].
+    pub featured: Option<u8>,
+    /// An optional hash associated with the palette.
+    pub hash: Option<Box<str>>,
+    /// How long ago the palette was created, as in [`Palette::time_ago`].
+    pub time_ago: Box<str>,
+}
+
+impl InternedPalette {
+    /// Interns the blocks of `palette` into `interner`.
+    pub fn new(palette: &Palette, interner: &mut Interner) -> Self {
+        Self {
+            id: palette.id,
+            user_id: palette.user_id,
+            date: palette.date.as_str().into(),
+            likes: palette.likes,
+            blocks: palette.blocks().map(|block| interner.intern(block)),
+            hidden: palette.hidden,
+            featured: palette.featured,
+            hash: palette.hash.as_deref().map(Into::into),
+            time_ago: palette.time_ago.as_str().into(),
+        }
+    }
+
+    /// Returns the names of the blocks of the palette.
+    pub fn block_names<'a>(&self, interner: &'a Interner) -> [&'a str; 6] {
+        self.blocks.map(|block| interner.resolve(block))
+    }
+
+    /// Checks if the palette contains `block`.
+    pub fn contains(&self, block: BlockRef) -> bool {
+        self.blocks.contains(&block)
+    }
+
+    /// Converts the palette back to a regular [`Palette`].
+    pub fn to_palette(&self, interner: &Interner) -> Palette {
+        let [one, two, three, four, five, six] = self.block_names(interner).map(str::to_string);
+        Palette {
+            id: self.id,
+            user_id: self.user_id,
+            date: self.date.to_string(),
+            likes: self.likes,
+            block_one: one,
+            block_two: two,
+            block_three: three,
+            block_four: four,
+            block_five: five,
+            block_six: six,
+            hidden: self.hidden,
+            featured: self.featured,
+            hash: self.hash.as_deref().map(str::to_string),
+            time_ago: self.time_ago.to_string(),
+        }
+    }
+}
+
+/// A corpus of [`InternedPalette`]s sharing one [`Interner`].
+#[derive(Debug, Clone, Default)]
+pub struct InternedCorpus {
+    interner: Interner,
+    palettes: Vec<InternedPalette>,
+}
+
+impl InternedCorpus {
+    /// Creates an empty corpus.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a palette to the corpus.
+    pub fn push(&mut self, palette: &Palette) {
+        let palette = InternedPalette::new(palette, &mut self.interner);
+        self.palettes.push(palette);
+    }
+
+    /// Returns the interner holding the block names of the corpus.
+    pub fn interner(&self) -> &Interner {
+        &self.interner
+    }
+
+    /// Returns the palettes of the corpus, in insertion order.
+    pub fn palettes(&self) -> &[InternedPalette] {
+        &self.palettes
+    }
+
+    /// Returns the number of palettes.
+    pub fn len(&self) -> usize {
+        self.palettes.len()
+    }
+
+    /// Returns `true` if the corpus has no palette.
+    pub fn is_empty(&self) -> bool {
+        self.palettes.is_empty()
+    }
+
+    /// Returns the palettes containing every block of `blocks`.
+    ///
+    /// Block names are resolved once, then palettes are compared by
+    /// reference, without any string comparison.
+    pub fn containing_all(&self, blocks: &[&str]) -> impl Iterator<Item = &InternedPalette> {
+        let refs: Option<Vec<BlockRef>> = blocks.iter().map(|b| self.interner.get(b)).collect();
+        let palettes = if refs.is_some() {
+            &self.palettes[..]
+        } else {
+            &[]
+        };
+        let refs = refs.unwrap_or_default();
+        palettes
+            .iter()
+            .filter(move |palette| refs.iter().all(|&block| palette.contains(block)))
+    }
+
+    /// Converts every palette back to a regular [`Palette`].
+    pub fn to_palettes(&self) -> Vec<Palette> {
+        self.palettes
+            .iter()
+            .map(|palette| palette.to_palette(&self.interner))
+            .collect()
+    }
+}
+
+impl From<&[Palette]> for InternedCorpus {
+    fn from(palettes: &[Palette]) -> Self {
+        let mut corpus = Self::new();
+        corpus.palettes.reserve(palettes.len());
+        for palette in palettes {
+            corpus.push(palette);
+        }
+        corpus
+    }
+}
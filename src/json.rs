@@ -0,0 +1,114 @@
+//! Machine-readable JSON output for every serializable type.
+//!
+//! [`ToJson`] is implemented for every type implementing `Serialize`, which
+//! covers the public types of the crate as well as collections of them, so
+//! command-line tools and shell pipelines can print any result with
+//! [`to_json_pretty`](ToJson::to_json_pretty) or
+//! [`to_json_compact`](ToJson::to_json_compact) without picking a serializer.
+//!
+//! Keys are the serde names of the types, the same as in every other
+//! serialized form, and object keys are sorted, so the output of a value does
+//! not depend on field order. Map keys that are data, such as collection
+//! names or block IDs, are written as they are.
+//! [`schema`](ToJson::schema) describes the shape of a value as a JSON
+//! Schema, for consumers written in other languages.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use blockpalettes_client::json::ToJson;
+//! # let palette = blockpalettes_client::Palette {
+//! #    id: 7, user_id: 1, date: "2023-01-01 12:00:00".to_string(), likes: 10,
+//! #    block_one: "stone".to_string(), block_two: "andesite".to_string(),
+//! #    block_three: "oak_planks".to_string(), block_four: "spruce_log".to_string(),
+//! #    block_five: "moss_block".to_string(), block_six: "dirt".to_string(),
+//! #    hidden: Some(0), featured: Some(0), hash: None, time_ago: String::new()
+//! # };
+//! let json = palette.to_json_compact()?;
+//! assert!(json.starts_with(r#"{"blockFive":"moss_block","blockFour":"spruce_log","#));
+//!
+//! let schema = palette.schema()?;
+//! assert_eq!(schema["type"], "object");
+//! assert_eq!(schema["properties"]["likes"]["type"], "integer");
+//! assert_eq!(schema["properties"]["blockOne"]["type"], "string");
+//!
+//! // collections work too
+//! let many = vec![palette.clone(), palette];
+//! assert_eq!(many.schema()?["items"]["properties"]["id"]["type"], "integer");
+//!
+//! // map keys are data, and are left alone
+//! let tags = std::collections::BTreeMap::from([("copperBuilds", 3)]);
+//! assert_eq!(tags.to_json_compact()?, r#"{"copperBuilds":3}"#);
+//! # Ok::<(), blockpalettes_client::BlockPalettesError>(())
+//! ```
+
+use crate::Result;
+use serde::Serialize;
+use serde_json::{Map, Value, json};
+
+/// JSON output for serializable values.
+///
+/// Implemented for every type implementing `Serialize`.
+pub trait ToJson: Serialize {
+    /// Serializes the value as indented JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BlockPalettesError::Json`](crate::BlockPalettesError::Json)
+    /// if serialization fails, e.g. for maps whose keys are not strings or
+    /// numbers.
+    fn to_json_pretty(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(&serde_json::to_value(self)?)?)
+    }
+
+    /// Serializes the value as JSON on a single line, e.g. for JSON Lines
+    /// output.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BlockPalettesError::Json`](crate::BlockPalettesError::Json)
+    /// if serialization fails.
+    fn to_json_compact(&self) -> Result<String> {
+        Ok(serde_json::to_string(&serde_json::to_value(self)?)?)
+    }
+
+    /// Returns a JSON Schema describing the shape of the value.
+    ///
+    /// The schema is inferred from the serialized value, so it describes this
+    /// value rather than every possible one: a field that is `None` has type
+    /// `null`, and the items of an array are described by its first item (an
+    /// empty array has no `items`). Object properties are listed in
+    /// alphabetical order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BlockPalettesError::Json`](crate::BlockPalettesError::Json)
+    /// if serialization fails.
+    fn schema(&self) -> Result<Value> {
+        Ok(shape(&serde_json::to_value(self)?))
+    }
+}
+
+impl<T: Serialize + ?Sized> ToJson for T {}
+
+/// Returns the JSON Schema of `value`.
+fn shape(value: &Value) -> Value {
+    match value {
+        Value::Null => json!({ "type": "null" }),
+        Value::Bool(_) => json!({ "type": "boolean" }),
+        Value::Number(n) if n.is_f64() => json!({ "type": "number" }),
+        Value::Number(_) => json!({ "type": "integer" }),
+        Value::String(_) => json!({ "type": "string" }),
+        Value::Array(items) => match items.first() {
+            Some(first) => json!({ "type": "array", "items": shape(first) }),
+            None => json!({ "type": "array" }),
+        },
+        Value::Object(fields) => {
+            let properties: Map<String, Value> = fields
+                .iter()
+                .map(|(name, value)| (name.clone(), shape(value)))
+                .collect();
+            json!({ "type": "object", "properties": properties })
+        }
+    }
+}
@@ -0,0 +1,80 @@
+//! Deserializers for the list fields of API responses.
+//!
+//! The PHP backend does not always encode lists as JSON arrays. Empty
+//! results sometimes come back as `{}` (an empty PHP array serialized as an
+//! object) or `false` instead of `[]` or `null`, and arrays whose keys are not
+//! sequential, such as filtered results, come back as objects keyed by index.
+
+use serde::de::{self, Deserialize, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use std::fmt;
+use std::marker::PhantomData;
+
+/// Deserializes a list, treating `null`, `false` and `{}` as an empty list
+/// and any other object as the list of its values.
+pub(crate) fn list<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Ok(option_list(deserializer)?.unwrap_or_default())
+}
+
+/// Deserializes an optional list, treating `null`, `false` and `{}` as
+/// `None` and any other object as the list of its values.
+///
+/// Fields using it need `#[serde(default)]` to stay optional.
+pub(crate) fn option_list<'de, D, T>(deserializer: D) -> Result<Option<Vec<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    deserializer.deserialize_any(ListVisitor(PhantomData))
+}
+
+struct ListVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for ListVisitor<T> {
+    type Value = Option<Vec<T>>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a list, an object, `false` or `null`")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        // the hint comes from the input, so it is capped
+        let mut items = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(1024));
+        while let Some(item) = seq.next_element()? {
+            items.push(item);
+        }
+        Ok(Some(items))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        // keys are the PHP array indices; the values come in array order
+        let mut items = Vec::new();
+        while let Some((IgnoredAny, item)) = map.next_entry()? {
+            items.push(item);
+        }
+        Ok((!items.is_empty()).then_some(items))
+    }
+
+    fn visit_bool<E: de::Error>(self, value: bool) -> Result<Self::Value, E> {
+        if value {
+            Err(E::invalid_value(de::Unexpected::Bool(value), &self))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
@@ -15,8 +15,51 @@
 //! - Search for palettes based on contained blocks.
 //! - Retrieve lists of popular blocks.
 //! - Fetch detailed information for individual palettes.
-//! - Get similar palettes based on a given palette ID.
+//! - Get similar palettes based on a given palette ID, and find chains of
+//!   similar palettes between two palettes with [`graph`].
+//! - Guess which palettes were remixed from which with [`lineage`].
 //! - Scrape palette page details (blocks and similar palette IDs) directly from HTML.
+//! - Fetch palettes from the cache, the API or their page, whichever is the
+//!   cheapest to answer, with [`smart`] fallback rules.
+//! - Curate local [`collections`] of palettes with notes and tags.
+//! - [`watcher`]: get notified about newly added palettes.
+//! - Exclude blocks, users or palettes from every result with a [`denylist`],
+//!   and reorder results with a custom [`ranking`].
+//! - Patch bad upstream data by pinning corrected blocks and custom titles to
+//!   palettes with [`overrides`].
+//! - Filter large corpora by block with a bitset [`index`], and store them
+//!   compactly by [`intern`]ing block names.
+//! - Keep local [`dataset`] mirrors honest by checking them against the site
+//!   with [`mirror`].
+//! - Render palettes, contact sheets and side-by-side comparisons of palettes
+//!   to PNG with [`render`], and compare their blocks with [`diff`].
+//! - Build local variations of palettes with [`remix`], [`generate`] random
+//!   ones offline, and classify their colors with [`analysis`].
+//! - Find palettes by build [`styles`], such as medieval or desert.
+//! - Suggest human-readable [`naming`] for palettes.
+//! - Follow block trends and popularity month by month with [`stats`],
+//!   [`report`] how the blocks of a Minecraft update are adopted, and track
+//!   how popular blocks move up and down the ranking with [`trends`].
+//! - Turn palettes into fixed-length vectors for nearest neighbour search,
+//!   and cluster corpora into archetypes, with [`ml`].
+//! - Credit palette creators consistently with [`attribution`].
+//! - Protect the site and your service during outages with a per-endpoint
+//!   [`circuit`] breaker, and [`coalesce`] bursts of identical requests.
+//! - Retry rate-limited or failed requests, and [`observe`] cache hits,
+//!   rate-limit waits and retries as they happen.
+//! - Pace requests with a prioritized [`queue`], so interactive commands are
+//!   not starved by background crawls sharing the client, and cap the
+//!   requests in flight across every subsystem with a shared [`budget`].
+//! - Run apps [`offline`] from recorded fixtures, without touching the
+//!   network.
+//! - Keep long-lived [`connection`]s healthy with HTTP/2 and TCP keepalive
+//!   probes.
+//! - Swap backends at runtime behind an `Arc<dyn PaletteSource>` with
+//!   [`source`].
+//! - Render palettes and [`upload`] the images to S3-compatible storage or
+//!   any image host, uploading each image only once.
+//! - Print any result as pretty or compact [`json`], with an inferred schema,
+//!   for shell pipelines and non-Rust consumers.
 //! - Robust error handling with custom error types.
 //!
 //! # Error Handling
@@ -28,14 +71,128 @@
 //! # Data Structures
 //!
 //! Key data structures like [`Palette`], [`PaletteDetails`], and [`PopularBlock`]
-//! are provided to represent the API responses.
+//! are provided to represent the API responses. They mirror the API's wire
+//! format; the [`domain`] module provides cleaner, typed equivalents.
+//!
+//! # Cargo Features
+//!
+//! - `archive`: enables [`BlockPalettesClient::fetch_from_wayback`], which
+//!   recovers deleted palettes from the Internet Archive's Wayback Machine.
+//! - `http-cache`: enables [`HttpCache`], a disk-backed HTTP cache honoring
+//!   `Cache-Control`, `ETag` and `Vary` for every request the client makes.
+//! - `tls-pinning`: enables [`BlockPalettesClient::with_tls_pins`], which
+//!   rejects TLS connections whose certificate key is not pinned.
+//! - `textures`: enables [`blocks::texture_png`], which renders 16x16 PNG
+//!   textures of blocks from their known colors, and [`model`], which exports
+//!   palettes as 3D walls of blocks for Blockbench and glTF viewers.
+//! - `test-server`: enables [`test_server`], a local mock of the site for
+//!   offline integration tests, and the `blockpalettes-test-server` binary.
+
+pub mod analysis;
+#[cfg(feature = "archive")]
+pub mod archive;
+pub mod attribution;
+pub mod blocks;
+pub mod budget;
+pub mod circuit;
+pub mod coalesce;
+pub mod collections;
+pub mod color;
+pub mod connection;
+pub mod daily;
+pub mod dataset;
+pub mod denylist;
+pub mod diff;
+pub mod domain;
+pub mod export;
+pub mod generate;
+pub mod graph;
+#[cfg(feature = "http-cache")]
+mod http_cache;
+pub mod index;
+pub mod intern;
+pub mod json;
+mod lenient;
+pub mod limits;
+pub mod lineage;
+pub mod meta;
+pub mod mirror;
+pub mod ml;
+#[cfg(feature = "textures")]
+pub mod model;
+pub mod naming;
+pub mod observe;
+pub mod offline;
+pub mod overrides;
+pub mod pagination;
+pub mod palette_string;
+pub mod partial;
+pub mod permalink;
+mod png;
+pub mod pool;
+pub mod queue;
+pub mod ranking;
+pub mod remix;
+pub mod render;
+pub mod report;
+pub mod scrape;
+mod sha256;
+pub mod smart;
+pub mod sort;
+pub mod source;
+pub mod state;
+pub mod stats;
+pub mod styles;
+#[cfg(feature = "test-server")]
+pub mod test_server;
+#[cfg(feature = "tls-pinning")]
+pub mod tls_pin;
+pub mod trends;
+mod ttl_cache;
+pub mod upload;
+pub mod users;
+pub mod validation;
+pub mod watcher;
+
+#[cfg(feature = "http-cache")]
+pub use http_cache::HttpCache;
+pub use palette_string::PaletteString;
+#[cfg(feature = "tls-pinning")]
+use tls_pin::TlsPins;
 
+use blocks::BlockQuery;
+use budget::ConcurrencyBudget;
 use chrono::NaiveDateTime;
-use reqwest::Client;
-use scraper::{Html, Selector};
+use circuit::CircuitBreaker;
+use coalesce::Coalescer;
+use connection::ConnectionOptions;
+use denylist::Denylist;
+use futures_util::{StreamExt, TryStreamExt, stream};
+use limits::Limits;
+use meta::{CORRELATION_HEADER, CorrelationId, MetaRecorder, ResponseMeta};
+use observe::{Observer, Observers, RetryPolicy};
+use offline::ClientMode;
+use overrides::Overrides;
+use pagination::PaletteWalker;
+use partial::PartialResult;
+use permalink::PaletteLink;
+use pool::ClientPool;
+use queue::{Priority, RequestQueue};
+use ranking::{Ranker, Ranking};
+use reqwest::header::HeaderMap;
+use reqwest::{Client, StatusCode};
+use scrape::ScrapeMode;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use smart::{DataSource, PaletteCache, SmartPalette, SmartPolicy};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+use std::time::Instant;
+use styles::{Style, StyleMatch};
 use thiserror::Error;
+use users::UserCache;
+use validation::{ResponseValidator, Validators};
 
 /// Represents the possible errors that can occur when interacting with the
 /// Block Palettes API.
@@ -47,26 +204,195 @@ pub enum BlockPalettesError {
     /// This error wraps the underlying `reqwest::Error`.
     #[error("HTTP request failed: {0}")]
     Http(#[from] reqwest::Error),
+    /// A response body could not be deserialized from JSON.
+    ///
+    /// This error wraps the underlying `serde_json::Error`.
+    #[error("JSON decoding failed: {0}")]
+    Json(#[from] serde_json::Error),
+    /// A local file could not be read or written, for example when loading
+    /// or saving a [`collections::CollectionStore`].
+    ///
+    /// This error wraps the underlying `std::io::Error`.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
     /// The Block Palettes API returned an error message or indicated a failure
     /// in its response.
     ///
     /// The contained `String` provides more details about the API-specific error.
     #[error("API error: {0}")]
     Api(String),
+    /// The API reported that a palette does not exist, for example because
+    /// it was deleted.
+    ///
+    /// The contained `u64` is the ID of the palette.
+    #[error("Palette {0} not found")]
+    NotFound(u64),
     /// An error occurred during the parsing of HTML content, typically when
     /// scraping a palette page.
     ///
     /// This can happen if the HTML structure changes unexpectedly.
-    #[error("HTML parsing error")]
-    HtmlParse,
+    #[error("HTML parsing error: invalid selector `{selector}`: {source}")]
+    HtmlParse {
+        /// The CSS selector that could not be parsed.
+        selector: String,
+        /// The underlying parse error.
+        #[source]
+        source: scrape::SelectorError,
+    },
     /// The date string received from the API could not be parsed into a
     /// `NaiveDateTime` object.
     ///
     /// This usually indicates an unexpected date format from the API.
-    #[error("Invalid date format")]
-    InvalidDateFormat,
+    #[error("Invalid date format `{input}`: {source}")]
+    InvalidDateFormat {
+        /// The date string as received.
+        input: String,
+        /// The underlying parse error.
+        #[source]
+        source: chrono::ParseError,
+    },
+    /// A response was rejected by a [`validation::ResponseValidator`]
+    /// registered on the client.
+    ///
+    /// The contained `String` explains why the data was rejected.
+    #[error("Validation failed: {0}")]
+    Validation(String),
+    /// A link could not be resolved to a palette, see
+    /// [`BlockPalettesClient::resolve_palette_link`].
+    ///
+    /// The contained `String` is the link.
+    #[error("Unresolved palette link `{0}`")]
+    UnresolvedLink(String),
+    /// A scraped page was missing expected content while scraping in
+    /// [`scrape::ScrapeMode::Strict`] mode.
+    ///
+    /// The contained warnings describe everything that was missing or malformed.
+    #[error("Scraped page is incomplete ({} problem(s))", .0.len())]
+    ScrapeIncomplete(Vec<scrape::ScrapeWarning>),
+    /// The endpoint's circuit is open after repeated failures, so the call was
+    /// not attempted. See the [`circuit`] module.
+    #[error("Circuit open for `{endpoint}`, retry in {retry_after:?}")]
+    CircuitOpen {
+        /// The endpoint whose circuit is open.
+        endpoint: String,
+        /// How long until the endpoint accepts a trial call.
+        retry_after: std::time::Duration,
+    },
+    /// The startup check of [`BlockPalettesClient::connect`] failed.
+    #[error("Startup check against `{base_url}` failed: {reason}")]
+    StartupCheck {
+        /// The base URL the client was configured with.
+        base_url: String,
+        /// What went wrong, e.g. "could not connect (DNS, TCP or TLS failure)".
+        reason: String,
+        /// The underlying error, if any.
+        #[source]
+        source: Option<Box<BlockPalettesError>>,
+    },
+    /// A call made with [`BlockPalettesClient::with_correlation_id`] failed.
+    ///
+    /// Use [`BlockPalettesError::root`] to match on the underlying error.
+    #[error("[{id}] {source}")]
+    Correlated {
+        /// The correlation ID of the failed call.
+        id: CorrelationId,
+        /// The underlying error.
+        #[source]
+        source: Box<BlockPalettesError>,
+    },
+    /// A client in [`offline::ClientMode::Offline`] has no fixture for a
+    /// request.
+    ///
+    /// The contained `String` is the [`offline::fixture_key`] of the request.
+    #[error("No fixture for `{0}` in offline mode")]
+    MissingFixture(String),
+    /// The TLS configuration for pinning could not be built.
+    ///
+    /// Requires the `tls-pinning` feature.
+    #[cfg(feature = "tls-pinning")]
+    #[error("TLS configuration failed: {0}")]
+    TlsConfig(#[source] rustls::Error),
+    /// The server's certificate did not match any pinned public key, which
+    /// usually means the connection is being intercepted.
+    ///
+    /// Requires the `tls-pinning` feature.
+    #[cfg(feature = "tls-pinning")]
+    #[error("TLS pin mismatch: {0}")]
+    TlsPinMismatch(#[source] tls_pin::PinMismatch),
+}
+
+impl BlockPalettesError {
+    /// Returns the correlation ID attached by
+    /// [`BlockPalettesClient::with_correlation_id`], if any.
+    pub fn correlation_id(&self) -> Option<&CorrelationId> {
+        match self {
+            BlockPalettesError::Correlated { id, .. } => Some(id),
+            _ => None,
+        }
+    }
+
+    /// Returns the underlying error, without the correlation ID.
+    pub fn root(&self) -> &BlockPalettesError {
+        match self {
+            BlockPalettesError::Correlated { source, .. } => source.root(),
+            error => error,
+        }
+    }
+}
+
+/// The base URL of the production Block Palettes site.
+pub const DEFAULT_BASE_URL: &str = "https://www.blockpalettes.com";
+
+/// The site a [`BlockPalettesClient`] talks to, see
+/// [`BlockPalettesClient::with_environment`].
+///
+/// # Examples
+///
+/// ```rust
+/// use blockpalettes_client::{DEFAULT_BASE_URL, Environment};
+///
+/// assert_eq!(Environment::Production.base_url(), DEFAULT_BASE_URL);
+/// let staging = Environment::Custom("https://staging.example.com/".to_string());
+/// assert_eq!(staging.base_url(), "https://staging.example.com");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub enum Environment {
+    /// The production site at [`DEFAULT_BASE_URL`].
+    #[default]
+    Production,
+    /// Another deployment of the site, such as a staging server, a mirror or
+    /// a local test server, by its base URL.
+    Custom(String),
 }
 
+impl Environment {
+    /// Returns the base URL of the environment, without a trailing slash.
+    pub fn base_url(&self) -> &str {
+        match self {
+            Environment::Production => DEFAULT_BASE_URL,
+            Environment::Custom(url) => url.trim_end_matches('/'),
+        }
+    }
+}
+
+/// The maximum number of concurrent requests made by
+/// [`BlockPalettesClient::search_blocks_many`].
+pub const BLOCK_SEARCH_CONCURRENCY: usize = 4;
+
+/// The maximum number of concurrent requests made by
+/// [`BlockPalettesClient::get_palette_details_many`].
+pub const DETAILS_CONCURRENCY: usize = 4;
+
+/// The maximum number of sort orders fetched concurrently by
+/// [`BlockPalettesClient::get_palettes_multi_sort`].
+///
+/// Each sort order makes one request per block, so up to this many times the
+/// number of blocks requests are in flight.
+pub const MULTI_SORT_CONCURRENCY: usize = 2;
+
+/// The number of popular palettes sampled by [`BlockPalettesClient::get_top_users`].
+pub const TOP_USERS_SAMPLE_SIZE: usize = 500;
+
 /// A specialized `Result` type for Block Palettes operations.
 ///
 /// This type is a convenience alias for `std::result::Result<T, BlockPalettesError>`.
@@ -94,7 +420,31 @@ pub type Result<T, E = BlockPalettesError> = std::result::Result<T, E>;
 #[derive(Debug, Clone)]
 pub struct BlockPalettesClient {
     client: Client,
-    base_url: &'static str,
+    base_url: Cow<'static, str>,
+    #[cfg(feature = "http-cache")]
+    http_cache: Option<Arc<HttpCache>>,
+    validators: Validators,
+    ranking: Ranking,
+    pool: Option<Arc<ClientPool>>,
+    meta: Option<MetaRecorder>,
+    circuit: Option<Arc<CircuitBreaker>>,
+    coalescer: Option<Arc<Coalescer>>,
+    observers: Observers,
+    retry_policy: Option<RetryPolicy>,
+    parse_offload: Option<usize>,
+    queue: Option<Arc<RequestQueue>>,
+    priority: Priority,
+    budget: Option<ConcurrencyBudget>,
+    limits: Limits,
+    extra_query: Vec<(String, String)>,
+    user_cache: Option<Arc<UserCache>>,
+    palette_cache: Option<Arc<PaletteCache>>,
+    smart_policy: SmartPolicy,
+    correlation: Option<CorrelationId>,
+    connection: Option<ConnectionOptions>,
+    mode: ClientMode,
+    #[cfg(feature = "tls-pinning")]
+    tls_pins: Option<TlsPins>,
 }
 
 impl BlockPalettesClient {
@@ -120,24 +470,1944 @@ impl BlockPalettesClient {
     pub const fn new(client: Client) -> Self {
         Self {
             client,
-            base_url: "https://www.blockpalettes.com",
+            base_url: Cow::Borrowed(DEFAULT_BASE_URL),
+            #[cfg(feature = "http-cache")]
+            http_cache: None,
+            validators: Validators::new(),
+            ranking: Ranking::new(),
+            pool: None,
+            meta: None,
+            circuit: None,
+            coalescer: None,
+            observers: Observers::new(),
+            retry_policy: None,
+            parse_offload: Some(scrape::DEFAULT_OFFLOAD_THRESHOLD),
+            queue: None,
+            priority: Priority::Normal,
+            budget: None,
+            limits: Limits::SITE,
+            extra_query: Vec::new(),
+            user_cache: None,
+            palette_cache: None,
+            smart_policy: SmartPolicy::DEFAULT,
+            correlation: None,
+            connection: None,
+            mode: ClientMode::Online,
+            #[cfg(feature = "tls-pinning")]
+            tls_pins: None,
         }
     }
 
-    /// Searches for blocks that match a given query string.
+    /// Sends requests to `base_url` instead of [`DEFAULT_BASE_URL`], e.g. a
+    /// mirror or a local test server.
     ///
-    /// This method queries the `/api/palettes/search-block.php` endpoint.
+    /// A trailing slash is ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use blockpalettes_client::BlockPalettesClient;
+    ///
+    /// let client = BlockPalettesClient::new(reqwest::Client::new())
+    ///     .with_base_url("http://127.0.0.1:8080/");
+    /// assert_eq!(client.palette_url(42), "http://127.0.0.1:8080/palette/42");
+    /// ```
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        let mut base_url = base_url.into();
+        while base_url.ends_with('/') {
+            base_url.pop();
+        }
+        self.base_url = Cow::Owned(base_url);
+        self
+    }
+
+    /// Sends requests to the site of `environment`.
+    ///
+    /// [`Environment::Custom`] is equivalent to
+    /// [`with_base_url`](Self::with_base_url); [`Environment::Production`]
+    /// switches a client back to [`DEFAULT_BASE_URL`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use blockpalettes_client::{BlockPalettesClient, Environment};
+    ///
+    /// let local = Environment::Custom("http://127.0.0.1:8080".to_string());
+    /// let client = BlockPalettesClient::new(reqwest::Client::new()).with_environment(local);
+    /// assert_eq!(client.palette_url(42), "http://127.0.0.1:8080/palette/42");
+    ///
+    /// let client = client.with_environment(Environment::Production);
+    /// assert_eq!(client.palette_url(42), "https://www.blockpalettes.com/palette/42");
+    /// ```
+    pub fn with_environment(mut self, environment: Environment) -> Self {
+        self.base_url = match environment {
+            Environment::Production => Cow::Borrowed(DEFAULT_BASE_URL),
+            Environment::Custom(url) => Cow::Owned(url.trim_end_matches('/').to_string()),
+        };
+        self
+    }
+
+    /// Sets where the client gets its responses from: the network, fixtures
+    /// only, or the network while recording fixtures.
+    ///
+    /// See the [`offline`] module for details.
+    pub fn with_mode(mut self, mode: ClientMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Returns the mode set with [`with_mode`](Self::with_mode).
+    pub fn mode(&self) -> &ClientMode {
+        &self.mode
+    }
+
+    /// Distributes requests round-robin across the clients of a [`ClientPool`].
+    ///
+    /// The `reqwest::Client` passed to [`new`](Self::new) is then only used to
+    /// build requests, not to send them. See the [`pool`] module for details.
+    pub fn with_pool(mut self, pool: ClientPool) -> Self {
+        self.pool = Some(Arc::new(pool));
+        self
+    }
+
+    /// Adds query parameters to every request made by this client.
+    ///
+    /// This is an escape hatch for API options the crate does not model, such
+    /// as experimental filters. Parameters are appended after the ones the
+    /// crate sets, and calling this again adds to the previous ones. Since
+    /// clients are cheap to clone, parameters can be scoped to a single call.
+    ///
+    /// The parameters are only sent to the scheme, host and port of the base
+    /// URL, never to other hosts such as the Wayback Machine.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use blockpalettes_client::{BlockPalettesClient, SortOrder};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = BlockPalettesClient::new(reqwest::Client::new());
+    ///     let response = client
+    ///         .clone()
+    ///         .with_extra_query_params([("featured", "1")])
+    ///         .get_palettes(&["stone"], SortOrder::Recent, 1, 20)
+    ///         .await?;
+    ///     println!("{} palettes", response.filtered_count);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn with_extra_query_params<K, V>(mut self, params: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.extra_query
+            .extend(params.into_iter().map(|(k, v)| (k.into(), v.into())));
+        self
+    }
+
+    /// Short-circuits calls to endpoints that keep failing.
+    ///
+    /// See the [`circuit`] module for details.
+    pub fn with_circuit_breaker(mut self, breaker: CircuitBreaker) -> Self {
+        self.circuit = Some(Arc::new(breaker));
+        self
+    }
+
+    /// Returns the circuit breaker attached to this client, to query endpoint health.
+    pub fn circuit_breaker(&self) -> Option<&CircuitBreaker> {
+        self.circuit.as_deref()
+    }
+
+    /// Shares identical concurrent requests to the endpoints configured in
+    /// `coalescer`.
+    ///
+    /// See the [`coalesce`] module for details.
+    pub fn with_coalescer(mut self, coalescer: Coalescer) -> Self {
+        self.coalescer = Some(Arc::new(coalescer));
+        self
+    }
+
+    /// Spaces requests apart with `queue`, sending waiting requests by
+    /// priority.
+    ///
+    /// See the [`queue`] module for details.
+    pub fn with_request_queue(mut self, queue: RequestQueue) -> Self {
+        self.queue = Some(Arc::new(queue));
+        self
+    }
+
+    /// Sets the priority of this client's requests in its [`RequestQueue`].
+    /// Defaults to [`Priority::Normal`].
+    ///
+    /// Since clients are cheap to clone, one client can be given a priority
+    /// per task while sharing its queue. Without a queue, the priority has no
+    /// effect.
+    pub fn with_priority(mut self, priority: Priority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Caps the requests in flight with `budget`, which can be shared with
+    /// other clients and subsystems.
+    ///
+    /// Every request holds a permit from its turn in the [`RequestQueue`]
+    /// until its response has been read. See the [`budget`] module for
+    /// details.
+    pub fn with_concurrency_budget(mut self, budget: ConcurrencyBudget) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// Checks requests against `limits` instead of [`Limits::SITE`], e.g.
+    /// limits measured with [`Limits::probe`].
+    ///
+    /// See the [`limits`] module for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use blockpalettes_client::limits::{Limits, MAX_PAGE_LIMIT};
+    /// use blockpalettes_client::{BlockPalettesClient, BlockPalettesError, SortOrder};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = BlockPalettesClient::new(reqwest::Client::new());
+    ///     assert_eq!(client.limits().max_page_limit, MAX_PAGE_LIMIT);
+    ///
+    ///     // rejected before anything is sent
+    ///     let result = client.get_palettes(&["stone"], SortOrder::Recent, 1, 500).await;
+    ///     assert!(matches!(result, Err(BlockPalettesError::Validation(_))));
+    ///
+    ///     let mirror = client.with_limits(Limits { max_page_limit: 500, ..Limits::SITE });
+    ///     assert_eq!(mirror.limits().max_page_limit, 500);
+    /// }
+    /// ```
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Returns the limits requests are checked against.
+    pub fn limits(&self) -> Limits {
+        self.limits
+    }
+
+    /// Retries failed requests as described by `policy`.
+    ///
+    /// Without a policy, every request is sent once. See the [`observe`]
+    /// module for details.
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(policy);
+        self
+    }
+
+    /// Parses scraped HTML pages of at least `threshold` bytes on Tokio's
+    /// blocking thread pool, so that large pages do not stall other tasks
+    /// while they are parsed. `None` parses every page on the calling task.
+    ///
+    /// Defaults to [`scrape::DEFAULT_OFFLOAD_THRESHOLD`]. Offloading costs a
+    /// thread hand-off, which only pays for itself on large pages; lower the
+    /// threshold on single-threaded runtimes, or disable offloading when the
+    /// blocking pool is reserved for other work.
+    pub fn with_parse_offload(mut self, threshold: Option<usize>) -> Self {
+        self.parse_offload = threshold;
+        self
+    }
+
+    /// Registers an [`Observer`] told about cache hits, rate limits and
+    /// retries.
+    ///
+    /// Observers are called in registration order. See the [`observe`]
+    /// module for details.
+    pub fn with_observer(mut self, observer: impl Observer + 'static) -> Self {
+        self.observers.push(Arc::new(observer));
+        self
+    }
+
+    /// Caches the users assembled by [`get_user`](Self::get_user) in a [`UserCache`].
+    pub fn with_user_cache(mut self, cache: UserCache) -> Self {
+        self.user_cache = Some(Arc::new(cache));
+        self
+    }
+
+    /// Returns the user cache attached with [`with_user_cache`](Self::with_user_cache), if any.
+    pub fn user_cache(&self) -> Option<&UserCache> {
+        self.user_cache.as_deref()
+    }
+
+    /// Caches the palette details fetched by
+    /// [`get_palette_smart`](Self::get_palette_smart) in a [`PaletteCache`].
+    pub fn with_palette_cache(mut self, cache: PaletteCache) -> Self {
+        self.palette_cache = Some(Arc::new(cache));
+        self
+    }
+
+    /// Returns the palette cache attached with
+    /// [`with_palette_cache`](Self::with_palette_cache), if any.
+    pub fn palette_cache(&self) -> Option<&PaletteCache> {
+        self.palette_cache.as_deref()
+    }
+
+    /// Sets when [`get_palette_smart`](Self::get_palette_smart) falls back to
+    /// scraping. Defaults to [`SmartPolicy::DEFAULT`].
+    pub fn with_smart_policy(mut self, policy: SmartPolicy) -> Self {
+        self.smart_policy = policy;
+        self
+    }
+
+    /// Registers a [`ResponseValidator`] run on every decoded palette.
+    ///
+    /// Validators are applied in registration order; each one sees the output
+    /// of the previous one. See the [`validation`] module for details.
+    pub fn with_validator(mut self, validator: impl ResponseValidator + 'static) -> Self {
+        self.validators.push(Arc::new(validator));
+        self
+    }
+
+    /// Reorders every palette listing the client returns by descending
+    /// score under `ranker`.
+    ///
+    /// Ranking runs after every [`ResponseValidator`]. Calling this again
+    /// replaces the previous ranker. See the [`ranking`] module for details.
+    pub fn with_ranker(mut self, ranker: impl Ranker + 'static) -> Self {
+        self.ranking.set(Arc::new(ranker));
+        self
+    }
+
+    /// Excludes denied blocks, users and palettes from every result.
+    ///
+    /// The denylist runs before any [`ResponseValidator`]. Calling this again
+    /// replaces the previous denylist. See the [`denylist`] module for details.
+    pub fn with_denylist(mut self, denylist: Denylist) -> Self {
+        self.validators.set_denylist(denylist);
+        self
+    }
+
+    /// Merges locally pinned corrections over every palette the client
+    /// returns.
+    ///
+    /// Overrides run before the denylist and any [`ResponseValidator`].
+    /// Calling this again replaces the previous overrides. See the
+    /// [`overrides`] module for details.
+    pub fn with_overrides(mut self, overrides: Overrides) -> Self {
+        self.validators.set_overrides(overrides);
+        self
+    }
+
+    /// Returns the overrides set with [`with_overrides`](Self::with_overrides),
+    /// e.g. to look up custom titles.
+    pub fn overrides(&self) -> Option<&Overrides> {
+        self.validators.overrides()
+    }
+
+    /// Routes every request made by this client through a disk-backed [`HttpCache`].
+    ///
+    /// This applies to API calls and HTML scrapes alike. Requires the
+    /// `http-cache` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use blockpalettes_client::{BlockPalettesClient, HttpCache};
+    ///
+    /// let cache = HttpCache::new(std::env::temp_dir().join("blockpalettes")).unwrap();
+    /// let client = BlockPalettesClient::new(reqwest::Client::new()).with_http_cache(cache);
+    /// ```
+    #[cfg(feature = "http-cache")]
+    pub fn with_http_cache(mut self, cache: HttpCache) -> Self {
+        self.http_cache = Some(Arc::new(cache));
+        self
+    }
+
+    /// Only accepts TLS connections whose certificate matches one of `pins`.
+    ///
+    /// This replaces the `reqwest::Client` passed to [`new`](Self::new) with
+    /// one built by [`TlsPins::client_builder`], so settings made on the
+    /// original client are lost. Clients of a [`ClientPool`] are not
+    /// affected; build them with [`TlsPins::client_builder`] as well.
+    ///
+    /// Connections failing the pin check fail with
+    /// [`BlockPalettesError::TlsPinMismatch`]. See the [`tls_pin`] module for
+    /// details. Requires the `tls-pinning` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BlockPalettesError::TlsConfig`] if `pins` is empty or the TLS
+    /// configuration cannot be built.
+    #[cfg(feature = "tls-pinning")]
+    pub fn with_tls_pins(mut self, pins: TlsPins) -> Result<Self> {
+        self.tls_pins = Some(pins);
+        self.rebuild_client()?;
+        Ok(self)
+    }
+
+    /// Configures HTTP/2 and TCP settings of the client's connections, such
+    /// as keepalive pings. See the [`connection`] module for details.
+    ///
+    /// Like [`with_tls_pins`](Self::with_tls_pins), this replaces the
+    /// `reqwest::Client` passed to [`new`](Self::new), so settings made on
+    /// the original client are lost; TLS pins are kept whichever is
+    /// configured first. Clients of a [`ClientPool`] are not affected; apply
+    /// the options to them with [`ConnectionOptions::apply`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the client cannot be built.
+    pub fn with_connection_options(mut self, options: ConnectionOptions) -> Result<Self> {
+        self.connection = Some(options);
+        self.rebuild_client()?;
+        Ok(self)
+    }
+
+    /// Replaces the `reqwest::Client` with one built from the TLS pins and
+    /// connection options of this client.
+    fn rebuild_client(&mut self) -> Result<()> {
+        #[cfg(feature = "tls-pinning")]
+        let builder = match &self.tls_pins {
+            Some(pins) => pins
+                .client_builder()
+                .map_err(BlockPalettesError::TlsConfig)?,
+            None => Client::builder(),
+        };
+        #[cfg(not(feature = "tls-pinning"))]
+        let builder = Client::builder();
+
+        let builder = match &self.connection {
+            Some(options) => options.apply(builder),
+            None => builder,
+        };
+        self.client = builder.build()?;
+        Ok(())
+    }
+
+    /// Runs `call` and returns its result along with metadata about every
+    /// HTTP request it made.
+    ///
+    /// `call` receives a copy of this client that records a [`ResponseMeta`]
+    /// (status, latency, URL and whether the response came from the cache)
+    /// for every response it receives. Requests that fail before a response is
+    /// received are not recorded.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use blockpalettes_client::BlockPalettesClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = BlockPalettesClient::new(reqwest::Client::new());
+    ///     let (blocks, meta) = client
+    ///         .with_meta(|client| async move { client.popular_blocks().await })
+    ///         .await;
+    ///     for m in &meta {
+    ///         println!("{} {} in {:?} (cached: {})", m.status, m.url, m.latency, m.from_cache);
+    ///     }
+    ///     println!("{} popular blocks", blocks?.len());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn with_meta<T, F, Fut>(&self, call: F) -> (Result<T>, Vec<ResponseMeta>)
+    where
+        F: FnOnce(BlockPalettesClient) -> Fut,
+        Fut: std::future::Future<Output = Result<T>>,
+    {
+        let recorder = MetaRecorder::default();
+        let mut client = self.clone();
+        client.meta = Some(recorder.clone());
+
+        let result = call(client).await;
+        (result, recorder.take())
+    }
+
+    /// Runs `call` as one logical operation identified by `id`.
+    ///
+    /// `call` receives a copy of this client that sends `id` in the
+    /// [`meta::CORRELATION_HEADER`] of every request and
+    /// records it in every [`ResponseMeta`]. If the call fails, its error is
+    /// wrapped in [`BlockPalettesError::Correlated`], so logs of the error can
+    /// be matched with the requests behind it.
+    ///
+    /// Without this, every request gets its own ID, except for the requests
+    /// of composite operations (multi-block searches, batch fetches, ...),
+    /// which share one.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use blockpalettes_client::{BlockPalettesClient, SortOrder};
+    /// use blockpalettes_client::meta::CorrelationId;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = BlockPalettesClient::new(reqwest::Client::new());
+    ///     let id = CorrelationId::new();
+    ///     let result = client
+    ///         .with_correlation_id(id, |client| async move {
+    ///             let blocks = client.popular_blocks().await?;
+    ///             client.get_palettes(&[blocks[0].name.as_str()], SortOrder::Recent, 1, 20).await
+    ///         })
+    ///         .await;
+    ///     if let Err(e) = result {
+    ///         eprintln!("{e}"); // "[3f2a...] HTTP request failed: ..."
+    ///     }
+    /// }
+    /// ```
+    pub async fn with_correlation_id<T, F, Fut>(&self, id: CorrelationId, call: F) -> Result<T>
+    where
+        F: FnOnce(BlockPalettesClient) -> Fut,
+        Fut: std::future::Future<Output = Result<T>>,
+    {
+        let mut client = self.clone();
+        client.correlation = Some(id.clone());
+
+        call(client).await.map_err(|error| match error {
+            BlockPalettesError::Correlated { .. } => error,
+            error => BlockPalettesError::Correlated {
+                id,
+                source: Box::new(error),
+            },
+        })
+    }
+
+    /// Returns the correlation ID of the operation this client is running,
+    /// inside [`with_correlation_id`](Self::with_correlation_id).
+    pub fn correlation_id(&self) -> Option<&CorrelationId> {
+        self.correlation.as_ref()
+    }
+
+    /// Returns this client if it already has a correlation ID, or a copy with
+    /// a new one, so that the requests of a composite operation share an ID.
+    fn correlated(&self) -> Cow<'_, Self> {
+        if self.correlation.is_some() {
+            Cow::Borrowed(self)
+        } else {
+            let mut client = self.clone();
+            client.correlation = Some(CorrelationId::new());
+            Cow::Owned(client)
+        }
+    }
+
+    /// Checks that the site is reachable before the client is put to use.
+    ///
+    /// This sends one lightweight request to the
+    /// `/api/palettes/popular-blocks.php` endpoint, which also resolves the
+    /// host, opens a connection and completes the TLS handshake, so later
+    /// requests reuse a warm connection. It fails fast if the base URL is
+    /// wrong, egress is blocked, or the server is not the Block Palettes API.
+    /// All of the client's configuration (pool, cache, TLS pins, ...) applies
+    /// to the check, so call this last.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`BlockPalettesError::StartupCheck`] describing which step
+    /// failed, with the underlying error as its source.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use blockpalettes_client::BlockPalettesClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = BlockPalettesClient::new(reqwest::Client::new())
+    ///         .connect()
+    ///         .await?;
+    ///     println!("{:?}", client.get_recent_palettes(1).await?);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn connect(self) -> Result<Self> {
+        let failure =
+            |reason: String, source: Option<BlockPalettesError>| BlockPalettesError::StartupCheck {
+                base_url: self.base_url.to_string(),
+                reason,
+                source: source.map(Box::new),
+            };
+
+        let response = match self.fetch("/api/palettes/popular-blocks.php", &[]).await {
+            Ok(response) => response,
+            Err(error) => {
+                let reason = match &error {
+                    BlockPalettesError::Http(e) if e.is_builder() => "invalid base URL",
+                    BlockPalettesError::Http(e) if e.is_timeout() => "timed out",
+                    BlockPalettesError::Http(e) if e.is_connect() => {
+                        "could not connect (DNS, TCP or TLS failure)"
+                    }
+                    _ => "request failed",
+                };
+                return Err(failure(reason.to_string(), Some(error)));
+            }
+        };
+
+        if !response.status.is_success() {
+            return Err(failure(
+                format!("probe returned HTTP {}", response.status),
+                None,
+            ));
+        }
+        match response.json::<PopularBlocksResponse>() {
+            Ok(probe) if probe.success => Ok(self),
+            Ok(_) => Err(failure("probe reported `success: false`".to_string(), None)),
+            Err(error) => Err(failure(
+                "probe response is not from the Block Palettes API".to_string(),
+                Some(error),
+            )),
+        }
+    }
+
+    /// Returns the URL of a palette's page on the site.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use blockpalettes_client::BlockPalettesClient;
+    ///
+    /// let client = BlockPalettesClient::new(reqwest::Client::new());
+    /// assert_eq!(client.palette_url(42), "https://www.blockpalettes.com/palette/42");
+    /// ```
+    pub fn palette_url(&self, palette_id: u64) -> String {
+        format!("{}/palette/{}", self.base_url, palette_id)
+    }
+
+    /// Resolves a link to a palette, in any of the formats the site has used,
+    /// to the palette's ID.
+    ///
+    /// Links containing the ID are resolved without any request, see
+    /// [`permalink::parse`]. Links naming the palette by hash are opened
+    /// relative to the client's base URL, following redirects; the ID is read
+    /// from the URL the site ends up at or, failing that, from the canonical
+    /// URL the page declares.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BlockPalettesError::UnresolvedLink`] if `link` does not name
+    /// a palette or the site does not lead to its ID, or an error if the page
+    /// cannot be fetched.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use blockpalettes_client::BlockPalettesClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = BlockPalettesClient::new(reqwest::Client::new());
+    ///     let id = client
+    ///         .resolve_palette_link("http://blockpalettes.com/palette.php?id=56655")
+    ///         .await?;
+    ///     assert_eq!(client.palette_url(id), "https://www.blockpalettes.com/palette/56655");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn resolve_palette_link(&self, link: &str) -> Result<u64> {
+        let unresolved = || BlockPalettesError::UnresolvedLink(link.to_string());
+        let hash = match permalink::parse(link) {
+            Some(PaletteLink::Id(id)) => return Ok(id),
+            Some(PaletteLink::Hash(hash)) => hash,
+            None => return Err(unresolved()),
+        };
+
+        let response = self.fetch(&format!("/palette/{hash}"), &[]).await?;
+        if !response.status.is_success() {
+            return Err(unresolved());
+        }
+        let mut candidates: Vec<String> = response.url.into_iter().collect();
+        candidates.extend(
+            scrape::parse_offloaded(response.body, self.parse_offload, scrape::canonical_urls)
+                .await?,
+        );
+        candidates
+            .iter()
+            .find_map(|url| match permalink::parse(url) {
+                Some(PaletteLink::Id(id)) => Some(id),
+                _ => None,
+            })
+            .ok_or_else(unresolved)
+    }
+
+    /// Sends a `GET` request to `path` (relative to the base URL) and reads the whole body.
+    ///
+    /// All endpoints go through this method, so cross-cutting concerns such as
+    /// the HTTP cache are applied uniformly.
+    async fn fetch(&self, path: &str, query: &[(&str, String)]) -> Result<HttpResponse> {
+        let url = format!("{}{}", self.base_url, path);
+        self.fetch_url(path, &url, query).await
+    }
+
+    /// Returns `true` if `url` has the scheme, host and port of the base URL.
+    fn is_own_origin(&self, url: &reqwest::Url) -> bool {
+        reqwest::Url::parse(&self.base_url).is_ok_and(|base| base.origin() == url.origin())
+    }
+
+    /// Sends a `GET` request to the absolute `url` and reads the whole body,
+    /// with the circuit breaker, coalescing, correlation and metadata of
+    /// `endpoint` applied like for [`fetch`](Self::fetch).
+    ///
+    /// The client's extra query parameters are only sent to the scheme, host
+    /// and port of its base URL.
+    async fn fetch_url(
+        &self,
+        endpoint: &str,
+        url: &str,
+        query: &[(&str, String)],
+    ) -> Result<HttpResponse> {
+        let circuit = match &self.circuit {
+            Some(breaker) => Some(breaker.acquire(endpoint).map_err(
+                |(endpoint, retry_after)| BlockPalettesError::CircuitOpen {
+                    endpoint,
+                    retry_after,
+                },
+            )?),
+            None => None,
+        };
+        let correlation_id = self.correlation.clone().unwrap_or_default();
+        let mut request = self
+            .client
+            .get(url)
+            .header(CORRELATION_HEADER, correlation_id.as_str())
+            .query(query)
+            .build()?;
+        if !self.extra_query.is_empty() && self.is_own_origin(request.url()) {
+            request
+                .url_mut()
+                .query_pairs_mut()
+                .extend_pairs(&self.extra_query);
+        }
+        let full_url = request.url().to_string();
+        #[cfg(feature = "tls-pinning")]
+        let host = request.url().host_str().map(str::to_string);
+        let started = Instant::now();
+
+        let send = || self.send(request);
+        let window = self
+            .coalescer
+            .as_ref()
+            .and_then(|c| Some((c, c.window(endpoint)?)));
+        #[cfg(feature = "http-cache")]
+        let cached = window.is_some() || self.http_cache.is_some();
+        #[cfg(not(feature = "http-cache"))]
+        let cached = window.is_some();
+        let result =
+            match window {
+                Some((coalescer, window)) => coalescer
+                    .run(full_url.clone(), window, send)
+                    .await
+                    .map(|(mut response, shared)| {
+                        response.from_cache |= shared;
+                        response
+                    }),
+                None => send().await,
+            };
+        match &result {
+            Ok(response) if response.from_cache => self.observers.cache_hit(&full_url),
+            Ok(_) if cached => self.observers.cache_miss(&full_url),
+            _ => {}
+        }
+        if let (Some(breaker), Some(endpoint)) = (&self.circuit, circuit) {
+            breaker.record(endpoint, result.as_ref().map(|r| r.status).map_err(|_| ()));
+        }
+        #[cfg(feature = "tls-pinning")]
+        let result = result.map_err(|e| self.pin_error(e, host.as_deref()));
+
+        if let (Some(recorder), Ok(response)) = (&self.meta, &result) {
+            recorder.record(ResponseMeta {
+                url: full_url,
+                status: response.status,
+                latency: started.elapsed(),
+                from_cache: response.from_cache,
+                correlation_id,
+            });
+        }
+        result
+    }
+
+    /// Replaces a connection error caused by a pin mismatch with
+    /// [`BlockPalettesError::TlsPinMismatch`].
+    #[cfg(feature = "tls-pinning")]
+    fn pin_error(&self, error: BlockPalettesError, host: Option<&str>) -> BlockPalettesError {
+        match (&error, &self.tls_pins, host) {
+            (BlockPalettesError::Http(_), Some(pins), Some(host)) => pins
+                .take_mismatch(host)
+                .map_or(error, BlockPalettesError::TlsPinMismatch),
+            _ => error,
+        }
+    }
+
+    /// Sends `request`, retrying transport errors, rate limits and server
+    /// errors as allowed by the [`RetryPolicy`], if any.
+    async fn send(&self, request: reqwest::Request) -> Result<HttpResponse> {
+        let Some(policy) = self.retry_policy else {
+            return self.send_once(request).await;
+        };
+
+        for attempt in 1..=policy.retries() {
+            let Some(retry) = request.try_clone() else {
+                break;
+            };
+            let result = self.send_once(retry).await;
+            let wait = match &result {
+                Ok(response) if response.status == StatusCode::TOO_MANY_REQUESTS => {
+                    let Some(wait) = policy.rate_limit_wait(&response.headers, attempt) else {
+                        return result;
+                    };
+                    self.observers.rate_limited(wait);
+                    wait
+                }
+                Ok(response) if response.status.is_server_error() => policy.backoff(attempt),
+                Err(BlockPalettesError::Http(_)) => policy.backoff(attempt),
+                _ => return result,
+            };
+            let error = match result {
+                Ok(response) => BlockPalettesError::Api(format!("HTTP {}", response.status)),
+                Err(error) => error,
+            };
+            self.observers.retry(attempt, &error);
+            tokio::time::sleep(wait).await;
+        }
+        self.send_once(request).await
+    }
+
+    /// Sends `request` once, with the next client of the pool if one is
+    /// configured, or answers it from fixtures in offline mode.
+    async fn send_once(&self, request: reqwest::Request) -> Result<HttpResponse> {
+        let recording = match &self.mode {
+            ClientMode::Online => None,
+            ClientMode::Offline(store) => {
+                return offline::serve(store.as_ref(), request.url(), &self.base_url).await;
+            }
+            ClientMode::Record(store) => Some((store, request.url().clone())),
+        };
+        if let Some(queue) = &self.queue {
+            queue.acquire(self.priority).await;
+        }
+        let _permit = match &self.budget {
+            Some(budget) => Some(budget.acquire().await),
+            None => None,
+        };
+        let result = match &self.pool {
+            Some(pool) => {
+                let (index, client) = pool.pick();
+                let result = self.execute(client, request).await;
+                pool.record(index, result.as_ref().map(|r| r.status).map_err(|_| ()));
+                result
+            }
+            None => self.execute(&self.client, request).await,
+        };
+        if let (Some((store, url)), Ok(response)) = (recording, &result) {
+            offline::record(store.as_ref(), &url, &self.base_url, response).await?;
+        }
+        result
+    }
+
+    /// Executes `request` with `client`, going through the HTTP cache if one is configured.
+    async fn execute(&self, client: &Client, request: reqwest::Request) -> Result<HttpResponse> {
+        #[cfg(feature = "http-cache")]
+        if let Some(cache) = &self.http_cache {
+            return cache.execute(client, request).await;
+        }
+
+        HttpResponse::from_reqwest(client.execute(request).await?).await
+    }
+
+    /// Searches for blocks that match a given query string.
+    ///
+    /// This method queries the `/api/palettes/search-block.php` endpoint.
+    /// The site returns at most
+    /// [`MAX_BLOCK_SEARCH_RESULTS`](limits::MAX_BLOCK_SEARCH_RESULTS) blocks
+    /// per search, without saying whether more matched; use
+    /// [`search_blocks_page`](Self::search_blocks_page) to find out, or
+    /// [`search_blocks_exhaustive`](Self::search_blocks_exhaustive) to get
+    /// every match.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The search string for blocks (e.g., "stone", "wood").
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a `Vec<String>` of block names if successful,
+    /// or a [`BlockPalettesError`] if the request fails or the API returns an error.
+    ///
+    /// [`BlockPalettesError`]: enum.BlockPalettesError.html
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use blockpalettes_client::BlockPalettesClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = BlockPalettesClient::new(reqwest::Client::new());
+    ///     let blocks = client.search_blocks("dirt").await?;
+    ///     println!("Found blocks: {:?}", blocks);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn search_blocks(&self, query: impl AsRef<str>) -> Result<Vec<String>> {
+        Ok(self.search_blocks_page(query).await?.blocks)
+    }
+
+    /// Searches for blocks like [`search_blocks`](Self::search_blocks), and
+    /// tells whether the results may be truncated.
+    ///
+    /// The endpoint has no paging: a search matching more blocks than the
+    /// site returns at most is cut short, and the only way to the other
+    /// matches is a more specific query. When
+    /// [`BlockSearchPage::truncated`] is set,
+    /// [`BlockSearchPage::refinements`] lists the queries that together cover
+    /// every match, which is what
+    /// [`search_blocks_exhaustive`](Self::search_blocks_exhaustive) follows.
+    ///
+    /// Truncation is detected before the client's denylist removes blocks,
+    /// so a page can be truncated with fewer blocks than the limit.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BlockPalettesError::Validation`] if the query is longer than
+    /// the client's [`Limits`] allow, or an error if the request fails or the
+    /// API reports a failure.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use blockpalettes_client::BlockPalettesClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = BlockPalettesClient::new(reqwest::Client::new());
+    ///     let page = client.search_blocks_page("stone").await?;
+    ///     if page.truncated {
+    ///         println!("{}+ blocks, refine with {:?}", page.blocks.len(), page.refinements());
+    ///     } else {
+    ///         println!("exactly {} blocks", page.blocks.len());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn search_blocks_page(&self, query: impl AsRef<str>) -> Result<BlockSearchPage> {
+        let query = query.as_ref();
+        self.limits.check_block_query(query)?;
+        let response = self
+            .fetch(
+                "/api/palettes/search-block.php",
+                &[("query", query.to_string())],
+            )
+            .await?
+            .json::<BlockSearchResponse>()?;
+
+        if !response.success {
+            return Err(BlockPalettesError::Api("Search failed".into()));
+        }
+        let truncated = response.blocks.len() >= self.limits.max_block_search_results;
+        let mut blocks = response.blocks;
+        if let Some(denylist) = self.validators.denylist() {
+            blocks.retain(|b| !denylist.denies_block(b));
+        }
+        Ok(BlockSearchPage {
+            query: query.to_string(),
+            blocks,
+            truncated,
+        })
+    }
+
+    /// Returns every block matching `query`, sorted and deduplicated,
+    /// however many there are.
+    ///
+    /// Truncated searches are followed by their
+    /// [refinements](BlockSearchPage::refinements), recursively, until no
+    /// search is truncated or refined queries reach the client's maximum
+    /// query length. This sends one request for `query`, plus one for each
+    /// refinement of every truncated search not already sent: a query that is
+    /// not truncated takes one request, a truncated one at least 55 (54
+    /// refinements), and every truncated refinement adds up to 54 more, so a
+    /// short, broad query can take hundreds.
+    ///
+    /// Each round of refinements is sent at most [`BLOCK_SEARCH_CONCURRENCY`]
+    /// at a time. To bound the requests of this and other calls together,
+    /// attach a [`ConcurrencyBudget`] with
+    /// [`with_concurrency_budget`](Self::with_concurrency_budget).
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error of the searches.
+    pub async fn search_blocks_exhaustive(&self, query: impl AsRef<str>) -> Result<Vec<String>> {
+        let client = &*self.correlated();
+        let first = client.search_blocks_page(query).await?;
+        let mut found: BTreeSet<String> = first.blocks.iter().cloned().collect();
+        let mut tried: HashSet<String> = HashSet::from([first.query.clone()]);
+        let mut pending = first.refinements();
+
+        while !pending.is_empty() {
+            pending.retain(|q| {
+                q.len() <= client.limits.max_block_query_len && tried.insert(q.clone())
+            });
+            let pages: Vec<BlockSearchPage> = stream::iter(std::mem::take(&mut pending))
+                .map(|query| async move { client.search_blocks_page(query).await })
+                .buffer_unordered(BLOCK_SEARCH_CONCURRENCY)
+                .try_collect()
+                .await?;
+            for page in pages {
+                pending.extend(page.refinements());
+                found.extend(page.blocks);
+            }
+        }
+        Ok(found.into_iter().collect())
+    }
+
+    /// Runs several block searches and merges the results into a map keyed by query.
+    ///
+    /// Queries are trimmed and deduplicated, then sent to the
+    /// `/api/palettes/search-block.php` endpoint at most
+    /// [`BLOCK_SEARCH_CONCURRENCY`] at a time, so bootstrapping a local block
+    /// list from dozens of prefix queries stays polite to the server.
+    ///
+    /// # Arguments
+    ///
+    /// * `queries` - The search strings (e.g., `["stone", "oak", "deepslate"]`).
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a map from each distinct query to its matching
+    /// block names, or the first [`BlockPalettesError`] encountered.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use blockpalettes_client::BlockPalettesClient;
+    /// use std::collections::BTreeSet;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = BlockPalettesClient::new(reqwest::Client::new());
+    ///     let results = client.search_blocks_many(&["oak", "spruce", "birch"]).await?;
+    ///     let universe: BTreeSet<&String> = results.values().flatten().collect();
+    ///     println!("{} distinct blocks", universe.len());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn search_blocks_many(
+        &self,
+        queries: &[&str],
+    ) -> Result<BTreeMap<String, Vec<String>>> {
+        let client = &*self.correlated();
+        let distinct: BTreeSet<&str> = queries
+            .iter()
+            .map(|q| q.trim())
+            .filter(|q| !q.is_empty())
+            .collect();
+
+        stream::iter(distinct)
+            .map(|query| async move {
+                let blocks = client.search_blocks(query).await?;
+                Ok((query.to_string(), blocks))
+            })
+            .buffer_unordered(BLOCK_SEARCH_CONCURRENCY)
+            .try_collect()
+            .await
+    }
+
+    /// Retrieves a list of popular blocks.
+    ///
+    /// This method queries the `/api/palettes/popular-blocks.php` endpoint.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a `Vec<PopularBlock>` if successful,
+    /// or a [`BlockPalettesError`] if the request fails or the API returns an error.
+    ///
+    /// [`PopularBlock`]: struct.PopularBlock.html
+    /// [`BlockPalettesError`]: enum.BlockPalettesError.html
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use blockpalettes_client::BlockPalettesClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = BlockPalettesClient::new(reqwest::Client::new());
+    ///     let popular_blocks = client.popular_blocks().await?;
+    ///     for block in popular_blocks.iter().take(3) {
+    ///         println!("Block: {}, Count: {}", block.name, block.count);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn popular_blocks(&self) -> Result<Vec<PopularBlock>> {
+        let response = self
+            .fetch("/api/palettes/popular-blocks.php", &[])
+            .await?
+            .json::<PopularBlocksResponse>()?;
+
+        if response.success {
+            let mut blocks = response.blocks;
+            if let Some(denylist) = self.validators.denylist() {
+                blocks.retain(|b| !denylist.denies_block(&b.name));
+            }
+            Ok(blocks)
+        } else {
+            Err(BlockPalettesError::Api(
+                "Popular blocks request failed".into(),
+            ))
+        }
+    }
+
+    /// Retrieves a list of palettes based on specified blocks, sorting order,
+    /// pagination, and limit.
+    ///
+    /// This method queries the `/api/palettes/all_palettes.php` endpoint once
+    /// per block. It internally filters the results to ensure all specified
+    /// blocks are present in the returned palettes, and returns each palette
+    /// only once.
+    ///
+    /// # Arguments
+    ///
+    /// * `blocks` - A slice of string references representing the blocks that
+    ///   must be present in the palettes.
+    /// * `sort` - The desired sorting order for the palettes (e.g., `SortOrder::Recent`).
+    /// * `page` - The page number of the results to retrieve (1-indexed).
+    /// * `limit` - The maximum number of palettes to return per page.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a [`MultiBlockPaletteResponse`] if successful,
+    /// or a [`BlockPalettesError`] if the request fails or the API returns an error.
+    /// Its totals are upper bounds, since results are filtered locally.
+    /// If one block's request fails, the whole call fails; use
+    /// [`get_palettes_matching_partial`](Self::get_palettes_matching_partial)
+    /// to keep the results of the other blocks.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use blockpalettes_client::{BlockPalettesClient, SortOrder};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = BlockPalettesClient::new(reqwest::Client::new());
+    ///     let blocks_to_search = &["oak_log", "dirt"];
+    ///     let response = client
+    ///         .get_palettes(blocks_to_search, SortOrder::Popular, 1, 5)
+    ///         .await?;
+    ///
+    ///     if let Some(palettes) = response.palettes {
+    ///         println!("Found {} popular palettes containing oak_log and dirt:", palettes.len());
+    ///         for palette in palettes {
+    ///             println!("- ID: {}, Name: {:?}", palette.id, palette.name());
+    ///         }
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_palettes(
+        &self,
+        blocks: &[&str],
+        sort: SortOrder,
+        page: u32,
+        limit: u32,
+    ) -> Result<MultiBlockPaletteResponse> {
+        let queries: Vec<BlockQuery<'_>> = blocks.iter().map(|&b| BlockQuery::Block(b)).collect();
+        self.get_palettes_matching(&queries, sort, page, limit)
+            .await
+    }
+
+    /// Retrieves palettes containing `blocks` like
+    /// [`get_palettes`](Self::get_palettes), once per sort order in `sorts`.
+    ///
+    /// The sort orders are fetched [`MULTI_SORT_CONCURRENCY`] at a time, for
+    /// views showing several orderings of the same query side by side.
+    /// Duplicate sort orders are only fetched once.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a map from each sort order to its response, or
+    /// the first error encountered.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use blockpalettes_client::{BlockPalettesClient, SortOrder};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = BlockPalettesClient::new(reqwest::Client::new());
+    ///     let sorts = [SortOrder::Recent, SortOrder::Popular];
+    ///     let columns = client.get_palettes_multi_sort(&["oak_log"], &sorts, 1, 10).await?;
+    ///     for sort in &sorts {
+    ///         let palettes = columns[sort].palettes.as_deref().unwrap_or_default();
+    ///         println!("{}: {} palettes", sort.to_display_name(), palettes.len());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_palettes_multi_sort(
+        &self,
+        blocks: &[&str],
+        sorts: &[SortOrder],
+        page: u32,
+        limit: u32,
+    ) -> Result<HashMap<SortOrder, MultiBlockPaletteResponse>> {
+        let client = &*self.correlated();
+        let mut distinct = HashSet::new();
+        let sorts: Vec<&SortOrder> = sorts.iter().filter(|s| distinct.insert(*s)).collect();
+
+        stream::iter(sorts)
+            .map(|sort| async move {
+                let response = client
+                    .get_palettes(blocks, sort.clone(), page, limit)
+                    .await?;
+                Ok((sort.clone(), response))
+            })
+            .buffer_unordered(MULTI_SORT_CONCURRENCY)
+            .try_collect()
+            .await
+    }
+
+    /// Retrieves palettes containing `blocks`, with every other parameter
+    /// taken from `options`.
+    ///
+    /// This is [`get_palettes`](Self::get_palettes) with named parameters and
+    /// more filters: [`GetPalettesOptions::match_mode`] chooses whether palettes
+    /// need all of `blocks` or any of them, and palettes containing an
+    /// excluded block, with fewer likes than [`GetPalettesOptions::min_likes`]
+    /// or, with [`GetPalettesOptions::full_blocks_only`], with partial blocks
+    /// are dropped, as are palettes harder to obtain in survival than
+    /// [`GetPalettesOptions::max_obtainability`]. Without blocks, every
+    /// palette of the requested page is listed. Filtering happens locally, so the totals are upper bounds.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any request fails or the API returns an error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use blockpalettes_client::{BlockPalettesClient, GetPalettesOptions, MatchMode, SortOrder};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = BlockPalettesClient::new(reqwest::Client::new());
+    ///     let options = GetPalettesOptions {
+    ///         sort: SortOrder::Popular,
+    ///         match_mode: MatchMode::Any,
+    ///         exclude: vec!["dirt".to_string()],
+    ///         min_likes: 10,
+    ///         ..Default::default()
+    ///     };
+    ///     let response = client.list_palettes(&["oak_log", "spruce_log"], &options).await?;
+    ///     println!("{} palettes", response.filtered_count);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn list_palettes(
+        &self,
+        blocks: &[&str],
+        options: &GetPalettesOptions,
+    ) -> Result<MultiBlockPaletteResponse> {
+        let client = &*self.correlated();
+        let mut response = if blocks.is_empty() {
+            let response = client
+                .fetch_ranked_palette_page(&options.sort, options.page, options.limit, None)
+                .await?;
+            MultiBlockPaletteResponse {
+                success: response.success,
+                total_results: response.total_results,
+                total_pages: response.total_pages,
+                palettes: response.palettes,
+                filtered_count: 0,
+                upstream_totals: Vec::new(),
+            }
+        } else {
+            let queries: Vec<BlockQuery<'_>> =
+                blocks.iter().map(|&b| BlockQuery::Block(b)).collect();
+            client
+                .search_palettes(
+                    &queries,
+                    &options.sort,
+                    options.page,
+                    options.limit,
+                    options.match_mode,
+                )
+                .await
+                .into_complete()?
+        };
+
+        let palettes = response.palettes.get_or_insert_with(Vec::new);
+        palettes.retain(|palette| {
+            palette.likes >= options.min_likes
+                && (!options.full_blocks_only || palette.has_only_full_blocks())
+                && options.max_obtainability.is_none_or(|max| {
+                    palette.obtainability_score(blocks::GameMode::Survival) >= max.score()
+                })
+                && !options
+                    .exclude
+                    .iter()
+                    .any(|block| palette.blocks().contains(&block.as_str()))
+        });
+        response.filtered_count = u32::try_from(palettes.len()).unwrap_or(u32::MAX);
+        Ok(response)
+    }
+
+    /// Finds palettes matching a build [`Style`], best matches first.
+    ///
+    /// The API is queried for palettes containing any of the style's
+    /// [core blocks](Style::core_blocks), with every other parameter taken
+    /// from `options` (its [`match_mode`](GetPalettesOptions::match_mode) is
+    /// ignored). The palettes are then ranked by [`Style::score`]; palettes
+    /// with the same score are ordered by the [tie-breakers](sort::TIE_BREAKERS).
+    /// See the [`styles`] module.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any request fails or the API returns an error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use blockpalettes_client::styles::Style;
+    /// use blockpalettes_client::{BlockPalettesClient, GetPalettesOptions, SortOrder};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = BlockPalettesClient::new(reqwest::Client::new());
+    ///     let options = GetPalettesOptions {
+    ///         sort: SortOrder::Popular,
+    ///         ..Default::default()
+    ///     };
+    ///     for found in client.find_palettes_by_style(Style::Medieval, &options).await? {
+    ///         println!("{} ({:.2})", found.palette.id, found.score);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn find_palettes_by_style(
+        &self,
+        style: Style,
+        options: &GetPalettesOptions,
+    ) -> Result<Vec<StyleMatch>> {
+        let options = GetPalettesOptions {
+            match_mode: MatchMode::Any,
+            ..options.clone()
+        };
+        let response = self.list_palettes(style.core_blocks(), &options).await?;
+
+        let mut matches: Vec<StyleMatch> = response
+            .palettes
+            .unwrap_or_default()
+            .into_iter()
+            .map(|palette| StyleMatch {
+                score: style.score(&palette),
+                palette,
+            })
+            .collect();
+        matches.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| sort::compare(&sort::TIE_BREAKERS, &a.palette, &b.palette))
+        });
+        Ok(matches)
+    }
+
+    /// Retrieves palettes satisfying every [`BlockQuery`], where a query is
+    /// either an exact block or "any block of a [`BlockGroup`]".
+    ///
+    /// Like [`get_palettes`](Self::get_palettes), this method queries the
+    /// `/api/palettes/all_palettes.php` endpoint once per block (the API only
+    /// accepts one block per request) and filters the results locally. A group
+    /// query therefore costs one request per block in the group.
+    ///
+    /// [`BlockGroup`]: blocks::BlockGroup
+    ///
+    /// # Arguments
+    ///
+    /// * `queries` - The requirements every returned palette must satisfy.
+    /// * `sort` - The desired sorting order for the palettes.
+    /// * `page` - The page number of the results to retrieve (1-indexed).
+    /// * `limit` - The maximum number of palettes to request per block.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a [`MultiBlockPaletteResponse`] with distinct
+    /// palettes, or a [`BlockPalettesError`] if a request fails. The palettes
+    /// are sorted locally by `sort` and the [tie-breakers](sort::TIE_BREAKERS),
+    /// so their order does not depend on the order of `queries` (see
+    /// [`sort::keys_for`]).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use blockpalettes_client::{BlockPalettesClient, SortOrder};
+    /// use blockpalettes_client::blocks::{BlockGroup, BlockQuery};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = BlockPalettesClient::new(reqwest::Client::new());
+    ///     let queries = [BlockQuery::Block("cobblestone"), BlockQuery::Group(BlockGroup::DARK_WOOD)];
+    ///     let response = client
+    ///         .get_palettes_matching(&queries, SortOrder::Popular, 1, 20)
+    ///         .await?;
+    ///     println!("{} palettes", response.palettes.unwrap_or_default().len());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_palettes_matching(
+        &self,
+        queries: &[BlockQuery<'_>],
+        sort: SortOrder,
+        page: u32,
+        limit: u32,
+    ) -> Result<MultiBlockPaletteResponse> {
+        self.get_palettes_matching_partial(queries, sort, page, limit)
+            .await
+            .into_complete()
+    }
+
+    /// Retrieves palettes satisfying every [`BlockQuery`] like
+    /// [`get_palettes_matching`](Self::get_palettes_matching), but keeps the
+    /// results of the blocks whose request succeeded when others fail.
+    ///
+    /// Every failed block is reported in [`PartialResult::errors`]. Palettes
+    /// containing a failed block may then be missing, and the totals only
+    /// account for the blocks that succeeded.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use blockpalettes_client::{BlockPalettesClient, SortOrder};
+    /// use blockpalettes_client::blocks::{BlockGroup, BlockQuery};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = BlockPalettesClient::new(reqwest::Client::new());
+    ///     let queries = [BlockQuery::Group(BlockGroup::DARK_WOOD)];
+    ///     let result = client
+    ///         .get_palettes_matching_partial(&queries, SortOrder::Popular, 1, 20)
+    ///         .await;
+    ///     for (context, error) in &result.errors {
+    ///         eprintln!("{context}: {error}");
+    ///     }
+    ///     println!("{} palettes", result.data.palettes.unwrap_or_default().len());
+    /// }
+    /// ```
+    pub async fn get_palettes_matching_partial(
+        &self,
+        queries: &[BlockQuery<'_>],
+        sort: SortOrder,
+        page: u32,
+        limit: u32,
+    ) -> PartialResult<MultiBlockPaletteResponse> {
+        self.search_palettes(queries, &sort, page, limit, MatchMode::All)
+            .await
+    }
+
+    /// Queries every block of `queries` and keeps the palettes matching them
+    /// according to `mode`.
+    async fn search_palettes(
+        &self,
+        queries: &[BlockQuery<'_>],
+        sort: &SortOrder,
+        page: u32,
+        limit: u32,
+        mode: MatchMode,
+    ) -> PartialResult<MultiBlockPaletteResponse> {
+        let client = &*self.correlated();
+        let mut seen = HashSet::new();
+        let mut matching = Vec::new();
+        let mut upstream_totals = Vec::new();
+        let mut bound: Option<(u32, u32)> = None;
+        let mut errors = Vec::new();
+
+        for query in queries {
+            // a palette matching the query appears in the results of at least
+            // one of its blocks, so the sum bounds the query's matches
+            let mut query_bound = (0u32, 0u32);
+            for block in query.api_blocks() {
+                let response = match client
+                    .fetch_palette_page(sort, page, limit, Some(block))
+                    .await
+                {
+                    Ok(response) => response,
+                    Err(error) => {
+                        errors.push((format!("block `{block}`"), error));
+                        continue;
+                    }
+                };
+
+                let total = UpstreamTotal {
+                    block: block.to_string(),
+                    total_results: response.total_results,
+                    total_pages: response.total_pages.unwrap_or_default(),
+                };
+                query_bound.0 = query_bound.0.saturating_add(total.total_results);
+                query_bound.1 = query_bound.1.saturating_add(total.total_pages);
+                upstream_totals.push(total);
+
+                for palette in response.palettes.unwrap_or_default() {
+                    let matches = match mode {
+                        MatchMode::All => palette.matches_all(queries),
+                        MatchMode::Any => palette.matches_any(queries),
+                    };
+                    if matches && seen.insert(palette.id) {
+                        matching.push(palette);
+                    }
+                }
+            }
+
+            bound = Some(match (bound, mode) {
+                (None, _) => query_bound,
+                // every query must match, so the tightest query bounds the total
+                (Some((results, pages)), MatchMode::All) => {
+                    (results.min(query_bound.0), pages.min(query_bound.1))
+                }
+                // any query may match, so the queries together bound the total
+                (Some((results, pages)), MatchMode::Any) => (
+                    results.saturating_add(query_bound.0),
+                    pages.saturating_add(query_bound.1),
+                ),
+            });
+        }
+
+        sort::by(&mut matching, &sort::keys_for(sort));
+        let matching = self.ranking.apply(matching);
+
+        let (total_results, total_pages) = bound.unwrap_or_default();
+        PartialResult {
+            data: MultiBlockPaletteResponse {
+                success: true,
+                total_results,
+                total_pages: Some(total_pages),
+                filtered_count: u32::try_from(matching.len()).unwrap_or(u32::MAX),
+                upstream_totals,
+                palettes: Some(matching),
+            },
+            errors,
+        }
+    }
+
+    /// Retrieves exactly `n` of the most recently added palettes.
+    ///
+    /// This method pages through the `/api/palettes/all_palettes.php` endpoint
+    /// with [`SortOrder::Recent`], requesting only as many pages as needed and
+    /// stopping as soon as `n` palettes have been collected. Pages shifted by
+    /// submissions or removals while fetching neither duplicate nor skip
+    /// palettes (see [`walk_palettes`](Self::walk_palettes)).
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number of palettes to return.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing up to `n` palettes, newest first. Fewer than `n`
+    /// palettes are returned only if the site has fewer palettes in total.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use blockpalettes_client::BlockPalettesClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = BlockPalettesClient::new(reqwest::Client::new());
+    ///     let newest = client.get_recent_palettes(30).await?;
+    ///     for palette in &newest {
+    ///         println!("- ID: {}, added {}", palette.id, palette.time_ago);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_recent_palettes(&self, n: usize) -> Result<Vec<Palette>> {
+        let client = &*self.correlated();
+        let limit = u32::try_from(n)
+            .unwrap_or(u32::MAX)
+            .min(self.limits.max_page_limit);
+        let mut walker = client.walk_palettes(SortOrder::Recent, limit).unranked();
+        let mut palettes = Vec::with_capacity(n);
+
+        while palettes.len() < n {
+            let Some(batch) = walker.next_page().await? else {
+                break;
+            };
+            palettes.extend(batch.into_iter().take(n - palettes.len()));
+        }
+
+        Ok(palettes)
+    }
+
+    /// Retrieves every palette added at or after `cutoff`.
+    ///
+    /// This method pages through the `/api/palettes/all_palettes.php` endpoint
+    /// with [`SortOrder::Recent`] and stops as soon as it encounters a palette
+    /// older than `cutoff`, so a mirror that syncs daily only downloads the
+    /// pages containing new palettes.
+    ///
+    /// Only palette creation dates are known to the API, so palettes whose
+    /// likes changed after `cutoff` but that were created before it are not
+    /// returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `cutoff` - The oldest creation date to include, in the site's time zone.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the new palettes, newest first, or a
+    /// [`BlockPalettesError::InvalidDateFormat`] if a palette's date cannot
+    /// be parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use blockpalettes_client::BlockPalettesClient;
+    /// use chrono::NaiveDate;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = BlockPalettesClient::new(reqwest::Client::new());
+    ///     let cutoff = NaiveDate::from_ymd_opt(2024, 6, 1)
+    ///         .unwrap()
+    ///         .and_hms_opt(0, 0, 0)
+    ///         .unwrap();
+    ///     let new_palettes = client.sync_since(cutoff).await?;
+    ///     println!("{} palettes added since {cutoff}", new_palettes.len());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn sync_since(&self, cutoff: NaiveDateTime) -> Result<Vec<Palette>> {
+        let client = &*self.correlated();
+        let mut walker = client.walk_palettes(SortOrder::Recent, u32::MAX).unranked();
+        let mut palettes = Vec::new();
+
+        'pages: while let Some(batch) = walker.next_page().await? {
+            for palette in batch {
+                if palette.parse_date()? < cutoff {
+                    break 'pages;
+                }
+                palettes.push(palette);
+            }
+        }
+
+        Ok(palettes)
+    }
+
+    /// Starts reading palettes page by page, sorted by `sort`, with `limit`
+    /// palettes per request.
+    ///
+    /// Unlike calling [`get_palettes`](Self::get_palettes) with increasing
+    /// page numbers, the returned [`PaletteWalker`] never returns a palette
+    /// twice and re-reads pages when palettes removed upstream shift the
+    /// listing, so none are skipped. See the [`pagination`] module.
+    ///
+    /// [`PaletteWalker::into_stream`] turns the walker into a `Send + 'static`
+    /// [`Stream`](futures_util::Stream) of palettes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use blockpalettes_client::{BlockPalettesClient, SortOrder};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = BlockPalettesClient::new(reqwest::Client::new());
+    ///     let mut walker = client.walk_palettes(SortOrder::Recent, 100);
+    ///     let mut count = 0;
+    ///     while let Some(palettes) = walker.next_page().await? {
+    ///         count += palettes.len();
+    ///     }
+    ///     println!("{count} palettes");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn walk_palettes(&self, sort: SortOrder, limit: u32) -> PaletteWalker<'_> {
+        PaletteWalker::new(self, sort, limit.min(self.limits.max_page_limit))
+    }
+
+    /// Fetches a single page from the `/api/palettes/all_palettes.php` endpoint,
+    /// optionally restricted to palettes containing `block`.
+    pub(crate) async fn fetch_palette_page(
+        &self,
+        sort: &SortOrder,
+        page: u32,
+        limit: u32,
+        block: Option<&str>,
+    ) -> Result<PaletteResponse> {
+        self.limits.check_page_limit(limit)?;
+        let mut query = vec![
+            ("sort", sort.to_string()),
+            ("page", page.to_string()),
+            ("limit", limit.to_string()),
+        ];
+        if let Some(block) = block {
+            query.push(("blocks", block.to_string()));
+        }
+
+        let json = self.fetch("/api/palettes/all_palettes.php", &query).await?;
+
+        if !json.status.is_success() {
+            return Err(BlockPalettesError::Api("Palettes request failed".into()));
+        }
+
+        let mut response = json.json::<PaletteResponse>()?;
+        if let Some(palettes) = response.palettes.take() {
+            response.palettes = Some(self.validators.palettes(palettes)?);
+        }
+        Ok(response)
+    }
+
+    /// Fetches a page like [`fetch_palette_page`](Self::fetch_palette_page),
+    /// reordered by the registered [`Ranker`]. Only listings returned as-is
+    /// to callers are ranked; internal walks rely on the site's order.
+    pub(crate) async fn fetch_ranked_palette_page(
+        &self,
+        sort: &SortOrder,
+        page: u32,
+        limit: u32,
+        block: Option<&str>,
+    ) -> Result<PaletteResponse> {
+        let mut response = self.fetch_palette_page(sort, page, limit, block).await?;
+        if let Some(palettes) = response.palettes.take() {
+            response.palettes = Some(self.ranking.apply(palettes));
+        }
+        Ok(response)
+    }
+
+    /// Retrieves detailed information for a single palette by its ID.
+    ///
+    /// This method queries the `/api/palettes/single_palette.php` endpoint.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The unique identifier of the palette.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a [`PaletteDetails`] if successful,
+    /// or a [`BlockPalettesError`] if the request fails, the API returns an error
+    /// (e.g., [`BlockPalettesError::NotFound`] if the palette does not exist),
+    /// or the response cannot be deserialized.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use blockpalettes_client::BlockPalettesClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = BlockPalettesClient::new(reqwest::Client::new());
+    ///     let palette_id = 12345; // Replace with an actual palette ID
+    ///     match client.get_palette_details(palette_id).await {
+    ///         Ok(details) => println!("Palette ID {}: Username {}", details.id, details.username),
+    ///         Err(e) => eprintln!("Failed to get palette details: {}", e),
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_palette_details(&self, id: u64) -> Result<PaletteDetails> {
+        let response = self
+            .fetch(
+                "/api/palettes/single_palette.php",
+                &[("id", id.to_string())],
+            )
+            .await?
+            .json::<SinglePaletteResponse>()?;
+
+        match response.palette {
+            Some(palette) if response.success => self.validators.details(palette),
+            _ => Err(BlockPalettesError::NotFound(id)),
+        }
+    }
+
+    /// Retrieves a palette from the cheapest source that has it: the
+    /// [`PaletteCache`], then the API, then the palette's page.
+    ///
+    /// The page is only scraped when the client's [`SmartPolicy`] calls for
+    /// it, and scraping never makes the result worse: if the page cannot be
+    /// scraped, the details from the cache or the API are returned as they
+    /// are, or the API's error if there are none. Details from the API are
+    /// cached. See the [`smart`] module for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns the API's error if the palette could not be fetched and the
+    /// policy does not allow scraping, or scraping found no blocks.
+    pub async fn get_palette_smart(&self, id: u64) -> Result<SmartPalette> {
+        let policy = self.smart_policy;
+        let cache = self.palette_cache.as_deref().filter(|_| policy.use_cache);
+
+        let (details, source) = match cache.and_then(|cache| cache.get(id)) {
+            Some(details) => (details, DataSource::Cache),
+            None => match self.get_palette_details(id).await {
+                Ok(details) => {
+                    if let Some(cache) = cache {
+                        cache.insert(details.clone());
+                    }
+                    (details, DataSource::Api)
+                }
+                Err(error) => {
+                    let escalate = match error.root() {
+                        BlockPalettesError::NotFound(_) => policy.scrape_if_not_found,
+                        BlockPalettesError::Validation(_) => false,
+                        _ => policy.scrape_if_api_error,
+                    };
+                    if !escalate {
+                        return Err(error);
+                    }
+                    return match self.scrape_palette_page(id).await {
+                        Ok(page) if page.blocks.is_some() => Ok(SmartPalette {
+                            id,
+                            details: None,
+                            page: Some(page),
+                            source: DataSource::Scrape,
+                        }),
+                        _ => Err(error),
+                    };
+                }
+            },
+        };
+
+        if policy.scrape_if_incomplete
+            && smart::is_incomplete(&details)
+            && let Ok(page) = self.scrape_palette_page(id).await
+        {
+            return Ok(SmartPalette {
+                id,
+                details: Some(details),
+                page: Some(page),
+                source: DataSource::Scrape,
+            });
+        }
+        Ok(SmartPalette {
+            id,
+            details: Some(details),
+            page: None,
+            source,
+        })
+    }
+
+    /// Retrieves the current number of likes of a palette.
+    ///
+    /// The site's front-end exposes no separate like-count endpoint that this
+    /// crate knows of, so this method queries the same
+    /// `/api/palettes/single_palette.php` endpoint as
+    /// [`get_palette_details`](Self::get_palette_details). It only
+    /// deserializes the like count, and shares that method's HTTP cache
+    /// entries and coalescing, which makes it the cheapest way to poll likes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails, the palette is not found
+    /// ([`BlockPalettesError::NotFound`]), or the response cannot be
+    /// deserialized.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use blockpalettes_client::BlockPalettesClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = BlockPalettesClient::new(reqwest::Client::new());
+    ///     println!("{} likes", client.get_palette_likes(12345).await?);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_palette_likes(&self, id: u64) -> Result<u32> {
+        let response = self
+            .fetch(
+                "/api/palettes/single_palette.php",
+                &[("id", id.to_string())],
+            )
+            .await?
+            .json::<SinglePaletteLikesResponse>()?;
+
+        match response.palette {
+            Some(palette) if response.success => Ok(palette.likes),
+            _ => Err(BlockPalettesError::NotFound(id)),
+        }
+    }
+
+    /// Retrieves the details of many palettes, keeping the ones that could be
+    /// fetched when others fail.
+    ///
+    /// Requests are sent at most [`DETAILS_CONCURRENCY`] at a time. Duplicate
+    /// IDs are only fetched once.
+    ///
+    /// # Returns
+    ///
+    /// A [`PartialResult`] with the details of every palette that could be
+    /// fetched, in the order of `ids`, and an error for each palette that
+    /// could not.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use blockpalettes_client::BlockPalettesClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = BlockPalettesClient::new(reqwest::Client::new());
+    ///     let result = client.get_palette_details_many(&[1, 2, 3, 12345]).await;
+    ///     for details in &result.data {
+    ///         println!("{} by {}", details.id, details.username);
+    ///     }
+    ///     for (context, error) in &result.errors {
+    ///         eprintln!("{context}: {error}");
+    ///     }
+    /// }
+    /// ```
+    pub async fn get_palette_details_many(
+        &self,
+        ids: &[u64],
+    ) -> PartialResult<Vec<PaletteDetails>> {
+        self.correlated()
+            .details_many(ids, DETAILS_CONCURRENCY)
+            .await
+    }
+
+    /// Fetches the details of distinct `ids`, at most `concurrency` at a time.
+    async fn details_many(
+        &self,
+        ids: &[u64],
+        concurrency: usize,
+    ) -> PartialResult<Vec<PaletteDetails>> {
+        let mut distinct = HashSet::new();
+        let ids: Vec<u64> = ids
+            .iter()
+            .copied()
+            .filter(|id| distinct.insert(*id))
+            .collect();
+
+        let results: Vec<(u64, Result<PaletteDetails>)> = stream::iter(ids)
+            .map(|id| async move { (id, self.get_palette_details(id).await) })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await;
+
+        let mut partial = PartialResult::new(Vec::with_capacity(results.len()));
+        for (id, result) in results {
+            match result {
+                Ok(details) => partial.data.push(details),
+                Err(error) => partial.push_error(format!("palette {id}"), error),
+            }
+        }
+        partial
+    }
+
+    /// Retrieves a list of palettes similar to a given palette ID.
+    ///
+    /// This method queries the `/api/palettes/similar_palettes.php` endpoint.
     ///
     /// # Arguments
     ///
-    /// * `query` - The search string for blocks (e.g., "stone", "wood").
+    /// * `palette_id` - The ID of the reference palette to find similar ones.
     ///
     /// # Returns
     ///
-    /// A `Result` containing a `Vec<String>` of block names if successful,
-    /// or a [`BlockPalettesError`] if the request fails or the API returns an error.
-    ///
-    /// [`BlockPalettesError`]: enum.BlockPalettesError.html
+    /// A `Result` containing a `Vec<Palette>` of similar palettes if successful,
+    /// or a [`BlockPalettesError`] if the request fails, the API returns an error,
+    /// or the response cannot be deserialized.
     ///
     /// # Examples
     ///
@@ -147,268 +2417,349 @@ impl BlockPalettesClient {
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
     ///     let client = BlockPalettesClient::new(reqwest::Client::new());
-    ///     let blocks = client.search_blocks("dirt").await?;
-    ///     println!("Found blocks: {:?}", blocks);
+    ///     let reference_palette_id = 56655; // Replace with an actual palette ID
+    ///     let similar_palettes = client.get_similar_palettes(reference_palette_id).await?;
+    ///     println!("Found {} similar palettes for ID {}:", similar_palettes.len(), reference_palette_id);
+    ///     for palette in similar_palettes.iter().take(3) {
+    ///         println!("- ID: {}, Name: {:?}", palette.id, palette.name());
+    ///     }
     ///     Ok(())
     /// }
     /// ```
-    pub async fn search_blocks(&self, query: impl AsRef<str>) -> Result<Vec<String>> {
-        let url = format!("{}/api/palettes/search-block.php", self.base_url);
+    pub async fn get_similar_palettes(&self, palette_id: u64) -> Result<Vec<Palette>> {
         let response = self
-            .client
-            .get(&url)
-            .query(&[("query", query.as_ref())])
-            .send()
+            .fetch(
+                "/api/palettes/similar_palettes.php",
+                &[("palette_id", palette_id.to_string())],
+            )
             .await?
-            .json::<BlockSearchResponse>()
-            .await?;
+            .json::<SimilarPalettesResponse>()?;
 
         if response.success {
-            Ok(response.blocks)
+            let palettes = self.validators.palettes(response.palettes)?;
+            Ok(self.ranking.apply(palettes))
         } else {
-            Err(BlockPalettesError::Api("Search failed".into()))
+            Err(BlockPalettesError::Api("Similar palettes not found".into()))
         }
     }
 
-    /// Retrieves a list of popular blocks.
+    /// Retrieves the full details of the palettes similar to a given palette
+    /// ID, including their creators' usernames.
     ///
-    /// This method queries the `/api/palettes/popular-blocks.php` endpoint.
+    /// The similar palettes are listed with
+    /// [`get_similar_palettes`](Self::get_similar_palettes), then their
+    /// details are fetched at most `concurrency` at a time (at least one).
     ///
     /// # Returns
     ///
-    /// A `Result` containing a `Vec<PopularBlock>` if successful,
-    /// or a [`BlockPalettesError`] if the request fails or the API returns an error.
-    ///
-    /// [`PopularBlock`]: struct.PopularBlock.html
-    /// [`BlockPalettesError`]: enum.BlockPalettesError.html
+    /// A [`PartialResult`] with the details of every similar palette that
+    /// could be fetched, in the order of the similar palettes, and an error
+    /// for each one that could not. If the similar palettes cannot be listed,
+    /// the result is empty with that single error.
     ///
     /// # Examples
     ///
-    /// ```rust
-    /// use blockpalettes_client::BlockPalettesClient;
+    /// ```rust,no_run
+    /// use blockpalettes_client::{BlockPalettesClient, DETAILS_CONCURRENCY};
     ///
     /// #[tokio::main]
-    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// async fn main() {
     ///     let client = BlockPalettesClient::new(reqwest::Client::new());
-    ///     let popular_blocks = client.popular_blocks().await?;
-    ///     for block in popular_blocks.iter().take(3) {
-    ///         println!("Block: {}, Count: {}", block.name, block.count);
+    ///     let result = client.get_similar_palettes_detailed(56655, DETAILS_CONCURRENCY).await;
+    ///     for details in &result.data {
+    ///         println!("{} by {}", details.id, details.username);
+    ///     }
+    ///     for (context, error) in &result.errors {
+    ///         eprintln!("{context}: {error}");
     ///     }
-    ///     Ok(())
     /// }
     /// ```
-    pub async fn popular_blocks(&self) -> Result<Vec<PopularBlock>> {
-        let url = format!("{}/api/palettes/popular-blocks.php", self.base_url);
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await?
-            .json::<PopularBlocksResponse>()
-            .await?;
+    pub async fn get_similar_palettes_detailed(
+        &self,
+        palette_id: u64,
+        concurrency: usize,
+    ) -> PartialResult<Vec<PaletteDetails>> {
+        let client = &*self.correlated();
+        let similar = match client.get_similar_palettes(palette_id).await {
+            Ok(similar) => similar,
+            Err(error) => {
+                let mut partial = PartialResult::new(Vec::new());
+                partial.push_error(format!("similar palettes of {palette_id}"), error);
+                return partial;
+            }
+        };
 
-        if response.success {
-            Ok(response.blocks)
-        } else {
-            Err(BlockPalettesError::Api(
-                "Popular blocks request failed".into(),
-            ))
-        }
+        let ids: Vec<u64> = similar.iter().map(|palette| palette.id).collect();
+        client.details_many(&ids, concurrency).await
     }
 
-    /// Retrieves a list of palettes based on specified blocks, sorting order,
-    /// pagination, and limit.
+    /// Retrieves at least `count` palettes similar to a given palette ID.
     ///
-    /// This method queries the `/api/palettes/all_palettes.php` endpoint.
-    /// It internally filters the results to ensure all specified blocks are present
-    /// in the returned palettes.
+    /// The `/api/palettes/similar_palettes.php` endpoint has no paging or limit
+    /// parameters and only returns a handful of results. This method expands
+    /// the results client-side by following similarity transitively: the
+    /// palettes similar to the reference palette are queried in turn for their
+    /// own similar palettes (breadth-first), until `count` distinct palettes
+    /// have been collected or there is nothing left to explore.
     ///
     /// # Arguments
     ///
-    /// * `blocks` - A slice of string references representing the blocks that
-    ///   must be present in the palettes.
-    /// * `sort` - The desired sorting order for the palettes (e.g., `SortOrder::Recent`).
-    /// * `page` - The page number of the results to retrieve (1-indexed).
-    /// * `limit` - The maximum number of palettes to return per page.
+    /// * `palette_id` - The ID of the reference palette to find similar ones.
+    /// * `count` - The number of similar palettes to return.
     ///
     /// # Returns
     ///
-    /// A `Result` containing a [`PaletteResponse`] if successful,
-    /// or a [`BlockPalettesError`] if the request fails or the API returns an error.
+    /// A `Result` containing up to `count` distinct palettes, never including
+    /// the reference palette itself. Direct neighbours come first, followed by
+    /// palettes found further away in the similarity graph.
     ///
     /// # Examples
     ///
-    /// ```rust
-    /// use blockpalettes_client::{BlockPalettesClient, SortOrder};
+    /// ```rust,no_run
+    /// use blockpalettes_client::BlockPalettesClient;
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
     ///     let client = BlockPalettesClient::new(reqwest::Client::new());
-    ///     let blocks_to_search = &["oak_log", "dirt"];
-    ///     let response = client
-    ///         .get_palettes(blocks_to_search, SortOrder::Popular, 1, 5)
-    ///         .await?;
-    ///
-    ///     if let Some(palettes) = response.palettes {
-    ///         println!("Found {} popular palettes containing oak_log and dirt:", palettes.len());
-    ///         for palette in palettes {
-    ///             println!("- ID: {}, Name: {:?}", palette.id, palette.name());
-    ///         }
-    ///     }
+    ///     let more_like_this = client.get_similar_palettes_expanded(56655, 20).await?;
+    ///     println!("Found {} similar palettes", more_like_this.len());
     ///     Ok(())
     /// }
     /// ```
-    pub async fn get_palettes(
+    pub async fn get_similar_palettes_expanded(
         &self,
-        blocks: &[&str],
-        sort: SortOrder,
-        page: u32,
-        limit: u32,
-    ) -> Result<PaletteResponse> {
-        let url = format!("{}/api/palettes/all_palettes.php", self.base_url);
-
-        let mut all_palettes = Vec::new();
-        let mut total_results = 0;
-        let mut total_pages = 0;
-
-        for &block in blocks {
-            let json = self
-                .client
-                .get(&url)
-                .query(&[
-                    ("sort", sort.to_string()),
-                    ("page", page.to_string()),
-                    ("limit", limit.to_string()),
-                    ("blocks", block.to_string()),
-                ])
-                .send()
-                .await?;
+        palette_id: u64,
+        count: usize,
+    ) -> Result<Vec<Palette>> {
+        let mut seen = HashSet::from([palette_id]);
+        let mut queue = VecDeque::from([palette_id]);
+        let mut palettes = Vec::with_capacity(count);
 
-            if !json.status().is_success() {
-                return Err(BlockPalettesError::Api("Palettes request failed".into()));
-            }
-
-            let response = json.json::<PaletteResponse>().await?;
-
-            if total_results == 0 {
-                total_results = response.total_results;
-                total_pages = response.total_pages.unwrap_or_default();
-            }
+        while palettes.len() < count {
+            let Some(id) = queue.pop_front() else {
+                break;
+            };
 
-            if let Some(mut ps) = response.palettes {
-                all_palettes.append(&mut ps);
+            for palette in self.get_similar_palettes(id).await? {
+                if palettes.len() < count && seen.insert(palette.id) {
+                    queue.push_back(palette.id);
+                    palettes.push(palette);
+                }
             }
         }
 
-        // filter the collected palettes to ensure they contain ALL specified blocks
-        let filtered = all_palettes
-            .into_iter()
-            .filter(|p| p.contains_all_blocks(blocks))
-            .collect();
-
-        Ok(PaletteResponse {
-            success: true,
-            palettes: Some(filtered),
-            total_results,
-            total_pages: Some(total_pages),
-        })
+        Ok(palettes)
     }
 
-    /// Retrieves detailed information for a single palette by its ID.
+    /// Retrieves the `count` creators with the most likes across the site's
+    /// most popular palettes.
     ///
-    /// This method queries the `/api/palettes/single_palette.php` endpoint.
+    /// Block Palettes has no leaderboard endpoint, so the ranking is computed
+    /// client-side: the first pages of the `/api/palettes/all_palettes.php`
+    /// endpoint sorted by [`SortOrder::Popular`] (up to
+    /// [`TOP_USERS_SAMPLE_SIZE`] palettes) are aggregated by creator, and the
+    /// username of each ranked creator is then resolved with
+    /// [`get_palette_details`](Self::get_palette_details). Counts and likes are
+    /// therefore relative to that sample, not to a creator's whole history.
     ///
     /// # Arguments
     ///
-    /// * `id` - The unique identifier of the palette.
+    /// * `count` - The number of creators to return.
     ///
     /// # Returns
     ///
-    /// A `Result` containing a [`PaletteDetails`] if successful,
-    /// or a [`BlockPalettesError`] if the request fails, the API returns an error
-    /// (e.g., palette not found), or the response cannot be deserialized.
+    /// A `Result` containing up to `count` [`TopUser`]s, ordered by total likes
+    /// (descending), then palette count (descending), then user ID.
     ///
     /// # Examples
     ///
-    /// ```rust
+    /// ```rust,no_run
     /// use blockpalettes_client::BlockPalettesClient;
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
     ///     let client = BlockPalettesClient::new(reqwest::Client::new());
-    ///     let palette_id = 12345; // Replace with an actual palette ID
-    ///     match client.get_palette_details(palette_id).await {
-    ///         Ok(details) => println!("Palette ID {}: Username {}", details.id, details.username),
-    ///         Err(e) => eprintln!("Failed to get palette details: {}", e),
+    ///     for user in client.get_top_users(10).await? {
+    ///         println!("{}: {} palettes, {} likes", user.username, user.palette_count, user.total_likes);
     ///     }
     ///     Ok(())
     /// }
     /// ```
-    pub async fn get_palette_details(&self, id: u64) -> Result<PaletteDetails> {
-        let url = format!("{}/api/palettes/single_palette.php", self.base_url);
-        let response = self
-            .client
-            .get(&url)
-            .query(&[("id", id.to_string())])
-            .send()
-            .await?
-            .json::<SinglePaletteResponse>()
-            .await?;
+    pub async fn get_top_users(&self, count: usize) -> Result<Vec<TopUser>> {
+        let client = &*self.correlated();
+        let mut totals: HashMap<u64, (u32, u64, u64)> = HashMap::new();
+        let mut walker = client
+            .walk_palettes(SortOrder::Popular, u32::MAX)
+            .unranked();
+        let mut sampled = 0;
 
-        if response.success {
-            Ok(response.palette)
-        } else {
-            Err(BlockPalettesError::Api("Palette not found".into()))
+        while sampled < TOP_USERS_SAMPLE_SIZE {
+            let Some(batch) = walker.next_page().await? else {
+                break;
+            };
+
+            for palette in batch.into_iter().take(TOP_USERS_SAMPLE_SIZE - sampled) {
+                let entry = totals.entry(palette.user_id).or_insert((0, 0, palette.id));
+                entry.0 += 1;
+                entry.1 += u64::from(palette.likes);
+                sampled += 1;
+            }
+        }
+
+        let mut ranked: Vec<_> = totals.into_iter().collect();
+        ranked
+            .sort_by(|(a_id, a), (b_id, b)| b.1.cmp(&a.1).then(b.0.cmp(&a.0)).then(a_id.cmp(b_id)));
+        ranked.truncate(count);
+
+        let mut users = Vec::with_capacity(ranked.len());
+        for (user_id, (palette_count, total_likes, sample_palette)) in ranked {
+            let details = client.get_palette_details(sample_palette).await?;
+            users.push(TopUser {
+                user_id,
+                username: details.username,
+                palette_count,
+                total_likes,
+            });
         }
+
+        Ok(users)
     }
 
-    /// Retrieves a list of palettes similar to a given palette ID.
+    /// Retrieves a user and the IDs of all their palettes.
     ///
-    /// This method queries the `/api/palettes/similar_palettes.php` endpoint.
+    /// Block Palettes has no user endpoint, so the user is assembled from the
+    /// palette cards of their profile page (see
+    /// [`scrape_palette_page`](Self::scrape_palette_page) for the caveats of
+    /// scraping) and from [`get_palette_details`](Self::get_palette_details),
+    /// which provides the username. Likes are summed from the cards; the
+    /// details of palettes whose card shows no likes are fetched as well.
+    ///
+    /// With a [`UserCache`] attached, cached users are returned without any
+    /// request.
     ///
     /// # Arguments
     ///
-    /// * `palette_id` - The ID of the reference palette to find similar ones.
+    /// * `user_id` - The ID of the user, e.g. from [`Palette::user_id`].
     ///
     /// # Returns
     ///
-    /// A `Result` containing a `Vec<Palette>` of similar palettes if successful,
-    /// or a [`BlockPalettesError`] if the request fails, the API returns an error,
-    /// or the response cannot be deserialized.
+    /// A `Result` containing the [`User`], or a [`BlockPalettesError::Api`] if
+    /// the user has no visible palettes, and therefore no known username. Users
+    /// excluded by the client's denylist fail with
+    /// [`BlockPalettesError::Validation`], and denied palettes are left out.
     ///
     /// # Examples
     ///
-    /// ```rust
+    /// ```rust,no_run
+    /// use blockpalettes_client::BlockPalettesClient;
+    /// use blockpalettes_client::users::UserCache;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = BlockPalettesClient::new(reqwest::Client::new())
+    ///         .with_user_cache(UserCache::new());
+    ///     let palette = client.get_palette_details(12345).await?;
+    ///     let creator = client.get_user(palette.user_id).await?;
+    ///     println!(
+    ///         "More from {} ({} palettes, {} likes): {:?}",
+    ///         creator.username,
+    ///         creator.palette_ids.len(),
+    ///         creator.total_likes,
+    ///         creator.palette_ids
+    ///     );
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_user(&self, user_id: u64) -> Result<User> {
+        let client = &*self.correlated();
+        let denylist = client.validators.denylist();
+        if denylist.is_some_and(|d| d.users.contains(&user_id)) {
+            return Err(BlockPalettesError::Validation(format!(
+                "user {user_id} is denylisted"
+            )));
+        }
+        if let Some(user) = client.user_cache.as_ref().and_then(|c| c.get(user_id)) {
+            return Ok(user);
+        }
+
+        let html = client
+            .fetch(&format!("/profile/{user_id}"), &[])
+            .await?
+            .body;
+        let mut cards = scrape::parse_offloaded(html, client.parse_offload, |html| {
+            scrape::parse_user_page(html, ScrapeMode::Lenient)
+        })
+        .await?;
+        client.validators.cards(&mut cards);
+        let Some(first) = cards.first() else {
+            return Err(BlockPalettesError::Api(format!(
+                "user {user_id} has no palettes"
+            )));
+        };
+
+        let details = client.get_palette_details(first.id).await?;
+        if details.user_id != user_id {
+            return Err(BlockPalettesError::Api(format!(
+                "palette {} on the profile of user {user_id} belongs to user {}",
+                details.id, details.user_id
+            )));
+        }
+
+        let mut total_likes = 0;
+        for card in &cards {
+            total_likes += match card.likes {
+                Some(likes) => u64::from(likes),
+                None if card.id == details.id => u64::from(details.likes),
+                None => u64::from(client.get_palette_details(card.id).await?.likes),
+            };
+        }
+
+        let user = User {
+            id: user_id,
+            username: details.username,
+            palette_ids: cards.iter().map(|card| card.id).collect(),
+            total_likes,
+        };
+        if let Some(cache) = &client.user_cache {
+            cache.insert(user.clone());
+        }
+        Ok(user)
+    }
+
+    /// Picks the palette of the day for `date` among the most popular palettes.
+    ///
+    /// This fetches one page of 100 palettes sorted by
+    /// [`SortOrder::Popular`] and applies [`daily::palette_of_the_day`]. The
+    /// most popular palettes change slowly, so instances calling this on the
+    /// same day almost always agree; pass a shared corpus to
+    /// [`daily::palette_of_the_day`] directly if they must always agree.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
     /// use blockpalettes_client::BlockPalettesClient;
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
     ///     let client = BlockPalettesClient::new(reqwest::Client::new());
-    ///     let reference_palette_id = 56655; // Replace with an actual palette ID
-    ///     let similar_palettes = client.get_similar_palettes(reference_palette_id).await?;
-    ///     println!("Found {} similar palettes for ID {}:", similar_palettes.len(), reference_palette_id);
-    ///     for palette in similar_palettes.iter().take(3) {
-    ///         println!("- ID: {}, Name: {:?}", palette.id, palette.name());
+    ///     let today = chrono::Utc::now().date_naive();
+    ///     if let Some(palette) = client.palette_of_the_day(today).await? {
+    ///         println!("Palette of the day: {}", client.palette_url(palette.id));
     ///     }
     ///     Ok(())
     /// }
     /// ```
-    pub async fn get_similar_palettes(&self, palette_id: u64) -> Result<Vec<Palette>> {
-        let url = format!("{}/api/palettes/similar_palettes.php", self.base_url);
+    pub async fn palette_of_the_day(&self, date: chrono::NaiveDate) -> Result<Option<Palette>> {
         let response = self
-            .client
-            .get(&url)
-            .query(&[("palette_id", palette_id.to_string())])
-            .send()
-            .await?
-            .json::<SimilarPalettesResponse>()
+            .fetch_palette_page(
+                &SortOrder::Popular,
+                1,
+                limits::MAX_PAGE_LIMIT.min(self.limits.max_page_limit),
+                None,
+            )
             .await?;
-
-        if response.success {
-            Ok(response.palettes)
-        } else {
-            Err(BlockPalettesError::Api("Similar palettes not found".into()))
-        }
+        let palettes = response.palettes.unwrap_or_default();
+        Ok(daily::palette_of_the_day(date, &palettes).cloned())
     }
 
     /// Scrapes details directly from a Block Palettes HTML page for a given palette ID.
@@ -424,13 +2775,16 @@ impl BlockPalettesClient {
     /// # Returns
     ///
     /// A `Result` containing a [`PalettePageDetails`] if successful,
-    /// or a [`BlockPalettesError`] if the request fails, HTML parsing fails,
-    /// or expected elements are not found.
+    /// or a [`BlockPalettesError`] if the request or HTML parsing fails.
+    /// Expected elements that are not found are reported in
+    /// [`PalettePageDetails::warnings`].
     ///
     /// # Caveats
     ///
     /// This method relies on the specific HTML structure of `blockpalettes.com`.
     /// Any changes to the website's front-end might break this scraping functionality.
+    /// Use [`scrape_palette_page_with`](Self::scrape_palette_page_with) and
+    /// [`ScrapeMode::Strict`] to turn such breakage into an error.
     ///
     /// # Examples
     ///
@@ -444,7 +2798,7 @@ impl BlockPalettesClient {
     ///     match client.scrape_palette_page(palette_id).await {
     ///         Ok(details) => {
     ///             println!("Scraped blocks for palette {}: {:?}", palette_id, details.blocks);
-    ///             println!("Similar palette IDs: {:?}", details.similar_palette_ids);
+    ///             println!("Similar palette IDs: {:?}", details.similar_palette_ids());
     ///         },
     ///         Err(e) => eprintln!("Failed to scrape palette page: {}", e),
     ///     }
@@ -452,50 +2806,319 @@ impl BlockPalettesClient {
     /// }
     /// ```
     pub async fn scrape_palette_page(&self, palette_id: u64) -> Result<PalettePageDetails> {
-        let url = format!("{}/palette/{}", self.base_url, palette_id);
-        let html = self.client.get(&url).send().await?.text().await?;
+        self.scrape_palette_page_with(palette_id, ScrapeMode::Lenient)
+            .await
+    }
+
+    /// Scrapes a palette page like [`scrape_palette_page`](Self::scrape_palette_page),
+    /// with an explicit [`ScrapeMode`].
+    ///
+    /// In [`ScrapeMode::Lenient`] mode, problems such as a missing block list
+    /// or malformed palette links are reported in
+    /// [`PalettePageDetails::warnings`], which lets callers tell a broken page
+    /// apart from one that is genuinely empty. In [`ScrapeMode::Strict`] mode,
+    /// any such problem fails the call with
+    /// [`BlockPalettesError::ScrapeIncomplete`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use blockpalettes_client::BlockPalettesClient;
+    /// use blockpalettes_client::scrape::ScrapeMode;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = BlockPalettesClient::new(reqwest::Client::new());
+    ///     let details = client.scrape_palette_page_with(12345, ScrapeMode::Lenient).await?;
+    ///     for warning in &details.warnings {
+    ///         eprintln!("warning: {warning}");
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn scrape_palette_page_with(
+        &self,
+        palette_id: u64,
+        mode: ScrapeMode,
+    ) -> Result<PalettePageDetails> {
+        let html = self
+            .fetch(&format!("/palette/{palette_id}"), &[])
+            .await?
+            .body;
+
+        let details = scrape::parse_offloaded(html, self.parse_offload, move |html| {
+            scrape::parse_palette_page(html, mode)
+        })
+        .await?;
+        self.apply_page_rules(palette_id, details)
+    }
 
-        let document = Html::parse_document(&html);
+    /// Fetches the most recent snapshot of a palette's page from the
+    /// Wayback Machine, for palettes deleted from the site.
+    ///
+    /// `id_or_url` is a palette ID or a link to the palette in any format
+    /// [`permalink::parse`] accepts. The snapshot is scraped like
+    /// [`scrape_palette_page`](Self::scrape_palette_page), in
+    /// [`ScrapeMode::Lenient`] mode. See the [`archive`] module for details.
+    /// Requires the `archive` feature.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the archived page, or `None` if the page was
+    /// never archived successfully.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BlockPalettesError::UnresolvedLink`] if `id_or_url` does not
+    /// name a palette, or an error if the archive cannot be queried or the
+    /// palette is excluded by the client's denylist.
+    ///
+    /// Both requests to the archive go through the client's circuit breaker
+    /// (as the `https://archive.org/wayback/available` and
+    /// `https://web.archive.org/web` endpoints), and are correlated and
+    /// recorded like requests to the site. The client's extra query
+    /// parameters are not sent to the archive.
+    #[cfg(feature = "archive")]
+    pub async fn fetch_from_wayback(
+        &self,
+        id_or_url: &str,
+    ) -> Result<Option<archive::ArchivedPalettePage>> {
+        let (link, original_url) = archive::original_url(id_or_url)
+            .ok_or_else(|| BlockPalettesError::UnresolvedLink(id_or_url.to_string()))?;
 
-        // extract palette blocks
-        let block_selector =
-            Selector::parse(".single-block").map_err(|_| BlockPalettesError::HtmlParse)?;
-        let mut blocks = Vec::new();
+        let availability_url = format!("{}/wayback/available", archive::WAYBACK_URL);
+        let availability: archive::Availability = self
+            .fetch_url(
+                &availability_url,
+                &availability_url,
+                &[("url", original_url.clone())],
+            )
+            .await?
+            .json()?;
+        let Some(snapshot) = availability.snapshot() else {
+            return Ok(None);
+        };
 
-        for element in document.select(&block_selector) {
-            if let Some(block_name) = element.text().last() {
-                blocks.push(block_name.trim().to_string());
-            }
+        let snapshot_url = snapshot.raw_url(&original_url);
+        let response = self
+            .fetch_url(archive::SNAPSHOT_ENDPOINT, &snapshot_url, &[])
+            .await?;
+        if !response.status.is_success() {
+            return Err(BlockPalettesError::Api(format!(
+                "HTTP {} fetching {snapshot_url}",
+                response.status
+            )));
         }
+        let details = scrape::parse_offloaded(response.body, self.parse_offload, |html| {
+            scrape::parse_palette_page(html, ScrapeMode::Lenient)
+        })
+        .await?;
+        let details = match link {
+            PaletteLink::Id(id) => self.apply_page_rules(id, details)?,
+            PaletteLink::Hash(_) => details,
+        };
 
-        // extract similar palettes if available
-        let similar_selector =
-            Selector::parse(".palette-card").map_err(|_| BlockPalettesError::HtmlParse)?;
-        let mut similar = Vec::new();
+        Ok(Some(archive::ArchivedPalettePage {
+            link,
+            original_url,
+            snapshot_url,
+            archived_at: snapshot.archived_at()?,
+            details,
+        }))
+    }
 
-        for element in document.select(&similar_selector) {
-            if let Some(id) = element
-                .value()
-                .attr("href")
-                .and_then(|href| href.split('/').next_back())
-                .and_then(|id| id.parse::<u64>().ok())
-            {
-                similar.push(id);
-            }
+    /// Scrapes one page of a block's gallery, which lists every palette
+    /// featuring that block.
+    ///
+    /// The JSON listing sometimes misses older palettes; the gallery pages are
+    /// rendered from the site's own database and can be used to fill the gaps.
+    /// Each [`PaletteCard`] carries the palette ID and whatever summary data
+    /// the card shows. Fetch the full palette with
+    /// [`get_palette_details`](Self::get_palette_details) if needed.
+    ///
+    /// # Arguments
+    ///
+    /// * `block_name` - The block, with or without the `minecraft:` namespace.
+    /// * `page` - The page number, starting at 1.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a [`BlockPageDetails`]. A page past the last one
+    /// has no palettes. Cards that cannot be parsed are reported in
+    /// [`BlockPageDetails::warnings`]. Palettes excluded by the client's
+    /// denylist are left out.
+    ///
+    /// # Caveats
+    ///
+    /// Like [`scrape_palette_page`](Self::scrape_palette_page), this relies on
+    /// the HTML structure of `blockpalettes.com`. Use
+    /// [`scrape_block_page_with`](Self::scrape_block_page_with) and
+    /// [`ScrapeMode::Strict`] to turn breakage into an error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use blockpalettes_client::BlockPalettesClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = BlockPalettesClient::new(reqwest::Client::new());
+    ///     let mut page = 1;
+    ///     loop {
+    ///         let details = client.scrape_block_page("oak_planks", page).await?;
+    ///         if details.palettes.is_empty() {
+    ///             break;
+    ///         }
+    ///         for card in &details.palettes {
+    ///             println!("{}: {:?} ({:?} likes)", card.id, card.blocks, card.likes);
+    ///         }
+    ///         page += 1;
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn scrape_block_page(&self, block_name: &str, page: u32) -> Result<BlockPageDetails> {
+        self.scrape_block_page_with(block_name, page, ScrapeMode::Lenient)
+            .await
+    }
+
+    /// Scrapes a block's gallery page like
+    /// [`scrape_block_page`](Self::scrape_block_page), with an explicit
+    /// [`ScrapeMode`].
+    pub async fn scrape_block_page_with(
+        &self,
+        block_name: &str,
+        page: u32,
+        mode: ScrapeMode,
+    ) -> Result<BlockPageDetails> {
+        let block = block_name.trim();
+        let block = block.strip_prefix("minecraft:").unwrap_or(block);
+        let html = self
+            .fetch(
+                &format!("/block/{}", encode_path_segment(block)),
+                &[("page", page.to_string())],
+            )
+            .await?
+            .body;
+
+        let owned_block = block.to_string();
+        let mut details = scrape::parse_offloaded(html, self.parse_offload, move |html| {
+            scrape::parse_block_page(html, &owned_block, page, mode)
+        })
+        .await?;
+        self.validators.cards(&mut details.palettes);
+        Ok(details)
+    }
+
+    /// Applies the client's overrides and denylist to a scraped palette page.
+    fn apply_page_rules(
+        &self,
+        palette_id: u64,
+        mut details: PalettePageDetails,
+    ) -> Result<PalettePageDetails> {
+        if let Some(overrides) = self.validators.overrides() {
+            overrides.apply_page(palette_id, &mut details);
+        }
+        let Some(denylist) = self.validators.denylist() else {
+            return Ok(details);
+        };
+        if denylist.denies_palette_id(palette_id)
+            || details
+                .blocks
+                .iter()
+                .flatten()
+                .any(|b| denylist.denies_block(b))
+        {
+            return Err(BlockPalettesError::Validation(format!(
+                "palette {palette_id} is denylisted"
+            )));
         }
+        if let Some(similar) = &mut details.similar_palettes {
+            similar.retain(|card| {
+                !denylist.denies_palette_id(card.id)
+                    && !card.blocks.iter().any(|b| denylist.denies_block(b))
+            });
+        }
+        Ok(details)
+    }
+}
 
-        Ok(PalettePageDetails {
-            blocks,
-            similar_palette_ids: similar,
+/// Percent-encodes everything but unreserved characters in a URL path segment.
+fn encode_path_segment(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            encoded.push(char::from(byte));
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+/// A fully buffered HTTP response.
+///
+/// Bodies are read eagerly so that responses can be cached and decoded
+/// independently of the underlying connection.
+#[derive(Debug, Clone)]
+pub(crate) struct HttpResponse {
+    pub(crate) status: StatusCode,
+    #[cfg_attr(not(feature = "http-cache"), allow(dead_code))]
+    pub(crate) headers: HeaderMap,
+    pub(crate) body: String,
+    /// Whether the response was served from the HTTP cache.
+    pub(crate) from_cache: bool,
+    /// The URL the response came from, after redirects, unless it was
+    /// served from the HTTP cache.
+    pub(crate) url: Option<String>,
+}
+
+impl HttpResponse {
+    /// Buffers a `reqwest::Response` into memory.
+    pub(crate) async fn from_reqwest(response: reqwest::Response) -> Result<Self> {
+        let status = response.status();
+        let headers = response.headers().clone();
+        let url = response.url().to_string();
+        let body = response.text().await?;
+        Ok(Self {
+            status,
+            headers,
+            body,
+            from_cache: false,
+            url: Some(url),
         })
     }
+
+    /// Deserializes the body as JSON.
+    pub(crate) fn json<T: DeserializeOwned>(&self) -> Result<T> {
+        Ok(serde_json::from_str(&self.body)?)
+    }
 }
 
 /// Represents the different sorting orders available for retrieving palettes.
 ///
 /// These variants correspond to the `sort` parameter in the Block Palettes API.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
+///
+/// Parsing with [`FromStr`](std::str::FromStr) or [`TryFrom<&str>`] is strict and
+/// only accepts the known orders, which makes it suitable for validating user
+/// input. Deserialization is lenient instead: sort strings this crate does not
+/// know about are preserved in [`SortOrder::Other`], so data written by newer
+/// versions of the site still round-trips.
+///
+/// # Examples
+///
+/// ```rust
+/// # use blockpalettes_client::SortOrder;
+/// let order: SortOrder = "Popular".parse().unwrap();
+/// assert_eq!(order, SortOrder::Popular);
+/// assert_eq!(order.to_string(), "popular");
+/// assert!("most-liked".parse::<SortOrder>().is_err());
+///
+/// let unknown: SortOrder = serde_json::from_str("\"most_liked\"").unwrap();
+/// assert_eq!(unknown, SortOrder::Other("most_liked".to_string()));
+/// assert_eq!(serde_json::to_string(&unknown).unwrap(), "\"most_liked\"");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum SortOrder {
     /// Sort by the most recently added palettes.
     Recent,
@@ -505,6 +3128,8 @@ pub enum SortOrder {
     Oldest,
     /// Sort by trending palettes.
     Trending,
+    /// A sort order not known to this crate, passed to the API verbatim.
+    Other(String),
 }
 
 impl std::fmt::Display for SortOrder {
@@ -512,22 +3137,40 @@ impl std::fmt::Display for SortOrder {
     ///
     /// # Returns
     ///
-    /// A `String` slice representing the sort order (e.g., "recent", "popular").
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    /// A `String` slice representing the sort order (e.g., "recent", "popular").
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl SortOrder {
+    /// All sort orders known to this crate.
+    pub const KNOWN: [SortOrder; 4] = [
+        SortOrder::Recent,
+        SortOrder::Popular,
+        SortOrder::Oldest,
+        SortOrder::Trending,
+    ];
+
+    /// Returns the API string representation of the sort order.
+    ///
+    /// This is the same string produced by [`Display`](std::fmt::Display) and
+    /// serialization.
+    pub fn as_str(&self) -> &str {
         match self {
-            SortOrder::Recent => write!(f, "recent"),
-            SortOrder::Popular => write!(f, "popular"),
-            SortOrder::Oldest => write!(f, "oldest"),
-            SortOrder::Trending => write!(f, "trending"),
+            SortOrder::Recent => "recent",
+            SortOrder::Popular => "popular",
+            SortOrder::Oldest => "oldest",
+            SortOrder::Trending => "trending",
+            SortOrder::Other(sort) => sort,
         }
     }
-}
 
-impl SortOrder {
     /// Returns a user-friendly display name for the `SortOrder` enum variant.
     ///
     /// This is intended for UI or logging purposes, and differs from the
-    /// API representation used by [`Display`] or serialization.
+    /// API representation used by [`Display`](std::fmt::Display) or serialization. Unknown sort
+    /// orders are displayed as received from the API.
     ///
     /// # Examples
     ///
@@ -536,13 +3179,186 @@ impl SortOrder {
     /// let order = SortOrder::Recent;
     /// assert_eq!(order.to_display_name(), "Recent");
     /// ```
-    pub const fn to_display_name(&self) -> &'static str {
+    pub fn to_display_name(&self) -> &str {
         match self {
             SortOrder::Recent => "Recent",
             SortOrder::Popular => "Popular",
             SortOrder::Oldest => "Oldest",
             SortOrder::Trending => "Trending",
+            SortOrder::Other(sort) => sort,
+        }
+    }
+}
+
+/// Whether a palette must contain all the requested blocks, or only one of them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchMode {
+    /// The palette must contain every requested block.
+    #[default]
+    All,
+    /// The palette must contain at least one requested block.
+    Any,
+}
+
+/// The parameters of [`BlockPalettesClient::list_palettes`].
+///
+/// Every field has a default, so only the ones that matter need to be set.
+///
+/// # Examples
+///
+/// ```rust
+/// use blockpalettes_client::{GetPalettesOptions, MatchMode, SortOrder};
+///
+/// let options = GetPalettesOptions {
+///     limit: 50,
+///     ..Default::default()
+/// };
+/// assert_eq!(options.sort, SortOrder::Recent);
+/// assert_eq!((options.page, options.limit), (1, 50));
+/// assert_eq!(options.match_mode, MatchMode::All);
+/// assert!(options.exclude.is_empty());
+/// assert_eq!(options.min_likes, 0);
+/// assert!(!options.full_blocks_only);
+/// assert_eq!(options.max_obtainability, None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetPalettesOptions {
+    /// The sorting order of the palettes. Defaults to [`SortOrder::Recent`].
+    pub sort: SortOrder,
+    /// The page to retrieve, starting at 1. Defaults to 1.
+    pub page: u32,
+    /// The number of palettes requested per block. Defaults to 20.
+    pub limit: u32,
+    /// Whether palettes must contain all the blocks, or any of them.
+    /// Defaults to [`MatchMode::All`].
+    pub match_mode: MatchMode,
+    /// Blocks that returned palettes must not contain.
+    pub exclude: Vec<String>,
+    /// The minimum number of likes of returned palettes. Defaults to 0.
+    pub min_likes: u32,
+    /// Whether to only return palettes made of full blocks (see
+    /// [`Palette::has_only_full_blocks`]). Defaults to `false`.
+    pub full_blocks_only: bool,
+    /// The hardest [`Obtainability`](blocks::Obtainability) in survival of
+    /// the blocks of returned palettes (see [`Palette::obtainability_score`]).
+    /// Defaults to `None`, which keeps every palette.
+    pub max_obtainability: Option<blocks::Obtainability>,
+}
+
+impl Default for GetPalettesOptions {
+    fn default() -> Self {
+        Self {
+            sort: SortOrder::Recent,
+            page: 1,
+            limit: 20,
+            match_mode: MatchMode::All,
+            exclude: Vec::new(),
+            min_likes: 0,
+            full_blocks_only: false,
+            max_obtainability: None,
+        }
+    }
+}
+
+/// The error returned when parsing an unknown [`SortOrder`] from a string.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("unknown sort order `{0}` (expected one of: recent, popular, oldest, trending)")]
+pub struct ParseSortOrderError(pub String);
+
+impl std::str::FromStr for SortOrder {
+    type Err = ParseSortOrderError;
+
+    /// Parses one of the known sort orders, ignoring case and surrounding whitespace.
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let trimmed = s.trim();
+        SortOrder::KNOWN
+            .into_iter()
+            .find(|order| order.as_str().eq_ignore_ascii_case(trimmed))
+            .ok_or_else(|| ParseSortOrderError(s.to_string()))
+    }
+}
+
+impl TryFrom<&str> for SortOrder {
+    type Error = ParseSortOrderError;
+
+    fn try_from(s: &str) -> std::result::Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl Serialize for SortOrder {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for SortOrder {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        Ok(raw.parse().unwrap_or(SortOrder::Other(raw)))
+    }
+}
+
+/// The results of a block search, returned by
+/// [`BlockPalettesClient::search_blocks_page`].
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct BlockSearchPage {
+    /// The query, as sent.
+    pub query: String,
+    /// The matching blocks, in the order the site returned them.
+    pub blocks: Vec<String>,
+    /// Whether the site returned as many blocks as it returns at most (see
+    /// [`Limits::max_block_search_results`]), in which case more blocks may
+    /// match. A search matching exactly that many blocks is reported as
+    /// truncated too, since the two cannot be told apart.
+    pub truncated: bool,
+}
+
+impl BlockSearchPage {
+    /// The characters block IDs are made of, used to refine truncated queries.
+    const REFINEMENT_CHARS: &'static str = "abcdefghijklmnopqrstuvwxyz_";
+
+    /// Returns the queries whose results, together with this page, cover
+    /// every block matching the query, or nothing if the page is not
+    /// truncated.
+    ///
+    /// Block search matches anywhere in the ID, so every match other than the
+    /// query itself contains the query followed or preceded by another
+    /// character: the refinements are the query extended by one character on
+    /// either side.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use blockpalettes_client::BlockSearchPage;
+    ///
+    /// let page = BlockSearchPage { query: "oak".into(), blocks: vec![], truncated: true };
+    /// let refinements = page.refinements();
+    /// assert_eq!(refinements.len(), 54);
+    /// assert!(refinements.contains(&"oak_".to_string()));
+    /// assert!(refinements.contains(&"_oak".to_string()));
+    ///
+    /// let complete = BlockSearchPage { truncated: false, ..page };
+    /// assert!(complete.refinements().is_empty());
+    /// ```
+    pub fn refinements(&self) -> Vec<String> {
+        if !self.truncated {
+            return Vec::new();
         }
+        let query = self.query.trim().to_lowercase();
+        let after = Self::REFINEMENT_CHARS
+            .chars()
+            .map(|c| format!("{query}{c}"));
+        let before = Self::REFINEMENT_CHARS
+            .chars()
+            .map(|c| format!("{c}{query}"));
+        after.chain(before).collect()
     }
 }
 
@@ -550,6 +3366,7 @@ impl SortOrder {
 #[derive(Debug, Deserialize)]
 struct BlockSearchResponse {
     success: bool,
+    #[serde(deserialize_with = "lenient::list")]
     blocks: Vec<String>,
 }
 
@@ -557,6 +3374,7 @@ struct BlockSearchResponse {
 #[derive(Debug, Deserialize)]
 struct PopularBlocksResponse {
     success: bool,
+    #[serde(deserialize_with = "lenient::list")]
     blocks: Vec<PopularBlock>,
 }
 
@@ -564,18 +3382,36 @@ struct PopularBlocksResponse {
 #[derive(Debug, Deserialize)]
 struct SinglePaletteResponse {
     success: bool,
-    palette: PaletteDetails,
+    /// Missing when `success` is `false`.
+    #[serde(default)]
+    palette: Option<PaletteDetails>,
+}
+
+/// Internal struct for deserializing only the like count from the
+/// `/api/palettes/single_palette.php` endpoint.
+#[derive(Debug, Deserialize)]
+struct SinglePaletteLikesResponse {
+    success: bool,
+    /// Missing when `success` is `false`.
+    #[serde(default)]
+    palette: Option<PaletteLikes>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PaletteLikes {
+    likes: u32,
 }
 
 /// Internal struct for deserializing the response from the `/api/palettes/similar_palettes.php` endpoint.
 #[derive(Debug, Deserialize)]
 struct SimilarPalettesResponse {
     success: bool,
+    #[serde(deserialize_with = "lenient::list")]
     palettes: Vec<Palette>,
 }
 
 /// Represents a popular block returned by the API.
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct PopularBlock {
     /// The name of the block (e.g., "stone", "dirt").
     #[serde(rename = "block")]
@@ -585,6 +3421,20 @@ pub struct PopularBlock {
 }
 
 /// Represents the response structure when fetching a list of palettes.
+///
+/// # Examples
+///
+/// Empty results are read the same way whichever shape the API gives them:
+///
+/// ```rust
+/// use blockpalettes_client::PaletteResponse;
+///
+/// for palettes in ["null", "[]", "{}", "false"] {
+///     let json = format!(r#"{{"success":true,"total_results":0,"total_pages":0,"palettes":{palettes}}}"#);
+///     let response: PaletteResponse = serde_json::from_str(&json).unwrap();
+///     assert!(response.palettes.unwrap_or_default().is_empty());
+/// }
+/// ```
 #[derive(Debug, Deserialize, Serialize)]
 pub struct PaletteResponse {
     /// Indicates if the API request was successful.
@@ -594,13 +3444,118 @@ pub struct PaletteResponse {
     /// The total number of pages available for the query.
     pub total_pages: Option<u32>,
     /// An optional vector of [`Palette`] objects. It will be `None` if no palettes were found.
+    ///
+    /// The API sometimes encodes an empty result as `{}` or `false`, which
+    /// are read as `None` too.
+    #[serde(default, deserialize_with = "lenient::option_list")]
+    pub palettes: Option<Vec<Palette>>,
+}
+
+/// The response of a palette query spanning several blocks.
+///
+/// The API only accepts one block per request, so multi-block queries such as
+/// [`BlockPalettesClient::get_palettes`] make one request per block and filter
+/// the results locally. The exact number of matching palettes across all
+/// pages is unknown without fetching every page; `total_results` and
+/// `total_pages` are upper bounds derived from the per-block totals in
+/// `upstream_totals`.
+///
+/// It has the same fields as a [`PaletteResponse`], which it converts into.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MultiBlockPaletteResponse {
+    /// Indicates if the API requests were successful.
+    pub success: bool,
+    /// An upper bound on the number of matching palettes across all pages.
+    pub total_results: u32,
+    /// An upper bound on the number of pages of matching palettes.
+    pub total_pages: Option<u32>,
+    /// The matching palettes of the requested page, without duplicates.
+    #[serde(default, deserialize_with = "lenient::option_list")]
     pub palettes: Option<Vec<Palette>>,
+    /// The number of palettes in `palettes`, after filtering.
+    pub filtered_count: u32,
+    /// The totals reported by the API for each individual block request.
+    pub upstream_totals: Vec<UpstreamTotal>,
+}
+
+/// The totals reported by the API for a single block of a multi-block query.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct UpstreamTotal {
+    /// The block the request was filtered by.
+    pub block: String,
+    /// The total number of palettes containing the block.
+    pub total_results: u32,
+    /// The total number of pages of palettes containing the block.
+    pub total_pages: u32,
+}
+
+impl PaletteResponse {
+    /// Summarizes the palettes of the response: their number, likes, date
+    /// range and most used blocks, see [`stats::summarize`].
+    ///
+    /// Only the palettes of this page are summarized, not every result of
+    /// the query.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use blockpalettes_client::PaletteResponse;
+    ///
+    /// let json = r#"{"success":true,"total_results":0,"total_pages":0,"palettes":[]}"#;
+    /// let response: PaletteResponse = serde_json::from_str(json).unwrap();
+    /// assert_eq!(response.summary().to_string(), "0 palettes");
+    /// ```
+    pub fn summary(&self) -> stats::Summary {
+        stats::summarize(self.palettes.as_deref().unwrap_or_default())
+    }
+}
+
+impl From<MultiBlockPaletteResponse> for PaletteResponse {
+    fn from(response: MultiBlockPaletteResponse) -> Self {
+        PaletteResponse {
+            success: response.success,
+            total_results: response.total_results,
+            total_pages: response.total_pages,
+            palettes: response.palettes,
+        }
+    }
 }
 
 /// Represents a single palette returned by the Block Palettes API.
 ///
 /// This struct contains core information about a palette, including its ID,
 /// associated blocks, likes, and creation date.
+///
+/// Like the other models, it converts from a `serde_json::Value` with
+/// [`TryFrom`]:
+///
+/// ```rust
+/// use blockpalettes_client::Palette;
+///
+/// let value = serde_json::json!({
+///     "id": 1, "user_id": 2, "date": "2023-01-01 12:00:00", "likes": 10,
+///     "blockOne": "stone", "blockTwo": "dirt", "blockThree": "grass_block",
+///     "blockFour": "oak_log", "blockFive": "cobblestone", "blockSix": "sand",
+///     "hidden": 0, "featured": 0, "hash": null, "time_ago": "1 year ago"
+/// });
+/// let palette = Palette::try_from(value).unwrap();
+/// assert_eq!(palette.blocks()[0], "stone");
+///
+/// assert!(Palette::try_from(serde_json::json!({ "id": 1 })).is_err());
+/// ```
+///
+/// # Block order
+///
+/// The six block slots keep the order the creator chose, which is the order
+/// the site displays them in, starting with `block_one`. The order is part of
+/// the palette: two palettes with the same blocks in different slots are
+/// different palettes, for `==` as for the site, and every method returning
+/// blocks returns them in slot order. Order-insensitive comparisons, such as
+/// [`diff::PaletteDiff::is_reordered`] and the
+/// [`MatchKind::Reordered`](analysis::MatchKind::Reordered) matches of
+/// [`analysis::possible_duplicates`], say so explicitly; use
+/// [`canonicalized`](Self::canonicalized) to compare blocks regardless of
+/// their order elsewhere.
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct Palette {
     /// The unique identifier for the palette.
@@ -677,6 +3632,216 @@ impl Palette {
         ]
     }
 
+    /// Returns a copy of the palette with its blocks in a canonical order, for
+    /// comparisons that should ignore block order.
+    ///
+    /// Block names are trimmed and stripped of the `minecraft:` namespace,
+    /// then sorted alphabetically, so two palettes with the same blocks in
+    /// any order and any naming canonicalize to the same blocks. The other
+    /// fields are unchanged. Keep the original palette for display, since its
+    /// order is the creator's (see [Block order](Palette#block-order)).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # let palette = |id, blocks: [&str; 6]| blockpalettes_client::Palette {
+    /// #    id, user_id: 1, date: "2023-01-01 12:00:00".to_string(), likes: 10,
+    /// #    block_one: blocks[0].to_string(), block_two: blocks[1].to_string(),
+    /// #    block_three: blocks[2].to_string(), block_four: blocks[3].to_string(),
+    /// #    block_five: blocks[4].to_string(), block_six: blocks[5].to_string(),
+    /// #    hidden: Some(0), featured: Some(0), hash: None, time_ago: String::new()
+    /// # };
+    /// let original = palette(1, ["stone", "andesite", "oak_planks", "spruce_log", "moss_block", "dirt"]);
+    /// let reordered = palette(1, ["minecraft:dirt", "stone", "andesite", "oak_planks", "spruce_log", "moss_block"]);
+    /// assert_ne!(original, reordered);
+    /// assert_eq!(original.canonicalized(), reordered.canonicalized());
+    /// assert_eq!(
+    ///     original.canonicalized().blocks(),
+    ///     ["andesite", "dirt", "moss_block", "oak_planks", "spruce_log", "stone"]
+    /// );
+    /// ```
+    pub fn canonicalized(&self) -> Palette {
+        let mut blocks = self.blocks().map(|block| {
+            let block = block.trim();
+            block
+                .strip_prefix("minecraft:")
+                .unwrap_or(block)
+                .to_string()
+        });
+        blocks.sort_unstable();
+        let [
+            block_one,
+            block_two,
+            block_three,
+            block_four,
+            block_five,
+            block_six,
+        ] = blocks;
+        Palette {
+            block_one,
+            block_two,
+            block_three,
+            block_four,
+            block_five,
+            block_six,
+            ..self.clone()
+        }
+    }
+
+    /// Returns the six block names of the palette as borrowed strings, in display order.
+    ///
+    /// Unlike [`name`](Self::name), this does not allocate.
+    pub fn blocks(&self) -> [&str; 6] {
+        [
+            &self.block_one,
+            &self.block_two,
+            &self.block_three,
+            &self.block_four,
+            &self.block_five,
+            &self.block_six,
+        ]
+    }
+
+    /// Returns the six block names of the palette written in `format`, in
+    /// display order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use blockpalettes_client::blocks::NameFormat;
+    /// # let palette = blockpalettes_client::Palette {
+    /// #    id: 1, user_id: 1, date: "2023-01-01 12:00:00".to_string(), likes: 10,
+    /// #    block_one: "stone".to_string(), block_two: "dirt".to_string(),
+    /// #    block_three: "grass_block".to_string(), block_four: "oak_log".to_string(),
+    /// #    block_five: "cobblestone".to_string(), block_six: "sand".to_string(),
+    /// #    hidden: Some(0), featured: Some(0), hash: None, time_ago: "1 day ago".to_string()
+    /// # };
+    /// assert_eq!(palette.blocks_as(NameFormat::Namespaced)[2], "minecraft:grass_block");
+    /// assert_eq!(palette.blocks_as(NameFormat::TitleCase)[3], "Oak Log");
+    /// ```
+    pub fn blocks_as(&self, format: blocks::NameFormat) -> [String; 6] {
+        self.blocks().map(|block| format.apply(block))
+    }
+
+    /// Checks if the palette satisfies every [`BlockQuery`].
+    ///
+    /// Exact block queries behave like [`contains_all_blocks`](Self::contains_all_blocks);
+    /// group queries require at least one block of the group.
+    pub fn matches_all(&self, queries: &[BlockQuery<'_>]) -> bool {
+        queries.iter().all(|query| query.matches(self))
+    }
+
+    /// Checks if every block of the palette is a full cube, rather than a
+    /// slab, stairs, a door or another partial block (see [`blocks::shape`]).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use blockpalettes_client::Palette;
+    /// # let mut palette = Palette {
+    /// #    id: 1, user_id: 1, date: "2023-01-01 12:00:00".to_string(), likes: 10,
+    /// #    block_one: "stone".to_string(), block_two: "dirt".to_string(),
+    /// #    block_three: "grass_block".to_string(), block_four: "oak_log".to_string(),
+    /// #    block_five: "cobblestone".to_string(), block_six: "sand".to_string(),
+    /// #    hidden: Some(0), featured: Some(0), hash: None, time_ago: "1 day ago".to_string()
+    /// # };
+    /// assert!(palette.has_only_full_blocks());
+    ///
+    /// palette.block_six = "oak_door".to_string();
+    /// assert!(!palette.has_only_full_blocks());
+    /// ```
+    pub fn has_only_full_blocks(&self) -> bool {
+        self.blocks()
+            .iter()
+            .all(|block| blocks::shape(block).is_full())
+    }
+
+    /// Returns how obtainable the palette's blocks are in `mode`, from `1.0`
+    /// when every block is [common](blocks::Obtainability::Common) down to
+    /// `0.0` when one of them cannot be obtained at all.
+    ///
+    /// A palette is only as obtainable as its hardest block, so the score is
+    /// the lowest [`Obtainability::score`](blocks::Obtainability::score) of
+    /// its blocks (see [`blocks::obtainability`]). In
+    /// [`GameMode::Creative`](blocks::GameMode::Creative), every palette
+    /// scores `1.0`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use blockpalettes_client::Palette;
+    /// use blockpalettes_client::blocks::GameMode;
+    ///
+    /// # let mut palette = Palette {
+    /// #    id: 1, user_id: 1, date: "2023-01-01 12:00:00".to_string(), likes: 10,
+    /// #    block_one: "stone".to_string(), block_two: "dirt".to_string(),
+    /// #    block_three: "grass_block".to_string(), block_four: "oak_log".to_string(),
+    /// #    block_five: "cobblestone".to_string(), block_six: "sand".to_string(),
+    /// #    hidden: Some(0), featured: Some(0), hash: None, time_ago: "1 day ago".to_string()
+    /// # };
+    /// assert_eq!(palette.obtainability_score(GameMode::Survival), 1.0);
+    ///
+    /// palette.block_six = "purpur_block".to_string();
+    /// assert_eq!(palette.obtainability_score(GameMode::Survival), 0.25);
+    /// assert_eq!(palette.obtainability_score(GameMode::Creative), 1.0);
+    /// ```
+    pub fn obtainability_score(&self, mode: blocks::GameMode) -> f64 {
+        match mode {
+            blocks::GameMode::Creative => 1.0,
+            blocks::GameMode::Survival => self
+                .blocks()
+                .into_iter()
+                .map(|block| blocks::obtainability(block).score())
+                .fold(1.0, f64::min),
+        }
+    }
+
+    /// Returns the most recent Minecraft version among the versions its
+    /// blocks were introduced in (see [`blocks::introduced_in`]), i.e. the
+    /// oldest version the palette can be built in.
+    ///
+    /// Returns `None` if the version of any block is unknown.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use blockpalettes_client::Palette;
+    /// use blockpalettes_client::blocks::GameVersion;
+    ///
+    /// # let mut palette = Palette {
+    /// #    id: 1, user_id: 1, date: "2023-01-01 12:00:00".to_string(), likes: 10,
+    /// #    block_one: "stone".to_string(), block_two: "dirt".to_string(),
+    /// #    block_three: "grass_block".to_string(), block_four: "oak_log".to_string(),
+    /// #    block_five: "cobblestone".to_string(), block_six: "sand".to_string(),
+    /// #    hidden: Some(0), featured: Some(0), hash: None, time_ago: "1 day ago".to_string()
+    /// # };
+    /// palette.block_six = "deepslate_tiles".to_string();
+    /// assert_eq!(palette.newest_block_version(), Some(GameVersion::new(17, 0)));
+    ///
+    /// // palettes buildable in 1.18
+    /// let buildable = |p: &Palette| p.newest_block_version().is_some_and(|v| v <= GameVersion::new(18, 0));
+    /// assert!(buildable(&palette));
+    ///
+    /// palette.block_six = "cherry_planks".to_string();
+    /// assert!(!buildable(&palette));
+    ///
+    /// palette.block_six = "oak_door".to_string();
+    /// assert_eq!(palette.newest_block_version(), None);
+    /// ```
+    pub fn newest_block_version(&self) -> Option<blocks::GameVersion> {
+        self.blocks()
+            .into_iter()
+            .map(blocks::introduced_in)
+            .try_fold(blocks::GameVersion::new(0, 0), |newest, version| {
+                Some(newest.max(version?))
+            })
+    }
+
+    /// Checks if the palette satisfies at least one [`BlockQuery`].
+    pub fn matches_any(&self, queries: &[BlockQuery<'_>]) -> bool {
+        queries.iter().any(|query| query.matches(self))
+    }
+
     /// Checks if the palette contains all the specified blocks.
     ///
     /// This method is useful for client-side filtering of palettes.
@@ -706,16 +3871,31 @@ impl Palette {
     /// assert!(!palette.contains_all_blocks(&["stone", "diamond_block"]));
     /// ```
     pub fn contains_all_blocks(&self, blocks: &[&str]) -> bool {
-        let palette_blocks: HashSet<&str> = HashSet::from([
-            self.block_one.as_str(),
-            self.block_two.as_str(),
-            self.block_three.as_str(),
-            self.block_four.as_str(),
-            self.block_five.as_str(),
-            self.block_six.as_str(),
-        ]);
+        let palette_blocks = self.blocks();
+        blocks.iter().all(|b| palette_blocks.contains(b))
+    }
 
-        blocks.iter().all(|&b| palette_blocks.contains(b))
+    /// Encodes the palette in the compact, shareable [`PaletteString`] format.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use blockpalettes_client::{Palette, PaletteString};
+    /// # let palette = Palette {
+    /// #    id: 1234, user_id: 1, date: "2023-01-01 12:00:00".to_string(), likes: 10,
+    /// #    block_one: "stone".to_string(), block_two: "dirt".to_string(),
+    /// #    block_three: "oak_log".to_string(), block_four: "cobblestone".to_string(),
+    /// #    block_five: "sand".to_string(), block_six: "gravel".to_string(),
+    /// #    hidden: Some(0), featured: Some(0), hash: None, time_ago: "1 day ago".to_string()
+    /// # };
+    /// let shared = palette.to_palette_string();
+    /// assert_eq!(shared, "bp:stone,dirt,oak_log,cobblestone,sand,gravel#id=1234");
+    ///
+    /// let parsed = PaletteString::parse(&shared).unwrap();
+    /// assert_eq!(parsed, PaletteString::from(&palette));
+    /// ```
+    pub fn to_palette_string(&self) -> String {
+        PaletteString::from(self).to_string()
     }
 
     /// Parses the `date` string of the palette into a `NaiveDateTime` object.
@@ -743,10 +3923,66 @@ impl Palette {
     /// let datetime = palette.parse_date().unwrap();
     /// assert_eq!(datetime.date(), NaiveDate::from_ymd_opt(2023, 1, 1).unwrap());
     /// assert_eq!(datetime.hour(), 12);
+    ///
+    /// // the offending input and the underlying cause are kept
+    /// use std::error::Error;
+    /// let mut broken = palette.clone();
+    /// broken.date = "yesterday".to_string();
+    /// let error = broken.parse_date().unwrap_err();
+    /// assert!(error.to_string().contains("`yesterday`"));
+    /// assert!(error.source().is_some());
     /// ```
     pub fn parse_date(&self) -> Result<NaiveDateTime> {
-        NaiveDateTime::parse_from_str(&self.date, "%Y-%m-%d %H:%M:%S")
-            .map_err(|_| BlockPalettesError::InvalidDateFormat)
+        NaiveDateTime::parse_from_str(&self.date, "%Y-%m-%d %H:%M:%S").map_err(|source| {
+            BlockPalettesError::InvalidDateFormat {
+                input: self.date.clone(),
+                source,
+            }
+        })
+    }
+
+    /// Returns the average number of likes per day since the palette was created.
+    ///
+    /// Unlike raw likes, this does not favor old palettes, which makes it a
+    /// better "hot right now" signal. Palettes younger than a day are treated
+    /// as one day old, so brand-new palettes with a single like do not
+    /// dominate. The age is measured against the current UTC time; use
+    /// [`likes_velocity_at`](Self::likes_velocity_at) to pick the reference time.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BlockPalettesError::InvalidDateFormat`] if the date cannot be parsed.
+    pub fn likes_velocity(&self) -> Result<f64> {
+        self.likes_velocity_at(chrono::Utc::now().naive_utc())
+    }
+
+    /// Returns the average number of likes per day between the palette's
+    /// creation and `now`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BlockPalettesError::InvalidDateFormat`] if the date cannot be parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use blockpalettes_client::Palette;
+    /// # let palette = Palette {
+    /// #    id: 1, user_id: 1, date: "2023-01-01 12:00:00".to_string(), likes: 30,
+    /// #    block_one: "stone".to_string(), block_two: "dirt".to_string(),
+    /// #    block_three: "grass_block".to_string(), block_four: "oak_log".to_string(),
+    /// #    block_five: "cobblestone".to_string(), block_six: "sand".to_string(),
+    /// #    hidden: Some(0), featured: Some(0), hash: None, time_ago: "1 day ago".to_string()
+    /// # };
+    /// use chrono::NaiveDateTime;
+    ///
+    /// let now = NaiveDateTime::parse_from_str("2023-01-11 12:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+    /// assert_eq!(palette.likes_velocity_at(now).unwrap(), 3.0);
+    /// ```
+    pub fn likes_velocity_at(&self, now: NaiveDateTime) -> Result<f64> {
+        let age = now.signed_duration_since(self.parse_date()?);
+        let days = (age.num_seconds() as f64 / 86_400.0).max(1.0);
+        Ok(f64::from(self.likes) / days)
     }
 }
 
@@ -754,7 +3990,7 @@ impl Palette {
 ///
 /// This struct is typically returned by the [`BlockPalettesClient::get_palette_details`] method.
 /// It extends the basic [`Palette`] information with the `username` of the creator.
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct PaletteDetails {
     /// The unique identifier for the palette.
     pub id: u64,
@@ -796,17 +4032,126 @@ pub struct PaletteDetails {
     pub time_ago: String,
 }
 
+/// Represents a palette creator ranked by [`BlockPalettesClient::get_top_users`].
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct TopUser {
+    /// The ID of the user.
+    pub user_id: u64,
+    /// The username of the user.
+    pub username: String,
+    /// The number of the user's palettes among the sampled popular palettes.
+    pub palette_count: u32,
+    /// The total likes of the user's sampled palettes.
+    pub total_likes: u64,
+}
+
+/// A palette creator, assembled by [`BlockPalettesClient::get_user`].
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct User {
+    /// The ID of the user.
+    pub id: u64,
+    /// The username of the user.
+    pub username: String,
+    /// The IDs of the user's palettes, in the order of their profile page.
+    pub palette_ids: Vec<u64>,
+    /// The total likes of the user's palettes.
+    pub total_likes: u64,
+}
+
 /// Represents details scraped directly from a palette's HTML page.
 ///
 /// This struct is typically returned by the [`BlockPalettesClient::scrape_palette_page`] method.
 /// It contains information extracted by parsing the HTML, which might include
-/// blocks displayed on the page and IDs of similar palettes linked.
+/// blocks displayed on the page and similar palettes linked.
+///
+/// Every field is `None` when the page did not show it, so a section that
+/// could not be found is never mistaken for an empty one. Why a field is
+/// missing is reported in `warnings`.
 ///
 /// [`BlockPalettesClient::scrape_palette_page`]: struct.BlockPalettesClient.html#method.scrape_palette_page
 #[derive(Debug, Serialize)]
 pub struct PalettePageDetails {
-    /// A list of block names found on the palette's page.
+    /// The block names shown on the palette's page, or `None` if the page has
+    /// no block list.
+    pub blocks: Option<Vec<String>>,
+    /// The similar palettes linked on the page, with whatever their cards
+    /// show, or `None` if the page links none.
+    pub similar_palettes: Option<Vec<PaletteCard>>,
+    /// Problems noticed while scraping the page.
+    ///
+    /// An empty list means the page looked as expected.
+    pub warnings: Vec<scrape::ScrapeWarning>,
+}
+
+impl PalettePageDetails {
+    /// Returns the IDs of the similar palettes linked on the page, if any.
+    pub fn similar_palette_ids(&self) -> Vec<u64> {
+        self.similar_palettes
+            .iter()
+            .flatten()
+            .map(|card| card.id)
+            .collect()
+    }
+}
+
+/// One page of a block's gallery, scraped by
+/// [`BlockPalettesClient::scrape_block_page`].
+#[derive(Debug, Serialize)]
+pub struct BlockPageDetails {
+    /// The block whose gallery was scraped, without the `minecraft:` namespace.
+    pub block: String,
+    /// The page number.
+    pub page: u32,
+    /// The palettes shown on the page, in display order.
+    pub palettes: Vec<PaletteCard>,
+    /// Problems noticed while scraping the page.
+    ///
+    /// An empty list means the page looked as expected.
+    pub warnings: Vec<scrape::ScrapeWarning>,
+}
+
+/// The summary of a palette shown on a gallery card.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaletteCard {
+    /// The ID of the palette.
+    pub id: u64,
+    /// The blocks shown on the card, in display order. Empty if the card shows
+    /// no block names.
     pub blocks: Vec<String>,
-    /// A list of IDs of similar palettes linked on the page.
-    pub similar_palette_ids: Vec<u64>,
+    /// The number of likes, if the card shows it.
+    pub likes: Option<u32>,
+    /// The visible text of the card's link, with whitespace collapsed, if it
+    /// has any.
+    #[serde(default)]
+    pub text: Option<String>,
+}
+
+/// Implements `TryFrom<serde_json::Value>` for models, so data received as
+/// untyped JSON (e.g., a field of another service's payload) converts without
+/// going through a string.
+macro_rules! impl_try_from_json {
+    ($($model:ty),* $(,)?) => {
+        $(
+            impl TryFrom<serde_json::Value> for $model {
+                type Error = BlockPalettesError;
+
+                fn try_from(value: serde_json::Value) -> Result<Self> {
+                    Ok(serde_json::from_value(value)?)
+                }
+            }
+        )*
+    };
 }
+
+impl_try_from_json!(
+    PopularBlock,
+    PaletteResponse,
+    MultiBlockPaletteResponse,
+    UpstreamTotal,
+    Palette,
+    PaletteDetails,
+    TopUser,
+    User,
+    PaletteCard,
+    SortOrder,
+);
@@ -0,0 +1,235 @@
+//! The limits of the Block Palettes API.
+//!
+//! The API does not report invalid parameters: asking
+//! `/api/palettes/all_palettes.php` for more than [`MAX_PAGE_LIMIT`] palettes
+//! quietly returns a page of [`DEFAULT_PAGE_LIMIT`] palettes instead, which
+//! breaks paging for anyone who trusted the `limit` they sent. The client
+//! checks requests against a [`Limits`] before sending them:
+//!
+//! - methods paging on their own, such as
+//!   [`walk_palettes`](crate::BlockPalettesClient::walk_palettes), clamp
+//!   their page size to the limit;
+//! - methods taking an explicit page, such as
+//!   [`get_palettes`](crate::BlockPalettesClient::get_palettes), return a
+//!   [`BlockPalettesError::Validation`] error, since a clamped page would not
+//!   line up with the pages the caller expects;
+//! - block searches longer than [`MAX_BLOCK_QUERY_LEN`] return a
+//!   [`BlockPalettesError::Validation`] error;
+//! - block searches returning [`MAX_BLOCK_SEARCH_RESULTS`] blocks are
+//!   reported as possibly truncated.
+//!
+//! The constants are the limits of the live site. [`Limits::probe`] measures
+//! them on a server, for mirrors or when the site changes, and
+//! [`BlockPalettesClient::with_limits`] makes a client use the result.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use blockpalettes_client::limits::Limits;
+//! use blockpalettes_client::{BlockPalettesClient, BlockPalettesError, SortOrder};
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let client = BlockPalettesClient::new(reqwest::Client::new());
+//!     let result = client.get_palettes(&[], SortOrder::Recent, 1, 500).await;
+//!     assert!(matches!(result, Err(BlockPalettesError::Validation(_))));
+//!
+//!     let limits = Limits::probe(&client).await?;
+//!     println!("pages of up to {} palettes", limits.max_page_limit);
+//!     let client = client.with_limits(limits);
+//!     Ok(())
+//! }
+//! ```
+
+use crate::blocks;
+use crate::ranking::Ranking;
+use crate::validation::Validators;
+use crate::{BlockPalettesClient, BlockPalettesError, Result, SortOrder};
+
+/// The largest `limit` the site honors when listing palettes.
+pub const MAX_PAGE_LIMIT: u32 = 100;
+
+/// The number of palettes the site returns when `limit` is missing or larger
+/// than [`MAX_PAGE_LIMIT`].
+pub const DEFAULT_PAGE_LIMIT: u32 = 20;
+
+/// The longest block search query the client sends, in bytes.
+///
+/// The longest block IDs are around 40 characters, so longer queries cannot
+/// match anything.
+pub const MAX_BLOCK_QUERY_LEN: usize = 64;
+
+/// The most blocks the site returns for a block search.
+///
+/// A search returning this many blocks may have matched more; see
+/// [`BlockSearchPage`](crate::BlockSearchPage).
+pub const MAX_BLOCK_SEARCH_RESULTS: usize = 25;
+
+/// The largest page size and query length tried by [`Limits::probe`].
+const PROBE_CEILING: u32 = 1000;
+
+/// The limits a client checks requests against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Limits {
+    /// The largest `limit` honored when listing palettes.
+    pub max_page_limit: u32,
+    /// The longest block search query, in bytes.
+    pub max_block_query_len: usize,
+    /// The most blocks returned by a block search.
+    pub max_block_search_results: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self::SITE
+    }
+}
+
+impl Limits {
+    /// The limits of the live site.
+    pub const SITE: Self = Self {
+        max_page_limit: MAX_PAGE_LIMIT,
+        max_block_query_len: MAX_BLOCK_QUERY_LEN,
+        max_block_search_results: MAX_BLOCK_SEARCH_RESULTS,
+    };
+
+    /// Measures the limits of the server `client` talks to.
+    ///
+    /// The page limit is found by binary search between
+    /// [`DEFAULT_PAGE_LIMIT`] and 1000, listing recent palettes with
+    /// increasing limits and checking whether the page is as large as
+    /// requested. A page cannot be larger than the whole listing, so on
+    /// servers with fewer palettes than the limit, only limits up to the
+    /// number of palettes can be checked: the detected page limit is then the
+    /// number of palettes (or [`DEFAULT_PAGE_LIMIT`] if it is smaller), a
+    /// lower bound of the real one. The query length is the longest block search, up to 1000
+    /// bytes, the server answers successfully. The search result limit is
+    /// detected by searching `a` and `e`: if both return the same number of
+    /// blocks, fewer than the block registry (see
+    /// [`blocks::colored_blocks`]) has matches for, that number is the
+    /// limit; otherwise searches are assumed to be complete. Probing sends
+    /// about twenty requests, some of them for large pages, so do it once and
+    /// keep the result rather than on every start.
+    ///
+    /// Validators and ranking of `client` are not applied to the probing
+    /// requests.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the first listing if the server cannot be
+    /// reached.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "test-server")]
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use blockpalettes_client::GetPalettesOptions;
+    /// use blockpalettes_client::limits::Limits;
+    /// use blockpalettes_client::test_server::TestServer;
+    ///
+    /// let server = TestServer::start().await?;
+    /// let client = server.client();
+    /// let limits = Limits::probe(&client).await?;
+    ///
+    /// // the test server has fewer palettes than its page limit
+    /// let listing = client.list_palettes(&[], &GetPalettesOptions::default()).await?;
+    /// assert_eq!(limits.max_page_limit, listing.total_results);
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(feature = "test-server"))]
+    /// # fn main() {}
+    /// ```
+    pub async fn probe(client: &BlockPalettesClient) -> Result<Self> {
+        let mut probe = client.clone().with_limits(Self {
+            max_page_limit: u32::MAX,
+            max_block_query_len: usize::MAX,
+            max_block_search_results: usize::MAX,
+        });
+        probe.validators = Validators::new();
+        probe.ranking = Ranking::new();
+
+        let first = probe
+            .fetch_palette_page(&SortOrder::Recent, 1, DEFAULT_PAGE_LIMIT, None)
+            .await?;
+        let total = first.total_results;
+        let honored = |limit: u32| {
+            let probe = &probe;
+            async move {
+                probe
+                    .fetch_palette_page(&SortOrder::Recent, 1, limit, None)
+                    .await
+                    .is_ok_and(|page| {
+                        let len = page.palettes.map_or(0, |p| p.len());
+                        u32::try_from(len).is_ok_and(|len| len == limit)
+                    })
+            }
+        };
+        // larger pages would be cut short by the end of the listing
+        let (mut low, mut high) = (DEFAULT_PAGE_LIMIT, PROBE_CEILING.min(total));
+        while low < high {
+            let mid = low + (high - low).div_ceil(2);
+            if honored(mid).await {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+        let max_page_limit = low;
+
+        let (mut low, mut high) = (1, PROBE_CEILING as usize);
+        while low < high {
+            let mid = low + (high - low).div_ceil(2);
+            if probe.search_blocks("a".repeat(mid)).await.is_ok() {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+
+        let max_block_query_len = low;
+
+        let mut counts = Vec::new();
+        for letter in ["a", "e"] {
+            let found = probe.search_blocks(letter).await?.len();
+            let known = blocks::colored_blocks()
+                .filter(|(name, _)| name.contains(letter))
+                .count();
+            counts.push((found < known).then_some(found));
+        }
+        let max_block_search_results = match counts[..] {
+            [Some(a), Some(e)] if a == e => a,
+            _ => usize::MAX,
+        };
+
+        Ok(Self {
+            max_page_limit,
+            max_block_query_len,
+            max_block_search_results,
+        })
+    }
+
+    /// Returns an error if `limit` is larger than the page limit.
+    pub(crate) fn check_page_limit(&self, limit: u32) -> Result<()> {
+        if limit > self.max_page_limit {
+            return Err(BlockPalettesError::Validation(format!(
+                "limit {limit} exceeds the maximum page size of {}",
+                self.max_page_limit
+            )));
+        }
+        Ok(())
+    }
+
+    /// Returns an error if `query` is longer than the block query limit.
+    pub(crate) fn check_block_query(&self, query: &str) -> Result<()> {
+        if query.len() > self.max_block_query_len {
+            return Err(BlockPalettesError::Validation(format!(
+                "block query of {} bytes exceeds the maximum of {}",
+                query.len(),
+                self.max_block_query_len
+            )));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,222 @@
+//! Approximate remix history of palettes.
+//!
+//! Users often start from an existing palette and swap a block or two. The
+//! site keeps no record of this, but it can be guessed from a corpus:
+//! [`detect`] links every palette to an older palette differing from it by
+//! exactly one or two blocks, and returns the resulting chains as a forest of
+//! [`LineageTree`]s, oldest palettes at the roots.
+//!
+//! The forest can be exported to Graphviz with [`Lineage::to_dot`].
+//!
+//! # Examples
+//!
+//! ```rust
+//! use blockpalettes_client::lineage;
+//! # let palette = |id, date: &str, blocks: [&str; 6]| blockpalettes_client::Palette {
+//! #    id, user_id: 1, date: date.to_string(), likes: 10,
+//! #    block_one: blocks[0].to_string(), block_two: blocks[1].to_string(),
+//! #    block_three: blocks[2].to_string(), block_four: blocks[3].to_string(),
+//! #    block_five: blocks[4].to_string(), block_six: blocks[5].to_string(),
+//! #    hidden: Some(0), featured: Some(0), hash: None, time_ago: String::new()
+//! # };
+//! let corpus = [
+//!     palette(3, "2023-03-01 12:00:00", ["stone", "andesite", "birch_planks", "spruce_log", "moss_block", "gravel"]),
+//!     palette(1, "2023-01-01 12:00:00", ["stone", "andesite", "oak_planks", "spruce_log", "moss_block", "dirt"]),
+//!     palette(2, "2023-02-01 12:00:00", ["stone", "andesite", "birch_planks", "spruce_log", "moss_block", "dirt"]),
+//!     palette(4, "2023-04-01 12:00:00", ["sand", "sandstone", "cut_sandstone", "birch_log", "terracotta", "bone_block"]),
+//! ];
+//!
+//! let lineage = lineage::detect(&corpus);
+//! assert_eq!(lineage.trees.len(), 1);
+//! let root = &lineage.trees[0];
+//! assert_eq!(root.palette, 1);
+//! assert_eq!(root.children[0].palette, 2);
+//! assert_eq!(root.children[0].children[0].palette, 3);
+//! assert_eq!(lineage.parent(3), Some(2));
+//! assert_eq!((root.palette_count(), root.depth()), (3, 3));
+//!
+//! assert_eq!(
+//!     lineage.to_dot(),
+//!     "digraph lineage {\n    \"1\" -> \"2\" [label=\"1\"];\n    \"2\" -> \"3\" [label=\"1\"];\n}\n"
+//! );
+//! ```
+
+use crate::Palette;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+/// The largest number of changed blocks between a palette and its parent.
+const MAX_BLOCK_CHANGES: usize = 2;
+
+/// A palette and the palettes presumably remixed from it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LineageTree {
+    /// The ID of the palette.
+    pub palette: u64,
+    /// When the palette was added, if its date could be parsed.
+    pub date: Option<NaiveDateTime>,
+    /// How many blocks differ from the parent palette: `1` or `2`, or `0`
+    /// for the root of a tree.
+    pub block_changes: usize,
+    /// The palettes remixed from this one, oldest first.
+    pub children: Vec<LineageTree>,
+}
+
+impl LineageTree {
+    /// Returns the number of palettes in the tree, including its root.
+    pub fn palette_count(&self) -> usize {
+        1 + self
+            .children
+            .iter()
+            .map(LineageTree::palette_count)
+            .sum::<usize>()
+    }
+
+    /// Returns the length of the longest chain of remixes from the root.
+    pub fn depth(&self) -> usize {
+        self.children
+            .iter()
+            .map(LineageTree::depth)
+            .max()
+            .unwrap_or(0)
+            + 1
+    }
+}
+
+/// A forest of palette remix chains, as built by [`detect`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lineage {
+    /// The trees of linked palettes, by date of their root. Palettes linked
+    /// to no other palette are left out.
+    pub trees: Vec<LineageTree>,
+}
+
+impl Lineage {
+    /// Returns the ID of the palette `id` was presumably remixed from.
+    pub fn parent(&self, id: u64) -> Option<u64> {
+        fn find(tree: &LineageTree, id: u64) -> Option<u64> {
+            tree.children.iter().find_map(|child| {
+                if child.palette == id {
+                    Some(tree.palette)
+                } else {
+                    find(child, id)
+                }
+            })
+        }
+        self.trees.iter().find_map(|tree| find(tree, id))
+    }
+
+    /// Renders the forest as a Graphviz DOT digraph, with an edge from each
+    /// parent to its children labeled with the number of changed blocks.
+    pub fn to_dot(&self) -> String {
+        fn edges(tree: &LineageTree, out: &mut String) {
+            for child in &tree.children {
+                let _ = writeln!(
+                    out,
+                    "    \"{}\" -> \"{}\" [label=\"{}\"];",
+                    tree.palette, child.palette, child.block_changes
+                );
+                edges(child, out);
+            }
+        }
+
+        let mut out = String::from("digraph lineage {\n");
+        for tree in &self.trees {
+            edges(tree, &mut out);
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+/// Links the palettes of `corpus` differing by one or two blocks into remix
+/// chains.
+///
+/// Palettes are visited by date, oldest first (ties and unparseable dates are
+/// ordered by ID, unparseable dates last). Each palette becomes the child of
+/// the older palette with the fewest changed blocks, ignoring their order;
+/// among equally close ones, the most recent is picked, so chains of small
+/// edits are kept rather than fanning out from the first palette. Palettes
+/// with the same blocks as an older one are not linked, since they are
+/// duplicates rather than remixes. Duplicate IDs are only counted once.
+///
+/// Every pair of palettes is compared, so this is quadratic in the size of
+/// the corpus.
+pub fn detect(corpus: &[Palette]) -> Lineage {
+    let mut seen = HashSet::new();
+    let mut palettes: Vec<(u64, Option<NaiveDateTime>, [&str; 6])> = corpus
+        .iter()
+        .filter(|palette| seen.insert(palette.id))
+        .map(|palette| {
+            let mut blocks = palette.blocks();
+            blocks.sort_unstable();
+            (palette.id, palette.parse_date().ok(), blocks)
+        })
+        .collect();
+    palettes.sort_by_key(|(id, date, _)| (date.is_none(), *date, *id));
+
+    // parent index and changed blocks of every linked palette
+    let mut parents: Vec<Option<(usize, usize)>> = vec![None; palettes.len()];
+    for (index, (_, _, blocks)) in palettes.iter().enumerate() {
+        parents[index] = palettes[..index]
+            .iter()
+            .enumerate()
+            .rev()
+            .map(|(older, (_, _, other))| (older, block_changes(blocks, other)))
+            .filter(|(_, changes)| (1..=MAX_BLOCK_CHANGES).contains(changes))
+            .min_by_key(|(_, changes)| *changes);
+    }
+
+    let mut children: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (index, parent) in parents.iter().enumerate() {
+        if let Some((parent, _)) = parent {
+            children.entry(*parent).or_default().push(index);
+        }
+    }
+
+    fn build(
+        index: usize,
+        palettes: &[(u64, Option<NaiveDateTime>, [&str; 6])],
+        parents: &[Option<(usize, usize)>],
+        children: &HashMap<usize, Vec<usize>>,
+    ) -> LineageTree {
+        LineageTree {
+            palette: palettes[index].0,
+            date: palettes[index].1,
+            block_changes: parents[index].map_or(0, |(_, changes)| changes),
+            children: children
+                .get(&index)
+                .into_iter()
+                .flatten()
+                .map(|&child| build(child, palettes, parents, children))
+                .collect(),
+        }
+    }
+
+    Lineage {
+        trees: (0..palettes.len())
+            .filter(|index| parents[*index].is_none() && children.contains_key(index))
+            .map(|index| build(index, &palettes, &parents, &children))
+            .collect(),
+    }
+}
+
+/// Returns how many blocks of `a` must be swapped for others to get the
+/// blocks of `b`, both sorted.
+fn block_changes(a: &[&str; 6], b: &[&str; 6]) -> usize {
+    let (mut i, mut j, mut shared) = (0, 0, 0);
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(b[j]) {
+            std::cmp::Ordering::Less => i += 1,
+            std::cmp::Ordering::Greater => j += 1,
+            std::cmp::Ordering::Equal => {
+                shared += 1;
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    a.len() - shared
+}
@@ -0,0 +1,117 @@
+//! Metadata about the HTTP requests behind a call.
+//!
+//! [`BlockPalettesClient::with_meta`](crate::BlockPalettesClient::with_meta)
+//! runs any call on the client and returns, alongside its result, one
+//! [`ResponseMeta`] per HTTP request the call made. Composite operations such
+//! as multi-block searches make several requests, so they yield several
+//! entries, in the order the requests completed.
+//!
+//! Every request also carries a [`CorrelationId`], sent to the site in the
+//! [`CORRELATION_HEADER`] header and recorded in its [`ResponseMeta`]. All the
+//! requests of one logical operation, such as a multi-block search, share the
+//! same ID; [`BlockPalettesClient::with_correlation_id`](crate::BlockPalettesClient::with_correlation_id)
+//! extends an ID over several calls and attaches it to their errors.
+
+use crate::daily;
+use reqwest::StatusCode;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The HTTP header carrying the [`CorrelationId`] of every request.
+pub const CORRELATION_HEADER: &str = "x-correlation-id";
+
+/// Identifies the HTTP requests made for one logical operation.
+///
+/// Generated IDs are 16 lowercase hexadecimal digits; IDs supplied by the
+/// application (e.g. the ID of the bot command being served) are used as-is.
+///
+/// # Examples
+///
+/// ```rust
+/// use blockpalettes_client::meta::CorrelationId;
+///
+/// let generated = CorrelationId::new();
+/// assert_eq!(generated.as_str().len(), 16);
+/// assert_ne!(generated, CorrelationId::new());
+///
+/// let supplied = CorrelationId::from("discord-cmd-1234");
+/// assert_eq!(supplied.to_string(), "discord-cmd-1234");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CorrelationId(Arc<str>);
+
+impl CorrelationId {
+    /// Generates a new, practically unique ID.
+    pub fn new() -> Self {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos() as u64);
+        let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+        let id = daily::mix(nanos ^ daily::mix(count));
+        Self(format!("{id:016x}").into())
+    }
+
+    /// Returns the ID as a string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Default for CorrelationId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for CorrelationId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<&str> for CorrelationId {
+    fn from(id: &str) -> Self {
+        Self(id.into())
+    }
+}
+
+impl From<String> for CorrelationId {
+    fn from(id: String) -> Self {
+        Self(id.into())
+    }
+}
+
+/// Metadata about a single HTTP response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResponseMeta {
+    /// The full URL that was requested, including the query string.
+    pub url: String,
+    /// The HTTP status of the response.
+    pub status: StatusCode,
+    /// The time between sending the request and reading the whole body.
+    pub latency: Duration,
+    /// Whether the response was served from the HTTP cache.
+    ///
+    /// This is always `false` unless the `http-cache` feature is enabled and a
+    /// cache is attached to the client.
+    pub from_cache: bool,
+    /// The correlation ID the request was sent with.
+    pub correlation_id: CorrelationId,
+}
+
+/// Collects [`ResponseMeta`]s for the calls made by one client instance.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct MetaRecorder(Arc<Mutex<Vec<ResponseMeta>>>);
+
+impl MetaRecorder {
+    pub(crate) fn record(&self, meta: ResponseMeta) {
+        self.0.lock().unwrap().push(meta);
+    }
+
+    pub(crate) fn take(&self) -> Vec<ResponseMeta> {
+        std::mem::take(&mut *self.0.lock().unwrap())
+    }
+}
@@ -0,0 +1,207 @@
+//! Checking a local mirror against the live site.
+//!
+//! A mirror stored as a [`dataset`](crate::dataset) archive drifts from the
+//! site over time: palettes gain likes, get hidden or are deleted. [`diff`]
+//! fetches the current details of the mirrored palettes, either all of them or
+//! a random sample within a request budget, and reports what changed, so the
+//! mirror can be patched without a full recrawl.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use blockpalettes_client::BlockPalettesClient;
+//! use blockpalettes_client::dataset::Archive;
+//! use blockpalettes_client::mirror::{self, Sampling};
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let client = BlockPalettesClient::new(reqwest::Client::new());
+//!     let archive = Archive::open("mirror.jsonl")?;
+//!
+//!     // check 500 random palettes of the mirror
+//!     let report = mirror::diff(&archive, &client, Sampling::Sample { budget: 500, seed: 42 }).await?;
+//!     println!(
+//!         "{} checked: {} likes changed, {} newly hidden, {} deleted",
+//!         report.checked,
+//!         report.likes_changed.len(),
+//!         report.newly_hidden.len(),
+//!         report.deleted.len(),
+//!     );
+//!     Ok(())
+//! }
+//! ```
+
+use crate::dataset::Archive;
+use crate::{BlockPalettesClient, BlockPalettesError, DETAILS_CONCURRENCY, Palette, Result, daily};
+use futures_util::{StreamExt, stream};
+use serde::Serialize;
+
+/// Which palettes of the mirror [`diff`] checks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sampling {
+    /// Check every palette, with one request each.
+    Exhaustive,
+    /// Check at most `budget` palettes, picked at random.
+    ///
+    /// The pick only depends on `seed` and on the IDs in the mirror, so
+    /// changing the seed between runs spreads checks over the whole mirror.
+    Sample {
+        /// The maximum number of palettes to check, i.e. of requests to send.
+        budget: usize,
+        /// The seed of the random pick.
+        seed: u64,
+    },
+}
+
+/// A palette whose number of likes changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct LikesChange {
+    /// The ID of the palette.
+    pub id: u64,
+    /// The number of likes in the mirror.
+    pub local: u32,
+    /// The number of likes on the site.
+    pub live: u32,
+}
+
+/// The differences between a mirror and the site, found by [`diff`].
+#[derive(Debug, Default)]
+pub struct MirrorDiff {
+    /// The number of palettes compared, including failed ones.
+    pub checked: usize,
+    /// The number of palettes identical in the mirror and on the site.
+    pub unchanged: usize,
+    /// Palettes whose number of likes changed.
+    pub likes_changed: Vec<LikesChange>,
+    /// IDs of palettes visible in the mirror and hidden on the site.
+    pub newly_hidden: Vec<u64>,
+    /// IDs of palettes the site no longer has.
+    pub deleted: Vec<u64>,
+    /// IDs of palettes that could not be checked, with the cause.
+    pub failed: Vec<(u64, BlockPalettesError)>,
+}
+
+impl MirrorDiff {
+    /// Returns `true` if no difference was found and every check succeeded.
+    pub fn is_clean(&self) -> bool {
+        self.likes_changed.is_empty()
+            && self.newly_hidden.is_empty()
+            && self.deleted.is_empty()
+            && self.failed.is_empty()
+    }
+}
+
+/// Compares the palettes of `archive` with their live details.
+///
+/// Palettes are fetched with
+/// [`BlockPalettesClient::get_palette_details`], at most
+/// [`DETAILS_CONCURRENCY`] at a time. A palette the API reports as not found
+/// ([`BlockPalettesError::NotFound`]) counts as deleted; any other failure is
+/// recorded in [`MirrorDiff::failed`] and does not stop the comparison.
+/// Reports list palettes by increasing ID.
+///
+/// # Errors
+///
+/// Returns an error if the archive cannot be read.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "test-server")]
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use blockpalettes_client::dataset::{self, Archive};
+/// use blockpalettes_client::mirror::{self, Sampling};
+/// use blockpalettes_client::test_server::TestServer;
+///
+/// let server = TestServer::start().await?;
+/// let client = server.client();
+/// let mut palettes = client.get_recent_palettes(2).await?;
+/// // a palette the site no longer has
+/// palettes[1].id = 999_999;
+///
+/// let path = std::env::temp_dir().join("blockpalettes-mirror-doctest.jsonl");
+/// dataset::write_archive(&path, &palettes)?;
+/// let report = mirror::diff(&Archive::open(&path)?, &client, Sampling::Exhaustive).await?;
+/// assert_eq!(report.deleted, [999_999]);
+/// assert!(report.failed.is_empty());
+/// # Ok(())
+/// # }
+/// # #[cfg(not(feature = "test-server"))]
+/// # fn main() {}
+/// ```
+pub async fn diff(
+    archive: &Archive,
+    client: &BlockPalettesClient,
+    sampling: Sampling,
+) -> Result<MirrorDiff> {
+    let mut local = match sampling {
+        Sampling::Sample { budget, seed } if budget < archive.len() => {
+            let ids: Vec<u64> = archive.ids().collect();
+            let mut palettes = Vec::with_capacity(budget);
+            for id in sample(ids, budget, seed) {
+                palettes.extend(archive.get(id)?);
+            }
+            palettes
+        }
+        _ => archive.read_all()?,
+    };
+    local.sort_by_key(|p| p.id);
+
+    let results: Vec<(Palette, Result<_>)> = stream::iter(local)
+        .map(|palette| async move {
+            let live = client.get_palette_details(palette.id).await;
+            (palette, live)
+        })
+        .buffered(DETAILS_CONCURRENCY)
+        .collect()
+        .await;
+
+    let mut report = MirrorDiff {
+        checked: results.len(),
+        ..MirrorDiff::default()
+    };
+    for (palette, live) in results {
+        let live = match live {
+            Ok(live) => live,
+            Err(error) if matches!(error.root(), BlockPalettesError::NotFound(_)) => {
+                report.deleted.push(palette.id);
+                continue;
+            }
+            Err(error) => {
+                report.failed.push((palette.id, error));
+                continue;
+            }
+        };
+
+        let mut changed = false;
+        if live.likes != palette.likes {
+            report.likes_changed.push(LikesChange {
+                id: palette.id,
+                local: palette.likes,
+                live: live.likes,
+            });
+            changed = true;
+        }
+        if live.hidden != 0 && palette.hidden.unwrap_or_default() == 0 {
+            report.newly_hidden.push(palette.id);
+            changed = true;
+        }
+        if !changed {
+            report.unchanged += 1;
+        }
+    }
+    Ok(report)
+}
+
+/// Picks `count` of `ids` at random, with a partial Fisher-Yates shuffle.
+fn sample(mut ids: Vec<u64>, count: usize, seed: u64) -> Vec<u64> {
+    let mut state = seed;
+    for i in 0..count.min(ids.len()) {
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let j = i + (daily::mix(state) % (ids.len() - i) as u64) as usize;
+        ids.swap(i, j);
+    }
+    ids.truncate(count);
+    ids
+}
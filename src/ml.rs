@@ -0,0 +1,373 @@
+//! Fixed-length palette embeddings for machine learning workflows.
+//!
+//! [`embed`] turns a palette into a vector of [`EMBEDDING_DIM`] floats, so
+//! palettes can be fed to clustering algorithms or approximate nearest
+//! neighbour indexes. [`cosine_similarity`] and [`nearest`] compare
+//! embeddings without any other library, and [`cluster`] groups a corpus
+//! into archetypes with k-means.
+//!
+//! # Embedding scheme
+//!
+//! The scheme is identified by [`EMBEDDING_VERSION`]; any change to it bumps
+//! the version, so stored embeddings can be recomputed when it changes.
+//! Version 1 lays out the vector as follows:
+//!
+//! | Range   | Features |
+//! |---------|----------|
+//! | `0..64` | Blocks: every distinct block, without namespace, adds to the bucket given by the 64-bit FNV-1a hash of its name modulo 64. The buckets are scaled to a unit norm. |
+//! | `64..67` | The mean CIE L\*a\*b\* color of the blocks of known color, as `L / 100 - 0.5`, `a / 128` and `b / 128`. |
+//! | `67..70` | The standard deviation of the same three channels. |
+//! | `70`    | The mean chroma, `sqrt(a² + b²) / 128`. |
+//! | `71`    | The lightness range, `(max L - min L) / 100`. |
+//! | `72..80` | Hue histogram: 8 bins of 45°, starting at red, each adding the saturation of the blocks whose hue falls in the bin, divided by the number of blocks of known color. |
+//!
+//! Every color feature is then multiplied by [`COLOR_WEIGHT`]. Color features
+//! are zero for palettes without any block of known color.
+//! Palettes sharing blocks are mostly close through the block features; the
+//! color features separate palettes with distinct color statistics, such as
+//! muted and saturated palettes, and bring together palettes using different
+//! variants of similar colors.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use blockpalettes_client::ml::{self, EMBEDDING_DIM};
+//! # let palette = |id, blocks: [&str; 6]| blockpalettes_client::Palette {
+//! #    id, user_id: 1, date: "2023-01-01 12:00:00".to_string(), likes: 10,
+//! #    block_one: blocks[0].to_string(), block_two: blocks[1].to_string(),
+//! #    block_three: blocks[2].to_string(), block_four: blocks[3].to_string(),
+//! #    block_five: blocks[4].to_string(), block_six: blocks[5].to_string(),
+//! #    hidden: Some(0), featured: Some(0), hash: None, time_ago: String::new()
+//! # };
+//! let corpus = [
+//!     palette(1, ["stone", "cobblestone", "andesite", "gravel", "stone_bricks", "oak_log"]),
+//!     palette(2, ["stone", "cobblestone", "andesite", "gravel", "stone_bricks", "spruce_log"]),
+//!     palette(3, ["red_wool", "orange_wool", "yellow_wool", "lime_wool", "blue_wool", "purple_wool"]),
+//! ];
+//! let embeddings = ml::embed_all(&corpus);
+//! assert_eq!(embeddings[0].vector.len(), EMBEDDING_DIM);
+//!
+//! let vectors: Vec<Vec<f32>> = embeddings.into_iter().map(|e| e.vector).collect();
+//! assert!(ml::cosine_similarity(&vectors[0], &vectors[1]) > 0.8);
+//! assert!(ml::cosine_similarity(&vectors[0], &vectors[2]) < 0.5);
+//! assert_eq!(ml::nearest(&vectors[0], &vectors, 2)[1].0, 1);
+//! ```
+
+use crate::Palette;
+use crate::blocks::{self, normalize};
+use crate::daily::fnv1a;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// The version of the embedding scheme, see the [module documentation](self).
+pub const EMBEDDING_VERSION: u32 = 1;
+
+/// The number of block buckets at the start of an embedding.
+const BLOCK_BUCKETS: usize = 64;
+
+/// The number of hue bins at the end of an embedding.
+const HUE_BINS: usize = 8;
+
+/// The length of an embedding.
+pub const EMBEDDING_DIM: usize = BLOCK_BUCKETS + 8 + HUE_BINS;
+
+/// The factor applied to color features, balancing them against the block
+/// features, whose norm is 1.
+pub const COLOR_WEIGHT: f32 = 2.0;
+
+/// The embedding of a palette, with what is needed to store it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Embedding {
+    /// The ID of the embedded palette.
+    pub palette_id: u64,
+    /// The [`EMBEDDING_VERSION`] the vector was computed with.
+    pub version: u32,
+    /// The vector, of length [`EMBEDDING_DIM`].
+    pub vector: Vec<f32>,
+}
+
+/// Returns the embedding vector of `palette`, of length [`EMBEDDING_DIM`].
+pub fn embed(palette: &Palette) -> Vec<f32> {
+    let mut vector = vec![0.0; EMBEDDING_DIM];
+    let blocks = palette.blocks();
+
+    let distinct: BTreeSet<&str> = blocks.iter().map(|block| normalize(block)).collect();
+    for block in &distinct {
+        vector[(fnv1a(block.as_bytes()) % BLOCK_BUCKETS as u64) as usize] += 1.0;
+    }
+    let norm = norm(&vector[..BLOCK_BUCKETS]);
+    if norm > 0.0 {
+        vector[..BLOCK_BUCKETS].iter_mut().for_each(|x| *x /= norm);
+    }
+
+    let colors: Vec<_> = blocks
+        .iter()
+        .filter_map(|block| blocks::color(block))
+        .collect();
+    if colors.is_empty() {
+        return vector;
+    }
+    let labs: Vec<[f32; 3]> = colors
+        .iter()
+        .map(|color| {
+            let lab = color.to_lab();
+            [lab.l / 100.0 - 0.5, lab.a / 128.0, lab.b / 128.0]
+        })
+        .collect();
+    let count = labs.len() as f32;
+    let features = &mut vector[BLOCK_BUCKETS..];
+    for channel in 0..3 {
+        let mean = labs.iter().map(|lab| lab[channel]).sum::<f32>() / count;
+        let variance = labs
+            .iter()
+            .map(|lab| (lab[channel] - mean).powi(2))
+            .sum::<f32>()
+            / count;
+        features[channel] = mean;
+        features[3 + channel] = variance.sqrt();
+    }
+    features[6] = labs.iter().map(|lab| lab[1].hypot(lab[2])).sum::<f32>() / count;
+    let lightness = labs.iter().map(|lab| lab[0]);
+    features[7] = lightness.clone().fold(f32::NEG_INFINITY, f32::max)
+        - lightness.fold(f32::INFINITY, f32::min);
+    for color in &colors {
+        let hsl = color.to_hsl();
+        let bin = ((hsl.h / (360.0 / HUE_BINS as f32)) as usize).min(HUE_BINS - 1);
+        features[8 + bin] += hsl.s / count;
+    }
+    features.iter_mut().for_each(|x| *x *= COLOR_WEIGHT);
+    vector
+}
+
+/// Embeds every palette of `palettes`, in order.
+pub fn embed_all(palettes: &[Palette]) -> Vec<Embedding> {
+    palettes
+        .iter()
+        .map(|palette| Embedding {
+            palette_id: palette.id,
+            version: EMBEDDING_VERSION,
+            vector: embed(palette),
+        })
+        .collect()
+}
+
+/// Returns the cosine similarity of two vectors, from `-1.0` to `1.0`.
+///
+/// Returns `0.0` if either vector is zero. Extra components of the longer
+/// vector are ignored.
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let (norm_a, norm_b) = (norm(a), norm(b));
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    dot / (norm_a * norm_b)
+}
+
+/// Returns the indices of the `k` vectors of `candidates` most similar to
+/// `query`, with their [cosine similarity](cosine_similarity), most similar
+/// first.
+///
+/// Ties are ordered by index. A brute-force scan; use an approximate nearest
+/// neighbour index for large corpora.
+pub fn nearest(query: &[f32], candidates: &[Vec<f32>], k: usize) -> Vec<(usize, f32)> {
+    let mut scored: Vec<(usize, f32)> = candidates
+        .iter()
+        .map(|candidate| cosine_similarity(query, candidate))
+        .enumerate()
+        .collect();
+    scored.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+    scored.truncate(k);
+    scored
+}
+
+/// The most Lloyd iterations [`cluster`] runs before stopping.
+const MAX_ITERATIONS: usize = 100;
+
+/// A group of similar palettes found by [`cluster`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Cluster {
+    /// The mean embedding of the cluster's palettes.
+    pub centroid: Vec<f32>,
+    /// The palette closest to the centroid, which stands for the cluster.
+    pub representative: Palette,
+    /// The indices, in the clustered corpus, of the cluster's palettes.
+    pub members: Vec<usize>,
+}
+
+/// The result of [`cluster`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Clustering {
+    /// The index of the cluster of each palette, in corpus order.
+    pub assignments: Vec<usize>,
+    /// The clusters, largest first.
+    pub clusters: Vec<Cluster>,
+}
+
+/// Groups `palettes` into at most `k` clusters of similar palettes with
+/// k-means over their [embeddings](embed).
+///
+/// Initial centroids are picked deterministically (the palette closest to
+/// the mean embedding, then repeatedly the palette farthest from every
+/// centroid picked so far), so the same corpus always gives the same
+/// clusters. Distances are Euclidean. Fewer than `k` clusters are returned
+/// when the corpus has fewer distinct embeddings.
+///
+/// # Examples
+///
+/// ```rust
+/// use blockpalettes_client::ml;
+/// # let palette = |id, blocks: [&str; 6]| blockpalettes_client::Palette {
+/// #    id, user_id: 1, date: "2023-01-01 12:00:00".to_string(), likes: 10,
+/// #    block_one: blocks[0].to_string(), block_two: blocks[1].to_string(),
+/// #    block_three: blocks[2].to_string(), block_four: blocks[3].to_string(),
+/// #    block_five: blocks[4].to_string(), block_six: blocks[5].to_string(),
+/// #    hidden: Some(0), featured: Some(0), hash: None, time_ago: String::new()
+/// # };
+/// let corpus = [
+///     palette(1, ["stone", "cobblestone", "andesite", "gravel", "stone_bricks", "oak_log"]),
+///     palette(2, ["red_wool", "orange_wool", "yellow_wool", "lime_wool", "blue_wool", "purple_wool"]),
+///     palette(3, ["stone", "cobblestone", "andesite", "gravel", "stone_bricks", "spruce_log"]),
+///     palette(4, ["red_wool", "orange_wool", "yellow_wool", "lime_wool", "blue_wool", "pink_wool"]),
+///     palette(5, ["stone", "cobblestone", "diorite", "gravel", "stone_bricks", "oak_log"]),
+/// ];
+/// let clustering = ml::cluster(&corpus, 2);
+///
+/// assert_eq!(clustering.assignments, [0, 1, 0, 1, 0]);
+/// assert_eq!(clustering.clusters[0].members, [0, 2, 4]);
+/// assert_eq!(clustering.clusters[1].representative.block_one, "red_wool");
+/// ```
+pub fn cluster(palettes: &[Palette], k: usize) -> Clustering {
+    let vectors: Vec<Vec<f32>> = palettes.iter().map(embed).collect();
+    let mut centroids = initial_centroids(&vectors, k);
+    let mut assignments = vec![0; vectors.len()];
+
+    for iteration in 0..MAX_ITERATIONS {
+        let mut changed = false;
+        for (vector, assignment) in vectors.iter().zip(&mut assignments) {
+            let closest = closest(vector, &centroids);
+            changed |= *assignment != closest;
+            *assignment = closest;
+        }
+        if !changed && iteration > 0 {
+            break;
+        }
+
+        for (index, centroid) in centroids.iter_mut().enumerate() {
+            let members: Vec<&Vec<f32>> = vectors
+                .iter()
+                .zip(&assignments)
+                .filter(|(_, assignment)| **assignment == index)
+                .map(|(vector, _)| vector)
+                .collect();
+            // a centroid without members keeps its position
+            if !members.is_empty() {
+                *centroid = mean(&members);
+            }
+        }
+    }
+
+    // drop clusters left empty, and number the others largest first
+    let mut sizes: Vec<(usize, usize)> = (0..centroids.len())
+        .map(|index| (index, assignments.iter().filter(|a| **a == index).count()))
+        .filter(|(_, size)| *size > 0)
+        .collect();
+    sizes.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    let mut renumbered = vec![0; centroids.len()];
+    for (new, (old, _)) in sizes.iter().enumerate() {
+        renumbered[*old] = new;
+    }
+    let assignments: Vec<usize> = assignments.iter().map(|a| renumbered[*a]).collect();
+
+    let clusters = sizes
+        .iter()
+        .enumerate()
+        .map(|(new, (old, _))| {
+            let centroid = centroids[*old].clone();
+            let members: Vec<usize> = (0..assignments.len())
+                .filter(|index| assignments[*index] == new)
+                .collect();
+            let representative = members
+                .iter()
+                .copied()
+                .min_by(|a, b| {
+                    distance(&vectors[*a], &centroid).total_cmp(&distance(&vectors[*b], &centroid))
+                })
+                .map(|index| palettes[index].clone())
+                .expect("clusters have members");
+            Cluster {
+                centroid,
+                representative,
+                members,
+            }
+        })
+        .collect();
+
+    Clustering {
+        assignments,
+        clusters,
+    }
+}
+
+/// Picks up to `k` distinct initial centroids by farthest-point traversal.
+fn initial_centroids(vectors: &[Vec<f32>], k: usize) -> Vec<Vec<f32>> {
+    if vectors.is_empty() || k == 0 {
+        return Vec::new();
+    }
+    let overall = mean(&vectors.iter().collect::<Vec<_>>());
+    let first = closest(&overall, vectors);
+    let mut centroids = vec![vectors[first].clone()];
+
+    while centroids.len() < k {
+        let (farthest, gap) = vectors
+            .iter()
+            .map(|vector| {
+                centroids
+                    .iter()
+                    .map(|centroid| distance(vector, centroid))
+                    .fold(f32::INFINITY, f32::min)
+            })
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(&b.1).then(b.0.cmp(&a.0)))
+            .expect("vectors is not empty");
+        if gap == 0.0 {
+            break;
+        }
+        centroids.push(vectors[farthest].clone());
+    }
+    centroids
+}
+
+/// Returns the index of the candidate closest to `vector`, the first one on
+/// ties.
+fn closest(vector: &[f32], candidates: &[Vec<f32>]) -> usize {
+    candidates
+        .iter()
+        .map(|candidate| distance(vector, candidate))
+        .enumerate()
+        .min_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)))
+        .map_or(0, |(index, _)| index)
+}
+
+fn mean(vectors: &[&Vec<f32>]) -> Vec<f32> {
+    let mut mean = vec![0.0; EMBEDDING_DIM];
+    for vector in vectors {
+        mean.iter_mut()
+            .zip(vector.iter())
+            .for_each(|(m, x)| *m += x);
+    }
+    mean.iter_mut().for_each(|m| *m /= vectors.len() as f32);
+    mean
+}
+
+fn distance(a: &[f32], b: &[f32]) -> f32 {
+    a.iter()
+        .zip(b)
+        .map(|(x, y)| (x - y).powi(2))
+        .sum::<f32>()
+        .sqrt()
+}
+
+fn norm(vector: &[f32]) -> f32 {
+    vector.iter().map(|x| x * x).sum::<f32>().sqrt()
+}
@@ -0,0 +1,305 @@
+//! 3D models of palettes, for Blockbench and web viewers.
+//!
+//! A palette is modelled as a wall of six unit cubes, three wide and two
+//! high, in the same order as [`render::palette_png`](crate::render::palette_png):
+//! the first three blocks on the top row. Cubes are textured with the bundled
+//! [block textures](crate::blocks::texture_png); blocks of unknown color get
+//! a gray checkerboard. Textures are embedded as data URIs, so every model is
+//! a single self-contained JSON document.
+//!
+//! - [`blockbench`] produces a Blockbench project (`.bbmodel`).
+//! - [`gltf`] produces a glTF 2.0 asset (`.gltf`), which three.js (with
+//!   `GLTFLoader`), Babylon.js and most 3D tools can load.
+//!
+//! Cubes, nodes and materials are named after their block, written in the
+//! given [`NameFormat`].
+//!
+//! Requires the `textures` feature.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use blockpalettes_client::blocks::NameFormat;
+//! use blockpalettes_client::model;
+//! # let palette = blockpalettes_client::Palette {
+//! #    id: 56655, user_id: 1, date: "2023-01-01 12:00:00".to_string(), likes: 10,
+//! #    block_one: "stone".to_string(), block_two: "dirt".to_string(),
+//! #    block_three: "grass_block".to_string(), block_four: "oak_log".to_string(),
+//! #    block_five: "cobblestone".to_string(), block_six: "not_a_block".to_string(),
+//! #    hidden: Some(0), featured: Some(0), hash: None, time_ago: "1 day ago".to_string()
+//! # };
+//! let project = model::blockbench(&palette, NameFormat::Id);
+//! assert_eq!(project["elements"].as_array().unwrap().len(), 6);
+//! assert_eq!(project["elements"][3]["from"], serde_json::json!([0, 0, 0]));
+//!
+//! let gltf = model::gltf(&palette, NameFormat::TitleCase);
+//! assert_eq!(gltf["asset"]["version"], "2.0");
+//! assert_eq!(gltf["nodes"][3]["name"], "Oak Log");
+//! std::fs::write(std::env::temp_dir().join("palette.gltf"), gltf.to_string()).unwrap();
+//! ```
+
+use crate::Palette;
+use crate::blocks::{NameFormat, TEXTURE_SIZE, texture_png};
+use crate::render::{Canvas, UNKNOWN_COLORS};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use serde_json::{Value, json};
+
+/// The number of cubes in a row of the wall.
+const COLUMNS: usize = 3;
+
+/// Returns the wall position `(column, row)` of the block in `slot`, rows
+/// counted from the top.
+fn position(slot: usize) -> (usize, usize) {
+    (slot % COLUMNS, slot / COLUMNS)
+}
+
+/// Returns the texture of `block` as a PNG data URI.
+fn texture_uri(block: &str) -> String {
+    let png = texture_png(block).unwrap_or_else(|| {
+        let mut canvas = Canvas::new(TEXTURE_SIZE, TEXTURE_SIZE, UNKNOWN_COLORS[0]);
+        canvas.checkerboard(0, 0, TEXTURE_SIZE, TEXTURE_SIZE, UNKNOWN_COLORS);
+        canvas.into_png()
+    });
+    format!("data:image/png;base64,{}", BASE64.encode(png))
+}
+
+/// Returns a UUID for the `index`-th element or texture of a model.
+///
+/// Blockbench only requires UUIDs to be unique within a project, so they
+/// are derived from the index to keep models reproducible.
+fn uuid(kind: u8, index: usize) -> String {
+    format!("00000000-0000-4000-8000-{kind:02x}{index:010x}")
+}
+
+/// Returns a Blockbench project of `palette` as a wall of cubes.
+///
+/// Save it with a `.bbmodel` extension and open it with Blockbench's
+/// *File > Open Model*. Each block is a 16-pixel cube named after its block.
+pub fn blockbench(palette: &Palette, names: NameFormat) -> Value {
+    let size = TEXTURE_SIZE as usize;
+    let blocks = palette.blocks();
+    let block_names = palette.blocks_as(names);
+
+    let elements: Vec<Value> = block_names
+        .iter()
+        .enumerate()
+        .map(|(slot, name)| {
+            let (column, row) = position(slot);
+            let from = [column * size, (1 - row) * size, 0];
+            let to = [from[0] + size, from[1] + size, size];
+            let face = json!({ "uv": [0, 0, size, size], "texture": slot });
+            json!({
+                "name": name,
+                "type": "cube",
+                "uuid": uuid(0, slot),
+                "from": from,
+                "to": to,
+                "origin": from,
+                "faces": {
+                    "north": face, "east": face, "south": face,
+                    "west": face, "up": face, "down": face,
+                },
+            })
+        })
+        .collect();
+
+    let textures: Vec<Value> = blocks
+        .iter()
+        .enumerate()
+        .map(|(slot, block)| {
+            json!({
+                "name": format!("{block}.png"),
+                "id": slot.to_string(),
+                "uuid": uuid(1, slot),
+                "width": size,
+                "height": size,
+                "uv_width": size,
+                "uv_height": size,
+                "source": texture_uri(block),
+            })
+        })
+        .collect();
+
+    json!({
+        "meta": { "format_version": "4.5", "model_format": "free", "box_uv": false },
+        "name": format!("palette_{}", palette.id),
+        "resolution": { "width": size, "height": size },
+        "elements": elements,
+        "outliner": (0..blocks.len()).map(|slot| uuid(0, slot)).collect::<Vec<_>>(),
+        "textures": textures,
+    })
+}
+
+/// The faces of a unit cube centered on the origin: the outward normal and
+/// the bottom-left, bottom-right, top-right and top-left corners as seen
+/// from outside, so that corners run counter-clockwise.
+const CUBE_FACES: [([f32; 3], [[f32; 3]; 4]); 6] = [
+    (
+        [0.0, 0.0, 1.0],
+        [
+            [-0.5, -0.5, 0.5],
+            [0.5, -0.5, 0.5],
+            [0.5, 0.5, 0.5],
+            [-0.5, 0.5, 0.5],
+        ],
+    ),
+    (
+        [0.0, 0.0, -1.0],
+        [
+            [0.5, -0.5, -0.5],
+            [-0.5, -0.5, -0.5],
+            [-0.5, 0.5, -0.5],
+            [0.5, 0.5, -0.5],
+        ],
+    ),
+    (
+        [1.0, 0.0, 0.0],
+        [
+            [0.5, -0.5, 0.5],
+            [0.5, -0.5, -0.5],
+            [0.5, 0.5, -0.5],
+            [0.5, 0.5, 0.5],
+        ],
+    ),
+    (
+        [-1.0, 0.0, 0.0],
+        [
+            [-0.5, -0.5, -0.5],
+            [-0.5, -0.5, 0.5],
+            [-0.5, 0.5, 0.5],
+            [-0.5, 0.5, -0.5],
+        ],
+    ),
+    (
+        [0.0, 1.0, 0.0],
+        [
+            [-0.5, 0.5, 0.5],
+            [0.5, 0.5, 0.5],
+            [0.5, 0.5, -0.5],
+            [-0.5, 0.5, -0.5],
+        ],
+    ),
+    (
+        [0.0, -1.0, 0.0],
+        [
+            [-0.5, -0.5, -0.5],
+            [0.5, -0.5, -0.5],
+            [0.5, -0.5, 0.5],
+            [-0.5, -0.5, 0.5],
+        ],
+    ),
+];
+
+/// Texture coordinates of the corners of a face, in the order of [`CUBE_FACES`].
+const FACE_UVS: [[f32; 2]; 4] = [[0.0, 1.0], [1.0, 1.0], [1.0, 0.0], [0.0, 0.0]];
+
+/// The glTF code of `NEAREST` texture filtering, which keeps textures crisp.
+const NEAREST: u32 = 9728;
+
+/// Returns a glTF 2.0 asset of `palette` as a wall of cubes.
+///
+/// Save it with a `.gltf` extension. Cubes are one unit wide and the wall
+/// stands on the `y = 0` plane, centered on `x = 0` and facing `+z`. Each
+/// block is a node named after its block.
+pub fn gltf(palette: &Palette, names: NameFormat) -> Value {
+    // one cube mesh shared by every block: positions, normals, texture
+    // coordinates, then indices
+    let mut buffer = Vec::new();
+    for (_, corners) in CUBE_FACES {
+        for corner in corners {
+            corner.iter().for_each(|v| buffer.extend(v.to_le_bytes()));
+        }
+    }
+    let normals_offset = buffer.len();
+    for (normal, _) in CUBE_FACES {
+        for _ in 0..4 {
+            normal.iter().for_each(|v| buffer.extend(v.to_le_bytes()));
+        }
+    }
+    let uvs_offset = buffer.len();
+    for _ in CUBE_FACES {
+        for uv in FACE_UVS {
+            uv.iter().for_each(|v| buffer.extend(v.to_le_bytes()));
+        }
+    }
+    let indices_offset = buffer.len();
+    for face in 0..CUBE_FACES.len() as u16 {
+        for corner in [0, 1, 2, 0, 2, 3] {
+            buffer.extend((face * 4 + corner).to_le_bytes());
+        }
+    }
+    let vertices = CUBE_FACES.len() * 4;
+    let indices = CUBE_FACES.len() * 6;
+
+    let blocks = palette.blocks();
+    let block_names = palette.blocks_as(names);
+    let nodes: Vec<Value> = block_names
+        .iter()
+        .enumerate()
+        .map(|(slot, block)| {
+            let (column, row) = position(slot);
+            json!({
+                "name": block,
+                "mesh": slot,
+                "translation": [column as f32 - 1.0, 1.5 - row as f32, 0.0],
+            })
+        })
+        .collect();
+    let meshes: Vec<Value> = (0..blocks.len())
+        .map(|slot| {
+            json!({
+                "primitives": [{
+                    "attributes": { "POSITION": 0, "NORMAL": 1, "TEXCOORD_0": 2 },
+                    "indices": 3,
+                    "material": slot,
+                }],
+            })
+        })
+        .collect();
+    let materials: Vec<Value> = block_names
+        .iter()
+        .enumerate()
+        .map(|(slot, block)| {
+            json!({
+                "name": block,
+                "pbrMetallicRoughness": {
+                    "baseColorTexture": { "index": slot },
+                    "metallicFactor": 0.0,
+                    "roughnessFactor": 1.0,
+                },
+            })
+        })
+        .collect();
+
+    json!({
+        "asset": { "version": "2.0", "generator": "blockpalettes-client" },
+        "scene": 0,
+        "scenes": [{ "name": format!("palette {}", palette.id), "nodes": [0, 1, 2, 3, 4, 5] }],
+        "nodes": nodes,
+        "meshes": meshes,
+        "materials": materials,
+        "textures": (0..blocks.len()).map(|slot| json!({ "sampler": 0, "source": slot })).collect::<Vec<_>>(),
+        "images": blocks.iter().map(|block| json!({ "uri": texture_uri(block) })).collect::<Vec<_>>(),
+        "samplers": [{ "magFilter": NEAREST, "minFilter": NEAREST }],
+        "buffers": [{
+            "byteLength": buffer.len(),
+            "uri": format!("data:application/octet-stream;base64,{}", BASE64.encode(&buffer)),
+        }],
+        "bufferViews": [
+            { "buffer": 0, "byteOffset": 0, "byteLength": normals_offset, "target": 34962 },
+            { "buffer": 0, "byteOffset": normals_offset, "byteLength": uvs_offset - normals_offset, "target": 34962 },
+            { "buffer": 0, "byteOffset": uvs_offset, "byteLength": indices_offset - uvs_offset, "target": 34962 },
+            { "buffer": 0, "byteOffset": indices_offset, "byteLength": buffer.len() - indices_offset, "target": 34963 },
+        ],
+        "accessors": [
+            {
+                "bufferView": 0, "componentType": 5126, "count": vertices, "type": "VEC3",
+                "min": [-0.5, -0.5, -0.5], "max": [0.5, 0.5, 0.5],
+            },
+            { "bufferView": 1, "componentType": 5126, "count": vertices, "type": "VEC3" },
+            { "bufferView": 2, "componentType": 5126, "count": vertices, "type": "VEC2" },
+            { "bufferView": 3, "componentType": 5123, "count": indices, "type": "SCALAR" },
+        ],
+    })
+}
@@ -0,0 +1,209 @@
+//! Human-readable name suggestions for palettes.
+//!
+//! Palettes on the site have no names, only IDs. [`suggest`] makes one up
+//! from what the palette is made of and how its colors look, such as
+//! "Warm Spruce & Copper" or "Deepslate Monochrome". Names are deterministic:
+//! the same palette always gets the same name.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use blockpalettes_client::naming;
+//! # let palette = |blocks: [&str; 6]| blockpalettes_client::Palette {
+//! #    id: 1, user_id: 1, date: "2023-01-01 12:00:00".to_string(), likes: 10,
+//! #    block_one: blocks[0].to_string(), block_two: blocks[1].to_string(),
+//! #    block_three: blocks[2].to_string(), block_four: blocks[3].to_string(),
+//! #    block_five: blocks[4].to_string(), block_six: blocks[5].to_string(),
+//! #    hidden: Some(0), featured: Some(0), hash: None, time_ago: String::new()
+//! # };
+//! let cabin = palette(["spruce_planks", "spruce_log", "cut_copper", "copper_block", "stripped_spruce_log", "orange_terracotta"]);
+//! assert_eq!(naming::suggest(&cabin), "Warm Spruce & Copper");
+//!
+//! let cave = palette(["deepslate", "deepslate_bricks", "cobbled_deepslate", "polished_deepslate", "deepslate_tiles", "tuff"]);
+//! assert_eq!(naming::suggest(&cave), "Deepslate Monochrome");
+//!
+//! let ocean = palette(["prismarine", "dark_prismarine", "light_blue_concrete", "blue_wool", "sea_lantern", "cyan_terracotta"]);
+//! assert_eq!(naming::suggest(&ocean), "Cool Prismarine & Concrete");
+//! ```
+
+use crate::Palette;
+use crate::analysis::{self, Harmony};
+use crate::blocks::{self, normalize};
+use std::cmp::Reverse;
+
+/// Materials named in suggestions: the words identifying them in block IDs,
+/// and their display name. Longer word sequences come first, so that
+/// `dark_oak` wins over `oak`.
+static MATERIALS: &[(&str, &str)] = &[
+    ("red_nether_bricks", "Nether Brick"),
+    ("red_sandstone", "Red Sandstone"),
+    ("nether_bricks", "Nether Brick"),
+    ("nether_brick", "Nether Brick"),
+    ("end_stone", "End Stone"),
+    ("dark_oak", "Dark Oak"),
+    ("pale_oak", "Pale Oak"),
+    ("oak", "Oak"),
+    ("spruce", "Spruce"),
+    ("birch", "Birch"),
+    ("jungle", "Jungle"),
+    ("acacia", "Acacia"),
+    ("mangrove", "Mangrove"),
+    ("cherry", "Cherry"),
+    ("bamboo", "Bamboo"),
+    ("crimson", "Crimson"),
+    ("warped", "Warped"),
+    ("deepslate", "Deepslate"),
+    ("blackstone", "Blackstone"),
+    ("cobblestone", "Cobblestone"),
+    ("sandstone", "Sandstone"),
+    ("andesite", "Andesite"),
+    ("diorite", "Diorite"),
+    ("granite", "Granite"),
+    ("tuff", "Tuff"),
+    ("calcite", "Calcite"),
+    ("basalt", "Basalt"),
+    ("prismarine", "Prismarine"),
+    ("purpur", "Purpur"),
+    ("quartz", "Quartz"),
+    ("copper", "Copper"),
+    ("gold", "Gold"),
+    ("iron", "Iron"),
+    ("amethyst", "Amethyst"),
+    ("netherrack", "Netherrack"),
+    ("obsidian", "Obsidian"),
+    ("mud", "Mud"),
+    ("moss", "Moss"),
+    ("mossy", "Moss"),
+    ("terracotta", "Terracotta"),
+    ("concrete", "Concrete"),
+    ("wool", "Wool"),
+    ("glass", "Glass"),
+    ("bricks", "Brick"),
+    ("brick", "Brick"),
+    ("stone", "Stone"),
+    ("sand", "Sand"),
+    ("dirt", "Dirt"),
+    ("snow", "Snow"),
+    ("ice", "Ice"),
+];
+
+/// The minimum HSL saturation for a block to count as colored, as in
+/// [`analysis::harmony_score`].
+const MIN_SATURATION: f32 = 0.2;
+
+/// The number of blocks of one material making a single-material palette.
+const DOMINANT_BLOCKS: usize = 4;
+
+/// Suggests a human-readable name for `palette`.
+///
+/// The name is built from the two materials the palette uses most (wood
+/// species, stones, metals, dyed blocks...), prefixed by an adjective
+/// describing its colors: "Warm" or "Cool" when most colored blocks lean one
+/// way, otherwise "Dark" or "Light" for very dark or very light palettes.
+/// Palettes dominated by one material with a single hue, or none, are named
+/// "Monochrome". Palettes without any known material are named after their
+/// [`Harmony`].
+pub fn suggest(palette: &Palette) -> String {
+    let materials = materials(palette);
+    let harmony = analysis::harmony_score(palette).harmony;
+    let adjective = adjective(palette);
+
+    let name = match materials.as_slice() {
+        [(top, count), ..]
+            if *count >= DOMINANT_BLOCKS
+                && matches!(harmony, Harmony::Achromatic | Harmony::Monochrome) =>
+        {
+            return format!("{top} Monochrome");
+        }
+        [(top, _)] => format!("{top} Blend"),
+        [(top, _), (second, _), ..] => format!("{top} & {second}"),
+        [] => format!("{} Mix", harmony_name(harmony)),
+    };
+    match adjective {
+        Some(adjective) => format!("{adjective} {name}"),
+        None => name,
+    }
+}
+
+/// Returns the materials of `palette` with their number of blocks, most used
+/// first, then in order of first appearance.
+fn materials(palette: &Palette) -> Vec<(&'static str, usize)> {
+    let mut counts: Vec<(&'static str, usize)> = Vec::new();
+    for block in palette.blocks() {
+        let Some(material) = material(normalize(block)) else {
+            continue;
+        };
+        match counts.iter_mut().find(|(name, _)| *name == material) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((material, 1)),
+        }
+    }
+    // stable, so ties keep their order of first appearance
+    counts.sort_by_key(|(_, count)| Reverse(*count));
+    counts
+}
+
+/// Returns the display name of the material `block` is made of.
+pub(crate) fn material(block: &str) -> Option<&'static str> {
+    let words: Vec<&str> = block.split('_').collect();
+    MATERIALS.iter().find_map(|(id, name)| {
+        let needle: Vec<&str> = id.split('_').collect();
+        words
+            .windows(needle.len())
+            .any(|window| window == needle.as_slice())
+            .then_some(*name)
+    })
+}
+
+/// Describes the colors of `palette` in one word, if one fits.
+fn adjective(palette: &Palette) -> Option<&'static str> {
+    let colors: Vec<_> = palette
+        .blocks()
+        .iter()
+        .filter_map(|block| blocks::color(block))
+        .map(|color| color.to_hsl())
+        .collect();
+    if colors.is_empty() {
+        return None;
+    }
+
+    let colored: Vec<f32> = colors
+        .iter()
+        .filter(|hsl| hsl.s >= MIN_SATURATION)
+        .map(|hsl| hsl.h)
+        .collect();
+    let warm = colored
+        .iter()
+        .filter(|h| **h < 75.0 || **h >= 330.0)
+        .count();
+    let cool = colored
+        .iter()
+        .filter(|h| (165.0..285.0).contains(*h))
+        .count();
+    if warm * 2 > colored.len() && warm >= 2 {
+        return Some("Warm");
+    }
+    if cool * 2 > colored.len() && cool >= 2 {
+        return Some("Cool");
+    }
+
+    let lightness = colors.iter().map(|hsl| hsl.l).sum::<f32>() / colors.len() as f32;
+    if lightness < 0.25 {
+        Some("Dark")
+    } else if lightness > 0.75 {
+        Some("Light")
+    } else {
+        None
+    }
+}
+
+fn harmony_name(harmony: Harmony) -> &'static str {
+    match harmony {
+        Harmony::Achromatic => "Neutral",
+        Harmony::Monochrome => "Monochrome",
+        Harmony::Analogous => "Analogous",
+        Harmony::Complementary => "Complementary",
+        Harmony::Triadic => "Triadic",
+        Harmony::Mixed => "Eclectic",
+    }
+}
@@ -0,0 +1,211 @@
+//! Hooks into what the client is doing behind a call.
+//!
+//! A single call can be slow for reasons invisible to the caller: the server
+//! asked the client to back off, or a request failed and is being retried.
+//! An [`Observer`] registered with
+//! [`BlockPalettesClient::with_observer`](crate::BlockPalettesClient::with_observer)
+//! is told about these events as they happen, so applications can show
+//! "waiting 3s due to rate limit" instead of appearing frozen.
+//!
+//! Retries are opt-in: without a [`RetryPolicy`], set with
+//! [`BlockPalettesClient::with_retry_policy`](crate::BlockPalettesClient::with_retry_policy),
+//! every request is sent once and rate limits are reported as errors.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use blockpalettes_client::{BlockPalettesClient, BlockPalettesError};
+//! use blockpalettes_client::observe::{Observer, RetryPolicy};
+//! use std::time::Duration;
+//!
+//! struct StatusLine;
+//!
+//! impl Observer for StatusLine {
+//!     fn on_rate_limited(&self, wait: Duration) {
+//!         eprintln!("waiting {}s due to rate limit", wait.as_secs());
+//!     }
+//!
+//!     fn on_retry(&self, attempt: u32, error: &BlockPalettesError) {
+//!         eprintln!("retrying ({attempt}) after: {error}");
+//!     }
+//! }
+//!
+//! let client = BlockPalettesClient::new(reqwest::Client::new())
+//!     .with_retry_policy(RetryPolicy::new().max_retries(5))
+//!     .with_observer(StatusLine);
+//! ```
+
+use crate::BlockPalettesError;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// The default number of times a failed request is retried.
+pub const DEFAULT_MAX_RETRIES: u32 = 3;
+
+/// The default delay before the first retry; later retries double it.
+pub const DEFAULT_BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// The default longest wait before a retry.
+pub const DEFAULT_MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// Callbacks for events happening while the client serves a call.
+///
+/// Every method does nothing by default, so implementors only need to
+/// override the ones they care about. Callbacks run on the task making the
+/// request and should return quickly.
+pub trait Observer: Send + Sync {
+    /// A response was served from the [`HttpCache`](crate::HttpCache) or
+    /// shared by a [`Coalescer`](crate::coalesce::Coalescer).
+    fn on_cache_hit(&self, url: &str) {
+        let _ = url;
+    }
+
+    /// A request that a cache or coalescer could have served was sent to the
+    /// server.
+    fn on_cache_miss(&self, url: &str) {
+        let _ = url;
+    }
+
+    /// The server rate-limited a request; the client waits for `wait` before
+    /// retrying it.
+    fn on_rate_limited(&self, wait: Duration) {
+        let _ = wait;
+    }
+
+    /// A request failed with `error` and is about to be retried for the
+    /// `attempt`-th time, starting at 1.
+    fn on_retry(&self, attempt: u32, error: &BlockPalettesError) {
+        let _ = (attempt, error);
+    }
+}
+
+/// How failed requests are retried.
+///
+/// Transport errors, `429 Too Many Requests` and server errors are retried
+/// with exponential backoff. A `429` response carrying a `Retry-After` header
+/// (in seconds) is retried after the requested delay, unless it exceeds
+/// [`max_delay`](Self::max_delay), in which case the response is returned
+/// as is.
+///
+/// # Examples
+///
+/// ```rust
+/// use blockpalettes_client::observe::RetryPolicy;
+/// use std::time::Duration;
+///
+/// let policy = RetryPolicy::new()
+///     .max_retries(4)
+///     .base_delay(Duration::from_millis(250))
+///     .max_delay(Duration::from_secs(10));
+/// assert_eq!(policy.backoff(1), Duration::from_millis(250));
+/// assert_eq!(policy.backoff(3), Duration::from_secs(1));
+/// assert_eq!(policy.backoff(10), Duration::from_secs(10));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    max_retries: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RetryPolicy {
+    /// Creates a policy with the default limits.
+    pub fn new() -> Self {
+        Self {
+            max_retries: DEFAULT_MAX_RETRIES,
+            base_delay: DEFAULT_BASE_DELAY,
+            max_delay: DEFAULT_MAX_DELAY,
+        }
+    }
+
+    /// Sets how many times a request is retried after its first attempt.
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Sets the delay before the first retry.
+    pub fn base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    /// Sets the longest the client waits before a retry.
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Returns how long to wait before the `attempt`-th retry, starting at 1.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.base_delay
+            .checked_mul(factor)
+            .map_or(self.max_delay, |delay| delay.min(self.max_delay))
+    }
+
+    pub(crate) fn retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    /// Returns how long to wait before retrying a rate-limited request for the
+    /// `attempt`-th time, or `None` if the server asks for too long a wait.
+    pub(crate) fn rate_limit_wait(&self, headers: &HeaderMap, attempt: u32) -> Option<Duration> {
+        let requested = headers
+            .get(RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse().ok())
+            .map(Duration::from_secs);
+        match requested {
+            Some(wait) if wait > self.max_delay => None,
+            Some(wait) => Some(wait),
+            None => Some(self.backoff(attempt)),
+        }
+    }
+}
+
+/// The observers registered on a client.
+#[derive(Clone)]
+pub(crate) struct Observers(Vec<Arc<dyn Observer>>);
+
+impl Observers {
+    pub(crate) const fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    pub(crate) fn push(&mut self, observer: Arc<dyn Observer>) {
+        self.0.push(observer);
+    }
+
+    pub(crate) fn cache_hit(&self, url: &str) {
+        self.0.iter().for_each(|o| o.on_cache_hit(url));
+    }
+
+    pub(crate) fn cache_miss(&self, url: &str) {
+        self.0.iter().for_each(|o| o.on_cache_miss(url));
+    }
+
+    pub(crate) fn rate_limited(&self, wait: Duration) {
+        self.0.iter().for_each(|o| o.on_rate_limited(wait));
+    }
+
+    pub(crate) fn retry(&self, attempt: u32, error: &BlockPalettesError) {
+        self.0.iter().for_each(|o| o.on_retry(attempt, error));
+    }
+}
+
+impl fmt::Debug for Observers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Observers")
+            .field("count", &self.0.len())
+            .finish()
+    }
+}
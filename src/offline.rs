@@ -0,0 +1,141 @@
+//! Serving every request from fixtures instead of the network.
+//!
+//! A client in [`ClientMode::Offline`] never touches the network: every
+//! request it would send is answered with the body stored in a
+//! [`StateStore`] under the request's [`fixture_key`], and fails with
+//! [`BlockPalettesError::MissingFixture`] if there is none. Apps built on the
+//! crate can then run demos, tests and development sessions without the
+//! site, with every feature of the client (validators, denylists, ranking,
+//! caches) behaving as usual.
+//!
+//! Fixtures are easiest to make with [`ClientMode::Record`], which talks to
+//! the site as usual and stores the body of every successful response: record
+//! a session once into a [`FileStore`](crate::state::FileStore) directory,
+//! then replay it offline from the same directory.
+//!
+//! Only the requests of the client are covered. Components sending their own
+//! requests, such as [`upload`](crate::upload) and the webhook notifier of
+//! the [`watcher`](crate::watcher), still use the network.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use blockpalettes_client::offline::{self, ClientMode};
+//! use blockpalettes_client::state::{MemoryStore, StateStore};
+//! use blockpalettes_client::{BlockPalettesClient, BlockPalettesError};
+//! use std::sync::Arc;
+//!
+//! #[tokio::main]
+//! async fn main() -> blockpalettes_client::Result<()> {
+//!     let fixtures = Arc::new(MemoryStore::new());
+//!     let key = offline::fixture_key("/api/palettes/search-block.php?query=stone");
+//!     assert_eq!(key, "api/palettes/search-block.php?query=stone");
+//!     fixtures
+//!         .put(&key, br#"{"success":true,"blocks":["stone","stone_bricks"]}"#)
+//!         .await?;
+//!
+//!     let client = BlockPalettesClient::new(reqwest::Client::new())
+//!         .with_mode(ClientMode::Offline(fixtures));
+//!     assert_eq!(client.search_blocks("stone").await?, ["stone", "stone_bricks"]);
+//!
+//!     let missing = client.search_blocks("dirt").await;
+//!     assert!(matches!(missing, Err(BlockPalettesError::MissingFixture(_))));
+//!     Ok(())
+//! }
+//! ```
+
+use crate::state::StateStore;
+use crate::{BlockPalettesError, HttpResponse, Result};
+use reqwest::StatusCode;
+use reqwest::header::HeaderMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// Where a [`BlockPalettesClient`](crate::BlockPalettesClient) gets its
+/// responses from, see
+/// [`BlockPalettesClient::with_mode`](crate::BlockPalettesClient::with_mode).
+#[derive(Clone, Default)]
+pub enum ClientMode {
+    /// Requests go to the network.
+    #[default]
+    Online,
+    /// Requests are answered from the fixtures of the store, and never go
+    /// to the network.
+    Offline(Arc<dyn StateStore>),
+    /// Requests go to the network, and the bodies of successful responses
+    /// are stored as fixtures in the store.
+    Record(Arc<dyn StateStore>),
+}
+
+impl fmt::Debug for ClientMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientMode::Online => f.write_str("Online"),
+            ClientMode::Offline(_) => f.write_str("Offline(..)"),
+            ClientMode::Record(_) => f.write_str("Record(..)"),
+        }
+    }
+}
+
+/// Returns the key the fixture of a request is stored under, from the URL
+/// of the request relative to the client's base URL.
+///
+/// The key is the path without its leading slash, followed by the query
+/// parameters sorted by name, as sent (percent-encoded). Requests to other
+/// hosts, such as the Wayback Machine, are keyed by their host and path.
+pub fn fixture_key(url: &str) -> String {
+    let url = url.trim_start_matches('/');
+    let Some((path, query)) = url.split_once('?') else {
+        return url.to_string();
+    };
+    let mut params: Vec<&str> = query.split('&').filter(|p| !p.is_empty()).collect();
+    if params.is_empty() {
+        return path.to_string();
+    }
+    params.sort_unstable();
+    format!("{path}?{}", params.join("&"))
+}
+
+/// Returns the fixture key of `url`, relative to `base_url` if it is on it.
+pub(crate) fn request_key(url: &reqwest::Url, base_url: &str) -> String {
+    let url = url.as_str();
+    match url.strip_prefix(base_url) {
+        Some(relative) => fixture_key(relative),
+        None => fixture_key(url.split_once("://").map_or(url, |(_, rest)| rest)),
+    }
+}
+
+/// Answers the request for `url` with its fixture from `store`.
+pub(crate) async fn serve(
+    store: &dyn StateStore,
+    url: &reqwest::Url,
+    base_url: &str,
+) -> Result<HttpResponse> {
+    let key = request_key(url, base_url);
+    let Some(body) = store.get(&key).await? else {
+        return Err(BlockPalettesError::MissingFixture(key));
+    };
+    Ok(HttpResponse {
+        status: StatusCode::OK,
+        headers: HeaderMap::new(),
+        body: String::from_utf8_lossy(&body).into_owned(),
+        from_cache: false,
+        url: Some(url.to_string()),
+    })
+}
+
+/// Stores the body of `response` to the request for `url` in `store`, if the
+/// request succeeded.
+pub(crate) async fn record(
+    store: &dyn StateStore,
+    url: &reqwest::Url,
+    base_url: &str,
+    response: &HttpResponse,
+) -> Result<()> {
+    if response.status.is_success() {
+        store
+            .put(&request_key(url, base_url), response.body.as_bytes())
+            .await?;
+    }
+    Ok(())
+}
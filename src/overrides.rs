@@ -0,0 +1,198 @@
+//! Locally pinned corrections to palettes.
+//!
+//! Mirror operators sometimes need to patch bad upstream data, such as a
+//! misspelled or renamed block, without being able to change the site.
+//! [`Overrides`] registered with
+//! [`BlockPalettesClient::with_overrides`](crate::BlockPalettesClient::with_overrides)
+//! pin corrected blocks to specific palette IDs, and are merged over every
+//! result the client returns:
+//!
+//! - palettes from listings, searches and similarity responses, and palette
+//!   details, get their pinned blocks. This also covers everything built on
+//!   top of them, such as caches and the [`watcher`](crate::watcher);
+//! - scraped palette pages and the palette cards of scraped galleries and
+//!   profiles get their pinned blocks too.
+//!
+//! Overrides run before the [`denylist`](crate::denylist) and every
+//! [`ResponseValidator`], so those see the corrected palettes.
+//!
+//! Palettes have no titles on the site; custom titles are kept in the
+//! overrides and looked up with [`Overrides::title`].
+//!
+//! Overrides are serializable, so they can be loaded from a configuration
+//! file.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use blockpalettes_client::BlockPalettesClient;
+//! use blockpalettes_client::overrides::{Overrides, PaletteOverride};
+//! # let palette = blockpalettes_client::Palette {
+//! #    id: 42, user_id: 1, date: "2023-01-01 12:00:00".to_string(), likes: 10,
+//! #    block_one: "stone".to_string(), block_two: "grass_path".to_string(),
+//! #    block_three: "oak_planks".to_string(), block_four: "spruce_log".to_string(),
+//! #    block_five: "moss_block".to_string(), block_six: "dirt".to_string(),
+//! #    hidden: Some(0), featured: Some(0), hash: None, time_ago: String::new()
+//! # };
+//!
+//! let overrides = Overrides::new().palette(
+//!     42,
+//!     PaletteOverride::new()
+//!         // renamed in Minecraft 1.17
+//!         .block(1, "dirt_path")
+//!         .title("Forest Cabin"),
+//! );
+//!
+//! let patched = overrides.apply_palette(palette);
+//! assert_eq!(patched.block_two, "dirt_path");
+//! assert_eq!(overrides.title(42), Some("Forest Cabin"));
+//!
+//! let client = BlockPalettesClient::new(reqwest::Client::new()).with_overrides(overrides);
+//! ```
+
+use crate::validation::{ResponseValidator, Validation};
+use crate::{Palette, PaletteCard, PaletteDetails, PalettePageDetails};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The corrections pinned to a single palette.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PaletteOverride {
+    /// The corrected block of each slot, in display order, or `None` to keep
+    /// the block received from the site.
+    pub blocks: [Option<String>; 6],
+    /// A custom title for the palette.
+    pub title: Option<String>,
+}
+
+impl PaletteOverride {
+    /// Creates an override changing nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pins `block` to the slot at `index`, from `0` to `5` in display order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is larger than `5`.
+    pub fn block(mut self, index: usize, block: impl Into<String>) -> Self {
+        self.blocks[index] = Some(block.into());
+        self
+    }
+
+    /// Sets a custom title for the palette.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Replaces the blocks of `slots` with the pinned ones. Slots past the
+    /// end of `slots` are ignored.
+    fn patch<'a>(&self, slots: impl IntoIterator<Item = &'a mut String>) {
+        for (slot, pinned) in slots.into_iter().zip(&self.blocks) {
+            if let Some(block) = pinned {
+                slot.clone_from(block);
+            }
+        }
+    }
+}
+
+/// Corrections pinned to palettes, by palette ID.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Overrides {
+    /// The corrections of each palette.
+    pub palettes: BTreeMap<u64, PaletteOverride>,
+}
+
+impl Overrides {
+    /// Creates an empty set of overrides.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pins `corrections` to the palette with `palette_id`, replacing any
+    /// previous ones.
+    pub fn palette(mut self, palette_id: u64, corrections: PaletteOverride) -> Self {
+        self.palettes.insert(palette_id, corrections);
+        self
+    }
+
+    /// Returns `true` if no palette has overrides.
+    pub fn is_empty(&self) -> bool {
+        self.palettes.is_empty()
+    }
+
+    /// Returns the overrides of the palette with `palette_id`, if any.
+    pub fn get(&self, palette_id: u64) -> Option<&PaletteOverride> {
+        self.palettes.get(&palette_id)
+    }
+
+    /// Returns the custom title of the palette with `palette_id`, if any.
+    pub fn title(&self, palette_id: u64) -> Option<&str> {
+        self.get(palette_id)?.title.as_deref()
+    }
+
+    /// Returns `palette` with its pinned blocks.
+    pub fn apply_palette(&self, mut palette: Palette) -> Palette {
+        if let Some(corrections) = self.get(palette.id) {
+            corrections.patch([
+                &mut palette.block_one,
+                &mut palette.block_two,
+                &mut palette.block_three,
+                &mut palette.block_four,
+                &mut palette.block_five,
+                &mut palette.block_six,
+            ]);
+        }
+        palette
+    }
+
+    /// Returns `details` with their pinned blocks.
+    pub fn apply_details(&self, mut details: PaletteDetails) -> PaletteDetails {
+        if let Some(corrections) = self.get(details.id) {
+            corrections.patch([
+                &mut details.block_one,
+                &mut details.block_two,
+                &mut details.block_three,
+                &mut details.block_four,
+                &mut details.block_five,
+                &mut details.block_six,
+            ]);
+        }
+        details
+    }
+
+    /// Replaces the blocks shown on `card` with the pinned ones.
+    ///
+    /// Cards showing no block names are left alone.
+    pub fn apply_card(&self, card: &mut PaletteCard) {
+        if let Some(corrections) = self.get(card.id) {
+            corrections.patch(&mut card.blocks);
+        }
+    }
+
+    /// Replaces the blocks of the scraped page of the palette with
+    /// `palette_id`, and of the similar palettes it links, with the pinned
+    /// ones.
+    pub fn apply_page(&self, palette_id: u64, page: &mut PalettePageDetails) {
+        if let (Some(corrections), Some(blocks)) = (self.get(palette_id), &mut page.blocks) {
+            corrections.patch(blocks);
+        }
+        for card in page.similar_palettes.iter_mut().flatten() {
+            self.apply_card(card);
+        }
+    }
+}
+
+impl ResponseValidator for Overrides {
+    fn validate_palette(&self, palette: Palette) -> Validation<Palette> {
+        Validation::Keep(self.apply_palette(palette))
+    }
+
+    fn validate_details(&self, details: PaletteDetails) -> Validation<PaletteDetails> {
+        Validation::Keep(self.apply_details(details))
+    }
+}
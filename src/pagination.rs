@@ -0,0 +1,415 @@
+//! Walking palette listings page by page without duplicates or gaps.
+//!
+//! The API paginates with page numbers, so a listing shifts whenever palettes
+//! are added or removed upstream while it is being read. With
+//! [`SortOrder::Recent`], a new submission pushes
+//! the last palette of every page onto the next one, where it shows up a
+//! second time; a removed palette pulls the first palette of every page onto
+//! the previous one, which was already read, so it is silently skipped.
+//!
+//! A [`PaletteWalker`] handles both cases. It remembers the ID of every
+//! palette it returned and drops palettes it sees again. It also compares
+//! the listing's `total_results` between pages: when the total shrinks, it
+//! steps back as many pages as the listing shifted, so the palettes that moved
+//! onto already-read pages are fetched again and returned. The walker
+//! therefore never returns a palette twice and, as long as the upstream order
+//! of the remaining palettes is stable, never misses one.
+//!
+//! [`PaletteWalker::into_stream`] turns a walker into a [`PaletteStream`], a
+//! [`Stream`] of palettes that owns its client and can be stored, boxed or
+//! merged with other streams. With [`PaletteWalker::prefetch`], the stream
+//! fetches the next pages in the background while the consumer processes the
+//! current one, which shortens long crawls over high-latency connections.
+//!
+//! Orders that change as palettes are liked, such as
+//! [`SortOrder::Popular`], can still move a palette
+//! across the current page boundary without changing the total; duplicates
+//! are still dropped, but such moves cannot be detected.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use blockpalettes_client::{BlockPalettesClient, SortOrder};
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let client = BlockPalettesClient::new(reqwest::Client::new());
+//!     let mut walker = client.walk_palettes(SortOrder::Recent, 100).block("oak_planks");
+//!     while let Some(palettes) = walker.next_page().await? {
+//!         for palette in palettes {
+//!             println!("{}", palette.id);
+//!         }
+//!     }
+//!     println!("{} palettes, {} rewinds", walker.seen(), walker.rewinds());
+//!     Ok(())
+//! }
+//! ```
+//!
+//! Streams of several listings can be merged with `select_all`:
+//!
+//! ```rust,no_run
+//! use blockpalettes_client::pagination::PaletteStream;
+//! use blockpalettes_client::{BlockPalettesClient, SortOrder};
+//! use futures_util::{StreamExt, stream};
+//!
+//! struct Actor {
+//!     palettes: stream::SelectAll<PaletteStream>,
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let client = BlockPalettesClient::new(reqwest::Client::new());
+//!     let streams = ["oak_planks", "spruce_planks"]
+//!         .map(|block| client.walk_palettes(SortOrder::Recent, 100).block(block).into_stream());
+//!     let mut actor = Actor { palettes: stream::select_all(streams) };
+//!     while let Some(palette) = actor.palettes.next().await {
+//!         println!("{}", palette?.id);
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+use crate::{BlockPalettesClient, Palette, PaletteResponse, Result, SortOrder};
+use futures_util::Stream;
+use futures_util::stream::{self, BoxStream};
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::task::JoinHandle;
+
+/// Reads a palette listing page by page, see the [module documentation](self).
+///
+/// Create one with [`BlockPalettesClient::walk_palettes`].
+#[derive(Debug)]
+pub struct PaletteWalker<'a> {
+    client: &'a BlockPalettesClient,
+    state: WalkState,
+}
+
+/// The position of a walker in its listing.
+#[derive(Debug)]
+struct WalkState {
+    sort: SortOrder,
+    limit: u32,
+    block: Option<String>,
+    page: u32,
+    seen: HashSet<u64>,
+    total_results: Option<u32>,
+    rewinds: u32,
+    done: bool,
+    prefetch: u32,
+    ranked: bool,
+}
+
+impl<'a> PaletteWalker<'a> {
+    pub(crate) fn new(client: &'a BlockPalettesClient, sort: SortOrder, limit: u32) -> Self {
+        Self {
+            client,
+            state: WalkState {
+                sort,
+                limit: limit.max(1),
+                block: None,
+                page: 1,
+                seen: HashSet::new(),
+                total_results: None,
+                rewinds: 0,
+                done: false,
+                prefetch: 0,
+                ranked: true,
+            },
+        }
+    }
+
+    /// Keeps the pages in the listing's order, ignoring the client's
+    /// [`Ranker`](crate::ranking::Ranker), for internal walks relying on the
+    /// site's order.
+    pub(crate) fn unranked(mut self) -> Self {
+        self.state.ranked = false;
+        self
+    }
+
+    /// Restricts the listing to palettes containing `block`.
+    pub fn block(mut self, block: impl Into<String>) -> Self {
+        self.state.block = Some(block.into());
+        self
+    }
+
+    /// Sets how many pages a [stream](Self::into_stream) of the walker fetches
+    /// ahead of the page being read. Defaults to `0`: pages are fetched one at
+    /// a time, when the consumer reaches them.
+    ///
+    /// Pages are only fetched ahead as the consumer pulls palettes from the
+    /// stream, so a slow consumer never has more than `depth` pages buffered.
+    /// When the listing shifts, pages fetched ahead are discarded and fetched
+    /// again. [`next_page`](Self::next_page) never fetches ahead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "test-server")]
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use blockpalettes_client::SortOrder;
+    /// use blockpalettes_client::test_server::TestServer;
+    /// use futures_util::StreamExt;
+    /// use std::time::Duration;
+    ///
+    /// let server = TestServer::start().await?;
+    /// for (depth, requests) in [(0, 1), (1, 2), (2, 3)] {
+    ///     let (_, meta) = server
+    ///         .client()
+    ///         .with_meta(|client| async move {
+    ///             let mut stream = client
+    ///                 .walk_palettes(SortOrder::Recent, 5)
+    ///                 .prefetch(depth)
+    ///                 .into_stream();
+    ///             stream.next().await.transpose()?;
+    ///             // give pages fetched ahead time to arrive
+    ///             tokio::time::sleep(Duration::from_millis(200)).await;
+    ///             Ok(())
+    ///         })
+    ///         .await;
+    ///     // the page being read, and `depth` pages ahead of it
+    ///     assert_eq!(meta.len(), requests);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(feature = "test-server"))]
+    /// # fn main() {}
+    /// ```
+    pub fn prefetch(mut self, depth: u32) -> Self {
+        self.state.prefetch = depth;
+        self
+    }
+
+    /// Fetches the next page and returns the palettes not returned before.
+    ///
+    /// The list may be empty when a page only held palettes that were already
+    /// returned. `None` means the listing is exhausted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the page cannot be fetched. The walker can be
+    /// resumed by calling this method again, which retries the same page.
+    pub async fn next_page(&mut self) -> Result<Option<Vec<Palette>>> {
+        self.state.next_page(self.client).await
+    }
+
+    /// Returns the number of distinct palettes returned so far.
+    pub fn seen(&self) -> usize {
+        self.state.seen.len()
+    }
+
+    /// Returns how many times the walker stepped back after the listing shifted.
+    pub fn rewinds(&self) -> u32 {
+        self.state.rewinds
+    }
+
+    /// Turns the walker into a stream of palettes, resuming where the walker
+    /// stopped.
+    ///
+    /// The stream owns a clone of the client, so it is `Send + 'static`. It
+    /// ends after yielding the first error. Pages fetched ahead (see
+    /// [`prefetch`](Self::prefetch)) run on the Tokio runtime and are
+    /// cancelled when the stream is dropped.
+    pub fn into_stream(self) -> PaletteStream {
+        let prefetcher = Prefetcher {
+            client: self.client.clone(),
+            pending: VecDeque::new(),
+        };
+        let start = (prefetcher, self.state, VecDeque::new());
+        let inner = stream::unfold(Some(start), |walk| async move {
+            let (mut prefetcher, mut state, mut buffer) = walk?;
+            loop {
+                if let Some(palette) = buffer.pop_front() {
+                    return Some((Ok(palette), Some((prefetcher, state, buffer))));
+                }
+                match prefetcher.next_page(&mut state).await {
+                    Ok(Some(palettes)) => buffer.extend(palettes),
+                    Ok(None) => return None,
+                    Err(err) => return Some((Err(err), None)),
+                }
+            }
+        });
+        PaletteStream {
+            inner: Box::pin(inner),
+        }
+    }
+}
+
+impl WalkState {
+    async fn next_page(&mut self, client: &BlockPalettesClient) -> Result<Option<Vec<Palette>>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        let response = client
+            .fetch_palette_page(&self.sort, self.page, self.limit, self.block.as_deref())
+            .await?;
+        let palettes = self.advance(response);
+        Ok(self.rank(client, palettes))
+    }
+
+    /// Reorders a page returned by [`advance`](Self::advance) with the
+    /// client's ranker, unless the walk is unranked.
+    fn rank(
+        &self,
+        client: &BlockPalettesClient,
+        palettes: Option<Vec<Palette>>,
+    ) -> Option<Vec<Palette>> {
+        match palettes {
+            Some(palettes) if self.ranked => Some(client.ranking.apply(palettes)),
+            palettes => palettes,
+        }
+    }
+
+    /// Moves past the current page, given its `response`, and returns the
+    /// palettes not returned before.
+    fn advance(&mut self, response: PaletteResponse) -> Option<Vec<Palette>> {
+        let batch = response.palettes.unwrap_or_default();
+
+        // palettes removed upstream shifted the listing back, moving unread
+        // palettes onto pages that were already read
+        let shift = self.total_results.map_or(0, |previous| {
+            previous.saturating_sub(response.total_results)
+        });
+        self.total_results = Some(response.total_results);
+        let rewind = shift.div_ceil(self.limit).min(self.page - 1);
+
+        let last_page =
+            batch.is_empty() || response.total_pages.is_some_and(|total| self.page >= total);
+        if rewind > 0 {
+            self.rewinds += 1;
+            self.page -= rewind;
+        } else if last_page {
+            self.done = true;
+            if batch.is_empty() {
+                return None;
+            }
+        } else {
+            self.page += 1;
+        }
+
+        Some(
+            batch
+                .into_iter()
+                .filter(|palette| self.seen.insert(palette.id))
+                .collect(),
+        )
+    }
+
+    /// Returns the last page of the listing, as far as it is known.
+    fn last_page(&self) -> Option<u32> {
+        self.total_results
+            .map(|total| total.div_ceil(self.limit).max(1))
+    }
+}
+
+/// Fetches the pages of a stream, ahead of the consumer if asked to.
+struct Prefetcher {
+    client: BlockPalettesClient,
+    /// Pages being fetched in the background, in page order.
+    pending: VecDeque<(u32, JoinHandle<Result<PaletteResponse>>)>,
+}
+
+impl Prefetcher {
+    async fn next_page(&mut self, state: &mut WalkState) -> Result<Option<Vec<Palette>>> {
+        if state.done {
+            return Ok(None);
+        }
+
+        let response = match self.pending.pop_front() {
+            Some((page, task)) if page == state.page => match task.await {
+                Ok(response) => response?,
+                Err(err) => std::panic::resume_unwind(err.into_panic()),
+            },
+            other => {
+                if let Some((_, task)) = other {
+                    task.abort();
+                }
+                self.cancel();
+                self.client
+                    .fetch_palette_page(
+                        &state.sort,
+                        state.page,
+                        state.limit,
+                        state.block.as_deref(),
+                    )
+                    .await?
+            }
+        };
+
+        let rewinds = state.rewinds;
+        let palettes = state.advance(response);
+        if state.rewinds != rewinds {
+            // the listing shifted, so pages fetched ahead are stale
+            self.cancel();
+        }
+        self.fill(state);
+        Ok(state.rank(&self.client, palettes))
+    }
+
+    /// Starts fetching the next unread pages, up to the prefetch depth of
+    /// `state`.
+    fn fill(&mut self, state: &WalkState) {
+        if state.done || state.prefetch == 0 {
+            return;
+        }
+        // `advance` already moved `state.page` to the next unread page
+        let end = state
+            .last_page()
+            .map_or(u32::MAX, |last| last.saturating_add(1))
+            .min(state.page.saturating_add(state.prefetch));
+        let next = self
+            .pending
+            .back()
+            .map_or(state.page, |(page, _)| page + 1)
+            .max(state.page);
+        for page in next..end {
+            let client = self.client.clone();
+            let sort = state.sort.clone();
+            let (limit, block) = (state.limit, state.block.clone());
+            let task = tokio::spawn(async move {
+                client
+                    .fetch_palette_page(&sort, page, limit, block.as_deref())
+                    .await
+            });
+            self.pending.push_back((page, task));
+        }
+    }
+
+    /// Cancels every page fetched ahead.
+    fn cancel(&mut self) {
+        for (_, task) in self.pending.drain(..) {
+            task.abort();
+        }
+    }
+}
+
+impl Drop for Prefetcher {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+/// A stream of the palettes of a listing, without duplicates.
+///
+/// Create one with [`PaletteWalker::into_stream`].
+pub struct PaletteStream {
+    inner: BoxStream<'static, Result<Palette>>,
+}
+
+impl Stream for PaletteStream {
+    type Item = Result<Palette>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.as_mut().poll_next(cx)
+    }
+}
+
+impl fmt::Debug for PaletteStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PaletteStream").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,205 @@
+//! A compact, shareable text format for palettes.
+//!
+//! A palette string looks like this:
+//!
+//! ```text
+//! bp:stone,dirt,oak_log,cobblestone,sand,gravel#id=1234
+//! ```
+//!
+//! It starts with the `bp:` prefix, followed by the six blocks of the palette
+//! in display order, separated by commas. An optional fragment after `#`
+//! carries `key=value` fields separated by `&`:
+//!
+//! - `v`: the format version. It is omitted for version 1, the current one.
+//! - `id`: the ID of the palette on the site.
+//!
+//! Fields this version does not know about are kept in
+//! [`PaletteString::extra`] and written back out, so strings produced by newer
+//! versions of the format round-trip through older parsers unchanged.
+
+use crate::{Palette, PaletteDetails};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// The prefix of every palette string.
+pub const PREFIX: &str = "bp:";
+
+/// The version of the format written by this crate.
+pub const CURRENT_VERSION: u32 = 1;
+
+/// A palette in the shareable text format.
+///
+/// # Examples
+///
+/// ```rust
+/// use blockpalettes_client::PaletteString;
+///
+/// let parsed = PaletteString::parse("bp:stone,dirt,oak_log,cobblestone,sand,gravel#id=1234").unwrap();
+/// assert_eq!(parsed.blocks[2], "oak_log");
+/// assert_eq!(parsed.id, Some(1234));
+/// assert_eq!(parsed.to_string(), "bp:stone,dirt,oak_log,cobblestone,sand,gravel#id=1234");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PaletteString {
+    /// The format version the string was written with.
+    pub version: u32,
+    /// The six blocks of the palette, in display order.
+    pub blocks: [String; 6],
+    /// The ID of the palette on the site, if known.
+    pub id: Option<u64>,
+    /// Fields not known to this version of the format, by key.
+    pub extra: BTreeMap<String, String>,
+}
+
+/// The error returned when a palette string cannot be parsed.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParsePaletteStringError {
+    /// The string does not start with [`PREFIX`].
+    #[error("palette strings must start with `{PREFIX}`")]
+    MissingPrefix,
+    /// The string does not contain exactly six blocks.
+    #[error("expected 6 blocks, found {0}")]
+    WrongBlockCount(usize),
+    /// A block name is empty or contains reserved characters.
+    #[error("invalid block name `{0}`")]
+    InvalidBlock(String),
+    /// A field in the fragment is not a `key=value` pair.
+    #[error("invalid field `{0}`")]
+    InvalidField(String),
+    /// The `id` or `v` field is not a valid number.
+    #[error("invalid value `{value}` for field `{key}`")]
+    InvalidValue {
+        /// The field key.
+        key: String,
+        /// The offending value.
+        value: String,
+    },
+}
+
+impl PaletteString {
+    /// Creates a palette string for the current format version.
+    pub fn new(blocks: [String; 6], id: Option<u64>) -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            blocks,
+            id,
+            extra: BTreeMap::new(),
+        }
+    }
+
+    /// Parses a palette string.
+    ///
+    /// Surrounding whitespace is ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParsePaletteStringError`] describing the first problem found.
+    pub fn parse(s: &str) -> Result<Self, ParsePaletteStringError> {
+        let rest = s
+            .trim()
+            .strip_prefix(PREFIX)
+            .ok_or(ParsePaletteStringError::MissingPrefix)?;
+        let (blocks, fragment) = match rest.split_once('#') {
+            Some((blocks, fragment)) => (blocks, Some(fragment)),
+            None => (rest, None),
+        };
+
+        let names: Vec<&str> = blocks.split(',').map(str::trim).collect();
+        if names.len() != 6 {
+            return Err(ParsePaletteStringError::WrongBlockCount(names.len()));
+        }
+        if let Some(bad) = names.iter().find(|name| !is_valid_block(name)) {
+            return Err(ParsePaletteStringError::InvalidBlock(bad.to_string()));
+        }
+
+        let mut parsed = Self::new(std::array::from_fn(|i| names[i].to_string()), None);
+        for field in fragment.into_iter().flat_map(|f| f.split('&')) {
+            if field.is_empty() {
+                continue;
+            }
+            let (key, value) = field
+                .split_once('=')
+                .ok_or_else(|| ParsePaletteStringError::InvalidField(field.to_string()))?;
+            let invalid = || ParsePaletteStringError::InvalidValue {
+                key: key.to_string(),
+                value: value.to_string(),
+            };
+            match key {
+                "v" => parsed.version = value.parse().map_err(|_| invalid())?,
+                "id" => parsed.id = Some(value.parse().map_err(|_| invalid())?),
+                _ => {
+                    parsed.extra.insert(key.to_string(), value.to_string());
+                }
+            }
+        }
+
+        Ok(parsed)
+    }
+}
+
+impl fmt::Display for PaletteString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{PREFIX}{}", self.blocks.join(","))?;
+
+        let mut fields = Vec::new();
+        if self.version != CURRENT_VERSION {
+            fields.push(format!("v={}", self.version));
+        }
+        if let Some(id) = self.id {
+            fields.push(format!("id={id}"));
+        }
+        fields.extend(self.extra.iter().map(|(k, v)| format!("{k}={v}")));
+
+        if !fields.is_empty() {
+            write!(f, "#{}", fields.join("&"))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for PaletteString {
+    type Err = ParsePaletteStringError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl From<&Palette> for PaletteString {
+    fn from(palette: &Palette) -> Self {
+        Self::new(
+            [
+                palette.block_one.clone(),
+                palette.block_two.clone(),
+                palette.block_three.clone(),
+                palette.block_four.clone(),
+                palette.block_five.clone(),
+                palette.block_six.clone(),
+            ],
+            Some(palette.id),
+        )
+    }
+}
+
+impl From<&PaletteDetails> for PaletteString {
+    fn from(details: &PaletteDetails) -> Self {
+        Self::new(
+            [
+                details.block_one.clone(),
+                details.block_two.clone(),
+                details.block_three.clone(),
+                details.block_four.clone(),
+                details.block_five.clone(),
+                details.block_six.clone(),
+            ],
+            Some(details.id),
+        )
+    }
+}
+
+/// Block names may not be empty or contain the format's separators.
+fn is_valid_block(name: &str) -> bool {
+    !name.is_empty() && !name.contains([',', '#', '&', '=', ' '])
+}
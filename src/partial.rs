@@ -0,0 +1,76 @@
+//! Results of composite operations that can partially fail.
+//!
+//! Operations made of many requests, such as multi-block palette queries or
+//! fetching the details of many palettes, have `_partial` variants returning a
+//! [`PartialResult`]: everything that succeeded, plus the errors of the
+//! sub-requests that did not, each with a short description of what failed.
+//! One failed request then no longer throws away the rest.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use blockpalettes_client::BlockPalettesError;
+//! use blockpalettes_client::partial::PartialResult;
+//!
+//! let mut result = PartialResult::new(vec![1, 2]);
+//! result.push_error("palette 3", BlockPalettesError::NotFound(3));
+//! assert!(!result.is_complete());
+//! assert_eq!(result.data, [1, 2]);
+//! assert_eq!(result.errors[0].0, "palette 3");
+//!
+//! // callers that want all-or-nothing can still get it
+//! assert!(result.into_complete().is_err());
+//! ```
+
+use crate::{BlockPalettesError, Result};
+
+/// The data produced by a composite operation, and the errors of the
+/// sub-requests that failed.
+#[derive(Debug)]
+pub struct PartialResult<T> {
+    /// Everything the successful sub-requests produced.
+    pub data: T,
+    /// The failed sub-requests, as a description of what was requested
+    /// (e.g., ``block `stone` ``) and the error.
+    pub errors: Vec<(String, BlockPalettesError)>,
+}
+
+impl<T> PartialResult<T> {
+    /// Wraps `data` without any error.
+    pub fn new(data: T) -> Self {
+        Self {
+            data,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Records a failed sub-request.
+    pub fn push_error(&mut self, context: impl Into<String>, error: BlockPalettesError) {
+        self.errors.push((context.into(), error));
+    }
+
+    /// Returns `true` if every sub-request succeeded.
+    pub fn is_complete(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Returns the data if every sub-request succeeded, or the first error.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the first failed sub-request.
+    pub fn into_complete(self) -> Result<T> {
+        match self.errors.into_iter().next() {
+            Some((_, error)) => Err(error),
+            None => Ok(self.data),
+        }
+    }
+
+    /// Transforms the data, keeping the errors.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> PartialResult<U> {
+        PartialResult {
+            data: f(self.data),
+            errors: self.errors,
+        }
+    }
+}
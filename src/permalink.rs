@@ -0,0 +1,114 @@
+//! Recognizing palette links in the formats the site has used over time.
+//!
+//! Links to palettes found in old posts and archives do not all look like
+//! today's `https://www.blockpalettes.com/palette/56655`. [`parse`] accepts:
+//!
+//! - today's pretty paths, with or without scheme, `www.`, trailing slash,
+//!   query string or fragment, and with a slug after the ID
+//!   (`/palette/56655-cozy-cabin`);
+//! - query-string IDs, such as `/palette.php?id=56655` or `/?palette=56655`;
+//! - client-side routes in the fragment, such as `/#/palette/56655`;
+//! - alphanumeric hashes in place of the ID, such as `/palette/a1b2c3d4`;
+//! - bare IDs, such as `56655` or `#56655`.
+//!
+//! Hashes cannot be turned into IDs locally.
+//! [`BlockPalettesClient::resolve_palette_link`] asks the site, following
+//! its redirects to the current URL of the palette.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use blockpalettes_client::permalink::{self, PaletteLink};
+//!
+//! for link in [
+//!     "https://www.blockpalettes.com/palette/56655",
+//!     "blockpalettes.com/palette/56655/",
+//!     "http://blockpalettes.com/palette.php?id=56655",
+//!     "https://www.blockpalettes.com/#/palette/56655",
+//!     "https://www.blockpalettes.com/palette/56655-cozy-cabin?ref=reddit",
+//!     "#56655",
+//! ] {
+//!     assert_eq!(permalink::parse(link), Some(PaletteLink::Id(56655)), "{link}");
+//! }
+//! assert_eq!(
+//!     permalink::parse("https://www.blockpalettes.com/palette/a1b2c3d4"),
+//!     Some(PaletteLink::Hash("a1b2c3d4".to_string()))
+//! );
+//! assert_eq!(permalink::parse("https://www.blockpalettes.com/palettes/popular"), None);
+//! ```
+//!
+//! [`BlockPalettesClient::resolve_palette_link`]: crate::BlockPalettesClient::resolve_palette_link
+
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+
+/// The query parameters that have held palette IDs.
+const ID_PARAMETERS: &[&str] = &["id", "palette", "palette_id"];
+
+/// The path segments that have preceded palette IDs or hashes.
+const PALETTE_SEGMENTS: &[&str] = &["palette", "palettes", "p"];
+
+/// A palette, as named by a link.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PaletteLink {
+    /// The link contains the palette ID.
+    Id(u64),
+    /// The link names the palette by its hash, which only the site can
+    /// resolve to an ID.
+    Hash(String),
+}
+
+/// Recognizes the palette named by `link`, in any of the formats listed in
+/// the [module documentation](self).
+///
+/// The host is not checked, so links to mirrors are recognized too. Returns
+/// `None` if `link` does not name a palette.
+pub fn parse(link: &str) -> Option<PaletteLink> {
+    let link = link.trim().trim_start_matches('<').trim_end_matches('>');
+    if let Ok(id) = link.trim_start_matches('#').parse() {
+        return Some(PaletteLink::Id(id));
+    }
+
+    let url = if link.contains("://") {
+        Url::parse(link)
+    } else {
+        Url::parse(&format!("https://{link}"))
+    }
+    .ok()?;
+
+    // client-side routes keep the interesting part in the fragment
+    url.fragment()
+        .filter(|route| route.starts_with('/'))
+        .and_then(|route| url.join(route).ok())
+        .and_then(|route| from_url(&route))
+        .or_else(|| from_url(&url))
+}
+
+/// Recognizes the palette named by the path or query of `url`.
+fn from_url(url: &Url) -> Option<PaletteLink> {
+    let from_query = url.query_pairs().find_map(|(name, value)| {
+        ID_PARAMETERS
+            .contains(&name.as_ref())
+            .then(|| value.parse().ok())
+            .flatten()
+    });
+    if let Some(id) = from_query {
+        return Some(PaletteLink::Id(id));
+    }
+
+    let segments: Vec<&str> = url.path_segments()?.filter(|s| !s.is_empty()).collect();
+    let position = segments
+        .iter()
+        .position(|segment| PALETTE_SEGMENTS.contains(segment))?;
+    let segment = segments.get(position + 1)?;
+
+    // pretty paths may follow the ID with a slug
+    let digits = segment.split('-').next().unwrap_or_default();
+    if let Ok(id) = digits.parse() {
+        return Some(PaletteLink::Id(id));
+    }
+    // unlike hashes, page names such as `/palettes/popular` have no digits
+    let is_hash = segment.chars().all(|c| c.is_ascii_alphanumeric())
+        && segment.chars().any(|c| c.is_ascii_digit());
+    is_hash.then(|| PaletteLink::Hash(segment.to_string()))
+}
@@ -0,0 +1,238 @@
+//! Load distribution across several HTTP clients.
+//!
+//! A [`ClientPool`] holds several `reqwest::Client`s (typically each configured
+//! with a different proxy) and hands them out round-robin. The pool tracks
+//! the error rate of every client and temporarily ejects clients that fail
+//! too often, so a dead proxy does not slow down a whole crawl.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use blockpalettes_client::BlockPalettesClient;
+//! use blockpalettes_client::pool::ClientPool;
+//! use std::time::Duration;
+//!
+//! let proxied = |proxy: &str| {
+//!     reqwest::Client::builder()
+//!         .proxy(reqwest::Proxy::all(proxy).unwrap())
+//!         .build()
+//!         .unwrap()
+//! };
+//!
+//! let pool = ClientPool::new([
+//!     proxied("http://10.0.0.1:8080"),
+//!     proxied("http://10.0.0.2:8080"),
+//! ])
+//! .max_error_rate(0.3)
+//! .ejection_duration(Duration::from_secs(120));
+//!
+//! let client = BlockPalettesClient::new(reqwest::Client::new()).with_pool(pool);
+//! ```
+
+use reqwest::{Client, StatusCode};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// The default error rate above which a client is ejected.
+pub const DEFAULT_MAX_ERROR_RATE: f64 = 0.5;
+
+/// The default number of requests a client must have made before it can be ejected.
+pub const DEFAULT_MIN_REQUESTS: u64 = 10;
+
+/// The default time an ejected client stays out of rotation.
+pub const DEFAULT_EJECTION_DURATION: Duration = Duration::from_secs(60);
+
+/// A round-robin pool of HTTP clients with health tracking.
+///
+/// Attach it to a client with
+/// [`BlockPalettesClient::with_pool`](crate::BlockPalettesClient::with_pool).
+#[derive(Debug)]
+pub struct ClientPool {
+    members: Vec<Member>,
+    next: AtomicUsize,
+    max_error_rate: f64,
+    min_requests: u64,
+    ejection_duration: Duration,
+}
+
+#[derive(Debug)]
+struct Member {
+    client: Client,
+    requests: AtomicU64,
+    failures: AtomicU64,
+    ejected_until: Mutex<Option<Instant>>,
+}
+
+/// A snapshot of the health of one client in a [`ClientPool`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemberStats {
+    /// The position of the client in the pool, in the order it was added.
+    pub index: usize,
+    /// Requests made since the client was added or last re-admitted.
+    pub requests: u64,
+    /// Failed requests since the client was added or last re-admitted.
+    pub failures: u64,
+    /// Whether the client is currently out of rotation.
+    pub ejected: bool,
+}
+
+impl MemberStats {
+    /// Returns the fraction of failed requests, or `0.0` if none were made.
+    pub fn error_rate(&self) -> f64 {
+        if self.requests == 0 {
+            0.0
+        } else {
+            self.failures as f64 / self.requests as f64
+        }
+    }
+}
+
+impl ClientPool {
+    /// Creates a pool from the given clients using the default health settings.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `clients` is empty.
+    pub fn new(clients: impl IntoIterator<Item = Client>) -> Self {
+        let members: Vec<Member> = clients
+            .into_iter()
+            .map(|client| Member {
+                client,
+                requests: AtomicU64::new(0),
+                failures: AtomicU64::new(0),
+                ejected_until: Mutex::new(None),
+            })
+            .collect();
+        assert!(
+            !members.is_empty(),
+            "a client pool needs at least one client"
+        );
+
+        Self {
+            members,
+            next: AtomicUsize::new(0),
+            max_error_rate: DEFAULT_MAX_ERROR_RATE,
+            min_requests: DEFAULT_MIN_REQUESTS,
+            ejection_duration: DEFAULT_EJECTION_DURATION,
+        }
+    }
+
+    /// Sets the error rate (between `0.0` and `1.0`) above which a client is ejected.
+    pub fn max_error_rate(mut self, rate: f64) -> Self {
+        self.max_error_rate = rate;
+        self
+    }
+
+    /// Sets how many requests a client must have made before it can be ejected.
+    pub fn min_requests(mut self, requests: u64) -> Self {
+        self.min_requests = requests;
+        self
+    }
+
+    /// Sets how long an ejected client stays out of rotation.
+    ///
+    /// Once this time has passed, the client is re-admitted with fresh counters.
+    pub fn ejection_duration(mut self, duration: Duration) -> Self {
+        self.ejection_duration = duration;
+        self
+    }
+
+    /// Returns the number of clients in the pool, healthy or not.
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Returns `true` if the pool has no clients. This is never the case for a
+    /// pool built with [`ClientPool::new`].
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// Returns a health snapshot of every client.
+    pub fn stats(&self) -> Vec<MemberStats> {
+        let now = Instant::now();
+        self.members
+            .iter()
+            .enumerate()
+            .map(|(index, member)| MemberStats {
+                index,
+                requests: member.requests.load(Ordering::Relaxed),
+                failures: member.failures.load(Ordering::Relaxed),
+                ejected: member.is_ejected(now),
+            })
+            .collect()
+    }
+
+    /// Picks the next healthy client.
+    ///
+    /// If every client is ejected, the one whose ejection ends first is used
+    /// rather than failing the request.
+    pub(crate) fn pick(&self) -> (usize, &Client) {
+        let now = Instant::now();
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+
+        for offset in 0..self.members.len() {
+            let index = (start + offset) % self.members.len();
+            let member = &self.members[index];
+            if !member.is_ejected(now) {
+                return (index, &member.client);
+            }
+        }
+
+        let index = self
+            .members
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, m)| *m.ejected_until.lock().unwrap())
+            .map(|(index, _)| index)
+            .unwrap_or_default();
+        (index, &self.members[index].client)
+    }
+
+    /// Records the outcome of a request made with the client at `index`.
+    ///
+    /// Transport errors, `429 Too Many Requests` and server errors count as
+    /// failures.
+    pub(crate) fn record(&self, index: usize, outcome: Result<StatusCode, ()>) {
+        let member = &self.members[index];
+        let failed = is_failure(outcome);
+
+        let requests = member.requests.fetch_add(1, Ordering::Relaxed) + 1;
+        let failures = if failed {
+            member.failures.fetch_add(1, Ordering::Relaxed) + 1
+        } else {
+            member.failures.load(Ordering::Relaxed)
+        };
+
+        if requests >= self.min_requests && failures as f64 / requests as f64 > self.max_error_rate
+        {
+            *member.ejected_until.lock().unwrap() = Some(Instant::now() + self.ejection_duration);
+            member.requests.store(0, Ordering::Relaxed);
+            member.failures.store(0, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Returns `true` if a request outcome counts against the health of a client
+/// or endpoint: transport errors, `429 Too Many Requests` and server errors.
+pub(crate) fn is_failure(outcome: Result<StatusCode, ()>) -> bool {
+    match outcome {
+        Ok(status) => status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error(),
+        Err(()) => true,
+    }
+}
+
+impl Member {
+    fn is_ejected(&self, now: Instant) -> bool {
+        let mut ejected_until = self.ejected_until.lock().unwrap();
+        match *ejected_until {
+            Some(until) if until > now => true,
+            Some(_) => {
+                *ejected_until = None;
+                false
+            }
+            None => false,
+        }
+    }
+}
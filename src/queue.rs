@@ -0,0 +1,200 @@
+//! Prioritized pacing of requests shared by several tasks.
+//!
+//! A bot answering commands and mirroring the site in the background usually
+//! shares one client, and the background job can easily keep it busy. A
+//! [`RequestQueue`] spaces requests at least `interval` apart and, when
+//! several requests are waiting for their turn, lets the one with the highest
+//! [`Priority`] go first, in arrival order among equal priorities. Interactive
+//! commands then only ever wait behind other interactive commands and the
+//! request currently being sent.
+//!
+//! Every request sent by the client takes a turn, including retries and
+//! requests answered by the HTTP cache. Clients made with
+//! [`BlockPalettesClient::with_priority`](crate::BlockPalettesClient::with_priority)
+//! share the queue of the client they were made from, so one queue serves
+//! every priority.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use blockpalettes_client::queue::{Priority, RequestQueue};
+//! use blockpalettes_client::{BlockPalettesClient, SortOrder};
+//! use std::time::Duration;
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let client = BlockPalettesClient::new(reqwest::Client::new())
+//!         .with_request_queue(RequestQueue::new(Duration::from_millis(500)));
+//!
+//!     let crawler = client.clone().with_priority(Priority::Background);
+//!     tokio::spawn(async move {
+//!         let mut walker = crawler.walk_palettes(SortOrder::Recent, 100);
+//!         while let Ok(Some(palettes)) = walker.next_page().await {
+//!             println!("crawled {} palettes", palettes.len());
+//!         }
+//!     });
+//!
+//!     // goes ahead of the crawler's waiting requests
+//!     let bot = client.with_priority(Priority::Interactive);
+//!     let blocks = bot.popular_blocks().await?;
+//!     println!("{}", blocks.len());
+//!     Ok(())
+//! }
+//! ```
+//!
+//! Turns can also be taken directly, to pace other work the same way:
+//!
+//! ```rust
+//! use blockpalettes_client::queue::{Priority, RequestQueue};
+//! use std::sync::Mutex;
+//! use std::time::Duration;
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let queue = RequestQueue::new(Duration::from_millis(20));
+//!     let order = Mutex::new(Vec::new());
+//!     let take = |name, priority| {
+//!         let (queue, order) = (&queue, &order);
+//!         async move {
+//!             queue.acquire(priority).await;
+//!             order.lock().unwrap().push(name);
+//!         }
+//!     };
+//!
+//!     tokio::join!(
+//!         take("crawl 1", Priority::Background),
+//!         take("crawl 2", Priority::Background),
+//!         take("command", Priority::Interactive),
+//!     );
+//!     // the first crawl request went right away, the command overtook the second
+//!     assert_eq!(*order.lock().unwrap(), ["crawl 1", "command", "crawl 2"]);
+//! }
+//! ```
+
+use std::cmp::Reverse;
+use std::collections::BTreeSet;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::time::Instant;
+
+/// How urgently a request should be sent, when several are waiting.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Priority {
+    /// Bulk work nobody is waiting on, such as crawls and mirroring.
+    Background,
+    /// The priority of clients that were not given one.
+    #[default]
+    Normal,
+    /// Requests a user is waiting on, such as bot commands.
+    Interactive,
+}
+
+/// Spaces requests apart, sending waiting requests by priority.
+///
+/// Attach it to a client with
+/// [`BlockPalettesClient::with_request_queue`](crate::BlockPalettesClient::with_request_queue).
+/// Clones of the client share the queue. See the [module documentation](self).
+#[derive(Debug)]
+pub struct RequestQueue {
+    interval: Duration,
+    state: Mutex<QueueState>,
+    /// Notified whenever the head of the queue may have changed.
+    changed: Notify,
+}
+
+#[derive(Debug)]
+struct QueueState {
+    /// The waiting requests, the next one to go first.
+    waiting: BTreeSet<Ticket>,
+    /// The sequence number of the next ticket, ordering equal priorities.
+    next_ticket: u64,
+    /// The earliest time the next request may be sent.
+    next_start: Instant,
+}
+
+type Ticket = (Reverse<Priority>, u64);
+
+impl RequestQueue {
+    /// Creates a queue sending requests at least `interval` apart.
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            state: Mutex::new(QueueState {
+                waiting: BTreeSet::new(),
+                next_ticket: 0,
+                next_start: Instant::now(),
+            }),
+            changed: Notify::new(),
+        }
+    }
+
+    /// Returns the minimum time between two requests.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Returns the number of requests waiting for their turn.
+    pub fn waiting(&self) -> usize {
+        self.state().waiting.len()
+    }
+
+    /// Waits for the turn of a request with `priority`.
+    ///
+    /// The turn is taken when this returns: the next caller waits at least
+    /// the queue's interval. Dropping the future gives up the place in the
+    /// queue.
+    pub async fn acquire(&self, priority: Priority) {
+        let place = Place::new(self, priority);
+        loop {
+            let changed = self.changed.notified();
+            let wake = {
+                let mut state = self.state();
+                if state.waiting.first() != Some(&place.ticket) {
+                    None
+                } else if Instant::now() >= state.next_start {
+                    state.next_start = Instant::now() + self.interval;
+                    break;
+                } else {
+                    Some(state.next_start)
+                }
+            };
+            match wake {
+                // a request with a higher priority may arrive while sleeping,
+                // so the head is checked again on waking up
+                Some(wake) => tokio::time::sleep_until(wake).await,
+                None => changed.await,
+            }
+        }
+        // `place` leaves the queue when dropped
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, QueueState> {
+        self.state.lock().unwrap()
+    }
+}
+
+/// The place of a request in a queue, given up when dropped.
+struct Place<'a> {
+    queue: &'a RequestQueue,
+    ticket: Ticket,
+}
+
+impl<'a> Place<'a> {
+    fn new(queue: &'a RequestQueue, priority: Priority) -> Self {
+        let mut state = queue.state();
+        let ticket = (Reverse(priority), state.next_ticket);
+        state.next_ticket += 1;
+        state.waiting.insert(ticket);
+        drop(state);
+        queue.changed.notify_waiters();
+        Self { queue, ticket }
+    }
+}
+
+impl Drop for Place<'_> {
+    fn drop(&mut self) {
+        self.queue.state().waiting.remove(&self.ticket);
+        self.queue.changed.notify_waiters();
+    }
+}
@@ -0,0 +1,227 @@
+//! Rescoring palette results before they are returned.
+//!
+//! A [`Ranker`] registered with
+//! [`BlockPalettesClient::with_ranker`](crate::BlockPalettesClient::with_ranker)
+//! scores every palette of a result, and the palettes are reordered by
+//! descending score. Palettes with the same score keep the order the client
+//! would have returned them in, so a ranker only needs to score what it wants
+//! to move.
+//!
+//! Ranking is applied to every palette listing the client returns: pages of
+//! listings and searches, and therefore [walkers](crate::pagination) and
+//! streams built on them, merged multi-block searches and similar palettes.
+//! Pages are ranked one at a time, so a paged listing is never reordered
+//! across page boundaries.
+//!
+//! Methods whose results are defined by the site's order keep it:
+//! [`get_recent_palettes`](crate::BlockPalettesClient::get_recent_palettes)
+//! and [`sync_since`](crate::BlockPalettesClient::sync_since) return the
+//! newest palettes first, and the [`watcher`](crate::watcher), top users and
+//! palette of the day are computed from unranked pages.
+//!
+//! Any closure taking a `&Palette` and returning an `f64` is a ranker;
+//! [`Boosts`] covers the common case of favoring featured palettes and
+//! penalizing or promoting specific blocks.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use blockpalettes_client::BlockPalettesClient;
+//! use blockpalettes_client::ranking::Boosts;
+//!
+//! let boosts = Boosts::new()
+//!     .featured(10.0)
+//!     .block("cherry_planks", 2.0)
+//!     .block("dirt", -5.0);
+//! let client = BlockPalettesClient::new(reqwest::Client::new()).with_ranker(boosts);
+//!
+//! // or rank by any function of the palette
+//! let client = client.with_ranker(|palette: &blockpalettes_client::Palette| {
+//!     f64::from(palette.likes).ln_1p()
+//! });
+//! ```
+//!
+//! Rankers reorder walked pages, but not the newest palettes:
+//!
+//! ```rust
+//! # #[cfg(feature = "test-server")]
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use blockpalettes_client::test_server::TestServer;
+//! use blockpalettes_client::{Palette, SortOrder};
+//!
+//! let server = TestServer::start().await?;
+//! // oldest first
+//! let client = server.client().with_ranker(|palette: &Palette| -(palette.id as f64));
+//!
+//! let recent = client.get_recent_palettes(3).await?;
+//! assert!(recent.windows(2).all(|pair| pair[0].date >= pair[1].date));
+//!
+//! let mut walker = client.walk_palettes(SortOrder::Recent, 3);
+//! let page = walker.next_page().await?.unwrap_or_default();
+//! assert!(page.windows(2).all(|pair| pair[0].id < pair[1].id));
+//! # Ok(())
+//! # }
+//! # #[cfg(not(feature = "test-server"))]
+//! # fn main() {}
+//! ```
+
+use crate::Palette;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// Scores palettes to reorder results, highest score first.
+pub trait Ranker: Send + Sync {
+    /// Returns the score of `palette`.
+    ///
+    /// Scores are compared with [`f64::total_cmp`], so `NaN` ranks above
+    /// every other score.
+    fn score(&self, palette: &Palette) -> f64;
+}
+
+impl<F> Ranker for F
+where
+    F: Fn(&Palette) -> f64 + Send + Sync,
+{
+    fn score(&self, palette: &Palette) -> f64 {
+        self(palette)
+    }
+}
+
+/// A [`Ranker`] adding fixed weights for featured palettes and for blocks.
+///
+/// A palette's score is the sum of the weights that apply to it: the
+/// featured weight if it is featured, plus the weight of each of its blocks,
+/// counted once per slot. Negative weights push palettes down.
+///
+/// # Examples
+///
+/// ```rust
+/// use blockpalettes_client::ranking::{self, Boosts};
+/// # use blockpalettes_client::Palette;
+/// # let palette = |id, featured, block: &str| Palette {
+/// #    id, user_id: 1, date: "2023-01-01 12:00:00".to_string(), likes: 0,
+/// #    block_one: block.to_string(), block_two: "dirt".to_string(),
+/// #    block_three: "grass_block".to_string(), block_four: "oak_log".to_string(),
+/// #    block_five: "cobblestone".to_string(), block_six: "sand".to_string(),
+/// #    hidden: Some(0), featured: Some(featured), hash: None, time_ago: String::new()
+/// # };
+/// let mut palettes = vec![
+///     palette(1, 0, "tnt"),
+///     palette(2, 0, "stone"),
+///     palette(3, 1, "stone"),
+/// ];
+///
+/// let boosts = Boosts::new().featured(1.0).block("minecraft:tnt", -1.0);
+/// ranking::rank(&mut palettes, &boosts);
+/// let ids: Vec<u64> = palettes.iter().map(|p| p.id).collect();
+/// assert_eq!(ids, [3, 2, 1]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Boosts {
+    /// The weight added to featured palettes.
+    pub featured: f64,
+    /// The weight added per slot holding each block, keyed by block name
+    /// without the `minecraft:` namespace.
+    pub blocks: BTreeMap<String, f64>,
+}
+
+impl Boosts {
+    /// Creates boosts scoring every palette `0.0`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `weight` to the score of featured palettes.
+    pub fn featured(mut self, weight: f64) -> Self {
+        self.featured = weight;
+        self
+    }
+
+    /// Adds `weight` to the score of palettes for each slot holding `block`.
+    pub fn block(mut self, block: impl AsRef<str>, weight: f64) -> Self {
+        self.blocks
+            .insert(normalize(block.as_ref()).to_string(), weight);
+        self
+    }
+}
+
+impl Ranker for Boosts {
+    fn score(&self, palette: &Palette) -> f64 {
+        let featured = if palette.featured.unwrap_or_default() != 0 {
+            self.featured
+        } else {
+            0.0
+        };
+        let blocks: f64 = palette
+            .blocks()
+            .iter()
+            .filter_map(|b| self.blocks.get(normalize(b)))
+            .sum();
+        featured + blocks
+    }
+}
+
+/// Sorts `palettes` by descending score under `ranker`.
+///
+/// The sort is stable: palettes with the same score keep their relative order.
+/// Each palette is scored once.
+pub fn rank(palettes: &mut [Palette], ranker: &dyn Ranker) {
+    palettes.sort_by_cached_key(|p| std::cmp::Reverse(Score(ranker.score(p))));
+}
+
+/// An `f64` ordered by [`f64::total_cmp`].
+#[derive(PartialEq)]
+struct Score(f64);
+
+impl Eq for Score {}
+
+impl PartialOrd for Score {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Score {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+/// The ranker registered on a client, if any.
+#[derive(Clone, Default)]
+pub(crate) struct Ranking(Option<Arc<dyn Ranker>>);
+
+impl Ranking {
+    pub(crate) const fn new() -> Self {
+        Self(None)
+    }
+
+    pub(crate) fn set(&mut self, ranker: Arc<dyn Ranker>) {
+        self.0 = Some(ranker);
+    }
+
+    /// Reorders `palettes` with the registered ranker, if any.
+    pub(crate) fn apply(&self, mut palettes: Vec<Palette>) -> Vec<Palette> {
+        if let Some(ranker) = &self.0 {
+            rank(&mut palettes, ranker.as_ref());
+        }
+        palettes
+    }
+}
+
+impl fmt::Debug for Ranking {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Ranking")
+            .field("ranker", &self.0.is_some())
+            .finish()
+    }
+}
+
+fn normalize(block: &str) -> &str {
+    let block = block.trim();
+    block.strip_prefix("minecraft:").unwrap_or(block)
+}
@@ -0,0 +1,152 @@
+//! Building variations of existing palettes locally.
+//!
+//! A [`PaletteBuilder`] starts from an existing palette and applies edits,
+//! such as swapping a block for another one. The result is a local candidate
+//! palette: it can be shared as a [`PaletteString`], or used to look for
+//! similar palettes on the site through [`PaletteBuilder::queries`].
+//!
+//! # Examples
+//!
+//! ```rust
+//! use blockpalettes_client::remix::{BlockSlot, PaletteBuilder};
+//! # let palette = blockpalettes_client::Palette {
+//! #    id: 1234, user_id: 1, date: "2023-01-01 12:00:00".to_string(), likes: 10,
+//! #    block_one: "stone".to_string(), block_two: "dirt".to_string(),
+//! #    block_three: "oak_planks".to_string(), block_four: "cobblestone".to_string(),
+//! #    block_five: "copper_block".to_string(), block_six: "gravel".to_string(),
+//! #    hidden: Some(0), featured: Some(0), hash: None, time_ago: String::new()
+//! # };
+//!
+//! let remix = PaletteBuilder::from(&palette)
+//!     .replace_slot(BlockSlot::Three, "spruce_planks")
+//!     .replace_with_nearest_color(BlockSlot::Five);
+//!
+//! assert_eq!(remix.block(BlockSlot::Three), "spruce_planks");
+//! assert_eq!(remix.block(BlockSlot::Five), "cut_copper");
+//! assert_eq!(
+//!     remix.build().to_string(),
+//!     "bp:stone,dirt,spruce_planks,cobblestone,cut_copper,gravel"
+//! );
+//! ```
+
+use crate::blocks::{self, BlockQuery};
+use crate::{Palette, PaletteDetails, PaletteString};
+use serde::{Deserialize, Serialize};
+
+/// One of the six block positions of a palette, in display order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum BlockSlot {
+    /// The first block.
+    One,
+    /// The second block.
+    Two,
+    /// The third block.
+    Three,
+    /// The fourth block.
+    Four,
+    /// The fifth block.
+    Five,
+    /// The sixth block.
+    Six,
+}
+
+impl BlockSlot {
+    /// All slots, in display order.
+    pub const ALL: [BlockSlot; 6] = [
+        BlockSlot::One,
+        BlockSlot::Two,
+        BlockSlot::Three,
+        BlockSlot::Four,
+        BlockSlot::Five,
+        BlockSlot::Six,
+    ];
+
+    /// Returns the zero-based position of the slot.
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// A candidate palette built from an existing one.
+///
+/// Every edit consumes the builder and returns it, so edits can be chained.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PaletteBuilder {
+    blocks: [String; 6],
+}
+
+impl PaletteBuilder {
+    /// Creates a builder from six blocks, in display order.
+    pub fn new(blocks: [String; 6]) -> Self {
+        Self { blocks }
+    }
+
+    /// Returns the block in `slot`.
+    pub fn block(&self, slot: BlockSlot) -> &str {
+        &self.blocks[slot.index()]
+    }
+
+    /// Returns the six blocks, in display order.
+    pub fn blocks(&self) -> [&str; 6] {
+        std::array::from_fn(|i| self.blocks[i].as_str())
+    }
+
+    /// Replaces the block in `slot`.
+    pub fn replace_slot(mut self, slot: BlockSlot, block: impl Into<String>) -> Self {
+        self.blocks[slot.index()] = block.into();
+        self
+    }
+
+    /// Swaps the blocks in two slots.
+    pub fn swap_slots(mut self, a: BlockSlot, b: BlockSlot) -> Self {
+        self.blocks.swap(a.index(), b.index());
+        self
+    }
+
+    /// Replaces the block in `slot` with the block closest to it in color.
+    ///
+    /// Blocks already in the palette are never picked. If the color of the
+    /// current block is unknown (see [`blocks::color`]), the slot is left
+    /// unchanged.
+    pub fn replace_with_nearest_color(self, slot: BlockSlot) -> Self {
+        let current = self.blocks();
+        match blocks::nearest_by_color(current[slot.index()], &current) {
+            Some(nearest) => self.replace_slot(slot, nearest),
+            None => self,
+        }
+    }
+
+    /// Returns one [`BlockQuery`] per block, to find similar palettes on the site.
+    ///
+    /// Pass them to
+    /// [`get_palettes_matching`](crate::BlockPalettesClient::get_palettes_matching)
+    /// to retrieve palettes containing every block of the candidate.
+    pub fn queries(&self) -> Vec<BlockQuery<'_>> {
+        self.blocks.iter().map(|b| BlockQuery::Block(b)).collect()
+    }
+
+    /// Finishes the candidate as a shareable [`PaletteString`].
+    ///
+    /// The result has no ID, since the palette does not exist on the site.
+    pub fn build(self) -> PaletteString {
+        PaletteString::new(self.blocks, None)
+    }
+}
+
+impl From<&Palette> for PaletteBuilder {
+    fn from(palette: &Palette) -> Self {
+        Self::new(palette.blocks().map(str::to_string))
+    }
+}
+
+impl From<&PaletteDetails> for PaletteBuilder {
+    fn from(details: &PaletteDetails) -> Self {
+        Self::from(&PaletteString::from(details))
+    }
+}
+
+impl From<&PaletteString> for PaletteBuilder {
+    fn from(palette: &PaletteString) -> Self {
+        Self::new(palette.blocks.clone())
+    }
+}
@@ -0,0 +1,507 @@
+//! Rendering palettes to PNG images.
+//!
+//! Palettes are drawn as a 3x2 grid of block swatches in the colors of
+//! [`blocks::color`], optionally labeled with their ID and number of likes.
+//! [`palette_png`] renders a single palette, and [`mosaic`] lays many of them
+//! out on one contact sheet, e.g. for a weekly "top 20 palettes" post, and
+//! [`comparison`] shows two palettes side by side with their differences.
+//! [`site_preview`] draws a palette that has not been submitted yet as a card
+//! styled like the site's. Blocks whose color is unknown are drawn with a
+//! gray checkerboard.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use blockpalettes_client::render::{self, RenderOptions};
+//! # use blockpalettes_client::Palette;
+//! # let palette: Palette = serde_json::from_value(serde_json::json!({
+//! #     "id": 1, "user_id": 1, "date": "2024-01-01 00:00:00", "likes": 12,
+//! #     "blockOne": "stone", "blockTwo": "oak_planks", "blockThree": "glass",
+//! #     "blockFour": "dirt", "blockFive": "sand", "blockSix": "not_a_block",
+//! #     "hash": null, "time_ago": "1 day ago"
+//! # })).unwrap();
+//! # let palettes = vec![palette; 20];
+//!
+//! let png = render::mosaic(&palettes, 5, &RenderOptions::default());
+//! assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+//!
+//! // 5 columns and 4 rows of palettes
+//! let width = u32::from_be_bytes(png[16..20].try_into().unwrap());
+//! let height = u32::from_be_bytes(png[20..24].try_into().unwrap());
+//! assert_eq!((width, height), (5 * 120 + 6 * 12, 4 * (80 + 44) + 5 * 12));
+//! ```
+
+mod font;
+
+use crate::color::Rgb;
+use crate::remix::PaletteBuilder;
+use crate::{Palette, blocks, diff, png};
+
+/// The number of swatch columns in a rendered palette.
+const SWATCH_COLUMNS: u32 = 3;
+
+/// The number of swatch rows in a rendered palette.
+const SWATCH_ROWS: u32 = 2;
+
+/// The two colors of the checkerboard drawn for blocks of unknown color.
+pub(crate) const UNKNOWN_COLORS: [Rgb; 2] = [Rgb::new(160, 160, 160), Rgb::new(96, 96, 96)];
+
+/// The outline of blocks found in both palettes of a [`comparison`].
+const SHARED_COLOR: Rgb = Rgb::new(46, 160, 67);
+
+/// The outline of blocks found in only one palette of a [`comparison`].
+const CHANGED_COLOR: Rgb = Rgb::new(214, 39, 40);
+
+/// The color of the slot markers of a [`comparison`].
+const MARKER_TEXT_COLOR: Rgb = Rgb::new(255, 255, 255);
+
+/// The color of the card of a [`site_preview`].
+const CARD_COLOR: Rgb = Rgb::new(255, 255, 255);
+
+/// How palettes are drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenderOptions {
+    /// The width and height of a block swatch, in pixels.
+    pub swatch_size: u32,
+    /// The space around and between palettes, in pixels.
+    pub gap: u32,
+    /// Whether to draw the ID and likes of each palette below it.
+    pub labels: bool,
+    /// The size of a font pixel, in image pixels.
+    pub text_scale: u32,
+    /// The color of the background.
+    pub background: Rgb,
+    /// The color of the labels.
+    pub text_color: Rgb,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            swatch_size: 40,
+            gap: 12,
+            labels: true,
+            text_scale: 2,
+            background: Rgb::new(245, 245, 245),
+            text_color: Rgb::new(32, 32, 32),
+        }
+    }
+}
+
+impl RenderOptions {
+    /// Returns the height of a label line, in pixels.
+    fn line_height(&self) -> u32 {
+        (font::GLYPH_HEIGHT + 2) * self.text_scale
+    }
+
+    /// Returns the height of the labels below a palette, or `0` without labels.
+    fn labels_height(&self) -> u32 {
+        if self.labels {
+            self.text_scale * 4 + 2 * self.line_height()
+        } else {
+            0
+        }
+    }
+
+    /// Returns the size of a rendered palette, including its label.
+    fn palette_size(&self) -> (u32, u32) {
+        let width = SWATCH_COLUMNS * self.swatch_size;
+        let height = SWATCH_ROWS * self.swatch_size + self.labels_height();
+        (width, height)
+    }
+}
+
+/// Renders a single palette as a PNG image.
+///
+/// The palette is surrounded by a margin of [`RenderOptions::gap`] pixels.
+pub fn palette_png(palette: &Palette, options: &RenderOptions) -> Vec<u8> {
+    mosaic(std::slice::from_ref(palette), 1, options)
+}
+
+/// Renders `palettes` on a single PNG contact sheet, `columns` palettes wide.
+///
+/// Palettes are laid out left to right, then top to bottom, in the given
+/// order, so sort them beforehand (e.g. by likes) to rank them on the sheet.
+/// Every palette gets the same size; labels too long for it are cut off.
+pub fn mosaic(palettes: &[Palette], columns: usize, options: &RenderOptions) -> Vec<u8> {
+    let columns = columns.clamp(1, palettes.len().max(1));
+    let rows = palettes.len().div_ceil(columns);
+    let (cell_width, cell_height) = options.palette_size();
+    let gap = options.gap;
+
+    let width = gap + columns as u32 * (cell_width + gap);
+    let height = gap + rows as u32 * (cell_height + gap);
+    let mut canvas = Canvas::new(width.max(1), height.max(1), options.background);
+
+    for (i, palette) in palettes.iter().enumerate() {
+        let x = gap + (i % columns) as u32 * (cell_width + gap);
+        let y = gap + (i / columns) as u32 * (cell_height + gap);
+        draw_palette(&mut canvas, x, y, palette, options);
+    }
+    canvas.into_png()
+}
+
+/// Renders `first` and `second` side by side, highlighting their differences.
+///
+/// Every swatch is outlined in green if the other palette has the same block
+/// in any slot, and in red otherwise. Slots whose block differs between the
+/// palettes are marked: `-` on a block missing from `second`, `+` on a block
+/// missing from `first`, and `~` on a shared block that moved to another
+/// slot. With labels enabled, a summary of the [`diff`] is
+/// written below both palettes.
+///
+/// # Examples
+///
+/// ```rust
+/// use blockpalettes_client::render::{self, RenderOptions};
+/// # let palette = |id, blocks: [&str; 6]| blockpalettes_client::Palette {
+/// #    id, user_id: 1, date: "2023-01-01 12:00:00".to_string(), likes: 10,
+/// #    block_one: blocks[0].to_string(), block_two: blocks[1].to_string(),
+/// #    block_three: blocks[2].to_string(), block_four: blocks[3].to_string(),
+/// #    block_five: blocks[4].to_string(), block_six: blocks[5].to_string(),
+/// #    hidden: Some(0), featured: Some(0), hash: None, time_ago: String::new()
+/// # };
+/// let original = palette(1, ["stone", "andesite", "oak_planks", "spruce_log", "moss_block", "dirt"]);
+/// let remix = palette(2, ["stone", "andesite", "birch_planks", "spruce_log", "moss_block", "dirt"]);
+///
+/// let png = render::comparison(&original, &remix, &RenderOptions::default());
+/// assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+/// ```
+pub fn comparison(first: &Palette, second: &Palette, options: &RenderOptions) -> Vec<u8> {
+    let diff = diff::palettes(first, second);
+    let (cell_width, cell_height) = options.palette_size();
+    let gap = options.gap;
+    let footer = if options.labels {
+        options.text_scale * 4 + options.line_height()
+    } else {
+        0
+    };
+
+    let width = 2 * cell_width + 4 * gap;
+    let height = cell_height + footer + 2 * gap;
+    let mut canvas = Canvas::new(width.max(1), height.max(1), options.background);
+
+    let right = gap + cell_width + 2 * gap;
+    for (x, palette, other, marker) in [(gap, first, second, '-'), (right, second, first, '+')] {
+        draw_palette(&mut canvas, x, gap, palette, options);
+
+        let size = options.swatch_size;
+        let border = (size / 10).max(2);
+        let other = other.blocks();
+        for (slot, block) in palette.blocks().into_iter().enumerate() {
+            let (sx, sy) = swatch_origin(x, gap, slot, size, 0);
+            let shared = other.contains(&block);
+            let color = if shared { SHARED_COLOR } else { CHANGED_COLOR };
+            canvas.outline(sx, sy, size, size, border, color);
+
+            if diff.changed_slots.contains(&slot) {
+                let marker = if shared { '~' } else { marker };
+                let scale = options.text_scale;
+                let side = (font::GLYPH_HEIGHT + 2) * scale;
+                canvas.fill_rect(sx, sy, side, side, color);
+                let text = marker.to_string();
+                canvas.text(
+                    sx + scale,
+                    sy + scale,
+                    &text,
+                    side,
+                    scale,
+                    MARKER_TEXT_COLOR,
+                );
+            }
+        }
+    }
+
+    if options.labels {
+        let summary = if diff.is_identical() {
+            "identical".to_string()
+        } else if diff.is_reordered() {
+            "same blocks, reordered".to_string()
+        } else {
+            format!(
+                "{} shared, {} changed",
+                diff.shared.len(),
+                diff.block_changes()
+            )
+        };
+        let y = gap + cell_height + options.text_scale * 4;
+        let max_width = width.saturating_sub(2 * gap);
+        canvas.text(
+            gap,
+            y,
+            &summary,
+            max_width,
+            options.text_scale,
+            options.text_color,
+        );
+    }
+    canvas.into_png()
+}
+
+/// Renders a palette that has not been submitted yet as a card styled like
+/// the site's, to preview a submission before posting it.
+///
+/// Blocks are drawn in display order, left to right then top to bottom, as
+/// the site orders them, on rounded swatches slightly apart from each other,
+/// inside a white card with rounded corners. With labels enabled, the card
+/// is labeled as a draft with no likes yet. The card is surrounded by a
+/// margin of [`RenderOptions::gap`] pixels of the background color.
+///
+/// # Examples
+///
+/// ```rust
+/// use blockpalettes_client::remix::PaletteBuilder;
+/// use blockpalettes_client::render::{self, RenderOptions};
+///
+/// let draft = PaletteBuilder::new(
+///     ["stone", "andesite", "oak_planks", "spruce_log", "moss_block", "dirt"].map(String::from),
+/// );
+/// let png = render::site_preview(&draft, &RenderOptions::default());
+/// assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+///
+/// // a 136x136 card with a 12 pixel margin
+/// let width = u32::from_be_bytes(png[16..20].try_into().unwrap());
+/// let height = u32::from_be_bytes(png[20..24].try_into().unwrap());
+/// assert_eq!((width, height), (160, 160));
+/// ```
+pub fn site_preview(draft: &PaletteBuilder, options: &RenderOptions) -> Vec<u8> {
+    let size = options.swatch_size;
+    let spacing = (size / 10).max(1);
+    let gap = options.gap;
+
+    let card_width = SWATCH_COLUMNS * size + (SWATCH_COLUMNS + 1) * spacing;
+    let card_height = SWATCH_ROWS * size + (SWATCH_ROWS + 1) * spacing + options.labels_height();
+    let width = card_width + 2 * gap;
+    let height = card_height + 2 * gap;
+    let mut canvas = Canvas::new(width.max(1), height.max(1), options.background);
+
+    canvas.fill_rect(gap, gap, card_width, card_height, CARD_COLOR);
+    canvas.round_corners(
+        gap,
+        gap,
+        card_width,
+        card_height,
+        2 * spacing,
+        options.background,
+    );
+
+    let (x, y) = (gap + spacing, gap + spacing);
+    let radius = size / 6;
+    for (slot, block) in draft.blocks().into_iter().enumerate() {
+        let (sx, sy) = swatch_origin(x, y, slot, size, spacing);
+        draw_swatch(&mut canvas, sx, sy, size, block);
+        canvas.round_corners(sx, sy, size, size, radius, CARD_COLOR);
+    }
+
+    let top = y + SWATCH_ROWS * size + (SWATCH_ROWS - 1) * spacing;
+    let inner_width = card_width - 2 * spacing;
+    draw_labels(
+        &mut canvas,
+        x,
+        top,
+        inner_width,
+        ["draft", "0 likes"],
+        options,
+    );
+    canvas.into_png()
+}
+
+/// Returns the top-left corner of the swatch of `slot` in a palette drawn at
+/// `(x, y)`, with swatches `size` pixels wide and `spacing` pixels apart.
+fn swatch_origin(x: u32, y: u32, slot: usize, size: u32, spacing: u32) -> (u32, u32) {
+    let (column, row) = (slot as u32 % SWATCH_COLUMNS, slot as u32 / SWATCH_COLUMNS);
+    (x + column * (size + spacing), y + row * (size + spacing))
+}
+
+/// Draws the swatch of `block` with its top-left corner at `(x, y)`.
+fn draw_swatch(canvas: &mut Canvas, x: u32, y: u32, size: u32, block: &str) {
+    match blocks::color(block) {
+        Some(color) => canvas.fill_rect(x, y, size, size, color),
+        None => canvas.checkerboard(x, y, size, size, UNKNOWN_COLORS),
+    }
+}
+
+/// Draws the label lines of a palette whose swatches end at `top`, if labels
+/// are enabled.
+fn draw_labels<S: AsRef<str>>(
+    canvas: &mut Canvas,
+    x: u32,
+    top: u32,
+    width: u32,
+    lines: [S; 2],
+    options: &RenderOptions,
+) {
+    if !options.labels {
+        return;
+    }
+    let top = top + options.text_scale * 4;
+    for (i, line) in lines.iter().enumerate() {
+        let line_y = top + i as u32 * options.line_height();
+        canvas.text(
+            x,
+            line_y,
+            line.as_ref(),
+            width,
+            options.text_scale,
+            options.text_color,
+        );
+    }
+}
+
+/// Draws a palette, and its label if enabled, with its top-left corner at `(x, y)`.
+fn draw_palette(canvas: &mut Canvas, x: u32, y: u32, palette: &Palette, options: &RenderOptions) {
+    let size = options.swatch_size;
+    for (slot, block) in palette.blocks().into_iter().enumerate() {
+        let (sx, sy) = swatch_origin(x, y, slot, size, 0);
+        draw_swatch(canvas, sx, sy, size, block);
+    }
+
+    let (width, _) = options.palette_size();
+    let likes = match palette.likes {
+        1 => "1 like".to_string(),
+        n => format!("{n} likes"),
+    };
+    let top = y + SWATCH_ROWS * size;
+    draw_labels(
+        canvas,
+        x,
+        top,
+        width,
+        [format!("#{}", palette.id), likes],
+        options,
+    );
+}
+
+/// An RGB image being drawn.
+#[derive(Debug, Clone)]
+pub(crate) struct Canvas {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    /// Creates a canvas filled with `background`.
+    pub(crate) fn new(width: u32, height: u32, background: Rgb) -> Self {
+        let pixels = [background.r, background.g, background.b].repeat((width * height) as usize);
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Sets a single pixel, ignoring pixels outside the canvas.
+    fn set(&mut self, x: u32, y: u32, color: Rgb) {
+        if x < self.width && y < self.height {
+            let offset = ((y * self.width + x) * 3) as usize;
+            self.pixels[offset..offset + 3].copy_from_slice(&[color.r, color.g, color.b]);
+        }
+    }
+
+    /// Fills a rectangle, clipped to the canvas.
+    pub(crate) fn fill_rect(&mut self, x: u32, y: u32, width: u32, height: u32, color: Rgb) {
+        for py in y..(y + height).min(self.height) {
+            for px in x..(x + width).min(self.width) {
+                self.set(px, py, color);
+            }
+        }
+    }
+
+    /// Draws the inner border of a rectangle, `thickness` pixels wide.
+    pub(crate) fn outline(
+        &mut self,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+        thickness: u32,
+        color: Rgb,
+    ) {
+        let thickness = thickness.min(width).min(height);
+        self.fill_rect(x, y, width, thickness, color);
+        self.fill_rect(x, y + height - thickness, width, thickness, color);
+        self.fill_rect(x, y, thickness, height, color);
+        self.fill_rect(x + width - thickness, y, thickness, height, color);
+    }
+
+    /// Rounds the corners of a rectangle with a `radius` pixels wide quarter
+    /// circle, by painting the pixels outside of it with `color`.
+    pub(crate) fn round_corners(
+        &mut self,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+        radius: u32,
+        color: Rgb,
+    ) {
+        let radius = radius.min(width / 2).min(height / 2);
+        let limit = (radius * radius) as f32;
+        for dy in 0..radius {
+            for dx in 0..radius {
+                // distance between the pixel's center and the circle's center
+                let cx = radius as f32 - dx as f32 - 0.5;
+                let cy = radius as f32 - dy as f32 - 0.5;
+                if cx * cx + cy * cy > limit {
+                    self.set(x + dx, y + dy, color);
+                    self.set(x + width - 1 - dx, y + dy, color);
+                    self.set(x + dx, y + height - 1 - dy, color);
+                    self.set(x + width - 1 - dx, y + height - 1 - dy, color);
+                }
+            }
+        }
+    }
+
+    /// Fills a rectangle with a checkerboard of squares a quarter of its width.
+    pub(crate) fn checkerboard(
+        &mut self,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+        colors: [Rgb; 2],
+    ) {
+        let square = (width / 4).max(1);
+        for py in y..(y + height).min(self.height) {
+            for px in x..(x + width).min(self.width) {
+                let parity = ((px - x) / square + (py - y) / square) % 2;
+                self.set(px, py, colors[parity as usize]);
+            }
+        }
+    }
+
+    /// Draws a line of text with its top-left corner at `(x, y)`, cutting off
+    /// the glyphs that do not fit in `max_width` pixels.
+    pub(crate) fn text(
+        &mut self,
+        x: u32,
+        y: u32,
+        text: &str,
+        max_width: u32,
+        scale: u32,
+        color: Rgb,
+    ) {
+        let advance = font::ADVANCE * scale;
+        for (i, c) in text.chars().enumerate() {
+            let gx = x + i as u32 * advance;
+            if gx + font::GLYPH_WIDTH * scale > x + max_width {
+                break;
+            }
+            for (column, bits) in font::glyph(c).into_iter().enumerate() {
+                for row in 0..font::GLYPH_HEIGHT {
+                    if bits & (1 << row) != 0 {
+                        let px = gx + column as u32 * scale;
+                        let py = y + row * scale;
+                        self.fill_rect(px, py, scale, scale, color);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Encodes the canvas as a PNG image.
+    pub(crate) fn into_png(self) -> Vec<u8> {
+        png::encode_rgb(self.width, self.height, &self.pixels)
+    }
+}
@@ -0,0 +1,186 @@
+//! Trend reports over palette corpora.
+//!
+//! [`update_impact`] follows how the blocks added by a Minecraft update are
+//! adopted in palettes, month by month after the release, combining the
+//! block versions of [`blocks::introduced_in`] with the monthly cohorts of
+//! [`stats`]. Reports export as CSV for spreadsheets and as JSON.
+//!
+//! Everything in this module works offline, on palettes already fetched.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use blockpalettes_client::report;
+//! # let palette = |date: &str, blocks: [&str; 6]| blockpalettes_client::Palette {
+//! #    id: 1, user_id: 1, date: date.to_string(), likes: 10,
+//! #    block_one: blocks[0].to_string(), block_two: blocks[1].to_string(),
+//! #    block_three: blocks[2].to_string(), block_four: blocks[3].to_string(),
+//! #    block_five: blocks[4].to_string(), block_six: blocks[5].to_string(),
+//! #    hidden: Some(0), featured: Some(0), hash: None, time_ago: String::new()
+//! # };
+//! let stone = ["stone", "andesite", "cobblestone", "gravel", "tuff", "oak_planks"];
+//! let cherry = ["cherry_planks", "cherry_log", "pink_wool", "stone", "calcite", "moss_block"];
+//! let corpus = [
+//!     palette("2023-05-20 10:00:00", stone),
+//!     palette("2023-06-12 18:30:00", stone),
+//!     palette("2023-07-02 09:15:00", cherry),
+//!     palette("2023-07-21 14:00:00", stone),
+//! ];
+//!
+//! let impact = report::update_impact("1.20".parse().unwrap(), &corpus);
+//! assert!(impact.new_blocks.contains(&"cherry_log".to_string()));
+//! // months before the release are left out
+//! assert_eq!(impact.months.len(), 2);
+//! let july = &impact.months[1];
+//! assert_eq!((july.palettes, july.adopting), (2, 1));
+//! assert_eq!(july.share(), 0.5);
+//!
+//! let csv = impact.to_csv();
+//! assert!(csv.starts_with("month,block,palettes,using,share\n"));
+//! assert!(csv.contains("\n2023-07,*,2,1,0.5000\n"));
+//! assert!(csv.contains("\n2023-07,cherry_log,2,1,0.5000\n"));
+//! ```
+
+use crate::blocks::{self, GameVersion};
+use crate::collections::csv_field;
+use crate::stats::{self, YearMonth};
+use crate::{Palette, Result};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// The adoption of the blocks added by a Minecraft update, see
+/// [`update_impact`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UpdateImpact {
+    /// The update.
+    pub version: GameVersion,
+    /// The release date of the update, if known.
+    pub release_date: Option<NaiveDate>,
+    /// The blocks added by the update, in alphabetical order.
+    pub new_blocks: Vec<String>,
+    /// The adoption of the new blocks in each month with palettes, oldest
+    /// first.
+    pub months: Vec<MonthlyAdoption>,
+}
+
+/// The adoption of the blocks added by an update in the palettes created in
+/// one month.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MonthlyAdoption {
+    /// The month the palettes were created in.
+    pub month: YearMonth,
+    /// The number of palettes created in the month.
+    pub palettes: usize,
+    /// The number of palettes using at least one of the new blocks.
+    pub adopting: usize,
+    /// The number of palettes using each new block, in the order of
+    /// [`UpdateImpact::new_blocks`].
+    pub blocks: Vec<usize>,
+}
+
+impl MonthlyAdoption {
+    /// Returns the share of the palettes using at least one of the new
+    /// blocks, from `0.0` to `1.0`.
+    pub fn share(&self) -> f64 {
+        share(self.adopting, self.palettes)
+    }
+}
+
+impl UpdateImpact {
+    /// Exports the report as CSV, with one row per month for all new blocks
+    /// together (block `*`) followed by one row per month and new block.
+    ///
+    /// The columns are `month` (`YYYY-MM`), `block`, `palettes`, `using`
+    /// (the number of palettes using the block) and `share` (`using` divided
+    /// by `palettes`).
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("month,block,palettes,using,share\n");
+        for month in &self.months {
+            let any = ("*", month.adopting);
+            let each = self
+                .new_blocks
+                .iter()
+                .map(String::as_str)
+                .zip(month.blocks.iter().copied());
+            for (block, using) in std::iter::once(any).chain(each) {
+                let row = [
+                    month.month.to_string(),
+                    csv_field(block),
+                    month.palettes.to_string(),
+                    using.to_string(),
+                    format!("{:.4}", share(using, month.palettes)),
+                ]
+                .join(",");
+                out.push_str(&row);
+                out.push('\n');
+            }
+        }
+        out
+    }
+
+    /// Exports the report as pretty-printed JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+/// Measures how the blocks introduced in `version` (see
+/// [`blocks::introduced_blocks`]) are adopted in `palettes`, month by month.
+///
+/// Months start with the month of the release, or with the oldest palette
+/// if the release date is unknown. Months without palettes are left out, as
+/// are palettes whose date cannot be parsed.
+pub fn update_impact(version: GameVersion, palettes: &[Palette]) -> UpdateImpact {
+    let release_date = version.release_date();
+    let new_blocks = blocks::introduced_blocks(version);
+    let first_month = release_date.map(|date| YearMonth::of(&date));
+
+    let months = stats::by_month(palettes)
+        .into_iter()
+        .filter(|(month, _)| first_month.is_none_or(|first| *month >= first))
+        .map(|(month, palettes)| {
+            let mut adopting = 0;
+            let mut counts = vec![0; new_blocks.len()];
+            for palette in &palettes {
+                let used: Vec<bool> = new_blocks
+                    .iter()
+                    .map(|new| {
+                        palette
+                            .blocks()
+                            .iter()
+                            .any(|block| blocks::normalize(block) == *new)
+                    })
+                    .collect();
+                for (count, used) in counts.iter_mut().zip(&used) {
+                    *count += usize::from(*used);
+                }
+                adopting += usize::from(used.contains(&true));
+            }
+            MonthlyAdoption {
+                month,
+                palettes: palettes.len(),
+                adopting,
+                blocks: counts,
+            }
+        })
+        .collect();
+
+    UpdateImpact {
+        version,
+        release_date,
+        new_blocks: new_blocks.into_iter().map(String::from).collect(),
+        months,
+    }
+}
+
+fn share(part: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 / total as f64
+    }
+}
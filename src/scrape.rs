@@ -0,0 +1,424 @@
+//! HTML scraping of the site's pages.
+//!
+//! Scraping relies on the specific HTML structure of `blockpalettes.com`, so
+//! it can break whenever the site's front-end changes. To make such breakage
+//! visible, scrapers report [`ScrapeWarning`]s for everything that looks off,
+//! and a [`ScrapeMode`] decides whether warnings are returned alongside the
+//! data or turned into an error.
+//!
+//! Pages fetched or saved by other means are parsed with the same code by
+//! [`scrape_palette_html`], [`scrape_block_html`] and [`scrape_user_html`],
+//! and single palette cards by [`parse_palette_card`]; the `scraper` types it
+//! works with are re-exported.
+
+use crate::{BlockPageDetails, BlockPalettesError, PaletteCard, PalettePageDetails, Result};
+pub use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use thiserror::Error;
+
+/// The default size, in bytes, from which scraped pages are parsed on the
+/// blocking thread pool (see
+/// [`BlockPalettesClient::with_parse_offload`](crate::BlockPalettesClient::with_parse_offload)).
+pub const DEFAULT_OFFLOAD_THRESHOLD: usize = 64 * 1024;
+
+/// The number of blocks a palette page is expected to show.
+const PALETTE_BLOCK_COUNT: usize = 6;
+
+/// How scrapers react to pages that do not look as expected.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ScrapeMode {
+    /// Return whatever could be extracted, with a list of [`ScrapeWarning`]s.
+    #[default]
+    Lenient,
+    /// Fail with [`BlockPalettesError::ScrapeIncomplete`] if anything is
+    /// missing or malformed.
+    Strict,
+}
+
+/// A problem noticed while scraping a page.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScrapeWarning {
+    /// An expected section of the page was not found at all.
+    MissingSection {
+        /// The name of the missing section (e.g., "blocks").
+        section: String,
+    },
+    /// A palette page did not show the expected number of blocks.
+    UnexpectedBlockCount {
+        /// The number of blocks a palette has.
+        expected: usize,
+        /// The number of blocks found on the page.
+        found: usize,
+    },
+    /// A block element had no name.
+    EmptyBlockName {
+        /// The zero-based position of the block on the page.
+        index: usize,
+    },
+    /// A palette link did not point to a recognizable palette ID.
+    UnparsableLink {
+        /// The `href` of the link, if it had one.
+        href: Option<String>,
+    },
+}
+
+impl fmt::Display for ScrapeWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScrapeWarning::MissingSection { section } => write!(f, "missing section: {section}"),
+            ScrapeWarning::UnexpectedBlockCount { expected, found } => {
+                write!(f, "expected {expected} blocks, found {found}")
+            }
+            ScrapeWarning::EmptyBlockName { index } => write!(f, "block {index} has no name"),
+            ScrapeWarning::UnparsableLink { href: Some(href) } => {
+                write!(f, "unparsable palette link `{href}`")
+            }
+            ScrapeWarning::UnparsableLink { href: None } => {
+                write!(f, "palette link without href")
+            }
+        }
+    }
+}
+
+/// The error returned by the HTML parser for an invalid CSS selector.
+///
+/// The parser's own error type borrows from the selector, so only its
+/// message is kept.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("{message}")]
+pub struct SelectorError {
+    /// The parser's description of the problem.
+    pub message: String,
+}
+
+/// Applies `mode` to the warnings collected while scraping.
+fn finish<T>(value: T, warnings: &[ScrapeWarning], mode: ScrapeMode) -> Result<T> {
+    if mode == ScrapeMode::Strict && !warnings.is_empty() {
+        return Err(BlockPalettesError::ScrapeIncomplete(warnings.to_vec()));
+    }
+    Ok(value)
+}
+
+/// Runs `parse` on `html`, on the blocking thread pool if `html` is at least
+/// `threshold` bytes long.
+pub(crate) async fn parse_offloaded<T, F>(
+    html: String,
+    threshold: Option<usize>,
+    parse: F,
+) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce(&str) -> Result<T> + Send + 'static,
+{
+    if threshold.is_none_or(|threshold| html.len() < threshold) {
+        return parse(&html);
+    }
+    match tokio::task::spawn_blocking(move || parse(&html)).await {
+        Ok(result) => result,
+        Err(err) if err.is_panic() => std::panic::resume_unwind(err.into_panic()),
+        Err(err) => Err(BlockPalettesError::Io(std::io::Error::other(err))),
+    }
+}
+
+fn selector(css: &str) -> Result<Selector> {
+    Selector::parse(css).map_err(|e| BlockPalettesError::HtmlParse {
+        selector: css.to_string(),
+        source: SelectorError {
+            message: e.to_string(),
+        },
+    })
+}
+
+/// Parses the HTML of a palette page.
+///
+/// Pages without similar palettes are valid, so a missing list of similar
+/// palettes is not reported as a warning; cards whose link cannot be parsed
+/// are.
+pub(crate) fn parse_palette_page(html: &str, mode: ScrapeMode) -> Result<PalettePageDetails> {
+    let document = Html::parse_document(html);
+    let mut warnings = Vec::new();
+
+    // extract palette blocks
+    let mut blocks = None;
+    for (index, element) in document.select(&selector(".single-block")?).enumerate() {
+        let names = blocks.get_or_insert_with(Vec::new);
+        match element.text().last().map(str::trim) {
+            Some(name) if !name.is_empty() => names.push(name.to_string()),
+            _ => warnings.push(ScrapeWarning::EmptyBlockName { index }),
+        }
+    }
+
+    match &blocks {
+        None => warnings.push(ScrapeWarning::MissingSection {
+            section: "blocks".into(),
+        }),
+        Some(names) if names.len() != PALETTE_BLOCK_COUNT => {
+            warnings.push(ScrapeWarning::UnexpectedBlockCount {
+                expected: PALETTE_BLOCK_COUNT,
+                found: names.len(),
+            });
+        }
+        Some(_) => {}
+    }
+
+    // extract similar palettes if available
+    let similar = parse_cards(&document, &mut warnings)?;
+
+    let details = PalettePageDetails {
+        blocks,
+        similar_palettes: (!similar.is_empty()).then_some(similar),
+        warnings: warnings.clone(),
+    };
+    finish(details, &warnings, mode)
+}
+
+/// Parses the HTML of a block's gallery page.
+///
+/// Past the last page the gallery is simply empty, so a page without cards is
+/// not reported as a warning.
+pub(crate) fn parse_block_page(
+    html: &str,
+    block: &str,
+    page: u32,
+    mode: ScrapeMode,
+) -> Result<BlockPageDetails> {
+    let document = Html::parse_document(html);
+    let mut warnings = Vec::new();
+
+    let palettes = parse_cards(&document, &mut warnings)?;
+
+    let details = BlockPageDetails {
+        block: block.to_string(),
+        page,
+        palettes,
+        warnings: warnings.clone(),
+    };
+    finish(details, &warnings, mode)
+}
+
+/// Parses the HTML of a user's profile page into the cards of their palettes.
+///
+/// A user without palettes has an empty profile, so a page without cards is
+/// not reported as a warning.
+pub(crate) fn parse_user_page(html: &str, mode: ScrapeMode) -> Result<Vec<PaletteCard>> {
+    let document = Html::parse_document(html);
+    let mut warnings = Vec::new();
+    let palettes = parse_cards(&document, &mut warnings)?;
+    finish(palettes, &warnings, mode)
+}
+
+/// Parses the HTML of a palette page fetched or saved by other means, like
+/// [`BlockPalettesClient::scrape_palette_page`](crate::BlockPalettesClient::scrape_palette_page)
+/// parses the pages it downloads, in [`ScrapeMode::Lenient`] mode.
+///
+/// This decouples fetching from parsing: archived pages can be parsed again
+/// with newer versions of the crate, without downloading them again. No
+/// client is involved, so no denylist is applied.
+///
+/// # Errors
+///
+/// Returns an error only if the crate's own selectors are invalid; problems
+/// with the page are reported in [`PalettePageDetails::warnings`].
+///
+/// # Examples
+///
+/// ```rust
+/// use blockpalettes_client::scrape;
+///
+/// let html = r#"<html><body>
+///     <div class="single-block">stone</div><div class="single-block">andesite</div>
+///     <div class="single-block">tuff</div><div class="single-block">gravel</div>
+///     <div class="single-block">cobblestone</div><div class="single-block">dirt</div>
+///     <a class="palette-card" href="/palette/7"></a>
+/// </body></html>"#;
+///
+/// let details = scrape::scrape_palette_html(html)?;
+/// assert_eq!(details.blocks.as_deref().unwrap()[0], "stone");
+/// assert_eq!(details.similar_palette_ids(), [7]);
+/// assert!(details.warnings.is_empty());
+/// # Ok::<(), blockpalettes_client::BlockPalettesError>(())
+/// ```
+pub fn scrape_palette_html(html: &str) -> Result<PalettePageDetails> {
+    parse_palette_page(html, ScrapeMode::Lenient)
+}
+
+/// Parses the HTML of a palette page like
+/// [`scrape_palette_html`], with an explicit [`ScrapeMode`].
+///
+/// # Errors
+///
+/// In [`ScrapeMode::Strict`] mode, returns
+/// [`BlockPalettesError::ScrapeIncomplete`] if the page does not look as
+/// expected.
+pub fn scrape_palette_html_with(html: &str, mode: ScrapeMode) -> Result<PalettePageDetails> {
+    parse_palette_page(html, mode)
+}
+
+/// Parses the HTML of page `page` of the gallery of `block`, like
+/// [`BlockPalettesClient::scrape_block_page`](crate::BlockPalettesClient::scrape_block_page),
+/// in [`ScrapeMode::Lenient`] mode.
+///
+/// `block` and `page` are only copied into the result, since the page does
+/// not reliably show them. See [`scrape_palette_html`] for why this is
+/// useful.
+///
+/// # Errors
+///
+/// Returns an error only if the crate's own selectors are invalid.
+pub fn scrape_block_html(html: &str, block: &str, page: u32) -> Result<BlockPageDetails> {
+    parse_block_page(html, block, page, ScrapeMode::Lenient)
+}
+
+/// Parses the HTML of a block's gallery page like [`scrape_block_html`],
+/// with an explicit [`ScrapeMode`].
+///
+/// # Errors
+///
+/// In [`ScrapeMode::Strict`] mode, returns
+/// [`BlockPalettesError::ScrapeIncomplete`] if the page does not look as
+/// expected.
+pub fn scrape_block_html_with(
+    html: &str,
+    block: &str,
+    page: u32,
+    mode: ScrapeMode,
+) -> Result<BlockPageDetails> {
+    parse_block_page(html, block, page, mode)
+}
+
+/// Parses the HTML of a user's profile page into the cards of their
+/// palettes, in profile order, in [`ScrapeMode::Lenient`] mode.
+///
+/// This is the page [`BlockPalettesClient::get_user`](crate::BlockPalettesClient::get_user)
+/// starts from. See [`scrape_palette_html`] for why this is useful.
+///
+/// # Errors
+///
+/// Returns an error only if the crate's own selectors are invalid.
+pub fn scrape_user_html(html: &str) -> Result<Vec<PaletteCard>> {
+    parse_user_page(html, ScrapeMode::Lenient)
+}
+
+/// Parses the HTML of a user's profile page like [`scrape_user_html`], with
+/// an explicit [`ScrapeMode`].
+///
+/// # Errors
+///
+/// In [`ScrapeMode::Strict`] mode, returns
+/// [`BlockPalettesError::ScrapeIncomplete`] if the page does not look as
+/// expected.
+pub fn scrape_user_html_with(html: &str, mode: ScrapeMode) -> Result<Vec<PaletteCard>> {
+    parse_user_page(html, mode)
+}
+
+/// Returns the canonical URLs a page declares for itself, with
+/// `<link rel="canonical">` or `<meta property="og:url">`.
+pub(crate) fn canonical_urls(html: &str) -> Result<Vec<String>> {
+    let document = Html::parse_document(html);
+    let (link, meta) = (
+        selector(r#"link[rel="canonical"]"#)?,
+        selector(r#"meta[property="og:url"]"#)?,
+    );
+    let links = document
+        .select(&link)
+        .filter_map(|link| link.value().attr("href"));
+    let metas = document
+        .select(&meta)
+        .filter_map(|meta| meta.value().attr("content"));
+    Ok(links.chain(metas).map(str::to_string).collect())
+}
+
+/// Parses every palette card of a gallery page.
+///
+/// Cards whose link cannot be parsed are skipped and reported; cards showing
+/// some blocks but not six are kept and reported.
+fn parse_cards(document: &Html, warnings: &mut Vec<ScrapeWarning>) -> Result<Vec<PaletteCard>> {
+    let mut palettes = Vec::new();
+    for card in document.select(&selector(".palette-card")?) {
+        let Some(palette) = parse_palette_card(card) else {
+            warnings.push(ScrapeWarning::UnparsableLink {
+                href: card.value().attr("href").map(str::to_string),
+            });
+            continue;
+        };
+        if !palette.blocks.is_empty() && palette.blocks.len() != PALETTE_BLOCK_COUNT {
+            warnings.push(ScrapeWarning::UnexpectedBlockCount {
+                expected: PALETTE_BLOCK_COUNT,
+                found: palette.blocks.len(),
+            });
+        }
+        palettes.push(palette);
+    }
+    Ok(palettes)
+}
+
+/// Parses a palette card: the `.palette-card` link shown for each palette on
+/// galleries, profiles and palette pages.
+///
+/// The ID is read from the link's `href`, the blocks from the `alt` (or
+/// `title`) of the card's images, in display order, and the likes from the
+/// digits of its `.likes` element. This is what every scraper of the crate
+/// uses, exposed for scraping saved HTML files offline.
+///
+/// Returns `None` if the element's `href` does not point to a palette.
+///
+/// # Examples
+///
+/// ```rust
+/// use blockpalettes_client::scrape::{self, Html, Selector};
+///
+/// let html = Html::parse_fragment(
+///     r#"<a class="palette-card" href="/palette/42">
+///         <img alt="stone"><img alt="andesite"><img alt="tuff">
+///         <img alt="gravel"><img alt="cobblestone"><img alt="dirt">
+///         <span class="likes">1,204 likes</span>
+///     </a>"#,
+/// );
+/// let card = html.select(&Selector::parse(".palette-card").unwrap()).next().unwrap();
+///
+/// let palette = scrape::parse_palette_card(card).unwrap();
+/// assert_eq!(palette.id, 42);
+/// assert_eq!(palette.blocks[0], "stone");
+/// assert_eq!(palette.likes, Some(1204));
+/// ```
+pub fn parse_palette_card(card: ElementRef<'_>) -> Option<PaletteCard> {
+    let id = card.value().attr("href").and_then(palette_id_from_href)?;
+
+    let block_selector = selector("img").ok()?;
+    let blocks: Vec<String> = card
+        .select(&block_selector)
+        .filter_map(|img| img.value().attr("alt").or(img.value().attr("title")))
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .collect();
+
+    let likes_selector = selector(".likes").ok()?;
+    let likes = card.select(&likes_selector).next().and_then(|element| {
+        let digits: String = element
+            .text()
+            .flat_map(str::chars)
+            .filter(char::is_ascii_digit)
+            .collect();
+        digits.parse().ok()
+    });
+
+    let text = card
+        .text()
+        .flat_map(str::split_whitespace)
+        .collect::<Vec<_>>();
+    let text = (!text.is_empty()).then(|| text.join(" "));
+
+    Some(PaletteCard {
+        id,
+        blocks,
+        likes,
+        text,
+    })
+}
+
+/// Extracts the palette ID from a link such as `/palette/12345`.
+fn palette_id_from_href(href: &str) -> Option<u64> {
+    href.split('/').next_back()?.parse().ok()
+}
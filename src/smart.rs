@@ -0,0 +1,211 @@
+//! Fetching palettes from the cheapest source that has them.
+//!
+//! [`BlockPalettesClient::get_palette_smart`] looks a palette up in a
+//! [`PaletteCache`] first, then asks the API, and only scrapes the palette's
+//! page when a [`SmartPolicy`] rule calls for it: the API does not know the
+//! palette, returned it with missing blocks, or failed altogether. Scraping
+//! is the most expensive request for the site and the most fragile for the
+//! client, so the default policy keeps it for the cases where the API cannot
+//! answer.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use blockpalettes_client::BlockPalettesClient;
+//! use blockpalettes_client::smart::{DataSource, PaletteCache, SmartPolicy};
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let client = BlockPalettesClient::new(reqwest::Client::new())
+//!         .with_palette_cache(PaletteCache::new())
+//!         .with_smart_policy(SmartPolicy {
+//!             scrape_if_api_error: true,
+//!             ..SmartPolicy::DEFAULT
+//!         });
+//!
+//!     let palette = client.get_palette_smart(12345).await?;
+//!     println!("{:?} from {:?}", palette.blocks(), palette.source);
+//!
+//!     // answered from the cache this time
+//!     let again = client.get_palette_smart(12345).await?;
+//!     assert_eq!(again.source, DataSource::Cache);
+//!     Ok(())
+//! }
+//! ```
+
+use crate::ttl_cache::TtlCache;
+use crate::{PaletteDetails, PalettePageDetails};
+use serde::Serialize;
+use std::time::Duration;
+
+/// The default time palette details stay cached.
+pub const DEFAULT_PALETTE_TTL: Duration = Duration::from_secs(300);
+
+/// When [`BlockPalettesClient::get_palette_smart`](crate::BlockPalettesClient::get_palette_smart)
+/// moves on to the next source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SmartPolicy {
+    /// Whether to answer from the client's [`PaletteCache`], if it has one.
+    pub use_cache: bool,
+    /// Whether to scrape the palette's page when the API reports the palette
+    /// as not found.
+    pub scrape_if_not_found: bool,
+    /// Whether to scrape the palette's page when the API returns the palette
+    /// with an empty block name.
+    pub scrape_if_incomplete: bool,
+    /// Whether to scrape the palette's page when the API request fails, e.g.
+    /// with a network error or an unreadable response.
+    pub scrape_if_api_error: bool,
+}
+
+impl SmartPolicy {
+    /// Uses the cache, and scrapes when the API does not know the palette or
+    /// returns it incomplete.
+    pub const DEFAULT: Self = Self {
+        use_cache: true,
+        scrape_if_not_found: true,
+        scrape_if_incomplete: true,
+        scrape_if_api_error: false,
+    };
+
+    /// Uses the cache and the API, and never scrapes.
+    pub const NO_SCRAPING: Self = Self {
+        use_cache: true,
+        scrape_if_not_found: false,
+        scrape_if_incomplete: false,
+        scrape_if_api_error: false,
+    };
+}
+
+impl Default for SmartPolicy {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Where the data of a [`SmartPalette`] came from, by the last source
+/// consulted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DataSource {
+    /// The client's [`PaletteCache`].
+    Cache,
+    /// The API.
+    Api,
+    /// The palette's page, possibly on top of cached or API details.
+    Scrape,
+}
+
+/// A palette fetched by
+/// [`BlockPalettesClient::get_palette_smart`](crate::BlockPalettesClient::get_palette_smart).
+#[derive(Debug, Serialize)]
+pub struct SmartPalette {
+    /// The ID of the palette.
+    pub id: u64,
+    /// The details of the palette, from the cache or the API. `None` if only
+    /// its page could be scraped.
+    pub details: Option<PaletteDetails>,
+    /// The scraped page of the palette, if it was scraped.
+    pub page: Option<PalettePageDetails>,
+    /// The last source consulted.
+    pub source: DataSource,
+}
+
+impl SmartPalette {
+    /// Returns the blocks of the palette: the blocks of its details if none
+    /// is empty, or else the blocks of its scraped page.
+    pub fn blocks(&self) -> Option<Vec<String>> {
+        self.details
+            .as_ref()
+            .filter(|details| !is_incomplete(details))
+            .map(|details| {
+                [
+                    &details.block_one,
+                    &details.block_two,
+                    &details.block_three,
+                    &details.block_four,
+                    &details.block_five,
+                    &details.block_six,
+                ]
+                .map(String::clone)
+                .to_vec()
+            })
+            .or_else(|| self.page.as_ref()?.blocks.clone())
+    }
+}
+
+/// Returns `true` if `details` has an empty block name.
+pub(crate) fn is_incomplete(details: &PaletteDetails) -> bool {
+    [
+        &details.block_one,
+        &details.block_two,
+        &details.block_three,
+        &details.block_four,
+        &details.block_five,
+        &details.block_six,
+    ]
+    .iter()
+    .any(|block| block.trim().is_empty())
+}
+
+/// An in-memory cache of palette details, keyed by palette ID.
+///
+/// Attach it to a client with
+/// [`BlockPalettesClient::with_palette_cache`](crate::BlockPalettesClient::with_palette_cache).
+/// Clones of the client share the cache.
+#[derive(Debug)]
+pub struct PaletteCache {
+    entries: TtlCache<u64, PaletteDetails>,
+}
+
+impl Default for PaletteCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PaletteCache {
+    /// Creates an empty cache with the default time to live.
+    pub fn new() -> Self {
+        Self {
+            entries: TtlCache::new(DEFAULT_PALETTE_TTL),
+        }
+    }
+
+    /// Sets how long palette details stay cached.
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.entries.set_ttl(ttl);
+        self
+    }
+
+    /// Returns the number of cached palettes, including expired ones not yet
+    /// evicted.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no palette is cached.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Forgets the palette with `id`, e.g. after it was edited.
+    pub fn invalidate(&self, id: u64) {
+        self.entries.remove(&id);
+    }
+
+    /// Forgets every palette.
+    pub fn clear(&self) {
+        self.entries.clear();
+    }
+
+    /// Returns the cached details of the palette with `id`, unless they
+    /// expired.
+    pub(crate) fn get(&self, id: u64) -> Option<PaletteDetails> {
+        self.entries.get(&id)
+    }
+
+    pub(crate) fn insert(&self, details: PaletteDetails) {
+        self.entries.insert(details.id, details);
+    }
+}
@@ -0,0 +1,197 @@
+//! Local, multi-key sorting of palettes.
+//!
+//! Unlike [`SortOrder`], which asks the API to sort its
+//! results, the helpers in this module sort palettes already in memory, so
+//! they work on any local corpus and can combine several keys.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use blockpalettes_client::sort::{self, SortKey};
+//! # use blockpalettes_client::Palette;
+//! # let palette = |id, likes, featured, date: &str| Palette {
+//! #    id, user_id: 1, date: date.to_string(), likes,
+//! #    block_one: "stone".to_string(), block_two: "dirt".to_string(),
+//! #    block_three: "grass_block".to_string(), block_four: "oak_log".to_string(),
+//! #    block_five: "cobblestone".to_string(), block_six: "sand".to_string(),
+//! #    hidden: Some(0), featured: Some(featured), hash: None, time_ago: String::new()
+//! # };
+//! let mut palettes = vec![
+//!     palette(1, 50, 0, "2023-01-01 12:00:00"),
+//!     palette(2, 10, 1, "2023-01-02 12:00:00"),
+//!     palette(3, 50, 0, "2023-03-01 12:00:00"),
+//! ];
+//!
+//! // featured first, then likes, then recency
+//! sort::by(&mut palettes, &[SortKey::FeaturedFirst, SortKey::LikesDesc, SortKey::DateDesc]);
+//! let ids: Vec<u64> = palettes.iter().map(|p| p.id).collect();
+//! assert_eq!(ids, [2, 3, 1]);
+//! ```
+//!
+//! # Ordering of merged results
+//!
+//! Results the client assembles from several responses, such as
+//! multi-block searches, are sorted locally so that their order never depends
+//! on the order in which responses were received or queries were given. They
+//! are sorted by the [keys of their sort order](keys_for), then by the
+//! [`TIE_BREAKERS`]: most liked first, then lowest ID first. Since IDs are
+//! unique, the order is total.
+
+use crate::{Palette, SortOrder};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// A single sort criterion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SortKey {
+    /// Most liked first.
+    LikesDesc,
+    /// Least liked first.
+    LikesAsc,
+    /// Newest first. Palettes with unparsable dates come last.
+    DateDesc,
+    /// Oldest first. Palettes with unparsable dates come last.
+    DateAsc,
+    /// Featured palettes before the others.
+    FeaturedFirst,
+    /// Lowest ID first.
+    IdAsc,
+    /// Highest ID first.
+    IdDesc,
+}
+
+impl SortKey {
+    /// Compares two palettes according to this key alone.
+    pub fn compare(self, a: &Palette, b: &Palette) -> Ordering {
+        match self {
+            SortKey::LikesDesc => b.likes.cmp(&a.likes),
+            SortKey::LikesAsc => a.likes.cmp(&b.likes),
+            SortKey::DateDesc => compare_dates(a, b, true),
+            SortKey::DateAsc => compare_dates(a, b, false),
+            SortKey::FeaturedFirst => is_featured(b).cmp(&is_featured(a)),
+            SortKey::IdAsc => a.id.cmp(&b.id),
+            SortKey::IdDesc => b.id.cmp(&a.id),
+        }
+    }
+}
+
+/// The keys ordering palettes that are equal under every other key: most
+/// liked first, then lowest ID first.
+pub const TIE_BREAKERS: [SortKey; 2] = [SortKey::LikesDesc, SortKey::IdAsc];
+
+/// Returns the keys sorting merged results requested with `order`, ending
+/// with the [`TIE_BREAKERS`].
+///
+/// [`SortOrder::Recent`] and [`SortOrder::Oldest`] sort by date first.
+/// Other orders, including [`SortOrder::Trending`] whose ranking is only
+/// known to the site, are sorted by the tie-breakers alone.
+///
+/// # Examples
+///
+/// ```rust
+/// use blockpalettes_client::SortOrder;
+/// use blockpalettes_client::sort::{self, SortKey};
+/// # use blockpalettes_client::Palette;
+/// # let palette = |id, likes, date: &str| Palette {
+/// #    id, user_id: 1, date: date.to_string(), likes,
+/// #    block_one: "stone".to_string(), block_two: "dirt".to_string(),
+/// #    block_three: "grass_block".to_string(), block_four: "oak_log".to_string(),
+/// #    block_five: "cobblestone".to_string(), block_six: "sand".to_string(),
+/// #    hidden: Some(0), featured: Some(0), hash: None, time_ago: String::new()
+/// # };
+/// assert_eq!(sort::keys_for(&SortOrder::Popular), [SortKey::LikesDesc, SortKey::IdAsc]);
+///
+/// let mut first = vec![
+///     palette(3, 10, "2023-01-01 12:00:00"),
+///     palette(1, 10, "2023-01-01 12:00:00"),
+///     palette(2, 50, "2023-01-01 12:00:00"),
+///     palette(4, 5, "2023-02-01 12:00:00"),
+/// ];
+/// let mut second = first.clone();
+/// second.reverse();
+///
+/// let keys = sort::keys_for(&SortOrder::Recent);
+/// sort::by(&mut first, &keys);
+/// sort::by(&mut second, &keys);
+/// assert_eq!(first, second);
+/// assert_eq!(first.iter().map(|p| p.id).collect::<Vec<_>>(), [4, 2, 1, 3]);
+/// ```
+pub fn keys_for(order: &SortOrder) -> Vec<SortKey> {
+    let primary = match order {
+        SortOrder::Recent => Some(SortKey::DateDesc),
+        SortOrder::Oldest => Some(SortKey::DateAsc),
+        SortOrder::Popular | SortOrder::Trending | SortOrder::Other(_) => None,
+    };
+    primary.into_iter().chain(TIE_BREAKERS).collect()
+}
+
+/// Compares two palettes by each key in turn, until one of them differs.
+pub fn compare(keys: &[SortKey], a: &Palette, b: &Palette) -> Ordering {
+    keys.iter()
+        .map(|key| key.compare(a, b))
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+/// Sorts `palettes` by `keys`, in priority order.
+///
+/// The sort is stable: palettes equal under every key keep their relative order.
+pub fn by(palettes: &mut [Palette], keys: &[SortKey]) {
+    palettes.sort_by(|a, b| compare(keys, a, b));
+}
+
+/// Returns the `k` palettes with the highest likes per day, best first.
+///
+/// Velocity is computed with [`Palette::likes_velocity_at`] relative to `now`.
+/// Palettes with unparsable dates are ignored; ties are broken by the
+/// [`TIE_BREAKERS`].
+///
+/// # Examples
+///
+/// ```rust
+/// use blockpalettes_client::sort;
+/// use chrono::NaiveDateTime;
+/// # use blockpalettes_client::Palette;
+/// # let palette = |id, likes, date: &str| Palette {
+/// #    id, user_id: 1, date: date.to_string(), likes,
+/// #    block_one: "stone".to_string(), block_two: "dirt".to_string(),
+/// #    block_three: "grass_block".to_string(), block_four: "oak_log".to_string(),
+/// #    block_five: "cobblestone".to_string(), block_six: "sand".to_string(),
+/// #    hidden: Some(0), featured: Some(0), hash: None, time_ago: String::new()
+/// # };
+/// let palettes = [
+///     palette(1, 500, "2022-01-01 12:00:00"), // old and popular
+///     palette(2, 40, "2023-06-25 12:00:00"),  // new and climbing fast
+///     palette(3, 5, "2023-06-20 12:00:00"),
+/// ];
+/// let now = NaiveDateTime::parse_from_str("2023-07-01 12:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+///
+/// let hot: Vec<u64> = sort::top_by_likes_velocity(&palettes, 2, now).iter().map(|p| p.id).collect();
+/// assert_eq!(hot, [2, 1]);
+/// ```
+pub fn top_by_likes_velocity(palettes: &[Palette], k: usize, now: NaiveDateTime) -> Vec<&Palette> {
+    let mut scored: Vec<(f64, &Palette)> = palettes
+        .iter()
+        .filter_map(|p| Some((p.likes_velocity_at(now).ok()?, p)))
+        .collect();
+    scored.sort_by(|a, b| {
+        b.0.total_cmp(&a.0)
+            .then_with(|| compare(&TIE_BREAKERS, a.1, b.1))
+    });
+    scored.into_iter().take(k).map(|(_, p)| p).collect()
+}
+
+fn is_featured(palette: &Palette) -> bool {
+    palette.featured.unwrap_or_default() != 0
+}
+
+fn compare_dates(a: &Palette, b: &Palette, newest_first: bool) -> Ordering {
+    match (a.parse_date().ok(), b.parse_date().ok()) {
+        (Some(a), Some(b)) if newest_first => b.cmp(&a),
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
@@ -0,0 +1,150 @@
+//! An object-safe abstraction over where palettes come from.
+//!
+//! [`PaletteSource`] covers the core reads of the site: listing palettes,
+//! fetching a palette's details, similar palettes, popular blocks and block
+//! search. Its methods return boxed futures, so sources can be used as trait
+//! objects: applications that pick a backend at runtime (the live site, a
+//! local mirror, a test double) can hold an `Arc<dyn PaletteSource>` and
+//! pass it around like any other value.
+//!
+//! [`BlockPalettesClient`] implements the trait with its regular methods, so
+//! validators, denylists, ranking and caching all apply.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use blockpalettes_client::source::PaletteSource;
+//! use blockpalettes_client::{BlockPalettesClient, SortOrder};
+//! use std::sync::Arc;
+//!
+//! async fn newest(source: &dyn PaletteSource) -> blockpalettes_client::Result<Vec<u64>> {
+//!     let page = source.palettes(&SortOrder::Recent, 1, 10).await?;
+//!     Ok(page.palettes.unwrap_or_default().iter().map(|p| p.id).collect())
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let source: Arc<dyn PaletteSource> = Arc::new(BlockPalettesClient::new(reqwest::Client::new()));
+//!     println!("{:?}", newest(source.as_ref()).await?);
+//!     Ok(())
+//! }
+//! ```
+
+use crate::{
+    BlockPalettesClient, Palette, PaletteDetails, PaletteResponse, PopularBlock, Result, SortOrder,
+};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// A boxed future returned by [`PaletteSource`] methods.
+pub type SourceFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T>> + Send + 'a>>;
+
+/// A source of palettes and blocks, usable as a trait object.
+pub trait PaletteSource: Send + Sync {
+    /// Lists a page of palettes sorted by `sort`, like
+    /// [`BlockPalettesClient::list_palettes`] without blocks.
+    fn palettes<'a>(
+        &'a self,
+        sort: &'a SortOrder,
+        page: u32,
+        limit: u32,
+    ) -> SourceFuture<'a, PaletteResponse>;
+
+    /// Returns the details of a palette, like
+    /// [`BlockPalettesClient::get_palette_details`].
+    fn palette_details(&self, id: u64) -> SourceFuture<'_, PaletteDetails>;
+
+    /// Returns the palettes similar to a palette, like
+    /// [`BlockPalettesClient::get_similar_palettes`].
+    fn similar_palettes(&self, palette_id: u64) -> SourceFuture<'_, Vec<Palette>>;
+
+    /// Returns the most popular blocks, like
+    /// [`BlockPalettesClient::popular_blocks`].
+    fn popular_blocks(&self) -> SourceFuture<'_, Vec<PopularBlock>>;
+
+    /// Returns the blocks matching `query`, like
+    /// [`BlockPalettesClient::search_blocks`].
+    fn search_blocks<'a>(&'a self, query: &'a str) -> SourceFuture<'a, Vec<String>>;
+}
+
+impl PaletteSource for BlockPalettesClient {
+    fn palettes<'a>(
+        &'a self,
+        sort: &'a SortOrder,
+        page: u32,
+        limit: u32,
+    ) -> SourceFuture<'a, PaletteResponse> {
+        Box::pin(self.fetch_ranked_palette_page(sort, page, limit, None))
+    }
+
+    fn palette_details(&self, id: u64) -> SourceFuture<'_, PaletteDetails> {
+        Box::pin(self.get_palette_details(id))
+    }
+
+    fn similar_palettes(&self, palette_id: u64) -> SourceFuture<'_, Vec<Palette>> {
+        Box::pin(self.get_similar_palettes(palette_id))
+    }
+
+    fn popular_blocks(&self) -> SourceFuture<'_, Vec<PopularBlock>> {
+        Box::pin(BlockPalettesClient::popular_blocks(self))
+    }
+
+    fn search_blocks<'a>(&'a self, query: &'a str) -> SourceFuture<'a, Vec<String>> {
+        Box::pin(BlockPalettesClient::search_blocks(self, query))
+    }
+}
+
+impl<S: PaletteSource + ?Sized> PaletteSource for Arc<S> {
+    fn palettes<'a>(
+        &'a self,
+        sort: &'a SortOrder,
+        page: u32,
+        limit: u32,
+    ) -> SourceFuture<'a, PaletteResponse> {
+        (**self).palettes(sort, page, limit)
+    }
+
+    fn palette_details(&self, id: u64) -> SourceFuture<'_, PaletteDetails> {
+        (**self).palette_details(id)
+    }
+
+    fn similar_palettes(&self, palette_id: u64) -> SourceFuture<'_, Vec<Palette>> {
+        (**self).similar_palettes(palette_id)
+    }
+
+    fn popular_blocks(&self) -> SourceFuture<'_, Vec<PopularBlock>> {
+        (**self).popular_blocks()
+    }
+
+    fn search_blocks<'a>(&'a self, query: &'a str) -> SourceFuture<'a, Vec<String>> {
+        (**self).search_blocks(query)
+    }
+}
+
+impl<S: PaletteSource + ?Sized> PaletteSource for Box<S> {
+    fn palettes<'a>(
+        &'a self,
+        sort: &'a SortOrder,
+        page: u32,
+        limit: u32,
+    ) -> SourceFuture<'a, PaletteResponse> {
+        (**self).palettes(sort, page, limit)
+    }
+
+    fn palette_details(&self, id: u64) -> SourceFuture<'_, PaletteDetails> {
+        (**self).palette_details(id)
+    }
+
+    fn similar_palettes(&self, palette_id: u64) -> SourceFuture<'_, Vec<Palette>> {
+        (**self).similar_palettes(palette_id)
+    }
+
+    fn popular_blocks(&self) -> SourceFuture<'_, Vec<PopularBlock>> {
+        (**self).popular_blocks()
+    }
+
+    fn search_blocks<'a>(&'a self, query: &'a str) -> SourceFuture<'a, Vec<String>> {
+        (**self).search_blocks(query)
+    }
+}
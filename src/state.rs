@@ -0,0 +1,162 @@
+//! Pluggable persistence for long-running components.
+//!
+//! Components that must survive restarts, such as the
+//! [`PaletteWatcher`](crate::watcher::PaletteWatcher)'s set of seen palettes,
+//! persist their state through a [`StateStore`]: a small asynchronous
+//! key-value interface storing raw bytes. Two stores are provided:
+//! [`FileStore`], which keeps one file per key in a directory, and
+//! [`MemoryStore`], which keeps everything in memory. Implement the trait to
+//! back persistence with Redis or an existing database instead.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use blockpalettes_client::BlockPalettesClient;
+//! use blockpalettes_client::state::FileStore;
+//! use blockpalettes_client::watcher::{PaletteWatcher, StdoutJsonNotifier};
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let client = BlockPalettesClient::new(reqwest::Client::new());
+//!     let store = FileStore::new("/var/lib/palette-bot")?;
+//!
+//!     // palettes seen before a restart are not reported again
+//!     PaletteWatcher::new(client)
+//!         .state_store(store)
+//!         .notifier(StdoutJsonNotifier)
+//!         .run()
+//!         .await?;
+//!     Ok(())
+//! }
+//! ```
+
+use crate::Result;
+use std::collections::HashMap;
+use std::future::Future;
+use std::io;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::Mutex;
+
+/// A boxed future returned by [`StateStore`] methods.
+pub type StoreFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T>> + Send + 'a>>;
+
+/// An asynchronous key-value store for persisted state.
+///
+/// Keys are short ASCII identifiers such as `watcher.seen`. The methods return
+/// boxed futures so that stores can be used as trait objects.
+pub trait StateStore: Send + Sync {
+    /// Returns the value stored under `key`, or `None` if there is none.
+    fn get<'a>(&'a self, key: &'a str) -> StoreFuture<'a, Option<Vec<u8>>>;
+
+    /// Stores `value` under `key`, replacing any previous value.
+    fn put<'a>(&'a self, key: &'a str, value: &'a [u8]) -> StoreFuture<'a, ()>;
+}
+
+/// Stores every key in its own file inside a directory.
+///
+/// Values are written to a temporary file first and then renamed over the
+/// previous one, so a crash mid-write never leaves a truncated value behind.
+///
+/// # Examples
+///
+/// ```rust
+/// use blockpalettes_client::state::{FileStore, StateStore};
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let store = FileStore::new(std::env::temp_dir().join("blockpalettes-state-doctest"))?;
+///     store.put("crawler/checkpoint", b"page=42").await?;
+///     assert_eq!(store.get("crawler/checkpoint").await?.as_deref(), Some(&b"page=42"[..]));
+///     assert_eq!(store.get("missing").await?, None);
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct FileStore {
+    dir: PathBuf,
+}
+
+impl FileStore {
+    /// Creates a store keeping its files in `dir`.
+    ///
+    /// The directory is created if it does not exist yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the directory cannot be created.
+    pub fn new(dir: impl Into<PathBuf>) -> io::Result<Self> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    /// Returns the path of the file holding `key`.
+    ///
+    /// Characters that are not safe in file names are percent-encoded.
+    fn path(&self, key: &str) -> PathBuf {
+        let mut name = String::with_capacity(key.len());
+        for (i, byte) in key.bytes().enumerate() {
+            let safe = byte.is_ascii_alphanumeric()
+                || matches!(byte, b'-' | b'_')
+                || (byte == b'.' && i > 0);
+            if safe {
+                name.push(char::from(byte));
+            } else {
+                name.push_str(&format!("%{byte:02X}"));
+            }
+        }
+        self.dir.join(name)
+    }
+}
+
+impl StateStore for FileStore {
+    fn get<'a>(&'a self, key: &'a str) -> StoreFuture<'a, Option<Vec<u8>>> {
+        Box::pin(async move {
+            match tokio::fs::read(self.path(key)).await {
+                Ok(bytes) => Ok(Some(bytes)),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+                Err(e) => Err(e.into()),
+            }
+        })
+    }
+
+    fn put<'a>(&'a self, key: &'a str, value: &'a [u8]) -> StoreFuture<'a, ()> {
+        Box::pin(async move {
+            let path = self.path(key);
+            let mut temp = path.clone().into_os_string();
+            temp.push(".tmp");
+            tokio::fs::write(&temp, value).await?;
+            tokio::fs::rename(&temp, &path).await?;
+            Ok(())
+        })
+    }
+}
+
+/// Keeps every key in memory, for tests and short-lived processes.
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    entries: Mutex<HashMap<String, Vec<u8>>>,
+}
+
+impl MemoryStore {
+    /// Creates an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl StateStore for MemoryStore {
+    fn get<'a>(&'a self, key: &'a str) -> StoreFuture<'a, Option<Vec<u8>>> {
+        let value = self.entries.lock().unwrap().get(key).cloned();
+        Box::pin(async move { Ok(value) })
+    }
+
+    fn put<'a>(&'a self, key: &'a str, value: &'a [u8]) -> StoreFuture<'a, ()> {
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), value.to_vec());
+        Box::pin(async { Ok(()) })
+    }
+}
@@ -0,0 +1,311 @@
+//! Statistics over palette corpora.
+//!
+//! [`cohorts`] groups palettes by the month they were created in, which shows
+//! how block choices and popularity shift over time, for example after a
+//! Minecraft update adds new blocks.
+//!
+//! [`summarize`] describes a result set in a few numbers, for quick
+//! command-line or bot summaries of a search; see
+//! [`PaletteResponse::summary`](crate::PaletteResponse::summary).
+//!
+//! Everything in this module works offline, on palettes already fetched.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use blockpalettes_client::stats::{self, YearMonth};
+//! # let palette = |date: &str, likes, blocks: [&str; 6]| blockpalettes_client::Palette {
+//! #    id: 1, user_id: 1, date: date.to_string(), likes,
+//! #    block_one: blocks[0].to_string(), block_two: blocks[1].to_string(),
+//! #    block_three: blocks[2].to_string(), block_four: blocks[3].to_string(),
+//! #    block_five: blocks[4].to_string(), block_six: blocks[5].to_string(),
+//! #    hidden: Some(0), featured: Some(0), hash: None, time_ago: String::new()
+//! # };
+//! let stone = ["stone", "andesite", "cobblestone", "gravel", "tuff", "oak_planks"];
+//! let cherry = ["cherry_planks", "cherry_log", "pink_wool", "stone", "calcite", "moss_block"];
+//! let palettes = [
+//!     palette("2023-05-02 10:00:00", 12, stone),
+//!     palette("2023-06-08 18:30:00", 30, cherry),
+//!     palette("2023-06-20 09:15:00", 4, cherry),
+//! ];
+//!
+//! let cohorts = stats::cohorts(&palettes);
+//! assert_eq!(cohorts.len(), 2);
+//! let june = &cohorts[1];
+//! assert_eq!(june.month, YearMonth { year: 2023, month: 6 });
+//! assert_eq!(june.month.to_string(), "2023-06");
+//! assert_eq!(june.palettes, 2);
+//! assert_eq!(june.average_likes, 17.0);
+//! assert!(june.top_blocks.contains(&("cherry_log".to_string(), 2)));
+//! // half of the June palettes have at least 10 likes
+//! assert_eq!(june.likes_survival[2], (10, 0.5));
+//! ```
+
+use crate::Palette;
+use crate::blocks::normalize;
+use crate::naming;
+use chrono::{Datelike, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+
+/// The number of blocks listed in [`Cohort::top_blocks`].
+pub const TOP_BLOCKS: usize = 10;
+
+/// The like counts at which [`Cohort::likes_survival`] is measured.
+pub const SURVIVAL_THRESHOLDS: [u32; 6] = [1, 5, 10, 25, 50, 100];
+
+/// The number of blocks listed in [`Summary::top_blocks`] and materials in
+/// [`Summary::top_materials`].
+pub const SUMMARY_TOP: usize = 5;
+
+/// The share of block slots a material must fill for a [`Summary`] to
+/// display as dominated by it.
+const DOMINANT_SHARE: f64 = 0.25;
+
+/// A calendar month.
+///
+/// Months compare in calendar order and display as `YYYY-MM`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct YearMonth {
+    /// The year, e.g. `2023`.
+    pub year: i32,
+    /// The month, from `1` to `12`.
+    pub month: u32,
+}
+
+impl YearMonth {
+    /// Returns the month `date` falls in.
+    pub fn of(date: &impl Datelike) -> Self {
+        Self {
+            year: date.year(),
+            month: date.month(),
+        }
+    }
+}
+
+impl fmt::Display for YearMonth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{:02}", self.year, self.month)
+    }
+}
+
+/// The palettes created in one month, see [`cohorts`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Cohort {
+    /// The month the palettes were created in.
+    pub month: YearMonth,
+    /// The number of palettes created in the month.
+    pub palettes: usize,
+    /// The average number of likes of the palettes.
+    pub average_likes: f64,
+    /// The [`TOP_BLOCKS`] blocks used by the most palettes, with the number
+    /// of palettes using them, most used first, then by name.
+    pub top_blocks: Vec<(String, usize)>,
+    /// For each of the [`SURVIVAL_THRESHOLDS`], the share of the palettes
+    /// with at least that many likes, from `0.0` to `1.0`.
+    ///
+    /// Since palettes keep collecting likes, comparing the curves of
+    /// successive cohorts shows how long palettes stay popular: the curve of
+    /// an older cohort flattening out means its palettes stopped gaining likes.
+    pub likes_survival: Vec<(u32, f64)>,
+}
+
+/// Groups `palettes` by the month they were created in, oldest month first.
+///
+/// Block names are compared without their `minecraft:` namespace, waxed
+/// copper counts as unwaxed copper, and a block used several times in a
+/// palette counts once. Palettes whose date cannot be
+/// parsed are skipped; months without palettes are left out.
+pub fn cohorts(palettes: &[Palette]) -> Vec<Cohort> {
+    by_month(palettes)
+        .into_iter()
+        .map(|(month, palettes)| cohort(month, &palettes))
+        .collect()
+}
+
+/// Groups `palettes` by the month they were created in, skipping palettes
+/// whose date cannot be parsed.
+pub(crate) fn by_month(palettes: &[Palette]) -> BTreeMap<YearMonth, Vec<&Palette>> {
+    let mut months: BTreeMap<YearMonth, Vec<&Palette>> = BTreeMap::new();
+    for palette in palettes {
+        if let Ok(date) = palette.parse_date() {
+            months
+                .entry(YearMonth::of(&date))
+                .or_default()
+                .push(palette);
+        }
+    }
+    months
+}
+
+fn cohort(month: YearMonth, palettes: &[&Palette]) -> Cohort {
+    let total = palettes.len() as f64;
+    let likes: u64 = palettes.iter().map(|p| u64::from(p.likes)).sum();
+
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for palette in palettes {
+        let distinct: HashSet<&str> = palette.blocks().into_iter().map(normalize).collect();
+        for block in distinct {
+            *counts.entry(block).or_default() += 1;
+        }
+    }
+    let mut top_blocks: Vec<(&str, usize)> = counts.into_iter().collect();
+    top_blocks.sort_by_key(|&(block, count)| (Reverse(count), block));
+    top_blocks.truncate(TOP_BLOCKS);
+
+    let likes_survival = SURVIVAL_THRESHOLDS
+        .iter()
+        .map(|&threshold| {
+            let surviving = palettes.iter().filter(|p| p.likes >= threshold).count();
+            (threshold, surviving as f64 / total)
+        })
+        .collect();
+
+    Cohort {
+        month,
+        palettes: palettes.len(),
+        average_likes: likes as f64 / total,
+        top_blocks: top_blocks
+            .into_iter()
+            .map(|(block, count)| (block.to_string(), count))
+            .collect(),
+        likes_survival,
+    }
+}
+
+/// A summary of a set of palettes, see [`summarize`].
+///
+/// It displays on one line, such as "142 palettes, avg 37.2 likes (0 to
+/// 512), from 2023-01-02 to 2024-05-01, dominated by deepslate variants".
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Summary {
+    /// The number of palettes.
+    pub palettes: usize,
+    /// The fewest likes of a palette, or `None` without palettes.
+    pub min_likes: Option<u32>,
+    /// The most likes of a palette, or `None` without palettes.
+    pub max_likes: Option<u32>,
+    /// The average number of likes, or `None` without palettes.
+    pub average_likes: Option<f64>,
+    /// When the oldest palette was added, ignoring unparseable dates.
+    pub first_date: Option<NaiveDateTime>,
+    /// When the newest palette was added, ignoring unparseable dates.
+    pub last_date: Option<NaiveDateTime>,
+    /// The [`SUMMARY_TOP`] blocks used by the most palettes, with the number
+    /// of palettes using them, most used first, then by name.
+    pub top_blocks: Vec<(String, usize)>,
+    /// The [`SUMMARY_TOP`] materials (wood species, stones, dyed blocks...)
+    /// filling the most block slots, with the number of slots they fill,
+    /// most used first, then by name.
+    pub top_materials: Vec<(String, usize)>,
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let noun = if self.palettes == 1 {
+            "palette"
+        } else {
+            "palettes"
+        };
+        write!(f, "{} {noun}", self.palettes)?;
+        if let (Some(average), Some(min), Some(max)) =
+            (self.average_likes, self.min_likes, self.max_likes)
+        {
+            write!(f, ", avg {average:.1} likes ({min} to {max})")?;
+        }
+        match (self.first_date, self.last_date) {
+            (Some(first), Some(last)) if first.date() == last.date() => {
+                write!(f, ", on {}", first.date())?;
+            }
+            (Some(first), Some(last)) => write!(f, ", from {} to {}", first.date(), last.date())?,
+            _ => {}
+        }
+        let slots = (self.palettes * 6) as f64;
+        match (self.top_materials.first(), self.top_blocks.first()) {
+            (Some((material, count)), _) if *count as f64 >= slots * DOMINANT_SHARE => {
+                write!(f, ", dominated by {} variants", material.to_lowercase())
+            }
+            (_, Some((block, count))) => write!(f, ", most used block {block} ({count})"),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Summarizes `palettes`: their number, likes, date range and most used
+/// blocks and materials.
+///
+/// Blocks are counted like in [`cohorts`]: without their `minecraft:`
+/// namespace, waxed copper as unwaxed copper, and once per palette.
+///
+/// # Examples
+///
+/// ```rust
+/// use blockpalettes_client::stats;
+/// # let palette = |date: &str, likes, blocks: [&str; 6]| blockpalettes_client::Palette {
+/// #    id: 1, user_id: 1, date: date.to_string(), likes,
+/// #    block_one: blocks[0].to_string(), block_two: blocks[1].to_string(),
+/// #    block_three: blocks[2].to_string(), block_four: blocks[3].to_string(),
+/// #    block_five: blocks[4].to_string(), block_six: blocks[5].to_string(),
+/// #    hidden: Some(0), featured: Some(0), hash: None, time_ago: String::new()
+/// # };
+/// let cave = ["deepslate", "deepslate_bricks", "cobbled_deepslate", "tuff", "stone", "gravel"];
+/// let palettes = [
+///     palette("2023-01-02 10:00:00", 12, cave),
+///     palette("2024-05-01 18:30:00", 40, cave),
+/// ];
+///
+/// let summary = stats::summarize(&palettes);
+/// assert_eq!(summary.max_likes, Some(40));
+/// assert_eq!(summary.top_materials[0], ("Deepslate".to_string(), 6));
+/// assert_eq!(
+///     summary.to_string(),
+///     "2 palettes, avg 26.0 likes (12 to 40), from 2023-01-02 to 2024-05-01, dominated by deepslate variants"
+/// );
+/// ```
+pub fn summarize(palettes: &[Palette]) -> Summary {
+    let likes = palettes.iter().map(|p| p.likes);
+    let total: u64 = likes.clone().map(u64::from).sum();
+    let dates: Vec<NaiveDateTime> = palettes
+        .iter()
+        .filter_map(|p| p.parse_date().ok())
+        .collect();
+
+    let mut blocks: HashMap<&str, usize> = HashMap::new();
+    let mut materials: HashMap<&str, usize> = HashMap::new();
+    for palette in palettes {
+        let distinct: HashSet<&str> = palette.blocks().into_iter().map(normalize).collect();
+        for block in distinct {
+            *blocks.entry(block).or_default() += 1;
+        }
+        for block in palette.blocks() {
+            if let Some(material) = naming::material(normalize(block)) {
+                *materials.entry(material).or_default() += 1;
+            }
+        }
+    }
+
+    Summary {
+        palettes: palettes.len(),
+        min_likes: likes.clone().min(),
+        max_likes: likes.max(),
+        average_likes: (!palettes.is_empty()).then(|| total as f64 / palettes.len() as f64),
+        first_date: dates.iter().min().copied(),
+        last_date: dates.iter().max().copied(),
+        top_blocks: top(blocks),
+        top_materials: top(materials),
+    }
+}
+
+/// Returns the [`SUMMARY_TOP`] most counted names, most counted first, then
+/// by name.
+fn top(counts: HashMap<&str, usize>) -> Vec<(String, usize)> {
+    let mut counts: Vec<(&str, usize)> = counts.into_iter().collect();
+    counts.sort_by_key(|&(name, count)| (Reverse(count), name));
+    counts
+        .into_iter()
+        .take(SUMMARY_TOP)
+        .map(|(name, count)| (name.to_string(), count))
+        .collect()
+}
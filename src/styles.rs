@@ -0,0 +1,301 @@
+//! Build style presets, such as medieval or desert builds.
+//!
+//! Builders often think in styles rather than in block lists. A [`Style`] is
+//! a curated set of weighted [`StyleGroup`]s: core blocks that define the
+//! style, supporting blocks that fit it well, and accents that often appear
+//! alongside. [`Style::score`] rates how well a palette fits a style, and
+//! [`BlockPalettesClient::find_palettes_by_style`](crate::BlockPalettesClient::find_palettes_by_style)
+//! queries the API for the core blocks of a style and ranks the palettes it
+//! finds by that score.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use blockpalettes_client::styles::Style;
+//! # let palette = |id, blocks: [&str; 6]| blockpalettes_client::Palette {
+//! #    id, user_id: 1, date: "2023-01-01 12:00:00".to_string(), likes: 10,
+//! #    block_one: blocks[0].to_string(), block_two: blocks[1].to_string(),
+//! #    block_three: blocks[2].to_string(), block_four: blocks[3].to_string(),
+//! #    block_five: blocks[4].to_string(), block_six: blocks[5].to_string(),
+//! #    hidden: Some(0), featured: Some(0), hash: None, time_ago: String::new()
+//! # };
+//! let castle = palette(1, ["cobblestone", "stone_bricks", "mossy_cobblestone", "spruce_planks", "oak_log", "gravel"]);
+//! let oasis = palette(2, ["sandstone", "cut_sandstone", "sand", "birch_planks", "terracotta", "acacia_log"]);
+//!
+//! assert!(Style::Medieval.score(&castle) > 0.8);
+//! assert!(Style::Desert.score(&castle) < 0.1);
+//! assert_eq!(Style::best_match(&oasis), Some(Style::Desert));
+//! ```
+
+use crate::Palette;
+use crate::blocks::normalize;
+use serde::{Deserialize, Serialize};
+
+/// Blocks contributing equally to a [`Style`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct StyleGroup {
+    /// How much each block of the group counts, from `0.0` to `1.0`.
+    pub weight: f32,
+    /// The blocks in the group.
+    pub blocks: &'static [&'static str],
+}
+
+/// The weight of the blocks that define a style.
+const CORE: f32 = 1.0;
+/// The weight of blocks that fit a style well.
+const SUPPORT: f32 = 0.6;
+/// The weight of blocks often used as accents in a style.
+const ACCENT: f32 = 0.3;
+
+/// A build style.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Style {
+    /// Castles and villages: cobblestone, stone bricks and dark woods.
+    Medieval,
+    /// Sandstone, terracotta and pale woods.
+    Desert,
+    /// Netherrack, nether bricks, blackstone and nether woods.
+    Nether,
+    /// Concrete, quartz, glass and polished stones.
+    Modern,
+}
+
+impl Style {
+    /// Every style.
+    pub const ALL: [Style; 4] = [Style::Medieval, Style::Desert, Style::Nether, Style::Modern];
+
+    /// Returns a short identifier for the style (e.g., `"medieval"`).
+    pub fn name(self) -> &'static str {
+        match self {
+            Style::Medieval => "medieval",
+            Style::Desert => "desert",
+            Style::Nether => "nether",
+            Style::Modern => "modern",
+        }
+    }
+
+    /// Returns the style named `name`, if it exists.
+    pub fn by_name(name: &str) -> Option<Style> {
+        Self::ALL.into_iter().find(|style| style.name() == name)
+    }
+
+    /// Returns the weighted block groups defining the style.
+    pub fn groups(self) -> &'static [StyleGroup] {
+        match self {
+            Style::Medieval => MEDIEVAL,
+            Style::Desert => DESERT,
+            Style::Nether => NETHER,
+            Style::Modern => MODERN,
+        }
+    }
+
+    /// Returns the blocks that define the style, those of its groups with
+    /// the highest weight.
+    pub fn core_blocks(self) -> &'static [&'static str] {
+        self.groups()
+            .iter()
+            .find(|group| group.weight == CORE)
+            .map_or(&[], |group| group.blocks)
+    }
+
+    /// Returns how much `block` counts towards the style, from `0.0` (not
+    /// part of the style) to `1.0` (a core block).
+    pub fn weight(self, block: &str) -> f32 {
+        let block = normalize(block);
+        self.groups()
+            .iter()
+            .filter(|group| group.blocks.contains(&block))
+            .map(|group| group.weight)
+            .fold(0.0, f32::max)
+    }
+
+    /// Rates how well `palette` fits the style, from `0.0` to `1.0` (six core
+    /// blocks): the average [`weight`](Self::weight) of its blocks.
+    pub fn score(self, palette: &Palette) -> f32 {
+        palette
+            .blocks()
+            .iter()
+            .map(|block| self.weight(block))
+            .sum::<f32>()
+            / 6.0
+    }
+
+    /// Returns the style `palette` fits best, or `None` if none of its blocks
+    /// belongs to any style.
+    pub fn best_match(palette: &Palette) -> Option<Style> {
+        Self::ALL
+            .into_iter()
+            .map(|style| (style, style.score(palette)))
+            .filter(|(_, score)| *score > 0.0)
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(style, _)| style)
+    }
+}
+
+/// A palette found by
+/// [`BlockPalettesClient::find_palettes_by_style`](crate::BlockPalettesClient::find_palettes_by_style).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StyleMatch {
+    /// The palette.
+    pub palette: Palette,
+    /// How well the palette fits the style (see [`Style::score`]).
+    pub score: f32,
+}
+
+static MEDIEVAL: &[StyleGroup] = &[
+    StyleGroup {
+        weight: CORE,
+        blocks: &["cobblestone", "stone_bricks", "spruce_planks", "oak_log"],
+    },
+    StyleGroup {
+        weight: SUPPORT,
+        blocks: &[
+            "mossy_cobblestone",
+            "mossy_stone_bricks",
+            "cracked_stone_bricks",
+            "stone",
+            "andesite",
+            "gravel",
+            "spruce_log",
+            "stripped_spruce_log",
+            "oak_planks",
+            "dark_oak_planks",
+            "dark_oak_log",
+        ],
+    },
+    StyleGroup {
+        weight: ACCENT,
+        blocks: &[
+            "bricks",
+            "hay_block",
+            "white_wool",
+            "polished_andesite",
+            "smooth_stone",
+            "cobbled_deepslate",
+            "coarse_dirt",
+            "dirt",
+        ],
+    },
+];
+
+static DESERT: &[StyleGroup] = &[
+    StyleGroup {
+        weight: CORE,
+        blocks: &["sandstone", "smooth_sandstone", "cut_sandstone", "sand"],
+    },
+    StyleGroup {
+        weight: SUPPORT,
+        blocks: &[
+            "chiseled_sandstone",
+            "red_sandstone",
+            "cut_red_sandstone",
+            "smooth_red_sandstone",
+            "red_sand",
+            "terracotta",
+            "orange_terracotta",
+            "birch_planks",
+            "stripped_birch_log",
+        ],
+    },
+    StyleGroup {
+        weight: ACCENT,
+        blocks: &[
+            "acacia_planks",
+            "acacia_log",
+            "white_terracotta",
+            "yellow_terracotta",
+            "brown_terracotta",
+            "light_gray_terracotta",
+            "packed_mud",
+            "mud_bricks",
+        ],
+    },
+];
+
+static NETHER: &[StyleGroup] = &[
+    StyleGroup {
+        weight: CORE,
+        blocks: &[
+            "netherrack",
+            "nether_bricks",
+            "blackstone",
+            "crimson_planks",
+        ],
+    },
+    StyleGroup {
+        weight: SUPPORT,
+        blocks: &[
+            "red_nether_bricks",
+            "polished_blackstone",
+            "polished_blackstone_bricks",
+            "basalt",
+            "polished_basalt",
+            "soul_sand",
+            "soul_soil",
+            "crimson_stem",
+            "warped_stem",
+            "warped_planks",
+            "nether_wart_block",
+            "warped_wart_block",
+            "magma_block",
+            "obsidian",
+            "crying_obsidian",
+        ],
+    },
+    StyleGroup {
+        weight: ACCENT,
+        blocks: &[
+            "glowstone",
+            "shroomlight",
+            "gilded_blackstone",
+            "gold_block",
+            "quartz_block",
+            "chiseled_nether_bricks",
+            "cracked_nether_bricks",
+        ],
+    },
+];
+
+static MODERN: &[StyleGroup] = &[
+    StyleGroup {
+        weight: CORE,
+        blocks: &[
+            "white_concrete",
+            "gray_concrete",
+            "smooth_quartz",
+            "quartz_block",
+        ],
+    },
+    StyleGroup {
+        weight: SUPPORT,
+        blocks: &[
+            "light_gray_concrete",
+            "black_concrete",
+            "polished_andesite",
+            "polished_diorite",
+            "polished_deepslate",
+            "smooth_stone",
+            "quartz_bricks",
+            "calcite",
+            "iron_block",
+            "glass",
+            "tinted_glass",
+        ],
+    },
+    StyleGroup {
+        weight: ACCENT,
+        blocks: &[
+            "white_stained_glass",
+            "gray_stained_glass",
+            "black_stained_glass",
+            "cyan_terracotta",
+            "light_gray_terracotta",
+            "sea_lantern",
+            "birch_planks",
+            "stripped_birch_log",
+            "spruce_planks",
+            "dark_oak_planks",
+        ],
+    },
+];
@@ -0,0 +1,512 @@
+//! A local mock of the Block Palettes site, for offline integration tests.
+//!
+//! [`TestServer`] serves a fixed set of palettes over HTTP on a local port,
+//! with the same routes and response shapes as `blockpalettes.com`: the JSON
+//! endpoints under `/api/palettes/`, including `success: false` responses for
+//! unknown palettes, and the HTML pages used by the scrapers (`/palette/{id}`,
+//! `/block/{name}` and `/profile/{user_id}`). Point a client at it with
+//! [`TestServer::client`] or
+//! [`BlockPalettesClient::with_base_url`](crate::BlockPalettesClient::with_base_url).
+//!
+//! The same server is available as the `blockpalettes-test-server` binary,
+//! for tests written in other languages.
+//!
+//! Requires the `test-server` feature.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use blockpalettes_client::SortOrder;
+//! use blockpalettes_client::test_server::TestServer;
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let server = TestServer::start().await?;
+//!     let client = server.client().connect().await?;
+//!
+//!     let response = client.get_palettes(&["stone"], SortOrder::Popular, 1, 10).await?;
+//!     assert!(response.palettes.unwrap().iter().all(|p| p.blocks().contains(&"stone")));
+//!
+//!     let details = client.get_palette_details(1).await?;
+//!     assert_eq!(details.username, "alice");
+//!     assert!(client.get_palette_details(9999).await.is_err());
+//!
+//!     let page = client.scrape_palette_page(1).await?;
+//!     assert_eq!(page.blocks.map(|blocks| blocks.len()), Some(6));
+//!     assert!(page.warnings.is_empty());
+//!     let user = client.get_user(2).await?;
+//!     assert_eq!((user.username.as_str(), user.palette_ids.len()), ("bob", 10));
+//!
+//!     let misconfigured = server.client().with_base_url(format!("{}/v2", server.url()));
+//!     assert!(misconfigured.connect().await.is_err());
+//!     Ok(())
+//! }
+//! ```
+
+use crate::{BlockPalettesClient, Palette, PaletteDetails};
+use serde_json::json;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+/// The number of palettes on a block gallery page.
+const GALLERY_PAGE_SIZE: usize = 20;
+
+/// The number of similar palettes returned for a palette.
+const SIMILAR_COUNT: usize = 4;
+
+/// The largest request head the server reads.
+const MAX_REQUEST_HEAD: usize = 16 * 1024;
+
+/// A running mock server. It stops when dropped.
+#[derive(Debug)]
+pub struct TestServer {
+    addr: SocketAddr,
+    task: JoinHandle<()>,
+}
+
+impl TestServer {
+    /// Starts a server on a free local port, serving [`fixtures`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if no local port can be bound.
+    pub async fn start() -> io::Result<Self> {
+        Self::start_with(fixtures()).await
+    }
+
+    /// Starts a server on a free local port, serving `palettes`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if no local port can be bound.
+    pub async fn start_with(palettes: Vec<PaletteDetails>) -> io::Result<Self> {
+        Self::bind("127.0.0.1:0".parse().unwrap(), palettes).await
+    }
+
+    /// Starts a server on `addr`, serving `palettes`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if `addr` cannot be bound.
+    pub async fn bind(addr: SocketAddr, palettes: Vec<PaletteDetails>) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr).await?;
+        let addr = listener.local_addr()?;
+        let site = Arc::new(Site { palettes });
+
+        let task = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let site = site.clone();
+                // a client hanging up mid-request is not the server's problem
+                tokio::spawn(async move { site.serve(stream).await.ok() });
+            }
+        });
+        Ok(Self { addr, task })
+    }
+
+    /// Returns the address the server listens on.
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Returns the base URL of the server, e.g. `http://127.0.0.1:34567`.
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Returns a client sending its requests to this server.
+    pub fn client(&self) -> BlockPalettesClient {
+        BlockPalettesClient::new(reqwest::Client::new()).with_base_url(self.url())
+    }
+
+    /// Runs the server until the task is cancelled, e.g. from a binary's `main`.
+    pub async fn wait(mut self) {
+        let _ = (&mut self.task).await;
+    }
+}
+
+impl Drop for TestServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Returns the palettes served by [`TestServer::start`].
+///
+/// There are 30 palettes by three users (`alice`, `bob` and `carol`, with IDs
+/// 1 to 3), enough to span several pages. Palette 30 is hidden and only
+/// reachable by ID, like hidden palettes on the site.
+pub fn fixtures() -> Vec<PaletteDetails> {
+    const BLOCKS: [&str; 12] = [
+        "stone",
+        "andesite",
+        "oak_planks",
+        "spruce_log",
+        "moss_block",
+        "dirt",
+        "deepslate",
+        "white_wool",
+        "blue_concrete",
+        "orange_wool",
+        "cherry_planks",
+        "quartz_block",
+    ];
+    const USERS: [&str; 3] = ["alice", "bob", "carol"];
+
+    (1..=30u64)
+        .map(|id| {
+            let block = |offset: u64| BLOCKS[((id * 5 + offset * 7) % 12) as usize].to_string();
+            let user_id = (id - 1) % 3 + 1;
+            PaletteDetails {
+                id,
+                user_id,
+                date: format!("2024-01-{:02} 12:00:00", (id - 1) % 28 + 1),
+                likes: ((id * 37) % 101) as u32,
+                block_one: block(0),
+                block_two: block(1),
+                block_three: block(2),
+                block_four: block(3),
+                block_five: block(4),
+                block_six: block(5),
+                hidden: u8::from(id == 30),
+                featured: u8::from(id % 10 == 0),
+                hash: format!("{id:08x}"),
+                username: USERS[(user_id - 1) as usize].to_string(),
+                time_ago: format!("{} months ago", 30 - id / 3),
+            }
+        })
+        .collect()
+}
+
+struct Site {
+    palettes: Vec<PaletteDetails>,
+}
+
+/// A response of the mock site.
+struct Reply {
+    status: &'static str,
+    content_type: &'static str,
+    body: String,
+}
+
+impl Site {
+    /// Answers a single `GET` request, then closes the connection.
+    ///
+    /// Only what the client sends is supported: a request head without a
+    /// body.
+    async fn serve(&self, mut stream: TcpStream) -> io::Result<()> {
+        let mut head = Vec::new();
+        let mut buffer = [0; 1024];
+        while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+            let read = stream.read(&mut buffer).await?;
+            if read == 0 || head.len() > MAX_REQUEST_HEAD {
+                return Ok(());
+            }
+            head.extend_from_slice(&buffer[..read]);
+        }
+
+        let head = String::from_utf8_lossy(&head);
+        let mut request_line = head.lines().next().unwrap_or_default().split(' ');
+        let reply = match (request_line.next(), request_line.next()) {
+            (Some("GET"), Some(target)) => {
+                let (path, query) = target.split_once('?').unwrap_or((target, ""));
+                self.handle(path, query)
+            }
+            _ => Reply {
+                status: "405 Method Not Allowed",
+                content_type: "text/plain",
+                body: "Method Not Allowed".to_string(),
+            },
+        };
+
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            reply.status,
+            reply.content_type,
+            reply.body.len(),
+            reply.body
+        );
+        stream.write_all(response.as_bytes()).await?;
+        stream.shutdown().await
+    }
+
+    fn handle(&self, path: &str, query: &str) -> Reply {
+        let query: HashMap<String, String> = query
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .map(|(k, v)| (decode(k), decode(v)))
+            .collect();
+        let param = |name: &str| query.get(name).map(String::as_str);
+        let number = |name: &str| param(name).and_then(|v| v.parse::<u64>().ok());
+
+        let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+        match segments.as_slice() {
+            ["api", "palettes", "all_palettes.php"] => self.all_palettes(
+                param("sort").unwrap_or("recent"),
+                number("page").unwrap_or(1),
+                number("limit").unwrap_or(20),
+                param("blocks"),
+            ),
+            ["api", "palettes", "single_palette.php"] => {
+                match number("id").and_then(|id| self.find(id)) {
+                    Some(palette) => json_response(json!({ "success": true, "palette": palette })),
+                    None => {
+                        json_response(json!({ "success": false, "message": "Palette not found" }))
+                    }
+                }
+            }
+            ["api", "palettes", "similar_palettes.php"] => {
+                match number("palette_id").and_then(|id| self.find(id)) {
+                    Some(palette) => {
+                        json_response(json!({ "success": true, "palettes": self.similar(palette) }))
+                    }
+                    None => json_response(json!({ "success": false, "palettes": [] })),
+                }
+            }
+            ["api", "palettes", "search-block.php"] => {
+                let needle = param("query").unwrap_or_default().to_lowercase();
+                let blocks: Vec<&String> = self
+                    .block_counts()
+                    .into_keys()
+                    .filter(|b| !needle.is_empty() && b.contains(&needle))
+                    .collect();
+                json_response(json!({ "success": true, "blocks": blocks }))
+            }
+            ["api", "palettes", "popular-blocks.php"] => {
+                let mut counts: Vec<_> = self.block_counts().into_iter().collect();
+                counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+                let blocks: Vec<_> = counts
+                    .into_iter()
+                    .map(|(block, count)| json!({ "block": block, "count": count }))
+                    .collect();
+                json_response(json!({ "success": true, "blocks": blocks }))
+            }
+            ["palette", id] => match id.parse().ok().and_then(|id| self.find(id)) {
+                Some(palette) => html_response(self.palette_page(palette)),
+                None => not_found(),
+            },
+            ["block", block] => {
+                let block = decode(block);
+                let page = number("page").unwrap_or(1).max(1) as usize;
+                let cards: Vec<&PaletteDetails> = self
+                    .visible()
+                    .filter(|p| blocks_of(p).contains(&block.as_str()))
+                    .skip((page - 1) * GALLERY_PAGE_SIZE)
+                    .take(GALLERY_PAGE_SIZE)
+                    .collect();
+                html_response(gallery_page(&format!("Palettes with {block}"), &cards))
+            }
+            ["profile", user_id] => {
+                let Ok(user_id) = user_id.parse::<u64>() else {
+                    return not_found();
+                };
+                let cards: Vec<&PaletteDetails> =
+                    self.visible().filter(|p| p.user_id == user_id).collect();
+                html_response(gallery_page(&format!("User {user_id}"), &cards))
+            }
+            _ => not_found(),
+        }
+    }
+
+    fn find(&self, id: u64) -> Option<&PaletteDetails> {
+        self.palettes.iter().find(|p| p.id == id)
+    }
+
+    fn visible(&self) -> impl Iterator<Item = &PaletteDetails> {
+        self.palettes.iter().filter(|p| p.hidden == 0)
+    }
+
+    fn block_counts(&self) -> BTreeMap<&String, u32> {
+        let mut counts = BTreeMap::new();
+        for palette in self.visible() {
+            for block in [
+                &palette.block_one,
+                &palette.block_two,
+                &palette.block_three,
+                &palette.block_four,
+                &palette.block_five,
+                &palette.block_six,
+            ] {
+                *counts.entry(block).or_insert(0) += 1;
+            }
+        }
+        counts
+    }
+
+    fn all_palettes(&self, sort: &str, page: u64, limit: u64, block: Option<&str>) -> Reply {
+        let mut matching: Vec<&PaletteDetails> = self
+            .visible()
+            .filter(|p| block.is_none_or(|b| blocks_of(p).contains(&b)))
+            .collect();
+        match sort {
+            "popular" | "trending" => {
+                matching.sort_by(|a, b| b.likes.cmp(&a.likes).then(b.id.cmp(&a.id)));
+            }
+            "oldest" => matching.sort_by(|a, b| a.date.cmp(&b.date).then(a.id.cmp(&b.id))),
+            _ => matching.sort_by(|a, b| b.date.cmp(&a.date).then(b.id.cmp(&a.id))),
+        }
+
+        let limit = limit.clamp(1, 100) as usize;
+        let total_results = matching.len();
+        let total_pages = total_results.div_ceil(limit);
+        let palettes: Vec<Palette> = matching
+            .into_iter()
+            .skip((page.max(1) as usize - 1) * limit)
+            .take(limit)
+            .map(to_palette)
+            .collect();
+
+        json_response(json!({
+            "success": true,
+            "total_results": total_results,
+            "total_pages": total_pages,
+            // the site sends `null` rather than an empty list
+            "palettes": if palettes.is_empty() { None } else { Some(palettes) },
+        }))
+    }
+
+    fn similar(&self, palette: &PaletteDetails) -> Vec<Palette> {
+        let blocks = blocks_of(palette);
+        let mut others: Vec<(usize, &PaletteDetails)> = self
+            .visible()
+            .filter(|p| p.id != palette.id)
+            .map(|p| {
+                let shared = blocks_of(p).iter().filter(|b| blocks.contains(b)).count();
+                (shared, p)
+            })
+            .filter(|(shared, _)| *shared > 0)
+            .collect();
+        others.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.id.cmp(&b.1.id)));
+        others
+            .into_iter()
+            .take(SIMILAR_COUNT)
+            .map(|(_, p)| to_palette(p))
+            .collect()
+    }
+
+    fn palette_page(&self, palette: &PaletteDetails) -> String {
+        let mut html = String::from("<html><body><div class=\"palette\">");
+        for block in blocks_of(palette) {
+            let _ = write!(
+                html,
+                "<div class=\"single-block\"><img src=\"/blocks/{block}.png\">{block}</div>"
+            );
+        }
+        html.push_str("</div><div class=\"similar\">");
+        for similar in self.similar(palette) {
+            let _ = write!(
+                html,
+                "<a class=\"palette-card\" href=\"/palette/{}\"></a>",
+                similar.id
+            );
+        }
+        html.push_str("</div></body></html>");
+        html
+    }
+}
+
+fn gallery_page(title: &str, cards: &[&PaletteDetails]) -> String {
+    let mut html = format!("<html><body><h1>{title}</h1><div class=\"gallery\">");
+    for palette in cards {
+        let _ = write!(
+            html,
+            "<a class=\"palette-card\" href=\"/palette/{}\">",
+            palette.id
+        );
+        for block in blocks_of(palette) {
+            let _ = write!(html, "<img src=\"/blocks/{block}.png\" alt=\"{block}\">");
+        }
+        let _ = write!(
+            html,
+            "<span class=\"likes\">{} likes</span></a>",
+            palette.likes
+        );
+    }
+    html.push_str("</div></body></html>");
+    html
+}
+
+fn blocks_of(palette: &PaletteDetails) -> [&str; 6] {
+    [
+        &palette.block_one,
+        &palette.block_two,
+        &palette.block_three,
+        &palette.block_four,
+        &palette.block_five,
+        &palette.block_six,
+    ]
+    .map(String::as_str)
+}
+
+fn to_palette(details: &PaletteDetails) -> Palette {
+    Palette {
+        id: details.id,
+        user_id: details.user_id,
+        date: details.date.clone(),
+        likes: details.likes,
+        block_one: details.block_one.clone(),
+        block_two: details.block_two.clone(),
+        block_three: details.block_three.clone(),
+        block_four: details.block_four.clone(),
+        block_five: details.block_five.clone(),
+        block_six: details.block_six.clone(),
+        hidden: Some(details.hidden),
+        featured: Some(details.featured),
+        hash: Some(details.hash.clone()),
+        time_ago: details.time_ago.clone(),
+    }
+}
+
+/// Decodes a percent-encoded query or path component.
+fn decode(component: &str) -> String {
+    let bytes = component.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+                match hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                    Some(byte) => {
+                        decoded.push(byte);
+                        i += 2;
+                    }
+                    None => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn json_response(body: serde_json::Value) -> Reply {
+    Reply {
+        status: "200 OK",
+        content_type: "application/json",
+        body: body.to_string(),
+    }
+}
+
+fn html_response(body: String) -> Reply {
+    Reply {
+        status: "200 OK",
+        content_type: "text/html; charset=utf-8",
+        body,
+    }
+}
+
+fn not_found() -> Reply {
+    Reply {
+        status: "404 Not Found",
+        content_type: "text/plain",
+        body: "Not Found".to_string(),
+    }
+}
@@ -0,0 +1,244 @@
+//! Public key pinning for TLS connections.
+//!
+//! With pinning enabled, a connection is only accepted if the server's
+//! certificate is valid for the platform *and* its public key matches one of
+//! the configured pins. This detects TLS-intercepting proxies, which present
+//! certificates signed by a locally trusted root.
+//!
+//! Pins are SHA-256 hashes of the certificate's DER-encoded
+//! `SubjectPublicKeyInfo`, encoded in base64, as used by HPKP. They survive
+//! certificate renewals as long as the key is kept. To compute the pin of a
+//! site:
+//!
+//! ```text
+//! openssl s_client -connect www.blockpalettes.com:443 </dev/null 2>/dev/null \
+//!   | openssl x509 -pubkey -noout | openssl pkey -pubin -outform der \
+//!   | openssl dgst -sha256 -binary | base64
+//! ```
+//!
+//! Configure at least one backup pin, or the client stops working when the
+//! site rotates its key.
+//!
+//! Requires the `tls-pinning` feature.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use blockpalettes_client::BlockPalettesClient;
+//! use blockpalettes_client::tls_pin::TlsPins;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let pins = TlsPins::new()
+//!     .sha256("sha256/AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=")?
+//!     .sha256("sha256/BBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBB=")?;
+//! let client = BlockPalettesClient::new(reqwest::Client::new()).with_tls_pins(pins)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use aws_lc_rs::digest::{SHA256, digest};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{CertificateError, ClientConfig, DigitallySignedStruct, SignatureScheme};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+
+/// The public keys a server certificate must match.
+#[derive(Debug, Clone, Default)]
+pub struct TlsPins {
+    pins: Vec<[u8; 32]>,
+    mismatches: Mismatches,
+}
+
+/// The error returned when a pin is not a base64-encoded SHA-256 hash.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("invalid SHA-256 pin `{0}`")]
+pub struct InvalidPinError(pub String);
+
+/// A server presented a certificate whose public key matches no pin.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("certificate for `{host}` matches no pinned key (found sha256/{found})")]
+pub struct PinMismatch {
+    /// The host name the client connected to.
+    pub host: String,
+    /// The pin of the certificate the server presented, in base64.
+    pub found: String,
+}
+
+/// The last mismatch seen per host, shared between the verifier and the client.
+///
+/// TLS errors reach the client as opaque `reqwest` errors, so the verifier
+/// leaves the details here for the client to pick up.
+#[derive(Debug, Clone, Default)]
+struct Mismatches(Arc<Mutex<HashMap<String, PinMismatch>>>);
+
+impl Mismatches {
+    fn record(&self, mismatch: PinMismatch) {
+        if let Ok(mut map) = self.0.lock() {
+            map.insert(mismatch.host.clone(), mismatch);
+        }
+    }
+
+    fn take(&self, host: &str) -> Option<PinMismatch> {
+        self.0.lock().ok()?.remove(host)
+    }
+}
+
+impl TlsPins {
+    /// Creates an empty set of pins.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a base64-encoded SHA-256 pin, with or without the `sha256/` prefix.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvalidPinError`] if `pin` is not valid base64 or not 32
+    /// bytes long.
+    pub fn sha256(self, pin: &str) -> Result<Self, InvalidPinError> {
+        let encoded = pin.trim();
+        let encoded = encoded.strip_prefix("sha256/").unwrap_or(encoded);
+        let hash = BASE64
+            .decode(encoded)
+            .ok()
+            .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+            .ok_or_else(|| InvalidPinError(pin.to_string()))?;
+        Ok(self.sha256_bytes(hash))
+    }
+
+    /// Adds a raw SHA-256 pin.
+    pub fn sha256_bytes(mut self, hash: [u8; 32]) -> Self {
+        if !self.pins.contains(&hash) {
+            self.pins.push(hash);
+        }
+        self
+    }
+
+    /// Returns `true` if no pin is configured.
+    pub fn is_empty(&self) -> bool {
+        self.pins.is_empty()
+    }
+
+    /// Returns the pin of a DER-encoded certificate, in base64.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`rustls::Error`] if the certificate cannot be parsed.
+    pub fn pin_of(certificate: &[u8]) -> Result<String, rustls::Error> {
+        spki_sha256(&CertificateDer::from(certificate)).map(|hash| BASE64.encode(hash))
+    }
+
+    /// Builds a `reqwest::ClientBuilder` that enforces these pins.
+    ///
+    /// Certificates are still validated against the platform's trust store
+    /// first. Use this to create the clients of a [`ClientPool`](crate::pool::ClientPool);
+    /// [`BlockPalettesClient::with_tls_pins`](crate::BlockPalettesClient::with_tls_pins)
+    /// calls it for the client's own connections.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`rustls::Error`] if no pin is configured or the platform
+    /// verifier cannot be set up.
+    pub fn client_builder(&self) -> Result<reqwest::ClientBuilder, rustls::Error> {
+        if self.pins.is_empty() {
+            return Err(rustls::Error::General("no TLS pins configured".into()));
+        }
+        let provider = Arc::new(rustls::crypto::aws_lc_rs::default_provider());
+        let verifier = PinningVerifier {
+            inner: Arc::new(rustls_platform_verifier::Verifier::new(provider.clone())?),
+            pins: self.pins.clone(),
+            mismatches: self.mismatches.clone(),
+        };
+
+        let mut config = ClientConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()?
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(verifier))
+            .with_no_client_auth();
+        config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+
+        Ok(reqwest::Client::builder().tls_backend_preconfigured(config))
+    }
+
+    /// Returns the last pin mismatch seen for `host`, if any, and forgets it.
+    pub(crate) fn take_mismatch(&self, host: &str) -> Option<PinMismatch> {
+        self.mismatches.take(host)
+    }
+}
+
+/// Validates certificates with the platform verifier, then checks the pins.
+#[derive(Debug)]
+struct PinningVerifier {
+    inner: Arc<rustls_platform_verifier::Verifier>,
+    pins: Vec<[u8; 32]>,
+    mismatches: Mismatches,
+}
+
+impl ServerCertVerifier for PinningVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let verified = self.inner.verify_server_cert(
+            end_entity,
+            intermediates,
+            server_name,
+            ocsp_response,
+            now,
+        )?;
+
+        let hash = spki_sha256(end_entity)?;
+        if self.pins.contains(&hash) {
+            return Ok(verified);
+        }
+
+        let mismatch = PinMismatch {
+            host: server_name.to_str().into_owned(),
+            found: BASE64.encode(hash),
+        };
+        self.mismatches.record(mismatch.clone());
+        Err(rustls::Error::InvalidCertificate(CertificateError::Other(
+            rustls::OtherError(Arc::new(mismatch)),
+        )))
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
+
+/// Hashes the `SubjectPublicKeyInfo` of a certificate.
+fn spki_sha256(certificate: &CertificateDer<'_>) -> Result<[u8; 32], rustls::Error> {
+    let cert = webpki::EndEntityCert::try_from(certificate)
+        .map_err(|_| rustls::Error::InvalidCertificate(CertificateError::BadEncoding))?;
+    let spki = cert.subject_public_key_info();
+    let mut hash = [0; 32];
+    hash.copy_from_slice(digest(&SHA256, spki.as_ref()).as_ref());
+    Ok(hash)
+}
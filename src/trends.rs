@@ -0,0 +1,327 @@
+//! Rank movement of popular blocks over time.
+//!
+//! [`BlockPalettesClient::popular_blocks`] only tells how popular blocks are
+//! today. A [`PopularBlocksTracker`] records dated snapshots of it in a
+//! [`StateStore`], and compares them to tell how blocks moved up or down the
+//! ranking, e.g. for a weekly trend post: "cherry_planks up 4 places".
+//!
+//! Snapshots are stored as versioned JSON (see [`SNAPSHOTS_VERSION`]) under a
+//! single key, so any store works:
+//! [`FileStore`](crate::state::FileStore) for a bot running on one machine,
+//! or a custom store backed by a database.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use blockpalettes_client::PopularBlock;
+//! use blockpalettes_client::state::MemoryStore;
+//! use blockpalettes_client::trends::PopularBlocksTracker;
+//! use chrono::NaiveDate;
+//!
+//! #[tokio::main]
+//! async fn main() -> blockpalettes_client::Result<()> {
+//!     # let block = |name: &str, count| PopularBlock { name: name.to_string(), count };
+//!     let tracker = PopularBlocksTracker::new(MemoryStore::new());
+//!     let day = |d| NaiveDate::from_ymd_opt(2024, 5, d).unwrap();
+//!
+//!     tracker
+//!         .record(day(1), vec![block("stone", 900), block("oak_planks", 800), block("cherry_planks", 300)])
+//!         .await?;
+//!     tracker
+//!         .record(day(8), vec![block("cherry_planks", 950), block("stone", 920), block("oak_planks", 810)])
+//!         .await?;
+//!
+//!     let changes = tracker.movement_since(day(1)).await?.unwrap();
+//!     assert_eq!(changes[0].to_string(), "cherry_planks up 2 places (#1)");
+//!     assert_eq!(changes[1].to_string(), "stone down 1 place (#2)");
+//!     assert_eq!(changes[0].movement(), Some(2));
+//!     Ok(())
+//! }
+//! ```
+
+use crate::dataset::{self, SchemaVersion};
+use crate::state::StateStore;
+use crate::{BlockPalettesClient, PopularBlock, Result};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::fmt;
+
+/// The key snapshots are stored under by default.
+pub const DEFAULT_TRACKER_KEY: &str = "popular_blocks.snapshots";
+
+/// The version of the persisted snapshots of a tracker, a JSON object listing
+/// the snapshots under `snapshots`.
+///
+/// Version 0 is the format used before versioning: a bare list of snapshots.
+pub const SNAPSHOTS_VERSION: SchemaVersion = SchemaVersion(1);
+
+/// The popular blocks on a given day.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PopularBlocksSnapshot {
+    /// The day the snapshot was taken.
+    pub date: NaiveDate,
+    /// The popular blocks, most popular first.
+    pub blocks: Vec<PopularBlock>,
+}
+
+impl PopularBlocksSnapshot {
+    /// Creates a snapshot, sorting `blocks` by descending count.
+    ///
+    /// Blocks with the same count keep their order.
+    pub fn new(date: NaiveDate, mut blocks: Vec<PopularBlock>) -> Self {
+        blocks.sort_by_key(|b| Reverse(b.count));
+        Self { date, blocks }
+    }
+
+    /// Returns the rank of `block`, starting at 1, if it is in the snapshot.
+    pub fn rank(&self, block: &str) -> Option<usize> {
+        self.blocks
+            .iter()
+            .position(|b| b.name == block)
+            .map(|index| index + 1)
+    }
+}
+
+/// How a block moved between two snapshots.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RankChange {
+    /// The name of the block.
+    pub block: String,
+    /// The rank in the newer snapshot, or `None` if the block dropped out.
+    pub rank: Option<usize>,
+    /// The rank in the older snapshot, or `None` if the block is new.
+    pub previous_rank: Option<usize>,
+    /// The palette count in the newer snapshot, `0` if the block dropped out.
+    pub count: u32,
+    /// The palette count in the older snapshot, `0` if the block is new.
+    pub previous_count: u32,
+}
+
+impl RankChange {
+    /// Returns how many places the block went up (positive) or down
+    /// (negative), or `None` if it is not in both snapshots.
+    pub fn movement(&self) -> Option<i64> {
+        let rank = i64::try_from(self.rank?).ok()?;
+        let previous = i64::try_from(self.previous_rank?).ok()?;
+        Some(previous - rank)
+    }
+
+    /// Returns `true` if the block is not in the older snapshot.
+    pub fn is_new(&self) -> bool {
+        self.previous_rank.is_none()
+    }
+
+    /// Returns `true` if the block is not in the newer snapshot.
+    pub fn dropped_out(&self) -> bool {
+        self.rank.is_none()
+    }
+}
+
+impl fmt::Display for RankChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let places = |n: i64| if n.abs() == 1 { "place" } else { "places" };
+        match (self.rank, self.previous_rank, self.movement()) {
+            (Some(rank), _, Some(0)) => write!(f, "{} unchanged (#{rank})", self.block),
+            (Some(rank), _, Some(up)) if up > 0 => {
+                write!(f, "{} up {up} {} (#{rank})", self.block, places(up))
+            }
+            (Some(rank), _, Some(down)) => {
+                let down = -down;
+                write!(f, "{} down {down} {} (#{rank})", self.block, places(down))
+            }
+            (Some(rank), None, None) => write!(f, "{} new (#{rank})", self.block),
+            (None, Some(previous), _) => {
+                write!(f, "{} dropped out (was #{previous})", self.block)
+            }
+            _ => write!(f, "{}", self.block),
+        }
+    }
+}
+
+/// Compares two snapshots.
+///
+/// Blocks of `current` come first, by rank, followed by the blocks that
+/// dropped out, by their previous rank.
+pub fn compare(
+    previous: &PopularBlocksSnapshot,
+    current: &PopularBlocksSnapshot,
+) -> Vec<RankChange> {
+    let before: HashMap<&str, (usize, u32)> = previous
+        .blocks
+        .iter()
+        .enumerate()
+        .map(|(index, b)| (b.name.as_str(), (index + 1, b.count)))
+        .collect();
+
+    let mut changes: Vec<RankChange> = current
+        .blocks
+        .iter()
+        .enumerate()
+        .map(|(index, b)| {
+            let previous = before.get(b.name.as_str());
+            RankChange {
+                block: b.name.clone(),
+                rank: Some(index + 1),
+                previous_rank: previous.map(|(rank, _)| *rank),
+                count: b.count,
+                previous_count: previous.map_or(0, |(_, count)| *count),
+            }
+        })
+        .collect();
+    changes.extend(
+        previous
+            .blocks
+            .iter()
+            .enumerate()
+            .filter(|(_, b)| current.rank(&b.name).is_none())
+            .map(|(index, b)| RankChange {
+                block: b.name.clone(),
+                rank: None,
+                previous_rank: Some(index + 1),
+                count: 0,
+                previous_count: b.count,
+            }),
+    );
+    changes
+}
+
+/// Records dated snapshots of the popular blocks in a [`StateStore`].
+///
+/// See the [module documentation](self) for details.
+pub struct PopularBlocksTracker {
+    store: Box<dyn StateStore>,
+    key: String,
+}
+
+impl PopularBlocksTracker {
+    /// Creates a tracker storing its snapshots in `store`.
+    pub fn new(store: impl StateStore + 'static) -> Self {
+        Self {
+            store: Box::new(store),
+            key: DEFAULT_TRACKER_KEY.to_string(),
+        }
+    }
+
+    /// Sets the key snapshots are stored under, so that several trackers can
+    /// share a store. Defaults to [`DEFAULT_TRACKER_KEY`].
+    pub fn key(mut self, key: impl Into<String>) -> Self {
+        self.key = key.into();
+        self
+    }
+
+    /// Returns every recorded snapshot, oldest first.
+    ///
+    /// Snapshots stored by older versions of this crate are upgraded to
+    /// [`SNAPSHOTS_VERSION`] when read, and saved in it by the next
+    /// [`record`](Self::record).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store fails or holds a value that is not a
+    /// list of snapshots, or
+    /// [`BlockPalettesError::Validation`](crate::BlockPalettesError::Validation)
+    /// if the snapshots were stored by a newer version of this crate.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use blockpalettes_client::state::{FileStore, StateStore};
+    /// use blockpalettes_client::trends::PopularBlocksTracker;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let dir = std::env::temp_dir().join("blockpalettes-trends-doctest");
+    ///     let store = FileStore::new(&dir)?;
+    ///     // stored before versioning
+    ///     let legacy = r#"[{"date":"2024-05-01","blocks":[{"block":"stone","count":900}]}]"#;
+    ///     store.put("snapshots", legacy.as_bytes()).await?;
+    ///
+    ///     let tracker = PopularBlocksTracker::new(FileStore::new(&dir)?).key("snapshots");
+    ///     let snapshots = tracker.snapshots().await?;
+    ///     assert_eq!(snapshots[0].blocks[0].name, "stone");
+    ///
+    ///     tracker.record(chrono::Utc::now().date_naive(), Vec::new()).await?;
+    ///     let stored: serde_json::Value = serde_json::from_slice(&store.get("snapshots").await?.unwrap())?;
+    ///     assert_eq!(stored["version"], 1);
+    ///     assert_eq!(stored["snapshots"].as_array().unwrap().len(), 2);
+    ///
+    ///     store.put("snapshots", br#"{"version":99,"snapshots":[]}"#).await?;
+    ///     assert!(tracker.snapshots().await.is_err());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn snapshots(&self) -> Result<Vec<PopularBlocksSnapshot>> {
+        let Some(bytes) = self.store.get(&self.key).await? else {
+            return Ok(Vec::new());
+        };
+        let mut stored: serde_json::Value = serde_json::from_slice(&bytes)?;
+        // version 0 is a bare list of snapshots
+        dataset::upgrade(
+            &mut stored,
+            SNAPSHOTS_VERSION,
+            "snapshots",
+            |_, snapshots| Ok(serde_json::json!({ "snapshots": snapshots })),
+        )?;
+        Ok(serde_json::from_value(stored["snapshots"].take())?)
+    }
+
+    /// Records the popular blocks of `date`, replacing any snapshot of the
+    /// same day.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store fails.
+    pub async fn record(&self, date: NaiveDate, blocks: Vec<PopularBlock>) -> Result<()> {
+        let mut snapshots = self.snapshots().await?;
+        snapshots.retain(|s| s.date != date);
+        snapshots.push(PopularBlocksSnapshot::new(date, blocks));
+        snapshots.sort_by_key(|s| s.date);
+        let stored = serde_json::json!({ "version": SNAPSHOTS_VERSION, "snapshots": snapshots });
+        self.store
+            .put(&self.key, &serde_json::to_vec(&stored)?)
+            .await
+    }
+
+    /// Fetches the popular blocks with `client` and records them as today's
+    /// snapshot (in UTC).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request or the store fails.
+    pub async fn record_from(&self, client: &BlockPalettesClient) -> Result<()> {
+        let blocks = client.popular_blocks().await?;
+        self.record(chrono::Utc::now().date_naive(), blocks).await
+    }
+
+    /// Compares the latest snapshot with the latest one taken on or before
+    /// `since`, e.g. a week ago for a weekly post.
+    ///
+    /// Returns `None` if there is no snapshot old enough, or if the latest
+    /// one is itself from `since` or earlier. See [`compare`] for the order
+    /// of the changes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store fails.
+    pub async fn movement_since(&self, since: NaiveDate) -> Result<Option<Vec<RankChange>>> {
+        let snapshots = self.snapshots().await?;
+        let Some(current) = snapshots.last().filter(|s| s.date > since) else {
+            return Ok(None);
+        };
+        Ok(snapshots
+            .iter()
+            .rev()
+            .find(|s| s.date <= since)
+            .map(|previous| compare(previous, current)))
+    }
+}
+
+impl fmt::Debug for PopularBlocksTracker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PopularBlocksTracker")
+            .field("key", &self.key)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,86 @@
+//! Caching of assembled [`User`]s.
+//!
+//! Block Palettes has no user endpoint, so
+//! [`BlockPalettesClient::get_user`](crate::BlockPalettesClient::get_user)
+//! combines a scraped profile page with palette details, which takes several
+//! requests. A [`UserCache`] keeps the result in memory for a while, so
+//! "more from this creator" views can look users up freely.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use blockpalettes_client::BlockPalettesClient;
+//! use blockpalettes_client::users::UserCache;
+//! use std::time::Duration;
+//!
+//! let cache = UserCache::new().ttl(Duration::from_secs(600));
+//! let client = BlockPalettesClient::new(reqwest::Client::new()).with_user_cache(cache);
+//! assert!(client.user_cache().unwrap().is_empty());
+//! ```
+
+use crate::User;
+use crate::ttl_cache::TtlCache;
+use std::time::Duration;
+
+/// The default time a user stays cached.
+pub const DEFAULT_USER_TTL: Duration = Duration::from_secs(300);
+
+/// An in-memory cache of users, keyed by user ID.
+///
+/// Attach it to a client with
+/// [`BlockPalettesClient::with_user_cache`](crate::BlockPalettesClient::with_user_cache).
+/// Clones of the client share the cache.
+#[derive(Debug)]
+pub struct UserCache {
+    entries: TtlCache<u64, User>,
+}
+
+impl Default for UserCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UserCache {
+    /// Creates an empty cache with the default time to live.
+    pub fn new() -> Self {
+        Self {
+            entries: TtlCache::new(DEFAULT_USER_TTL),
+        }
+    }
+
+    /// Sets how long a user stays cached.
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.entries.set_ttl(ttl);
+        self
+    }
+
+    /// Returns the number of cached users, including expired ones not yet evicted.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no user is cached.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Forgets the user with `user_id`, e.g. after they published a palette.
+    pub fn invalidate(&self, user_id: u64) {
+        self.entries.remove(&user_id);
+    }
+
+    /// Forgets every user.
+    pub fn clear(&self) {
+        self.entries.clear();
+    }
+
+    /// Returns the cached user with `user_id`, unless it expired.
+    pub(crate) fn get(&self, user_id: u64) -> Option<User> {
+        self.entries.get(&user_id)
+    }
+
+    pub(crate) fn insert(&self, user: User) {
+        self.entries.insert(user.id, user);
+    }
+}
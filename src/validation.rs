@@ -0,0 +1,200 @@
+//! Validation hooks applied to every decoded response.
+//!
+//! A [`ResponseValidator`] registered with
+//! [`BlockPalettesClient::with_validator`](crate::BlockPalettesClient::with_validator)
+//! sees every [`Palette`] and [`PaletteDetails`] the client decodes, before it
+//! is returned to the caller. For each item it decides whether to keep it
+//! (possibly transformed), silently skip it, or fail the whole call.
+//!
+//! Any closure taking a [`Palette`] and returning a [`Validation`] is a
+//! validator for palettes; implement the trait directly to validate
+//! [`PaletteDetails`] as well.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use blockpalettes_client::BlockPalettesClient;
+//! use blockpalettes_client::validation::Validation;
+//!
+//! let client = BlockPalettesClient::new(reqwest::Client::new())
+//!     // drop palettes with missing block names
+//!     .with_validator(|palette: blockpalettes_client::Palette| {
+//!         if palette.name().iter().any(|b| b.trim().is_empty()) {
+//!             Validation::Skip
+//!         } else {
+//!             Validation::Keep(palette)
+//!         }
+//!     })
+//!     // refuse implausible like counts outright
+//!     .with_validator(|palette: blockpalettes_client::Palette| {
+//!         if palette.likes > 10_000_000 {
+//!             Validation::Reject(format!("palette {} has {} likes", palette.id, palette.likes))
+//!         } else {
+//!             Validation::Keep(palette)
+//!         }
+//!     });
+//! ```
+
+use crate::denylist::Denylist;
+use crate::overrides::Overrides;
+use crate::{BlockPalettesError, Palette, PaletteCard, PaletteDetails, Result};
+use std::fmt;
+use std::sync::Arc;
+
+/// The decision made by a [`ResponseValidator`] for a single item.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Validation<T> {
+    /// Keep the item, possibly after transforming it.
+    Keep(T),
+    /// Drop the item from the results without failing the call.
+    Skip,
+    /// Fail the whole call with [`BlockPalettesError::Validation`].
+    Reject(String),
+}
+
+/// A data-quality rule applied to decoded API responses.
+///
+/// Both methods keep every item unchanged by default, so implementors only
+/// need to override the ones they care about.
+pub trait ResponseValidator: Send + Sync {
+    /// Validates a palette from a listing, search or similarity response.
+    fn validate_palette(&self, palette: Palette) -> Validation<Palette> {
+        Validation::Keep(palette)
+    }
+
+    /// Validates the details of a single palette.
+    ///
+    /// Skipping a palette's details makes the call fail with
+    /// [`BlockPalettesError::Validation`], since there is nothing else to return.
+    fn validate_details(&self, details: PaletteDetails) -> Validation<PaletteDetails> {
+        Validation::Keep(details)
+    }
+}
+
+impl<F> ResponseValidator for F
+where
+    F: Fn(Palette) -> Validation<Palette> + Send + Sync,
+{
+    fn validate_palette(&self, palette: Palette) -> Validation<Palette> {
+        self(palette)
+    }
+}
+
+/// The chain of validators registered on a client, applied in order.
+///
+/// The client's overrides, if any, run first, so every other validator sees
+/// the corrected palettes. The denylist, if any, runs next, so denied content
+/// is never passed to user code.
+#[derive(Clone, Default)]
+pub(crate) struct Validators {
+    overrides: Option<Arc<Overrides>>,
+    denylist: Option<Arc<Denylist>>,
+    chain: Vec<Arc<dyn ResponseValidator>>,
+}
+
+impl Validators {
+    pub(crate) const fn new() -> Self {
+        Self {
+            overrides: None,
+            denylist: None,
+            chain: Vec::new(),
+        }
+    }
+
+    pub(crate) fn push(&mut self, validator: Arc<dyn ResponseValidator>) {
+        self.chain.push(validator);
+    }
+
+    pub(crate) fn set_denylist(&mut self, denylist: Denylist) {
+        self.denylist = Some(Arc::new(denylist));
+    }
+
+    pub(crate) fn denylist(&self) -> Option<&Denylist> {
+        self.denylist.as_deref()
+    }
+
+    pub(crate) fn set_overrides(&mut self, overrides: Overrides) {
+        self.overrides = Some(Arc::new(overrides));
+    }
+
+    pub(crate) fn overrides(&self) -> Option<&Overrides> {
+        self.overrides.as_deref()
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &dyn ResponseValidator> {
+        let overrides = self
+            .overrides
+            .as_deref()
+            .map(|o| o as &dyn ResponseValidator);
+        let denylist = self
+            .denylist
+            .as_deref()
+            .map(|d| d as &dyn ResponseValidator);
+        overrides
+            .into_iter()
+            .chain(denylist)
+            .chain(self.chain.iter().map(|v| v.as_ref()))
+    }
+
+    /// Runs every palette through the chain, dropping skipped ones.
+    pub(crate) fn palettes(&self, palettes: Vec<Palette>) -> Result<Vec<Palette>> {
+        if self.overrides.is_none() && self.denylist.is_none() && self.chain.is_empty() {
+            return Ok(palettes);
+        }
+
+        let mut kept = Vec::with_capacity(palettes.len());
+        'palettes: for mut palette in palettes {
+            for validator in self.iter() {
+                match validator.validate_palette(palette) {
+                    Validation::Keep(p) => palette = p,
+                    Validation::Skip => continue 'palettes,
+                    Validation::Reject(reason) => {
+                        return Err(BlockPalettesError::Validation(reason));
+                    }
+                }
+            }
+            kept.push(palette);
+        }
+        Ok(kept)
+    }
+
+    /// Runs palette details through the chain.
+    pub(crate) fn details(&self, mut details: PaletteDetails) -> Result<PaletteDetails> {
+        for validator in self.iter() {
+            match validator.validate_details(details) {
+                Validation::Keep(d) => details = d,
+                Validation::Skip => {
+                    return Err(BlockPalettesError::Validation(
+                        "palette details skipped by validator".into(),
+                    ));
+                }
+                Validation::Reject(reason) => return Err(BlockPalettesError::Validation(reason)),
+            }
+        }
+        Ok(details)
+    }
+
+    /// Applies the overrides to scraped palette cards, then drops the cards
+    /// denied by the denylist.
+    pub(crate) fn cards(&self, cards: &mut Vec<PaletteCard>) {
+        if let Some(overrides) = &self.overrides {
+            cards.iter_mut().for_each(|card| overrides.apply_card(card));
+        }
+        if let Some(denylist) = &self.denylist {
+            cards.retain(|card| {
+                !denylist.denies_palette_id(card.id)
+                    && !card.blocks.iter().any(|b| denylist.denies_block(b))
+            });
+        }
+    }
+}
+
+impl fmt::Debug for Validators {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Validators")
+            .field("overrides", &self.overrides)
+            .field("denylist", &self.denylist)
+            .field("count", &self.chain.len())
+            .finish()
+    }
+}
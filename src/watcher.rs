@@ -0,0 +1,482 @@
+//! Polling for newly added palettes, with pluggable notifications.
+//!
+//! A [`PaletteWatcher`] periodically fetches the most recent palettes and
+//! reports the ones it has not seen before. Each new palette is turned into a
+//! [`PaletteEvent`] and handed to every registered [`Notifier`], so events can
+//! be forwarded to Discord, Slack, Matrix or any other service with little
+//! glue code.
+//!
+//! Two reference notifiers are provided: [`WebhookNotifier`], which posts
+//! each event as JSON to an HTTP endpoint, and [`StdoutJsonNotifier`], which
+//! prints one JSON document per line.
+//!
+//! A [`PaletteFilter`] restricts events to the palettes a channel cares
+//! about, such as palettes containing copper blocks. Since likes come in
+//! after a palette is posted, [`PaletteWatcher::recheck_after`] gives
+//! palettes that do not match at first sight a second chance: their likes
+//! are fetched again once the delay has passed, and they are reported if
+//! they match then.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use blockpalettes_client::BlockPalettesClient;
+//! use blockpalettes_client::watcher::{PaletteWatcher, StdoutJsonNotifier, WebhookNotifier};
+//! use std::time::Duration;
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let client = BlockPalettesClient::new(reqwest::Client::new());
+//!     let discord = WebhookNotifier::new(reqwest::Client::new(), "https://discord.com/api/webhooks/...")
+//!         .with_body(|event| serde_json::json!({ "content": format!("New palette: {}", event.url) }));
+//!
+//!     PaletteWatcher::new(client)
+//!         .interval(Duration::from_secs(300))
+//!         .notifier(discord)
+//!         .notifier(StdoutJsonNotifier)
+//!         .run()
+//!         .await?;
+//!     Ok(())
+//! }
+//! ```
+//!
+//! Only copper palettes with at least 10 likes an hour after they were
+//! first seen:
+//!
+//! ```rust,no_run
+//! use blockpalettes_client::watcher::{PaletteWatcher, StdoutJsonNotifier};
+//! use blockpalettes_client::{BlockPalettesClient, Palette};
+//! use std::time::Duration;
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let client = BlockPalettesClient::new(reqwest::Client::new());
+//!     PaletteWatcher::new(client)
+//!         .filter(|palette: &Palette| {
+//!             palette.blocks().iter().any(|b| b.contains("copper")) && palette.likes >= 10
+//!         })
+//!         .recheck_after(Duration::from_secs(3600))
+//!         .notifier(StdoutJsonNotifier)
+//!         .run()
+//!         .await?;
+//!     Ok(())
+//! }
+//! ```
+
+use crate::dataset::{self, SchemaVersion};
+use crate::state::StateStore;
+use crate::{BlockPalettesClient, BlockPalettesError, Palette, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// A boxed future returned by [`Notifier::notify`].
+pub type NotifyFuture<'a> = Pin<Box<dyn Future<Output = Result<()>> + Send + 'a>>;
+
+/// The default delay between two polls of a [`PaletteWatcher`].
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The default number of recent palettes fetched on every poll.
+pub const DEFAULT_BATCH_SIZE: usize = 50;
+
+/// The default [`StateStore`] key under which a watcher persists its seen palettes.
+pub const DEFAULT_STATE_KEY: &str = "watcher.seen";
+
+/// The version of the persisted state of a watcher, a JSON object listing
+/// the seen palette IDs under `seen`.
+///
+/// Version 0 is the format used before versioning: a bare list of IDs.
+pub const STATE_VERSION: SchemaVersion = SchemaVersion(1);
+
+/// A newly detected palette.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PaletteEvent {
+    /// The palette that was detected.
+    pub palette: Palette,
+    /// The URL of the palette's page on the site.
+    pub url: String,
+    /// When the palette was detected (`YYYY-MM-DD HH:MM:SS`, UTC).
+    pub detected_at: String,
+}
+
+/// A destination for [`PaletteEvent`]s.
+///
+/// The method returns a boxed future so that notifiers can be stored as
+/// trait objects inside the watcher.
+pub trait Notifier: Send + Sync {
+    /// Delivers a single event.
+    fn notify<'a>(&'a self, event: &'a PaletteEvent) -> NotifyFuture<'a>;
+}
+
+/// Decides which palettes a [`PaletteWatcher`] reports.
+///
+/// Any closure taking a `&Palette` and returning a `bool` is a filter.
+pub trait PaletteFilter: Send + Sync {
+    /// Returns `true` if `palette` should be reported.
+    fn matches(&self, palette: &Palette) -> bool;
+}
+
+impl<F> PaletteFilter for F
+where
+    F: Fn(&Palette) -> bool + Send + Sync,
+{
+    fn matches(&self, palette: &Palette) -> bool {
+        self(palette)
+    }
+}
+
+/// Posts every event as JSON to an HTTP webhook.
+///
+/// By default the body is the serialized [`PaletteEvent`]; use
+/// [`with_body`](Self::with_body) to adapt it to the payload format expected
+/// by a specific service.
+#[derive(Clone)]
+pub struct WebhookNotifier {
+    client: reqwest::Client,
+    url: String,
+    body: Arc<dyn Fn(&PaletteEvent) -> serde_json::Value + Send + Sync>,
+}
+
+impl WebhookNotifier {
+    /// Creates a notifier posting events to `url`.
+    pub fn new(client: reqwest::Client, url: impl Into<String>) -> Self {
+        Self {
+            client,
+            url: url.into(),
+            body: Arc::new(|event| serde_json::to_value(event).unwrap_or_default()),
+        }
+    }
+
+    /// Replaces the function building the JSON body sent for each event.
+    pub fn with_body(
+        mut self,
+        body: impl Fn(&PaletteEvent) -> serde_json::Value + Send + Sync + 'static,
+    ) -> Self {
+        self.body = Arc::new(body);
+        self
+    }
+}
+
+impl Notifier for WebhookNotifier {
+    fn notify<'a>(&'a self, event: &'a PaletteEvent) -> NotifyFuture<'a> {
+        Box::pin(async move {
+            let response = self
+                .client
+                .post(&self.url)
+                .json(&(self.body)(event))
+                .send()
+                .await?;
+
+            if response.status().is_success() {
+                Ok(())
+            } else {
+                Err(BlockPalettesError::Api(format!(
+                    "Webhook returned {}",
+                    response.status()
+                )))
+            }
+        })
+    }
+}
+
+impl fmt::Debug for WebhookNotifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WebhookNotifier")
+            .field("url", &self.url)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Prints every event to standard output as a single line of JSON.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StdoutJsonNotifier;
+
+impl Notifier for StdoutJsonNotifier {
+    fn notify<'a>(&'a self, event: &'a PaletteEvent) -> NotifyFuture<'a> {
+        Box::pin(async move {
+            println!("{}", serde_json::to_string(event)?);
+            Ok(())
+        })
+    }
+}
+
+/// Polls the site for new palettes and dispatches them to [`Notifier`]s.
+///
+/// The first poll only records the palettes that already exist, so that
+/// starting a watcher does not flood notifiers with old palettes. With a
+/// [`StateStore`], the seen palettes are persisted after every poll and
+/// restored on the first one, so palettes added while the watcher was down
+/// are reported after a restart. Palettes waiting for a
+/// [recheck](Self::recheck_after) are kept in memory only.
+pub struct PaletteWatcher {
+    client: BlockPalettesClient,
+    interval: Duration,
+    batch_size: usize,
+    seen: HashSet<u64>,
+    primed: bool,
+    notifiers: Vec<Box<dyn Notifier>>,
+    store: Option<Box<dyn StateStore>>,
+    state_key: String,
+    filter: Option<Box<dyn PaletteFilter>>,
+    recheck: Option<Duration>,
+    /// Palettes rejected by the filter, with when to check them again.
+    pending: VecDeque<(Instant, Palette)>,
+    /// Rechecked palettes matching the filter, not returned yet because the
+    /// poll that rechecked them failed.
+    rechecked: Vec<Palette>,
+}
+
+impl PaletteWatcher {
+    /// Creates a watcher using `client` with the default interval and batch size.
+    pub fn new(client: BlockPalettesClient) -> Self {
+        Self {
+            client,
+            interval: DEFAULT_POLL_INTERVAL,
+            batch_size: DEFAULT_BATCH_SIZE,
+            seen: HashSet::new(),
+            primed: false,
+            notifiers: Vec::new(),
+            store: None,
+            state_key: DEFAULT_STATE_KEY.to_string(),
+            filter: None,
+            recheck: None,
+            pending: VecDeque::new(),
+            rechecked: Vec::new(),
+        }
+    }
+
+    /// Sets the delay between two polls.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Sets how many recent palettes are fetched on every poll.
+    ///
+    /// This should comfortably exceed the number of palettes added to the
+    /// site during one interval, or some palettes may be missed.
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Registers a notifier that receives every new palette.
+    pub fn notifier(mut self, notifier: impl Notifier + 'static) -> Self {
+        self.notifiers.push(Box::new(notifier));
+        self
+    }
+
+    /// Only reports palettes matching `filter`. Calling this again replaces
+    /// the previous filter.
+    pub fn filter(mut self, filter: impl PaletteFilter + 'static) -> Self {
+        self.filter = Some(Box::new(filter));
+        self
+    }
+
+    /// Checks new palettes rejected by the filter again `delay` after they
+    /// were first seen, with their likes at that time, and reports them if
+    /// they match then.
+    ///
+    /// Each palette is rechecked once, on the first poll after its delay has
+    /// passed. Palettes removed from the site in the meantime are dropped.
+    pub fn recheck_after(mut self, delay: Duration) -> Self {
+        self.recheck = Some(delay);
+        self
+    }
+
+    /// Persists the seen palettes in `store`.
+    pub fn state_store(mut self, store: impl StateStore + 'static) -> Self {
+        self.store = Some(Box::new(store));
+        self
+    }
+
+    /// Sets the key under which the seen palettes are persisted, so several
+    /// watchers can share a store. Defaults to [`DEFAULT_STATE_KEY`].
+    pub fn state_key(mut self, key: impl Into<String>) -> Self {
+        self.state_key = key.into();
+        self
+    }
+
+    /// Fetches the most recent palettes once and returns the new ones
+    /// matching the filter, oldest first.
+    ///
+    /// Rechecked palettes that match now come first, in the order they were
+    /// seen. Notifiers are not called; use
+    /// [`poll_and_notify`](Self::poll_and_notify) for that.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the palettes cannot be fetched, or if the state
+    /// store fails or holds a value that is not a watcher state, or one
+    /// written by a newer version of this crate. A
+    /// palette whose recheck fails is rechecked again on the next poll, and
+    /// rechecked palettes matching the filter are returned by the next poll
+    /// that succeeds.
+    pub async fn poll(&mut self) -> Result<Vec<Palette>> {
+        if !self.primed {
+            self.restore().await?;
+        }
+
+        self.recheck_due().await?;
+
+        let recent = self.client.get_recent_palettes(self.batch_size).await?;
+        let mut new: Vec<Palette> = recent
+            .into_iter()
+            .filter(|p| self.seen.insert(p.id))
+            .collect();
+        new.reverse();
+
+        if !new.is_empty() || !self.primed {
+            self.persist().await?;
+        }
+        if !self.primed {
+            self.primed = true;
+            return Ok(Vec::new());
+        }
+
+        let mut matching = std::mem::take(&mut self.rechecked);
+        for palette in new {
+            if self.matches(&palette) {
+                matching.push(palette);
+            } else if let Some(delay) = self.recheck {
+                self.pending.push_back((Instant::now() + delay, palette));
+            }
+        }
+        Ok(matching)
+    }
+
+    fn matches(&self, palette: &Palette) -> bool {
+        self.filter.as_ref().is_none_or(|f| f.matches(palette))
+    }
+
+    /// Refreshes the likes of the pending palettes whose delay has passed and
+    /// keeps the ones matching the filter now for the poll to return.
+    async fn recheck_due(&mut self) -> Result<()> {
+        while self
+            .pending
+            .front()
+            .is_some_and(|(due, _)| *due <= Instant::now())
+        {
+            let Some((due, mut palette)) = self.pending.pop_front() else {
+                break;
+            };
+            match self.client.get_palette_likes(palette.id).await {
+                Ok(likes) => palette.likes = likes,
+                // the palette no longer exists
+                Err(e) if matches!(e.root(), BlockPalettesError::NotFound(_)) => continue,
+                Err(e) => {
+                    self.pending.push_front((due, palette));
+                    return Err(e);
+                }
+            }
+            if self.matches(&palette) {
+                self.rechecked.push(palette);
+            }
+        }
+        Ok(())
+    }
+
+    /// Loads the seen palettes from the state store, if any.
+    ///
+    /// A restored watcher counts as primed, so palettes added while it was not
+    /// running are reported by the next poll.
+    async fn restore(&mut self) -> Result<()> {
+        let Some(store) = &self.store else {
+            return Ok(());
+        };
+        if let Some(bytes) = store.get(&self.state_key).await? {
+            let mut state: serde_json::Value = serde_json::from_slice(&bytes)?;
+            // version 0 is a bare list of IDs
+            dataset::upgrade(&mut state, STATE_VERSION, "watcher state", |_, ids| {
+                Ok(serde_json::json!({ "seen": ids }))
+            })?;
+            let ids: Vec<u64> = serde_json::from_value(state["seen"].take())?;
+            self.seen.extend(ids);
+            self.primed = true;
+        }
+        Ok(())
+    }
+
+    /// Saves the seen palettes to the state store, if any.
+    async fn persist(&self) -> Result<()> {
+        let Some(store) = &self.store else {
+            return Ok(());
+        };
+        let mut ids: Vec<u64> = self.seen.iter().copied().collect();
+        ids.sort_unstable();
+        let state = serde_json::json!({ "version": STATE_VERSION, "seen": ids });
+        store
+            .put(&self.state_key, &serde_json::to_vec(&state)?)
+            .await
+    }
+
+    /// Polls once and sends an event for every new palette to all notifiers.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from fetching palettes or from a notifier.
+    /// Every notifier is still given every event before the error is returned.
+    pub async fn poll_and_notify(&mut self) -> Result<Vec<PaletteEvent>> {
+        let new = self.poll().await?;
+        let detected_at = chrono::Utc::now()
+            .naive_utc()
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string();
+
+        let events: Vec<PaletteEvent> = new
+            .into_iter()
+            .map(|palette| PaletteEvent {
+                url: self.client.palette_url(palette.id),
+                palette,
+                detected_at: detected_at.clone(),
+            })
+            .collect();
+
+        let mut first_error = None;
+        for event in &events {
+            for notifier in &self.notifiers {
+                if let Err(e) = notifier.notify(event).await {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        match first_error {
+            Some(e) => Err(e),
+            None => Ok(events),
+        }
+    }
+
+    /// Polls and notifies forever, sleeping for the configured interval between polls.
+    ///
+    /// # Errors
+    ///
+    /// Returns as soon as a poll or a notifier fails.
+    pub async fn run(&mut self) -> Result<()> {
+        loop {
+            self.poll_and_notify().await?;
+            tokio::time::sleep(self.interval).await;
+        }
+    }
+}
+
+impl fmt::Debug for PaletteWatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PaletteWatcher")
+            .field("interval", &self.interval)
+            .field("batch_size", &self.batch_size)
+            .field("seen", &self.seen.len())
+            .field("notifiers", &self.notifiers.len())
+            .field("state_key", &self.state_key)
+            .field("persistent", &self.store.is_some())
+            .field("filtered", &self.filter.is_some())
+            .field("recheck", &self.recheck)
+            .field("pending", &self.pending.len())
+            .field("rechecked", &self.rechecked.len())
+            .finish()
+    }
+}